@@ -0,0 +1,224 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Atomic swap module.
+//!
+//! A maker offers an amount of one asset to a counterparty in exchange for an amount of another asset, without
+//! a trusted intermediary. The offered amount is reserved on the maker until the offer is settled:
+//! - the counterparty accepts it, paying the requested amount to the maker and receiving the offered amount
+//!   in the same call
+//! - the maker cancels it
+//! - it expires undone at the end of its expiry block
+//! Either both legs of a swap are made or neither is.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use generic_asset::ReserveIdentifier;
+use parity_codec::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::traits::Zero;
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap, StorageValue};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The reservation offered amounts are held under on the maker
+pub const SWAP_RESERVE_ID: ReserveIdentifier = *b"atomswap";
+/// The max. number of swap offers which may expire at the same block
+pub const MAX_SWAPS_EXPIRING_PER_BLOCK: usize = 100;
+
+pub type SwapId = u32;
+
+pub trait Trait: generic_asset::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+/// An offer to swap an amount of one asset for an amount of another
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SwapOffer<AccountId, AssetId, Balance, BlockNumber> {
+	/// The account the offered amount is reserved on
+	pub maker: AccountId,
+	/// The only account which may accept the offer
+	pub counterparty: AccountId,
+	/// The asset offered by the maker
+	pub offered_asset: AssetId,
+	/// The amount offered by the maker
+	pub offered_amount: Balance,
+	/// The asset requested from the counterparty
+	pub requested_asset: AssetId,
+	/// The amount requested from the counterparty
+	pub requested_amount: Balance,
+	/// The block at the end of which the offer expires
+	pub expiry: BlockNumber,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Offer `offered_amount` of `offered_asset` to `counterparty` for `requested_amount` of `requested_asset`.
+		/// The offered amount is reserved on the sender until the offer is accepted, cancelled or expires at the
+		/// end of block `expiry`.
+		pub fn offer_swap(
+			origin,
+			#[compact] offered_asset: T::AssetId,
+			#[compact] offered_amount: T::Balance,
+			#[compact] requested_asset: T::AssetId,
+			#[compact] requested_amount: T::Balance,
+			counterparty: T::AccountId,
+			#[compact] expiry: T::BlockNumber
+		) -> Result {
+			let maker = ensure_signed(origin)?;
+			ensure!(
+				!offered_amount.is_zero() && !requested_amount.is_zero(),
+				"Swap amounts must be greater than zero"
+			);
+			ensure!(offered_asset != requested_asset, "Swap assets must differ");
+			ensure!(maker != counterparty, "Swap counterparty must not be the maker");
+			ensure!(expiry >= <system::Module<T>>::block_number(), "Swap offer has already expired");
+			let mut expiring = Self::swaps_expiring(expiry);
+			ensure!(expiring.len() < MAX_SWAPS_EXPIRING_PER_BLOCK, "Too many swap offers expire at the block");
+			let swap_id = Self::next_swap_id();
+			let next_swap_id = swap_id.checked_add(1).ok_or_else(|| "No new swap id is available")?;
+
+			<generic_asset::Module<T>>::reserve_named(&SWAP_RESERVE_ID, &offered_asset, &maker, offered_amount)?;
+			<NextSwapId<T>>::put(next_swap_id);
+			expiring.push(swap_id);
+			<SwapsExpiring<T>>::insert(expiry, expiring);
+			<Swaps<T>>::insert(
+				swap_id,
+				SwapOffer {
+					maker: maker.clone(),
+					counterparty: counterparty.clone(),
+					offered_asset,
+					offered_amount,
+					requested_asset,
+					requested_amount,
+					expiry,
+				},
+			);
+
+			Self::deposit_event(RawEvent::SwapOffered(
+				swap_id,
+				maker,
+				counterparty,
+				offered_asset,
+				offered_amount,
+				requested_asset,
+				requested_amount,
+			));
+			Ok(())
+		}
+
+		/// Accept a swap offer, the sender must be its counterparty.
+		/// The requested amount is transferred to the maker and the offered amount to the sender.
+		pub fn accept_swap(origin, #[compact] swap_id: SwapId) -> Result {
+			let counterparty = ensure_signed(origin)?;
+			let swap = Self::swap(swap_id).ok_or_else(|| "Swap offer does not exist")?;
+			ensure!(swap.counterparty == counterparty, "Only the counterparty can accept a swap offer");
+
+			// Both legs are checked in full before anything is written, so the swap is all or nothing
+			<generic_asset::Module<T>>::ensure_can_receive(&swap.offered_asset, &swap.maker, &counterparty)?;
+			<generic_asset::Module<T>>::make_transfer_with_event(
+				&swap.requested_asset,
+				&counterparty,
+				&swap.maker,
+				swap.requested_amount,
+			)?;
+			<generic_asset::Module<T>>::repatriate_reserved_named(
+				&SWAP_RESERVE_ID,
+				&swap.offered_asset,
+				&swap.maker,
+				&counterparty,
+				swap.offered_amount,
+			)?;
+			Self::remove_swap(swap_id, swap.expiry);
+
+			Self::deposit_event(RawEvent::SwapCompleted(swap_id));
+			Ok(())
+		}
+
+		/// Cancel a swap offer and release its offered amount, the sender must be its maker.
+		pub fn cancel_swap(origin, #[compact] swap_id: SwapId) -> Result {
+			let maker = ensure_signed(origin)?;
+			let swap = Self::swap(swap_id).ok_or_else(|| "Swap offer does not exist")?;
+			ensure!(swap.maker == maker, "Only the maker can cancel a swap offer");
+
+			Self::release(&swap);
+			Self::remove_swap(swap_id, swap.expiry);
+
+			Self::deposit_event(RawEvent::SwapCancelled(swap_id));
+			Ok(())
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			for swap_id in <SwapsExpiring<T>>::take(n) {
+				if let Some(swap) = <Swaps<T>>::take(swap_id) {
+					Self::release(&swap);
+					Self::deposit_event(RawEvent::SwapExpired(swap_id));
+				}
+			}
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+		<T as generic_asset::Trait>::AssetId,
+		<T as generic_asset::Trait>::Balance
+	{
+		/// A swap was offered:
+		/// (swap id, maker, counterparty, offered asset, offered amount, requested asset, requested amount)
+		SwapOffered(SwapId, AccountId, AccountId, AssetId, Balance, AssetId, Balance),
+		/// A swap offer was accepted and both legs were transferred (swap id)
+		SwapCompleted(SwapId),
+		/// A swap offer was cancelled by its maker (swap id)
+		SwapCancelled(SwapId),
+		/// A swap offer expired without being accepted (swap id)
+		SwapExpired(SwapId),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as AtomicSwap {
+		/// The id of the next swap to be offered
+		pub NextSwapId get(next_swap_id): SwapId;
+		/// Open swap offers: swap id => offer
+		pub Swaps get(swap): map SwapId => Option<SwapOffer<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>>;
+		/// The swap offers which expire at the end of a block
+		SwapsExpiring get(swaps_expiring): map T::BlockNumber => Vec<SwapId>;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Release the offered amount of a swap back to its maker
+	fn release(swap: &SwapOffer<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>) {
+		<generic_asset::Module<T>>::unreserve_named(
+			&SWAP_RESERVE_ID,
+			&swap.offered_asset,
+			&swap.maker,
+			swap.offered_amount,
+		);
+	}
+
+	fn remove_swap(swap_id: SwapId, expiry: T::BlockNumber) {
+		<Swaps<T>>::remove(swap_id);
+		<SwapsExpiring<T>>::mutate(expiry, |expiring| expiring.retain(|id| *id != swap_id));
+	}
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{
+	AtomicSwap, ExtBuilder, GenericAsset, Origin, System, COUNTERPARTY, MAKER, OFFERED_ASSET_ID, REQUESTED_ASSET_ID,
+};
+use runtime_io::with_externalities;
+use runtime_primitives::traits::OnFinalize;
+use support::{assert_noop, assert_ok};
+
+fn balances_of(who: u64) -> (u64, u64) {
+	(
+		GenericAsset::free_balance(&OFFERED_ASSET_ID, &who),
+		GenericAsset::free_balance(&REQUESTED_ASSET_ID, &who),
+	)
+}
+
+fn offer_swap() {
+	assert_ok!(AtomicSwap::offer_swap(
+		Origin::signed(MAKER),
+		OFFERED_ASSET_ID,
+		40,
+		REQUESTED_ASSET_ID,
+		30,
+		COUNTERPARTY,
+		10
+	));
+}
+
+#[test]
+fn offer_swap_reserves_the_offered_amount() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(5);
+		assert_noop!(
+			AtomicSwap::offer_swap(Origin::signed(MAKER), OFFERED_ASSET_ID, 0, REQUESTED_ASSET_ID, 30, COUNTERPARTY, 10),
+			"Swap amounts must be greater than zero"
+		);
+		assert_noop!(
+			AtomicSwap::offer_swap(Origin::signed(MAKER), OFFERED_ASSET_ID, 40, OFFERED_ASSET_ID, 30, COUNTERPARTY, 10),
+			"Swap assets must differ"
+		);
+		assert_noop!(
+			AtomicSwap::offer_swap(Origin::signed(MAKER), OFFERED_ASSET_ID, 40, REQUESTED_ASSET_ID, 30, COUNTERPARTY, 4),
+			"Swap offer has already expired"
+		);
+		offer_swap();
+
+		assert_eq!(balances_of(MAKER), (60, 100));
+		assert_eq!(
+			GenericAsset::reserves(&OFFERED_ASSET_ID, &MAKER),
+			vec![(b"atomswap".to_vec(), 40)]
+		);
+		assert_eq!(AtomicSwap::swap(0).map(|swap| swap.expiry), Some(10));
+		assert_eq!(AtomicSwap::swaps_expiring(10), vec![0]);
+	});
+}
+
+#[test]
+fn accepting_a_swap_transfers_both_legs() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		offer_swap();
+
+		assert_noop!(
+			AtomicSwap::accept_swap(Origin::signed(MAKER), 0),
+			"Only the counterparty can accept a swap offer"
+		);
+		assert_ok!(AtomicSwap::accept_swap(Origin::signed(COUNTERPARTY), 0));
+
+		assert_eq!(balances_of(MAKER), (60, 130));
+		assert_eq!(balances_of(COUNTERPARTY), (140, 70));
+		assert_eq!(GenericAsset::reserved_balance(&OFFERED_ASSET_ID, &MAKER), 0);
+		assert_eq!(AtomicSwap::swap(0), None);
+		assert_eq!(AtomicSwap::swaps_expiring(10), vec![]);
+		assert_noop!(AtomicSwap::accept_swap(Origin::signed(COUNTERPARTY), 0), "Swap offer does not exist");
+	});
+}
+
+#[test]
+fn a_swap_the_counterparty_can_not_pay_is_not_made() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(AtomicSwap::offer_swap(
+			Origin::signed(MAKER),
+			OFFERED_ASSET_ID,
+			40,
+			REQUESTED_ASSET_ID,
+			101,
+			COUNTERPARTY,
+			10
+		));
+
+		assert_noop!(
+			AtomicSwap::accept_swap(Origin::signed(COUNTERPARTY), 0),
+			"balance too low to send amount"
+		);
+		assert_eq!(balances_of(MAKER), (60, 100));
+		assert_eq!(balances_of(COUNTERPARTY), (100, 100));
+	});
+}
+
+#[test]
+fn cancelled_and_expired_swaps_release_the_offered_amount() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		offer_swap();
+		offer_swap();
+
+		assert_noop!(
+			AtomicSwap::cancel_swap(Origin::signed(COUNTERPARTY), 0),
+			"Only the maker can cancel a swap offer"
+		);
+		assert_ok!(AtomicSwap::cancel_swap(Origin::signed(MAKER), 0));
+		assert_eq!(balances_of(MAKER), (60, 100));
+		assert_eq!(AtomicSwap::swaps_expiring(10), vec![1]);
+
+		AtomicSwap::on_finalize(9);
+		assert!(AtomicSwap::swap(1).is_some());
+		AtomicSwap::on_finalize(10);
+		assert_eq!(AtomicSwap::swap(1), None);
+		assert_eq!(balances_of(MAKER), (100, 100));
+		assert_eq!(GenericAsset::reserves(&OFFERED_ASSET_ID, &MAKER), vec![]);
+	});
+}
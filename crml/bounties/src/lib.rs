@@ -0,0 +1,194 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Bounties module.
+//!
+//! The council posts bounties for public goods, paid in the spending asset from the treasury account.
+//! Each bounty is split into milestones and its whole value is reserved on the treasury when it is posted.
+//! The bounty's curator approves a claimant and releases the milestone payouts to them in order.
+//! Cancelling a bounty returns its unpaid milestones to the treasury's free balance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use generic_asset::ReserveIdentifier;
+use parity_codec::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::traits::{CheckedAdd, Zero};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap, StorageValue};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The reservation bounty payouts are held under on the treasury
+pub const BOUNTY_RESERVE_ID: ReserveIdentifier = *b"bounties";
+/// The max. length of a bounty description in bytes
+pub const MAX_DESCRIPTION_LENGTH: usize = 1024;
+/// The max. number of milestones of a bounty
+pub const MAX_MILESTONES: usize = 16;
+
+pub type BountyIndex = u32;
+
+pub trait Trait: generic_asset::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+/// A posted bounty
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Bounty<AccountId, Balance> {
+	/// What the bounty is for
+	pub description: Vec<u8>,
+	/// The treasury account the payouts are reserved on
+	pub treasury: AccountId,
+	/// The account which approves the claimant and releases milestones
+	pub curator: AccountId,
+	/// The account the milestones are paid to, once approved
+	pub claimant: Option<AccountId>,
+	/// The payouts still to be released, in order
+	pub milestones: Vec<Balance>,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Post a bounty paying `milestones` in order from the treasury, managed by `curator` (root only).
+		/// The total of the milestones is reserved on the treasury until it is paid or the bounty is cancelled.
+		pub fn post_bounty(description: Vec<u8>, curator: T::AccountId, milestones: Vec<T::Balance>) -> Result {
+			ensure!(description.len() <= MAX_DESCRIPTION_LENGTH, "Bounty description is too long");
+			ensure!(!milestones.is_empty(), "Bounty must have at least one milestone");
+			ensure!(milestones.len() <= MAX_MILESTONES, "Bounty has too many milestones");
+			ensure!(milestones.iter().all(|milestone| !milestone.is_zero()), "Bounty milestones must be non-zero");
+			let value = milestones
+				.iter()
+				.try_fold(T::Balance::zero(), |total, milestone| total.checked_add(milestone))
+				.ok_or_else(|| "Bounty value overflow")?;
+			let treasury = Self::treasury().ok_or_else(|| "No treasury account is set")?;
+			let index = Self::next_bounty_id();
+			let next_index = index.checked_add(1).ok_or_else(|| "No new bounty ids are available")?;
+
+			<generic_asset::Module<T>>::reserve_named(
+				&BOUNTY_RESERVE_ID,
+				&<generic_asset::Module<T>>::spending_asset_id(),
+				&treasury,
+				value,
+			)?;
+			<NextBountyId<T>>::put(next_index);
+			<Bounties<T>>::insert(index, Bounty {
+				description,
+				treasury,
+				curator: curator.clone(),
+				claimant: None,
+				milestones,
+			});
+
+			Self::deposit_event(RawEvent::BountyPosted(index, curator, value));
+			Ok(())
+		}
+
+		/// Approve `claimant` to receive the remaining milestones of a bounty, replacing any earlier claimant.
+		/// Only the bounty's curator may do this.
+		pub fn approve_claimant(origin, #[compact] bounty_id: BountyIndex, claimant: T::AccountId) -> Result {
+			let curator = ensure_signed(origin)?;
+			let mut bounty = Self::bounty(bounty_id).ok_or_else(|| "Bounty does not exist")?;
+			ensure!(bounty.curator == curator, "Only the bounty curator can approve a claimant");
+
+			bounty.claimant = Some(claimant.clone());
+			<Bounties<T>>::insert(bounty_id, bounty);
+
+			Self::deposit_event(RawEvent::ClaimantApproved(bounty_id, claimant));
+			Ok(())
+		}
+
+		/// Pay the next milestone of a bounty to its approved claimant.
+		/// Only the bounty's curator may do this, the bounty is completed once its last milestone is paid.
+		pub fn release_milestone(origin, #[compact] bounty_id: BountyIndex) -> Result {
+			let curator = ensure_signed(origin)?;
+			let mut bounty = Self::bounty(bounty_id).ok_or_else(|| "Bounty does not exist")?;
+			ensure!(bounty.curator == curator, "Only the bounty curator can release a milestone");
+			let claimant = bounty.claimant.clone().ok_or_else(|| "Bounty has no approved claimant")?;
+
+			let amount = bounty.milestones.remove(0);
+			<generic_asset::Module<T>>::repatriate_reserved_named(
+				&BOUNTY_RESERVE_ID,
+				&<generic_asset::Module<T>>::spending_asset_id(),
+				&bounty.treasury,
+				&claimant,
+				amount,
+			)?;
+			Self::deposit_event(RawEvent::MilestonePaid(bounty_id, claimant, amount));
+
+			if bounty.milestones.is_empty() {
+				<Bounties<T>>::remove(bounty_id);
+				Self::deposit_event(RawEvent::BountyCompleted(bounty_id));
+			} else {
+				<Bounties<T>>::insert(bounty_id, bounty);
+			}
+			Ok(())
+		}
+
+		/// Cancel a bounty, returning its unpaid milestones to the treasury (root only)
+		pub fn cancel_bounty(#[compact] bounty_id: BountyIndex) -> Result {
+			let bounty = Self::bounty(bounty_id).ok_or_else(|| "Bounty does not exist")?;
+			let refund = bounty.milestones.iter().fold(T::Balance::zero(), |total, milestone| total + *milestone);
+
+			<generic_asset::Module<T>>::unreserve_named(
+				&BOUNTY_RESERVE_ID,
+				&<generic_asset::Module<T>>::spending_asset_id(),
+				&bounty.treasury,
+				refund,
+			);
+			<Bounties<T>>::remove(bounty_id);
+
+			Self::deposit_event(RawEvent::BountyCancelled(bounty_id, refund));
+			Ok(())
+		}
+
+		/// Set the treasury account new bounties are paid from (root only).
+		/// Posted bounties keep being paid from the treasury they were posted with.
+		pub fn set_treasury(treasury: T::AccountId) -> Result {
+			<Treasury<T>>::put(treasury);
+			Ok(())
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where <T as system::Trait>::AccountId, <T as generic_asset::Trait>::Balance {
+		/// A bounty was posted: (bounty id, curator, value)
+		BountyPosted(BountyIndex, AccountId, Balance),
+		/// A claimant was approved for a bounty: (bounty id, claimant)
+		ClaimantApproved(BountyIndex, AccountId),
+		/// A bounty milestone was paid: (bounty id, claimant, amount)
+		MilestonePaid(BountyIndex, AccountId, Balance),
+		/// The last milestone of a bounty was paid (bounty id)
+		BountyCompleted(BountyIndex),
+		/// A bounty was cancelled: (bounty id, unpaid value returned to the treasury)
+		BountyCancelled(BountyIndex, Balance),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Bounties {
+		/// The account new bounties are paid from
+		pub Treasury get(treasury) config(): Option<T::AccountId>;
+		/// The id of the next bounty to be posted
+		pub NextBountyId get(next_bounty_id): BountyIndex;
+		/// Open bounties: bounty id => bounty
+		pub Bounties get(bounty): map BountyIndex => Option<Bounty<T::AccountId, T::Balance>>;
+	}
+}
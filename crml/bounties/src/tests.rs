@@ -0,0 +1,129 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{Bounties, ExtBuilder, GenericAsset, Origin, SPENDING_ASSET_ID, TREASURY};
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok};
+
+const CURATOR: u64 = 2;
+const CLAIMANT: u64 = 3;
+
+#[test]
+fn post_bounty_reserves_its_value_on_the_treasury() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(Bounties::post_bounty(b"docs".to_vec(), CURATOR, vec![10, 20]));
+
+		assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &TREASURY), 70);
+		assert_eq!(
+			GenericAsset::reserves(&SPENDING_ASSET_ID, &TREASURY),
+			vec![(b"bounties".to_vec(), 30)]
+		);
+		assert_eq!(Bounties::next_bounty_id(), 1);
+		assert_eq!(
+			Bounties::bounty(0),
+			Some(Bounty {
+				description: b"docs".to_vec(),
+				treasury: TREASURY,
+				curator: CURATOR,
+				claimant: None,
+				milestones: vec![10, 20],
+			})
+		);
+	});
+}
+
+#[test]
+fn post_bounty_is_validated() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_noop!(
+			Bounties::post_bounty(vec![], CURATOR, vec![]),
+			"Bounty must have at least one milestone"
+		);
+		assert_noop!(
+			Bounties::post_bounty(vec![], CURATOR, vec![10, 0]),
+			"Bounty milestones must be non-zero"
+		);
+		assert_noop!(
+			Bounties::post_bounty(vec![0; MAX_DESCRIPTION_LENGTH + 1], CURATOR, vec![10]),
+			"Bounty description is too long"
+		);
+		assert_noop!(
+			Bounties::post_bounty(vec![], CURATOR, vec![u64::max_value(), 1]),
+			"Bounty value overflow"
+		);
+		assert_noop!(
+			Bounties::post_bounty(vec![], CURATOR, vec![101]),
+			"not enough free funds"
+		);
+	});
+
+	with_externalities(&mut ExtBuilder::default().treasury(None).build(), || {
+		assert_noop!(
+			Bounties::post_bounty(vec![], CURATOR, vec![10]),
+			"No treasury account is set"
+		);
+	});
+}
+
+#[test]
+fn curator_releases_milestones_to_the_approved_claimant() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(Bounties::post_bounty(vec![], CURATOR, vec![10, 20]));
+		assert_noop!(
+			Bounties::release_milestone(Origin::signed(CURATOR), 0),
+			"Bounty has no approved claimant"
+		);
+		assert_noop!(
+			Bounties::approve_claimant(Origin::signed(CLAIMANT), 0, CLAIMANT),
+			"Only the bounty curator can approve a claimant"
+		);
+		assert_ok!(Bounties::approve_claimant(Origin::signed(CURATOR), 0, CLAIMANT));
+
+		assert_noop!(
+			Bounties::release_milestone(Origin::signed(CLAIMANT), 0),
+			"Only the bounty curator can release a milestone"
+		);
+		assert_ok!(Bounties::release_milestone(Origin::signed(CURATOR), 0));
+		assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &CLAIMANT), 10);
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &TREASURY), 20);
+		assert_eq!(Bounties::bounty(0).map(|bounty| bounty.milestones), Some(vec![20]));
+
+		assert_ok!(Bounties::release_milestone(Origin::signed(CURATOR), 0));
+		assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &CLAIMANT), 30);
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &TREASURY), 0);
+		assert_eq!(GenericAsset::reserves(&SPENDING_ASSET_ID, &TREASURY), vec![]);
+		assert_eq!(Bounties::bounty(0), None);
+	});
+}
+
+#[test]
+fn cancel_bounty_returns_unpaid_milestones_to_the_treasury() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(Bounties::post_bounty(vec![], CURATOR, vec![10, 20]));
+		assert_ok!(Bounties::approve_claimant(Origin::signed(CURATOR), 0, CLAIMANT));
+		assert_ok!(Bounties::release_milestone(Origin::signed(CURATOR), 0));
+
+		assert_ok!(Bounties::cancel_bounty(0));
+		assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &TREASURY), 90);
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &TREASURY), 0);
+		assert_eq!(Bounties::bounty(0), None);
+		assert_noop!(Bounties::cancel_bounty(0), "Bounty does not exist");
+	});
+}
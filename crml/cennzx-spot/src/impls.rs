@@ -19,10 +19,10 @@
 use super::{Module, Trait};
 use cennznet_primitives::FeeExchange;
 use fees::BuyFeeAsset;
-use rstd::{marker::PhantomData, mem, prelude::*};
-use runtime_primitives::traits::Hash;
+use rstd::{marker::PhantomData, mem, prelude::*, result};
+use runtime_primitives::traits::{CheckedSub, Hash};
 use primitives::crypto::{UncheckedFrom, UncheckedInto};
-use support::dispatch::Result;
+use support::traits::WithdrawReason;
 
 /// A function that generates an `AccountId` for a CENNZX-SPOT exchange / (core, asset) pair
 pub trait ExchangeAddressFor<AssetId: Sized, AccountId: Sized> {
@@ -53,21 +53,68 @@ fn u64_to_bytes(x: u64) -> [u8; 8] {
 
 impl<T: Trait> BuyFeeAsset<T::AccountId, T::Balance> for Module<T> {
 	type FeeExchange = FeeExchange<T::Balance>;
-	/// Use the CENNZX-Spot exchange to seamlessly buy fee asset
-	fn buy_fee_asset(who: &T::AccountId, amount: T::Balance, exchange_op: &FeeExchange<T::Balance>) -> Result {
+	type AssetId = T::AssetId;
+	/// Use the CENNZX-Spot exchange to seamlessly buy fee asset.
+	/// The fee exchange asset is tried first, then each fallback in order, until one can pay within its
+	/// max. payment. Fails with the reason the first asset could not pay if none can.
+	fn buy_fee_asset(
+		who: &T::AccountId,
+		amount: T::Balance,
+		exchange_op: &FeeExchange<T::Balance>,
+	) -> result::Result<(T::AssetId, T::Balance), &'static str> {
 		// TODO: Hard coded to use spending asset ID
 		let fee_asset_id: T::AssetId = <generic_asset::Module<T>>::spending_asset_id();
-		Self::make_asset_swap_output(
-			&who,
-			&who,
-			&T::AssetId::from(exchange_op.asset_id),
-			&fee_asset_id,
-			amount,
-			exchange_op.max_payment,
-			Self::fee_rate(),
-		)
-		.map(|_| ())
-		.map_err(|_| "Failed to charge transaction fees during conversion")
+
+		let mut first_error = None;
+		for (asset_id, max_payment) in exchange_op.candidates() {
+			let asset_sold = T::AssetId::from(asset_id);
+			// Storage is not rolled back on failure, so check the payment is possible before swapping
+			match Self::ensure_can_pay_fee(who, &asset_sold, &fee_asset_id, amount, max_payment) {
+				Ok(sell_amount) => {
+					return Self::make_asset_swap_output(
+						&who,
+						&who,
+						&asset_sold,
+						&fee_asset_id,
+						amount,
+						max_payment,
+						Self::fee_rate(),
+					)
+					.map(|_| (asset_sold, sell_amount))
+					.map_err(|_| "Failed to charge transaction fees during conversion");
+				}
+				Err(err) => {
+					first_error.get_or_insert(err);
+				}
+			}
+		}
+
+		Err(first_error.unwrap_or("Failed to charge transaction fees during conversion"))
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Return the amount of `asset_sold` to sell iff `who` can sell at most `max_payment` of it to buy `amount`
+	/// of the fee asset
+	fn ensure_can_pay_fee(
+		who: &T::AccountId,
+		asset_sold: &T::AssetId,
+		fee_asset_id: &T::AssetId,
+		amount: T::Balance,
+		max_payment: T::Balance,
+	) -> result::Result<T::Balance, &'static str> {
+		let sell_amount = Self::get_asset_swap_output_price(asset_sold, fee_asset_id, amount, Self::fee_rate())
+			.map_err(|_| "Failed to charge transaction fees during conversion")?;
+		if sell_amount > max_payment {
+			return Err("Failed to charge transaction fees during conversion");
+		}
+		let new_balance = <generic_asset::Module<T>>::free_balance(asset_sold, who)
+			.checked_sub(&sell_amount)
+			.ok_or_else(|| "Failed to charge transaction fees during conversion")?;
+		// Locked balances, e.g. bonded staking asset, may not be sold to pay fees
+		<generic_asset::Module<T>>::ensure_can_withdraw(asset_sold, who, sell_amount, WithdrawReason::Fee, new_balance)
+			.map_err(|_| "Fee exchange can not sell locked balance")?;
+		Ok(sell_amount)
 	}
 }
 
@@ -92,11 +139,10 @@ pub(crate) mod impl_tests {
 
 			let user = with_account!(CORE_ASSET => 0, OTHER_ASSET => 100);
 
-			assert_ok!(<CennzXSpot as BuyFeeAsset<_, _>>::buy_fee_asset(
-				&user,
-				51,
-				&FeeExchange::new(OTHER_ASSET, 1_000_000),
-			));
+			assert_eq!(
+				<CennzXSpot as BuyFeeAsset<_, _>>::buy_fee_asset(&user, 51, &FeeExchange::new(OTHER_ASSET, 1_000_000)),
+				Ok((OTHER_ASSET, 58))
+			);
 
 			assert_exchange_balance_eq!(CORE_ASSET => 946, OTHER_ASSET => 1058);
 			assert_exchange_balance_eq!(CORE_ASSET => 1054, FEE_ASSET => 949);
@@ -121,6 +167,64 @@ pub(crate) mod impl_tests {
 		});
 	}
 
+	#[test]
+	fn buy_fee_asset_respects_fee_locks() {
+		with_externalities(&mut ExtBuilder::default().build(), || {
+			with_exchange!(CORE_ASSET => 1000, FEE_ASSET => 1000);
+
+			// the core asset is also the staking asset
+			let user = with_account!(CORE_ASSET => 100, OTHER_ASSET => 0);
+			<generic_asset::Module<Test>>::set_lock_with_metadata(
+				&CORE_ASSET,
+				*b"staking ",
+				&user,
+				90,
+				1_000,
+				WithdrawReason::Fee.into(),
+				None,
+			);
+
+			assert_err!(
+				<CennzXSpot as BuyFeeAsset<_, _>>::buy_fee_asset(&user, 51, &FeeExchange::new(CORE_ASSET, 1_000_000)),
+				"Fee exchange can not sell locked balance"
+			);
+			assert_balance_eq!(user, CORE_ASSET => 100);
+
+			// unlocked balance may still be sold
+			assert!(
+				<CennzXSpot as BuyFeeAsset<_, _>>::buy_fee_asset(&user, 5, &FeeExchange::new(CORE_ASSET, 1_000_000))
+					.is_ok()
+			);
+		});
+	}
+
+	#[test]
+	fn buy_fee_asset_falls_back_to_the_next_asset() {
+		with_externalities(&mut ExtBuilder::default().build(), || {
+			with_exchange!(CORE_ASSET => 1000, OTHER_ASSET => 1000);
+			with_exchange!(CORE_ASSET => 1000, FEE_ASSET => 1000);
+
+			// too little of the other asset, the core asset pays instead
+			let user = with_account!(CORE_ASSET => 100, OTHER_ASSET => 10);
+			let exchange = FeeExchange::new(OTHER_ASSET, 1_000_000).with_fallbacks(vec![(CORE_ASSET, 1_000_000)]);
+			assert_eq!(
+				<CennzXSpot as BuyFeeAsset<_, _>>::buy_fee_asset(&user, 51, &exchange),
+				Ok((CORE_ASSET, 54))
+			);
+			assert_balance_eq!(user, OTHER_ASSET => 10);
+			assert_balance_eq!(user, CORE_ASSET => 46);
+
+			// the first asset's failure is reported when no asset can pay
+			let exchange = FeeExchange::new(OTHER_ASSET, 1_000_000).with_fallbacks(vec![(CORE_ASSET, 1)]);
+			assert_err!(
+				<CennzXSpot as BuyFeeAsset<_, _>>::buy_fee_asset(&user, 51, &exchange),
+				"Failed to charge transaction fees during conversion"
+			);
+			assert_balance_eq!(user, OTHER_ASSET => 10);
+			assert_balance_eq!(user, CORE_ASSET => 46);
+		});
+	}
+
 	#[test]
 	fn u64_to_bytes_works() {
 		assert_eq!(
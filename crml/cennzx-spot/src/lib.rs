@@ -23,15 +23,18 @@ mod tests;
 mod impls;
 mod types;
 pub use impls::{ExchangeAddressFor, ExchangeAddressGenerator};
-pub use types::FeeRate;
+pub use types::{FeeRate, LiquidityLock};
 
 #[macro_use]
 extern crate srml_support as support;
 
+use cennznet_primitives::fixed_point::{mul_div, Rounding, SCALE};
 use generic_asset;
 use rstd::prelude::*;
-use runtime_primitives::traits::{As, Bounded, One, Zero};
-use support::{dispatch::Result, Dispatchable, Parameter, StorageDoubleMap, StorageMap, StorageValue};
+use runtime_primitives::traits::{As, Bounded, One, Saturating, Zero};
+use support::{
+	dispatch::Result, traits::WithdrawReason, Dispatchable, Parameter, StorageDoubleMap, StorageMap, StorageValue,
+};
 use system::ensure_signed;
 
 // (core_asset_id, asset_id)
@@ -40,6 +43,18 @@ pub type ExchangeKey<T> = (
 	<T as generic_asset::Trait>::AssetId,
 );
 
+/// The maximum number of assets an account may have automatically sold
+pub const AUTO_SELL_ASSETS_MAX: usize = 8;
+
+/// The maximum number of accounts whose auto-sell assets are sold each block
+pub const AUTO_SELL_ACCOUNTS_PER_BLOCK: u32 = 10;
+
+/// The maximum number of accounts with auto-sell assets
+pub const AUTO_SELL_ACCOUNTS_MAX: u32 = 10_000;
+
+/// Identifies the core asset deposit reserved while an account has auto-sell assets
+pub const AUTO_SELL_RESERVE_ID: generic_asset::ReserveIdentifier = *b"autosell";
+
 pub trait Trait: system::Trait + generic_asset::Trait {
 	type Call: Parameter + Dispatchable<Origin = <Self as system::Trait>::Origin>;
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
@@ -53,6 +68,10 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		fn deposit_event<T>() = default;
 
+		fn on_finalize(_n: T::BlockNumber) {
+			Self::process_auto_sells();
+		}
+
 		/// Convert asset1 to asset2. User specifies maximum
 		/// input and exact output.
 		///  origin
@@ -70,6 +89,7 @@ decl_module! {
 			#[compact] max_paying_amount: T::Balance
 		) -> Result {
 			let buyer = ensure_signed(origin)?;
+			Self::ensure_fee_exchange_reserve_for_output(&asset_sold, &asset_bought, buy_amount)?;
 			let _ = Self::make_asset_swap_output(
 				&buyer,
 				&recipient.unwrap_or_else(|| buyer.clone()),
@@ -82,7 +102,6 @@ decl_module! {
 			Ok(())
 		}
 
-
 		/// Convert asset1 to asset2
 		/// Seller specifies exact input (asset 1) and minimum output (asset 2)
 		/// `recipient` - Account to receive asset_bought, defaults to origin if None
@@ -99,6 +118,7 @@ decl_module! {
 			#[compact] min_receive: T::Balance
 		) -> Result {
 			let seller = ensure_signed(origin)?;
+			Self::ensure_fee_exchange_reserve_for_input(&asset_sold, &asset_bought, sell_amount)?;
 			let _ = Self::make_asset_swap_input(
 				&seller,
 				&recipient.unwrap_or_else(|| seller.clone()),
@@ -157,8 +177,9 @@ decl_module! {
 				// TODO: shall i use total_balance instead? in which case the exchange address will have reserve balance?
 				let trade_asset_reserve = <generic_asset::Module<T>>::free_balance(&asset_id, &exchange_address);
 				let core_asset_reserve = <generic_asset::Module<T>>::free_balance(&core_asset_id, &exchange_address);
-				let trade_asset_amount = core_amount * trade_asset_reserve / core_asset_reserve + One::one();
-				let liquidity_minted = core_amount * total_liquidity / core_asset_reserve;
+				let trade_asset_amount =
+					Self::balance_mul_div(core_amount, trade_asset_reserve, core_asset_reserve)? + One::one();
+				let liquidity_minted = Self::balance_mul_div(core_amount, total_liquidity, core_asset_reserve)?;
 				ensure!(liquidity_minted >= min_liquidity, "Minimum liquidity is required");
 				ensure!(max_asset_amount >= trade_asset_amount, "Token liquidity check unsuccessful");
 
@@ -193,6 +214,10 @@ decl_module! {
 			let exchange_key = (core_asset_id, asset_id);
 			let account_liquidity = Self::get_liquidity(&exchange_key, &from_account);
 			ensure!(account_liquidity >= liquidity_withdrawn, "Tried to overdraw liquidity");
+			ensure!(
+				account_liquidity - Self::locked_liquidity(&exchange_key, &from_account) >= liquidity_withdrawn,
+				"Liquidity is timelocked"
+			);
 
 			let total_liquidity = Self::get_total_supply(&exchange_key);
 			let exchange_address = T::ExchangeAddressGenerator::exchange_address_for(core_asset_id, asset_id);
@@ -200,8 +225,8 @@ decl_module! {
 
 			let trade_asset_reserve = <generic_asset::Module<T>>::free_balance(&asset_id, &exchange_address);
 			let core_asset_reserve = <generic_asset::Module<T>>::free_balance(&core_asset_id, &exchange_address);
-			let core_asset_amount = liquidity_withdrawn * core_asset_reserve / total_liquidity;
-			let trade_asset_amount = liquidity_withdrawn * trade_asset_reserve / total_liquidity;
+			let core_asset_amount = Self::balance_mul_div(liquidity_withdrawn, core_asset_reserve, total_liquidity)?;
+			let trade_asset_amount = Self::balance_mul_div(liquidity_withdrawn, trade_asset_reserve, total_liquidity)?;
 			ensure!(core_asset_amount >= min_core_withdraw, "Minimum core asset is required");
 			ensure!(trade_asset_amount >= min_asset_withdraw, "Minimum trade asset is required");
 
@@ -210,6 +235,16 @@ decl_module! {
 			Self::set_liquidity(&exchange_key, &from_account,
 									account_liquidity - liquidity_withdrawn);
 			Self::burn_total_supply(&exchange_key, liquidity_withdrawn);
+			// Withdrawing forfeits the bonus of any lock, the liquidity still locked stays locked
+			if Self::locked_liquidity(&exchange_key, &from_account).is_zero() {
+				<LiquidityLocks<T>>::remove(&exchange_key, &from_account);
+			} else {
+				<LiquidityLocks<T>>::mutate(&exchange_key, &from_account, |lock| {
+					if let Some(lock) = lock {
+						lock.bonus = Zero::zero();
+					}
+				});
+			}
 			Self::deposit_event(RawEvent::RemoveLiquidity(from_account, core_asset_amount, asset_id, trade_asset_amount));
 			Ok(())
 		}
@@ -220,6 +255,137 @@ decl_module! {
 			Ok(())
 		}
 
+		/// Timelock liquidity in an exchange for `LiquidityLockPeriod` blocks.
+		/// Locked liquidity earns bonus liquidity at `LiquidityLockBonus`. Bonus liquidity can not be withdrawn,
+		/// it gives the provider a larger share of the swap fees the exchange accrues while locked, paid out of
+		/// those fees with `claim_liquidity_bonus` once the lock expires. Withdrawing liquidity forfeits the bonus.
+		///
+		/// `asset_id` - The trade asset ID
+		/// `liquidity_locked` - Amount of liquidity to lock
+		pub fn lock_liquidity(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			#[compact] liquidity_locked: T::Balance
+		) -> Result {
+			let from_account = ensure_signed(origin)?;
+			ensure!(!liquidity_locked.is_zero(), "Amount of liquidity to lock should exist");
+
+			let exchange_key = (Self::core_asset_id(), asset_id);
+			ensure!(
+				!<LiquidityLocks<T>>::exists(&exchange_key, &from_account),
+				"Liquidity is already locked, claim its bonus first"
+			);
+			ensure!(
+				Self::get_liquidity(&exchange_key, &from_account) >= liquidity_locked,
+				"Not enough unlocked liquidity"
+			);
+
+			let bonus: T::Balance =
+				(Self::liquidity_lock_bonus() * Into::<T::AsBalance>::into(liquidity_locked)).into();
+			let lock = LiquidityLock {
+				amount: liquidity_locked,
+				until: <system::Module<T>>::block_number() + Self::liquidity_lock_period(),
+				bonus,
+				accrued_fees: Self::accrued_fees(&exchange_key),
+			};
+
+			Self::deposit_event(RawEvent::LiquidityLocked(from_account.clone(), asset_id, liquidity_locked, bonus, lock.until));
+			<LiquidityLocks<T>>::insert(&exchange_key, &from_account, lock);
+			Ok(())
+		}
+
+		/// Set the liquidity timelock period and the bonus rate paid on locked liquidity (root only).
+		/// The bonus rate is at most 100%, so bonuses never claim more than the fees an exchange accrues.
+		pub fn set_liquidity_lock(period: T::BlockNumber, bonus: FeeRate) -> Result {
+			ensure!(!period.is_zero(), "Liquidity lock period must be greater than zero");
+			ensure!(bonus.as_() <= FeeRate::one().as_(), "Liquidity lock bonus can not exceed 100%");
+			<LiquidityLockPeriod<T>>::put(period);
+			<LiquidityLockBonus<T>>::put(bonus);
+			Ok(())
+		}
+
+		/// Set the fraction of each exchange's core asset reserve which may only be used
+		/// for fee exchange conversions (root only)
+		pub fn set_fee_exchange_reserve(new_reserve: FeeRate) -> Result {
+			ensure!(new_reserve.as_() <= FeeRate::one().as_(), "Fee exchange reserve can not exceed 100%");
+			<FeeExchangeReserve<T>>::put(new_reserve);
+			Ok(())
+		}
+
+		/// Set the assets which are automatically sold into core asset when held by the origin.
+		/// Sales happen at the current exchange price, for a bounded number of accounts each block.
+		/// Enrolling reserves `AutoSellDeposit` of core asset, an empty list opts out and returns it.
+		pub fn set_auto_sell(origin, assets: Vec<T::AssetId>) -> Result {
+			let who = ensure_signed(origin)?;
+			ensure!(assets.len() <= AUTO_SELL_ASSETS_MAX, "Too many auto-sell assets");
+			let core_asset_id = Self::core_asset_id();
+			ensure!(!assets.contains(&core_asset_id), "Core asset can not be auto-sold");
+
+			let enrolment = <AutoSellPosition<T>>::get(&who);
+			if assets.is_empty() {
+				<AutoSell<T>>::remove(&who);
+				if let Some((position, deposit)) = enrolment {
+					Self::remove_auto_sell_account(&who, position);
+					<generic_asset::Module<T>>::unreserve_named(&AUTO_SELL_RESERVE_ID, &core_asset_id, &who, deposit);
+				}
+			} else {
+				if enrolment.is_none() {
+					let count = Self::auto_sell_account_count();
+					ensure!(count < AUTO_SELL_ACCOUNTS_MAX, "Too many auto-sell accounts");
+					let deposit = Self::auto_sell_deposit();
+					if !deposit.is_zero() {
+						<generic_asset::Module<T>>::reserve_named(&AUTO_SELL_RESERVE_ID, &core_asset_id, &who, deposit)
+							.map_err(|_| "Not enough funds to pay the auto-sell deposit")?;
+					}
+					<AutoSellAccountAt<T>>::insert(count, who.clone());
+					<AutoSellPosition<T>>::insert(&who, (count, deposit));
+					<AutoSellAccountCount<T>>::put(count + 1);
+				}
+				<AutoSell<T>>::insert(&who, assets);
+			}
+			Ok(())
+		}
+
+		/// Pay the bonus of an expired liquidity lock out of the swap fees the exchange accrued since the lock
+		/// started, in proportion to the bonus liquidity's share of the exchange's liquidity, and remove the lock.
+		///
+		/// `asset_id` - The trade asset ID
+		pub fn claim_liquidity_bonus(origin, #[compact] asset_id: T::AssetId) -> Result {
+			let who = ensure_signed(origin)?;
+			let core_asset_id = Self::core_asset_id();
+			let exchange_key = (core_asset_id, asset_id);
+			let lock = Self::liquidity_lock(&exchange_key, &who).ok_or("No liquidity lock to claim")?;
+			ensure!(lock.until <= <system::Module<T>>::block_number(), "Liquidity is timelocked");
+
+			let (core_fees, trade_fees) = Self::accrued_fees(&exchange_key);
+			let total_liquidity = Self::get_total_supply(&exchange_key);
+			let (core_paid, trade_paid) = if total_liquidity.is_zero() {
+				(Zero::zero(), Zero::zero())
+			} else {
+				(
+					Self::balance_mul_div(core_fees - lock.accrued_fees.0, lock.bonus, total_liquidity)?,
+					Self::balance_mul_div(trade_fees - lock.accrued_fees.1, lock.bonus, total_liquidity)?,
+				)
+			};
+
+			let exchange_address = T::ExchangeAddressGenerator::exchange_address_for(core_asset_id, asset_id);
+			if !core_paid.is_zero() {
+				<generic_asset::Module<T>>::make_transfer(&core_asset_id, &exchange_address, &who, core_paid)?;
+			}
+			if !trade_paid.is_zero() {
+				<generic_asset::Module<T>>::make_transfer(&asset_id, &exchange_address, &who, trade_paid)?;
+			}
+			<LiquidityLocks<T>>::remove(&exchange_key, &who);
+			Self::deposit_event(RawEvent::LiquidityBonusPaid(who, asset_id, core_paid, trade_paid));
+			Ok(())
+		}
+
+		/// Set the core asset deposit reserved by accounts enrolling for auto-sells (root only).
+		/// Accounts already enrolled keep the deposit they reserved.
+		pub fn set_auto_sell_deposit(#[compact] deposit: T::Balance) -> Result {
+			<AutoSellDeposit<T>>::put(deposit);
+			Ok(())
+		}
 	}
 
 }
@@ -229,7 +395,8 @@ decl_event!(
 	where
 		<T as system::Trait>::AccountId,
 		<T as generic_asset::Trait>::AssetId,
-		<T as generic_asset::Trait>::Balance
+		<T as generic_asset::Trait>::Balance,
+		<T as system::Trait>::BlockNumber
 	{
 		// Provider, core asset amount, trade asset id, trade asset amount
 		AddLiquidity(AccountId, Balance, AssetId, Balance),
@@ -237,6 +404,10 @@ decl_event!(
 		RemoveLiquidity(AccountId, Balance, AssetId, Balance),
 	    // AssetSold, AssetBought, Buyer, SoldAmount, BoughtAmount
 		AssetPurchase(AssetId, AssetId, AccountId, Balance, Balance),
+		// Provider, trade asset id, liquidity locked, bonus liquidity, locked until
+		LiquidityLocked(AccountId, AssetId, Balance, Balance, BlockNumber),
+		// Provider, trade asset id, core asset fees paid, trade asset fees paid
+		LiquidityBonusPaid(AccountId, AssetId, Balance, Balance),
 	}
 );
 
@@ -246,6 +417,8 @@ decl_storage! {
 		pub CoreAssetId get(core_asset_id) config(): T::AssetId;
 		/// Default Trading fee rate
 		pub DefaultFeeRate get(fee_rate) config(): FeeRate;
+		/// The fraction of each exchange's core asset reserve which may only be used for fee exchange conversions
+		pub FeeExchangeReserve get(fee_exchange_reserve) config(): FeeRate;
 		/// Total supply of exchange token in existence.
 		/// it will always be less than the core asset's total supply
 		/// Key: `(asset id, core asset id)`
@@ -254,6 +427,31 @@ decl_storage! {
 		/// Asset balance of each user in each exchange pool.
 		/// Key: `(core_asset_id, trade_asset_id), account_id`
 		pub LiquidityBalance get(liquidity_balance): double_map  ExchangeKey<T>, twox_128(T::AccountId) => T::Balance;
+
+		/// Timelocked liquidity of each user in each exchange pool.
+		/// Key: `(core_asset_id, trade_asset_id), account_id`
+		pub LiquidityLocks get(liquidity_lock): double_map ExchangeKey<T>, twox_128(T::AccountId) => Option<LiquidityLock<T::Balance, T::BlockNumber>>;
+		/// Number of blocks liquidity stays timelocked for
+		pub LiquidityLockPeriod get(liquidity_lock_period) config(): T::BlockNumber;
+		/// Bonus liquidity earned by timelocked liquidity, as a fraction of the liquidity locked
+		pub LiquidityLockBonus get(liquidity_lock_bonus) config(): FeeRate;
+		/// The core and trade asset fees paid into each exchange by swaps, ever increasing.
+		/// Key: `(core_asset_id, trade_asset_id)`
+		pub AccruedFees get(accrued_fees): map ExchangeKey<T> => (T::Balance, T::Balance);
+
+		/// The assets each account has automatically sold into core asset
+		pub AutoSell get(auto_sell): map T::AccountId => Vec<T::AssetId>;
+		/// The core asset deposit an account reserves while it has auto-sell assets
+		pub AutoSellDeposit get(auto_sell_deposit) config(): T::Balance;
+		/// The number of accounts with auto-sell assets
+		pub AutoSellAccountCount get(auto_sell_account_count): u32;
+		/// The accounts with auto-sell assets by position, from zero to the account count, in the order they are
+		/// processed. A leaving account's position is taken by the last account.
+		pub AutoSellAccountAt get(auto_sell_account_at): map u32 => T::AccountId;
+		/// The position of an account in `AutoSellAccountAt` and the deposit it reserved
+		AutoSellPosition: map T::AccountId => Option<(u32, T::Balance)>;
+		/// The position in `AutoSellAccountAt` of the next account to process
+		AutoSellCursor: u32;
 	}
 }
 
@@ -281,6 +479,86 @@ impl<T: Trait> Module<T> {
 		<LiquidityBalance<T>>::get(exchange_key, who)
 	}
 
+	/// Record the fee included in `amount` of `paid_asset_id` paid into the `asset_id` exchange by a swap at
+	/// `fee_rate`, for liquidity lock bonuses to be paid out of
+	fn note_fee(asset_id: &T::AssetId, paid_asset_id: &T::AssetId, amount: T::Balance, fee_rate: FeeRate) {
+		let amount_less_fee: T::Balance =
+			FeeRate::div(Into::<T::AsBalance>::into(amount), FeeRate::one() + fee_rate).into();
+		let fee = amount.saturating_sub(amount_less_fee);
+		<AccruedFees<T>>::mutate(&(Self::core_asset_id(), *asset_id), |(core_fees, trade_fees)| {
+			if *paid_asset_id == Self::core_asset_id() {
+				*core_fees = core_fees.saturating_add(fee);
+			} else {
+				*trade_fees = trade_fees.saturating_add(fee);
+			}
+		});
+	}
+
+	/// The liquidity `who` can not withdraw yet from an exchange pool
+	pub fn locked_liquidity(exchange_key: &ExchangeKey<T>, who: &T::AccountId) -> T::Balance {
+		match <LiquidityLocks<T>>::get(exchange_key, who) {
+			Some(ref lock) if lock.until > <system::Module<T>>::block_number() => lock.amount,
+			_ => Zero::zero(),
+		}
+	}
+
+	/// Ensure a trade taking `core_amount` of core asset out of the `asset_id` exchange leaves the
+	/// core asset liquidity which is reserved for fee exchange conversions.
+	fn ensure_fee_exchange_reserve(asset_id: &T::AssetId, core_amount: T::Balance) -> Result {
+		let core_asset_id = Self::core_asset_id();
+		let exchange_address = T::ExchangeAddressGenerator::exchange_address_for(core_asset_id, *asset_id);
+		let core_reserve = <generic_asset::Module<T>>::free_balance(&core_asset_id, &exchange_address);
+		let reserved: T::Balance = (Self::fee_exchange_reserve() * Into::<T::AsBalance>::into(core_reserve)).into();
+
+		ensure!(
+			core_reserve >= core_amount && core_reserve - core_amount >= reserved,
+			"Trade would use core asset liquidity reserved for fee exchange"
+		);
+		Ok(())
+	}
+
+	/// Check the fee exchange reserve for a trade with exact output, see `make_asset_swap_output`
+	fn ensure_fee_exchange_reserve_for_output(
+		asset_sold: &T::AssetId,
+		asset_bought: &T::AssetId,
+		buy_amount: T::Balance,
+	) -> Result {
+		let core_asset_id = Self::core_asset_id();
+		// Nothing is reserved, or no core asset leaves an exchange
+		if Self::fee_exchange_reserve() == FeeRate::default()
+			|| *asset_sold == core_asset_id
+			|| asset_sold == asset_bought
+		{
+			return Ok(());
+		}
+
+		let core_amount = if *asset_bought == core_asset_id {
+			buy_amount
+		} else {
+			Self::get_core_to_asset_output_price(asset_bought, buy_amount, Self::fee_rate())?
+		};
+		Self::ensure_fee_exchange_reserve(asset_sold, core_amount)
+	}
+
+	/// Check the fee exchange reserve for a trade with exact input, see `make_asset_swap_input`
+	fn ensure_fee_exchange_reserve_for_input(
+		asset_sold: &T::AssetId,
+		asset_bought: &T::AssetId,
+		sell_amount: T::Balance,
+	) -> Result {
+		let core_asset_id = Self::core_asset_id();
+		// Nothing is reserved, or no core asset leaves an exchange
+		if Self::fee_exchange_reserve() == FeeRate::default()
+			|| *asset_sold == core_asset_id
+			|| asset_sold == asset_bought
+		{
+			return Ok(());
+		}
+
+		let core_amount = Self::get_asset_to_core_input_price(asset_sold, sell_amount, Self::fee_rate())?;
+		Self::ensure_fee_exchange_reserve(asset_sold, core_amount)
+	}
+
 	/// Trade core asset for asset (`asset_id`) at the given `fee_rate`.
 	/// `seller` - The address selling input asset
 	/// `recipient` - The address receiving payment of output asset
@@ -317,6 +595,7 @@ impl<T: Trait> Module<T> {
 		let _ = <generic_asset::Module<T>>::make_transfer(&core_asset_id, seller, &exchange_address, sell_amount).and(
 			<generic_asset::Module<T>>::make_transfer(asset_id, &exchange_address, recipient, sale_value),
 		);
+		Self::note_fee(asset_id, &core_asset_id, sell_amount, fee_rate);
 
 		Self::deposit_event(RawEvent::AssetPurchase(
 			core_asset_id,
@@ -362,6 +641,7 @@ impl<T: Trait> Module<T> {
 		let _ = <generic_asset::Module<T>>::make_transfer(asset_id, buyer, &exchange_address, sold_amount).and(
 			<generic_asset::Module<T>>::make_transfer(&core_asset_id, &exchange_address, recipient, buy_amount),
 		);
+		Self::note_fee(asset_id, asset_id, sold_amount, fee_rate);
 
 		Self::deposit_event(RawEvent::AssetPurchase(
 			*asset_id,
@@ -409,6 +689,7 @@ impl<T: Trait> Module<T> {
 		let _ = <generic_asset::Module<T>>::make_transfer(&core_asset_id, buyer, &exchange_address, sold_amount).and(
 			<generic_asset::Module<T>>::make_transfer(asset_id, &exchange_address, recipient, buy_amount),
 		);
+		Self::note_fee(asset_id, &core_asset_id, sold_amount, fee_rate);
 
 		Self::deposit_event(RawEvent::AssetPurchase(
 			core_asset_id,
@@ -478,6 +759,8 @@ impl<T: Trait> Module<T> {
 				recipient,
 				buy_amount_for_b,
 			));
+		Self::note_fee(asset_a, asset_a, asset_sold_a, fee_rate);
+		Self::note_fee(asset_b, &core_asset_id, core_asset_a, fee_rate);
 
 		Self::deposit_event(RawEvent::AssetPurchase(
 			*asset_a,         // asset sold
@@ -490,6 +773,56 @@ impl<T: Trait> Module<T> {
 		Ok(asset_sold_a)
 	}
 
+	/// Sell the auto-sell assets of the next `AUTO_SELL_ACCOUNTS_PER_BLOCK` enrolled accounts into core asset.
+	/// Sales which fail, e.g. due to missing liquidity or locked balances, are skipped until the next round.
+	fn process_auto_sells() {
+		let count = Self::auto_sell_account_count();
+		if count == 0 {
+			return;
+		}
+		let start = <AutoSellCursor<T>>::get() % count;
+		let fee_rate = Self::fee_rate();
+
+		for i in 0..AUTO_SELL_ACCOUNTS_PER_BLOCK.min(count) {
+			let who = Self::auto_sell_account_at((start + i) % count);
+			for asset_id in Self::auto_sell(&who) {
+				let amount = <generic_asset::Module<T>>::free_balance(&asset_id, &who);
+				if amount.is_zero() {
+					continue;
+				}
+				let _ = Self::auto_sell_asset(&who, &asset_id, amount, fee_rate);
+			}
+		}
+
+		<AutoSellCursor<T>>::put((start + AUTO_SELL_ACCOUNTS_PER_BLOCK) % count);
+	}
+
+	/// Remove `who` at `position` from the accounts with auto-sell assets, the last account takes its position
+	fn remove_auto_sell_account(who: &T::AccountId, position: u32) {
+		let last = Self::auto_sell_account_count().saturating_sub(1);
+		if position != last {
+			let moved = Self::auto_sell_account_at(last);
+			<AutoSellAccountAt<T>>::insert(position, moved.clone());
+			<AutoSellPosition<T>>::mutate(&moved, |enrolment| {
+				if let Some((moved_position, _)) = enrolment {
+					*moved_position = position;
+				}
+			});
+		}
+		<AutoSellAccountAt<T>>::remove(last);
+		<AutoSellPosition<T>>::remove(who);
+		<AutoSellAccountCount<T>>::put(last);
+	}
+
+	/// Sell all `amount` of `who`'s `asset_id` into core asset at the current price
+	fn auto_sell_asset(who: &T::AccountId, asset_id: &T::AssetId, amount: T::Balance, fee_rate: FeeRate) -> Result {
+		<generic_asset::Module<T>>::ensure_can_withdraw(asset_id, who, amount, WithdrawReason::Transfer, Zero::zero())?;
+		<generic_asset::Module<T>>::ensure_mint_cooldown_passed(asset_id, who, Zero::zero())?;
+		Self::ensure_fee_exchange_reserve_for_input(asset_id, &Self::core_asset_id(), amount)?;
+		let sale_value = Self::get_asset_to_core_input_price(asset_id, amount, fee_rate)?;
+		Self::make_asset_to_core_input(who, who, asset_id, amount, sale_value, fee_rate).map(|_| ())
+	}
+
 	/// Convert trade asset to core asset. User specifies exact
 	/// input (trade asset) and minimum output.
 	///
@@ -527,6 +860,7 @@ impl<T: Trait> Module<T> {
 		let _ = <generic_asset::Module<T>>::make_transfer(asset_id, buyer, &exchange_address, sell_amount).and(
 			<generic_asset::Module<T>>::make_transfer(&core_asset_id, &exchange_address, recipient, sale_value),
 		);
+		Self::note_fee(asset_id, asset_id, sell_amount, fee_rate);
 
 		Self::deposit_event(RawEvent::AssetPurchase(
 			*asset_id,
@@ -593,6 +927,8 @@ impl<T: Trait> Module<T> {
 				recipient,
 				asset_b_received,
 			));
+		Self::note_fee(asset_a, asset_a, sell_amount_for_a, fee_rate);
+		Self::note_fee(asset_b, &core_asset_id, sale_value_a, fee_rate);
 
 		Self::deposit_event(RawEvent::AssetPurchase(
 			*asset_a,          // asset sold
@@ -612,6 +948,25 @@ impl<T: Trait> Module<T> {
 	/// `asset_id` - Trade asset
 	/// `buy_amount`- Amount of the trade asset to buy
 	/// Returns the amount of core asset needed to purchase `buy_amount` of trade asset.
+	/// Returns the amount of `asset_sold` needed to buy `buy_amount` of `asset_bought`
+	pub fn get_asset_swap_output_price(
+		asset_sold: &T::AssetId,
+		asset_bought: &T::AssetId,
+		buy_amount: T::Balance,
+		fee_rate: FeeRate,
+	) -> rstd::result::Result<T::Balance, &'static str> {
+		ensure!(asset_sold != asset_bought, "Asset to swap should not be equal");
+		let core_asset = Self::core_asset_id();
+		if *asset_sold == core_asset {
+			Self::get_core_to_asset_output_price(asset_bought, buy_amount, fee_rate)
+		} else if *asset_bought == core_asset {
+			Self::get_asset_to_core_output_price(asset_sold, buy_amount, fee_rate)
+		} else {
+			let core_amount = Self::get_core_to_asset_output_price(asset_bought, buy_amount, fee_rate)?;
+			Self::get_asset_to_core_output_price(asset_sold, core_amount, fee_rate)
+		}
+	}
+
 	pub fn get_core_to_asset_output_price(
 		asset_id: &T::AssetId,
 		buy_amount: T::Balance,
@@ -658,6 +1013,19 @@ impl<T: Trait> Module<T> {
 		))
 	}
 
+	/// Calculate `x * y / denominator` rounded down, without overflowing before the division
+	fn balance_mul_div(
+		x: T::Balance,
+		y: T::Balance,
+		denominator: T::Balance,
+	) -> rstd::result::Result<T::Balance, &'static str> {
+		let to_u128 = |balance: T::Balance| -> u128 { Into::<T::AsBalance>::into(balance).as_() };
+		let result = mul_div(to_u128(x), to_u128(y), to_u128(denominator), Rounding::Down)
+			.ok_or("exchange ratio calculation overflow")?;
+		let result: T::AsBalance = As::sa(result);
+		Ok(result.into())
+	}
+
 	fn get_output_price(
 		output_amount: T::Balance,
 		input_reserve: T::Balance,
@@ -673,12 +1041,14 @@ impl<T: Trait> Module<T> {
 			return T::Balance::max_value();
 		}
 
-		let numerator: T::Balance = input_reserve * output_amount;
-		let denominator = output_reserve - output_amount;
-		let output: T::Balance = numerator / denominator + One::one();
+		let input_reserve: u128 = Into::<T::AsBalance>::into(input_reserve).as_();
+		let output_reserve: u128 = Into::<T::AsBalance>::into(output_reserve).as_();
+		let output_amount: u128 = Into::<T::AsBalance>::into(output_amount).as_();
+		let output = match mul_div(input_reserve, output_amount, output_reserve - output_amount, Rounding::Down) {
+			Some(output) => output.saturating_add(1),
+			None => return T::Balance::max_value(),
+		};
 
-		let output: T::AsBalance = output.into();
-		let output: u128 = output.as_();
 		let result = (FeeRate::one() + fee_rate) * output;
 		let result: T::AsBalance = As::sa(result);
 		Into::<T::Balance>::into(result)
@@ -693,18 +1063,23 @@ impl<T: Trait> Module<T> {
 		if input_reserve.is_zero() || output_reserve.is_zero() {
 			return Zero::zero();
 		}
-		let div_rate = FeeRate::one() + fee_rate;
-		// This operation rounds away necessary decimal points. In order to-
-		// counteract this, we scale the input amount
-		let input_amount_less_fee_scaled = FeeRate::div(
-			Into::<T::AsBalance>::into(input_amount * 1_000_000.into()), // scale up
-			div_rate,
-		);
-		let numerator: T::Balance = input_amount_less_fee_scaled.into() * output_reserve;
-		let denominator: T::Balance =
-			FeeRate::div(Into::<T::AsBalance>::into(input_amount), div_rate).into() + input_reserve;
-
-		numerator / denominator / 1_000_000.into() // undo scaling
+		let div_rate: u128 = (FeeRate::one() + fee_rate).as_();
+		let input_amount: u128 = Into::<T::AsBalance>::into(input_amount).as_();
+		let input_reserve: u128 = Into::<T::AsBalance>::into(input_reserve).as_();
+		let output_reserve: u128 = Into::<T::AsBalance>::into(output_reserve).as_();
+
+		// Dividing by the fee rate rounds away necessary decimal points. In order to-
+		// counteract this, we scale the input amount in the numerator
+		let output = mul_div(input_amount, SCALE * SCALE, div_rate, Rounding::Down)
+			.and_then(|input_amount_less_fee_scaled| {
+				let input_amount_less_fee = mul_div(input_amount, SCALE, div_rate, Rounding::Down)?;
+				let denominator = input_amount_less_fee.checked_add(input_reserve)?;
+				mul_div(input_amount_less_fee_scaled, output_reserve, denominator, Rounding::Down)
+			})
+			.map(|output| output / SCALE) // undo scaling
+			.unwrap_or_else(Zero::zero);
+		let output: T::AsBalance = As::sa(output);
+		Into::<T::Balance>::into(output)
 	}
 
 	/// `asset_id` - Trade asset
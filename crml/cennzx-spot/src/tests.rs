@@ -27,7 +27,7 @@ use generic_asset;
 use runtime_io::with_externalities;
 use runtime_primitives::{
 	testing::{Digest, DigestItem, Header},
-	traits::{BlakeTwo256, IdentityLookup, Zero},
+	traits::{BlakeTwo256, IdentityLookup, OnFinalize, Zero},
 	BuildStorage,
 };
 use primitives::{crypto::UncheckedInto, Blake2Hasher, H256};
@@ -76,6 +76,7 @@ impl generic_asset::Trait for Test {
 	type Balance = u128;
 	type AssetId = u32;
 	type Event = ();
+	type OnTransferPayload = ();
 }
 
 impl Trait for Test {
@@ -90,6 +91,7 @@ pub type CennzXSpot = Module<Test>;
 pub struct ExtBuilder {
 	core_asset_id: u32,
 	fee_rate: FeeRate,
+	fee_exchange_reserve: FeeRate,
 }
 
 impl Default for ExtBuilder {
@@ -97,11 +99,16 @@ impl Default for ExtBuilder {
 		Self {
 			core_asset_id: 0,
 			fee_rate: FeeRate::from_milli(3),
+			fee_exchange_reserve: FeeRate::default(),
 		}
 	}
 }
 
 impl ExtBuilder {
+	pub fn fee_exchange_reserve(mut self, fee_exchange_reserve: FeeRate) -> Self {
+		self.fee_exchange_reserve = fee_exchange_reserve;
+		self
+	}
 	pub fn build(self) -> runtime_io::TestExternalities<Blake2Hasher> {
 		let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap().0;
 		t.extend(
@@ -109,6 +116,8 @@ impl ExtBuilder {
 				assets: Vec::new(),
 				initial_balance: 0,
 				endowed_accounts: Vec::new(),
+				endowments: vec![],
+				genesis_locks: vec![],
 				next_asset_id: 100,
 				create_asset_stake: 1000,
 				staking_asset_id: 0,
@@ -122,6 +131,10 @@ impl ExtBuilder {
 			GenesisConfig::<Test> {
 				core_asset_id: self.core_asset_id,
 				fee_rate: self.fee_rate,
+				fee_exchange_reserve: self.fee_exchange_reserve,
+				liquidity_lock_period: 10,
+				liquidity_lock_bonus: FeeRate::from_percent(10),
+				auto_sell_deposit: 10,
 			}
 			.build_storage()
 			.unwrap()
@@ -1204,3 +1217,258 @@ fn set_fee_rate() {
 		assert_eq!(CennzXSpot::fee_rate(), new_fee_rate);
 	});
 }
+
+#[test]
+fn set_fee_exchange_reserve() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(CennzXSpot::set_fee_exchange_reserve(FeeRate::from_percent(10)));
+		assert_eq!(CennzXSpot::fee_exchange_reserve(), FeeRate::from_percent(10));
+
+		assert_err!(
+			CennzXSpot::set_fee_exchange_reserve(FeeRate::from_percent(101)),
+			"Fee exchange reserve can not exceed 100%"
+		);
+		assert_eq!(CennzXSpot::fee_exchange_reserve(), FeeRate::from_percent(10));
+	});
+}
+
+#[test]
+fn asset_swap_can_not_use_fee_exchange_reserve() {
+	with_externalities(
+		&mut ExtBuilder::default()
+			.fee_exchange_reserve(FeeRate::from_percent(10))
+			.build(),
+		|| {
+			with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A => 1000);
+			let trader: AccountId = with_account!(CORE_ASSET_ID => 100, TRADE_ASSET_A => 100_000);
+
+			// would leave 50 core asset, less than the 100 reserved
+			assert_err!(
+				CennzXSpot::asset_swap_output(
+					Origin::signed(trader.clone()),
+					None,
+					TRADE_ASSET_A,
+					CORE_ASSET_ID,
+					950,     // buy_amount
+					100_000, // max_sale
+				),
+				"Trade would use core asset liquidity reserved for fee exchange"
+			);
+			assert_err!(
+				CennzXSpot::asset_swap_input(
+					Origin::signed(trader.clone()),
+					None,
+					TRADE_ASSET_A,
+					CORE_ASSET_ID,
+					100_000, // sell_amount
+					1,       // min_receive
+				),
+				"Trade would use core asset liquidity reserved for fee exchange"
+			);
+			assert_exchange_balance_eq!(CORE_ASSET_ID => 1000, TRADE_ASSET_A => 1000);
+
+			// small trades are unaffected
+			assert_ok!(CennzXSpot::asset_swap_output(
+				Origin::signed(trader.clone()),
+				None,
+				TRADE_ASSET_A,
+				CORE_ASSET_ID,
+				5,       // buy_amount
+				100_000, // max_sale
+			));
+			assert_exchange_balance_eq!(CORE_ASSET_ID => 995, TRADE_ASSET_A => 1006);
+		},
+	);
+}
+
+#[test]
+fn fee_exchange_can_use_fee_exchange_reserve() {
+	with_externalities(
+		&mut ExtBuilder::default()
+			.fee_exchange_reserve(FeeRate::from_percent(10))
+			.build(),
+		|| {
+			with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A => 1000);
+			let trader: AccountId = with_account!(CORE_ASSET_ID => 100, TRADE_ASSET_A => 100_000);
+
+			// fee exchange conversions go through `make_asset_swap_output` directly
+			assert_ok!(CennzXSpot::make_asset_swap_output(
+				&trader,
+				&trader,
+				&TRADE_ASSET_A,
+				&CORE_ASSET_ID,
+				950,     // buy_amount
+				100_000, // max_sale
+				CennzXSpot::fee_rate(),
+			));
+			assert_exchange_balance_eq!(CORE_ASSET_ID => 50, TRADE_ASSET_A => 20_058);
+		},
+	);
+}
+
+#[test]
+fn lock_liquidity_blocks_withdrawal_and_earns_a_bonus_share_of_fees() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let investor: AccountId = with_account!(CORE_ASSET_ID => 1000, TRADE_ASSET_A => 1000);
+		assert_ok!(CennzXSpot::add_liquidity(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A,
+			2,    // min_liquidity: T::Balance,
+			1000, // max_asset_amount: T::Balance,
+			1000, // core_amount: T::Balance,
+		));
+
+		assert_err!(
+			CennzXSpot::lock_liquidity(Origin::signed(investor.clone()), TRADE_ASSET_A, 1001),
+			"Not enough unlocked liquidity"
+		);
+		assert_ok!(CennzXSpot::lock_liquidity(Origin::signed(investor.clone()), TRADE_ASSET_A, 500));
+		assert_err!(
+			CennzXSpot::lock_liquidity(Origin::signed(investor.clone()), TRADE_ASSET_A, 100),
+			"Liquidity is already locked, claim its bonus first"
+		);
+
+		// 10% bonus on the locked liquidity, which is neither minted nor withdrawable
+		assert_eq!(CennzXSpot::get_liquidity(&DEFAULT_EXCHANGE_KEY, &investor), 1000);
+		assert_eq!(CennzXSpot::get_total_supply(&DEFAULT_EXCHANGE_KEY), 1000);
+		assert_eq!(CennzXSpot::locked_liquidity(&DEFAULT_EXCHANGE_KEY, &investor), 500);
+		assert_eq!(CennzXSpot::liquidity_lock(&DEFAULT_EXCHANGE_KEY, &investor).unwrap().bonus, 50);
+		assert_err!(
+			CennzXSpot::remove_liquidity(Origin::signed(investor.clone()), TRADE_ASSET_A, 501, 1, 1),
+			"Liquidity is timelocked"
+		);
+
+		// 300 core asset of the sale is fee
+		let trader: AccountId = with_account!("bob", CORE_ASSET_ID => 100_300, TRADE_ASSET_A => 0);
+		assert_ok!(CennzXSpot::asset_swap_input(
+			Origin::signed(trader),
+			None,
+			CORE_ASSET_ID,
+			TRADE_ASSET_A,
+			100_300,
+			1
+		));
+		assert_eq!(CennzXSpot::accrued_fees(&DEFAULT_EXCHANGE_KEY), (300, 0));
+
+		assert_err!(
+			CennzXSpot::claim_liquidity_bonus(Origin::signed(investor.clone()), TRADE_ASSET_A),
+			"Liquidity is timelocked"
+		);
+		system::Module::<Test>::set_block_number(11);
+		assert_eq!(CennzXSpot::locked_liquidity(&DEFAULT_EXCHANGE_KEY, &investor), 0);
+
+		// the bonus is 50 of 1000 liquidity, paid out of the fees only
+		assert_ok!(CennzXSpot::claim_liquidity_bonus(Origin::signed(investor.clone()), TRADE_ASSET_A));
+		assert_balance_eq!(investor, CORE_ASSET_ID => 15);
+		assert_eq!(CennzXSpot::liquidity_lock(&DEFAULT_EXCHANGE_KEY, &investor), None);
+		assert_eq!(CennzXSpot::get_total_supply(&DEFAULT_EXCHANGE_KEY), 1000);
+	});
+}
+
+#[test]
+fn withdrawing_liquidity_forfeits_the_lock_bonus() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let investor: AccountId = with_account!(CORE_ASSET_ID => 1000, TRADE_ASSET_A => 1000);
+		assert_ok!(CennzXSpot::add_liquidity(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A,
+			2,    // min_liquidity: T::Balance,
+			1000, // max_asset_amount: T::Balance,
+			1000, // core_amount: T::Balance,
+		));
+		assert_ok!(CennzXSpot::lock_liquidity(Origin::signed(investor.clone()), TRADE_ASSET_A, 500));
+		let trader: AccountId = with_account!("bob", CORE_ASSET_ID => 100_300, TRADE_ASSET_A => 0);
+		assert_ok!(CennzXSpot::asset_swap_input(
+			Origin::signed(trader),
+			None,
+			CORE_ASSET_ID,
+			TRADE_ASSET_A,
+			100_300,
+			1
+		));
+
+		// the unlocked liquidity is withdrawn, the locked liquidity stays locked without its bonus
+		assert_ok!(CennzXSpot::remove_liquidity(Origin::signed(investor.clone()), TRADE_ASSET_A, 500, 1, 1));
+		assert_eq!(CennzXSpot::locked_liquidity(&DEFAULT_EXCHANGE_KEY, &investor), 500);
+		assert_eq!(CennzXSpot::liquidity_lock(&DEFAULT_EXCHANGE_KEY, &investor).unwrap().bonus, 0);
+
+		system::Module::<Test>::set_block_number(11);
+		let core_balance = <generic_asset::Module<Test>>::free_balance(&CORE_ASSET_ID, &investor);
+		assert_ok!(CennzXSpot::claim_liquidity_bonus(Origin::signed(investor.clone()), TRADE_ASSET_A));
+		assert_balance_eq!(investor, CORE_ASSET_ID => core_balance);
+	});
+}
+
+#[test]
+fn set_liquidity_lock() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(CennzXSpot::set_liquidity_lock(100, FeeRate::from_percent(20)));
+		assert_eq!(CennzXSpot::liquidity_lock_period(), 100);
+		assert_eq!(CennzXSpot::liquidity_lock_bonus(), FeeRate::from_percent(20));
+
+		assert_err!(
+			CennzXSpot::set_liquidity_lock(0, FeeRate::from_percent(20)),
+			"Liquidity lock period must be greater than zero"
+		);
+		assert_err!(
+			CennzXSpot::set_liquidity_lock(100, FeeRate::from_percent(101)),
+			"Liquidity lock bonus can not exceed 100%"
+		);
+	});
+}
+
+#[test]
+fn auto_sell_assets_are_sold_into_core_asset() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A => 1000);
+		let merchant = with_account!(CORE_ASSET_ID => 10, TRADE_ASSET_A => 123);
+
+		assert_err!(
+			CennzXSpot::set_auto_sell(Origin::signed(merchant.clone()), vec![CORE_ASSET_ID]),
+			"Core asset can not be auto-sold"
+		);
+		assert_ok!(CennzXSpot::set_auto_sell(Origin::signed(merchant.clone()), vec![TRADE_ASSET_A]));
+		assert_eq!(CennzXSpot::auto_sell_account_count(), 1);
+		assert_eq!(CennzXSpot::auto_sell_account_at(0), merchant);
+		assert_eq!(<generic_asset::Module<Test>>::reserved_balance(&CORE_ASSET_ID, &merchant), 10);
+
+		<CennzXSpot as OnFinalize<u64>>::on_finalize(1);
+		assert_balance_eq!(merchant, TRADE_ASSET_A => 0);
+		assert_balance_eq!(merchant, CORE_ASSET_ID => 109);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 891, TRADE_ASSET_A => 1123);
+
+		// opting out returns the deposit
+		assert_ok!(CennzXSpot::set_auto_sell(Origin::signed(merchant.clone()), vec![]));
+		assert_eq!(CennzXSpot::auto_sell_account_count(), 0);
+		assert!(CennzXSpot::auto_sell(&merchant).is_empty());
+		assert_balance_eq!(merchant, CORE_ASSET_ID => 119);
+	});
+}
+
+#[test]
+fn auto_sell_accounts_reserve_a_deposit_and_keep_their_positions_contiguous() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let andrea = with_account!("andrea", CORE_ASSET_ID => 10, TRADE_ASSET_A => 0);
+		let bob = with_account!("bob", CORE_ASSET_ID => 10, TRADE_ASSET_A => 0);
+		let charlie = with_account!("charlie", CORE_ASSET_ID => 9, TRADE_ASSET_A => 0);
+
+		assert_err!(
+			CennzXSpot::set_auto_sell(Origin::signed(charlie.clone()), vec![TRADE_ASSET_A]),
+			"Not enough funds to pay the auto-sell deposit"
+		);
+		assert_ok!(CennzXSpot::set_auto_sell(Origin::signed(andrea.clone()), vec![TRADE_ASSET_A]));
+		assert_ok!(CennzXSpot::set_auto_sell(Origin::signed(bob.clone()), vec![TRADE_ASSET_A]));
+		// changing the assets does not reserve again
+		assert_ok!(CennzXSpot::set_auto_sell(Origin::signed(bob.clone()), vec![TRADE_ASSET_B]));
+		assert_eq!(CennzXSpot::auto_sell_account_count(), 2);
+
+		// the last account takes the position of a leaving account
+		assert_ok!(CennzXSpot::set_auto_sell(Origin::signed(andrea.clone()), vec![]));
+		assert_eq!(CennzXSpot::auto_sell_account_count(), 1);
+		assert_eq!(CennzXSpot::auto_sell_account_at(0), bob);
+		assert_ok!(CennzXSpot::set_auto_sell(Origin::signed(bob.clone()), vec![]));
+		assert_eq!(CennzXSpot::auto_sell_account_count(), 0);
+		assert_balance_eq!(andrea, CORE_ASSET_ID => 10);
+		assert_balance_eq!(bob, CORE_ASSET_ID => 10);
+	});
+}
@@ -16,12 +16,10 @@
 //!
 //! CENNZX-SPOT Types
 //!
+use cennznet_primitives::fixed_point::{mul_div, Rounding, SCALE as SCALE_FACTOR};
 use parity_codec::{Compact, CompactAs, Decode, Encode};
 use runtime_primitives::traits::As;
 
-/// FeeRate S.F precision
-const SCALE_FACTOR: u128 = 1_000_000;
-
 /// FeeRate (based on Permill), uses a scale factor
 /// Inner type is `u128` in order to support compatibility with `generic_asset::Balance` type
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
@@ -39,9 +37,10 @@ impl FeeRate {
 		FeeRate(x * SCALE_FACTOR / 100)
 	}
 
-	/// Divide a `As::as_<u128>` supported numeric by a FeeRate
+	/// Divide a `As::as_<u128>` supported numeric by a FeeRate, rounding down.
+	/// Saturates if the result does not fit.
 	pub fn div<N: As<u128>>(lhs: N, rhs: FeeRate) -> N {
-		N::sa(lhs.as_() * SCALE_FACTOR / rhs.0)
+		N::sa(mul_div(lhs.as_(), SCALE_FACTOR, rhs.0, Rounding::Down).unwrap_or_else(u128::max_value))
 	}
 
 	/// Returns the equivalent of 1 or 100%
@@ -73,7 +72,7 @@ where
 {
 	type Output = N;
 	fn mul(self, rhs: N) -> Self::Output {
-		N::sa(N::as_(rhs).saturating_mul(self.0) / SCALE_FACTOR)
+		N::sa(mul_div(N::as_(rhs), self.0, SCALE_FACTOR, Rounding::Down).unwrap_or_else(u128::max_value))
 	}
 }
 
@@ -93,6 +92,21 @@ impl From<Compact<FeeRate>> for FeeRate {
 	}
 }
 
+/// A timelocked liquidity position, locked liquidity can not be withdrawn until the `until` block
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+pub struct LiquidityLock<Balance, BlockNumber> {
+	/// Amount of liquidity locked
+	pub amount: Balance,
+	/// The block number the lock expires at
+	pub until: BlockNumber,
+	/// Bonus liquidity earned by the lock. It is not withdrawable, it only weighs the provider's share of the
+	/// fees the exchange accrues from the start of the lock
+	pub bonus: Balance,
+	/// The exchange's accrued core and trade asset fees at the start of the lock
+	pub accrued_fees: (Balance, Balance),
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -112,6 +126,13 @@ mod tests {
 		assert_eq!(fee_rate * 2, 1);
 	}
 
+	#[test]
+	fn mul_does_not_overflow_before_scaling() {
+		let fee_rate = FeeRate::from_percent(50);
+		assert_eq!(fee_rate * u128::max_value(), u128::max_value() / 2);
+		assert_eq!(FeeRate::from_percent(200) * u128::max_value(), u128::max_value());
+	}
+
 	#[test]
 	fn add_works() {
 		let fee_rate = FeeRate::from_percent(50) + FeeRate::from_percent(12);
@@ -0,0 +1,221 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Doughnut module.
+//!
+//! Keeps the on-chain state of doughnuts (delegation certificates).
+//! A doughnut with an activation window can be pre-signed offline, e.g. in cold storage, and only becomes
+//! usable once its holder activates it on-chain within the window after its `not_before` time.
+//! The holder reserves a deposit for each activation. Once the doughnut expires anyone may purge its activation,
+//! earning a reward from the deposit, the rest of which is returned to the holder.
+//! Activations are kept in hourly buckets by the expiry of their doughnut, at most `MAX_EXPIRIES_PER_BUCKET`
+//! to a bucket, and purges walk the buckets oldest first.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use cennznet_primitives::{Certificate, Doughnut};
+use rstd::prelude::*;
+use runtime_primitives::traits::{As, Hash, Verify, Zero};
+use support::traits::{Currency, ReservableCurrency};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, Parameter, StorageMap, StorageValue};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use self::weights::{RefundWeight, Weight};
+
+pub trait Trait: system::Trait + timestamp::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	/// The signature type of doughnuts
+	type Signature: Parameter + Verify<Signer = Self::AccountId>;
+	/// The currency activation deposits are reserved in
+	type Currency: ReservableCurrency<Self::AccountId>;
+	/// Refunds the fee of weight a call did not use
+	type RefundWeight: RefundWeight<Self::AccountId>;
+}
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
+/// The most expired activations one `purge_expired_doughnuts` call may purge
+pub const MAX_PURGE_BATCH: u32 = 100;
+/// The span of doughnut expiry times sharing an expiry bucket
+pub const EXPIRY_BUCKET_PERIOD: u64 = 3_600;
+/// The max. number of activations in an expiry bucket
+pub const MAX_EXPIRIES_PER_BUCKET: usize = 1_000;
+/// The latest a doughnut may expire after it is activated, a year
+pub const MAX_ACTIVATION_LIFETIME: u64 = 365 * 24 * 3_600;
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Activate a doughnut which requires activation, the sender must be its holder.
+		/// It must be activated within its activation window after `not_before`.
+		pub fn activate_doughnut(origin, doughnut: Doughnut<T::AccountId, T::Signature>) -> Result {
+			let holder = ensure_signed(origin)?;
+			let certificate = &doughnut.certificate;
+			ensure!(certificate.holder == holder, "sender is not the doughnut holder");
+			let window = certificate
+				.activation_window()
+				.ok_or_else(|| "doughnut does not require activation")?;
+
+			let now = <timestamp::Module<T>>::now().as_();
+			doughnut.validate(now)?;
+			ensure!(
+				now <= certificate.not_before.saturating_add(window),
+				"doughnut activation window has passed"
+			);
+
+			let id = T::Hashing::hash_of(certificate);
+			ensure!(!Self::activated(&id), "doughnut is already activated");
+
+			let expires = certificate.expires;
+			ensure!(
+				expires <= now.saturating_add(MAX_ACTIVATION_LIFETIME),
+				"doughnut expires too far in the future to be activated"
+			);
+			let bucket = expires / EXPIRY_BUCKET_PERIOD;
+			let mut expiring = Self::expiring(bucket);
+			ensure!(expiring.len() < MAX_EXPIRIES_PER_BUCKET, "too many activated doughnuts expire in the hour");
+			let deposit = Self::activation_deposit();
+			ensure!(!deposit.is_zero(), "activation deposit is not set");
+			T::Currency::reserve(&holder, deposit).map_err(|_| "Not enough funds to pay the activation deposit")?;
+
+			<ActivationDeposits<T>>::insert(&id, (holder.clone(), deposit));
+			<Activated<T>>::insert(&id, true);
+			// With nothing left to purge, purges start from the current bucket instead of walking empty ones
+			if Self::pending_expiries() == 0 {
+				<NextExpiryBucket<T>>::put(now / EXPIRY_BUCKET_PERIOD);
+			}
+			<PendingExpiries<T>>::mutate(|pending| *pending += 1);
+			let index = expiring.iter().position(|(e, _)| *e > expires).unwrap_or_else(|| expiring.len());
+			expiring.insert(index, (expires, id));
+			<Expiring<T>>::insert(bucket, expiring);
+
+			Self::deposit_event(RawEvent::DoughnutActivated(id, certificate.issuer.clone(), holder));
+			Ok(())
+		}
+
+		/// Purge up to `limit` activations of expired doughnuts, oldest first.
+		/// The sender is paid the purge reward from the deposit of each purged activation, and refunded the
+		/// weight of the `limit` left unused.
+		pub fn purge_expired_doughnuts(origin, limit: u32) -> Result {
+			let purger = ensure_signed(origin)?;
+			ensure!(limit > 0 && limit <= MAX_PURGE_BATCH, "purge limit must be between 1 and MAX_PURGE_BATCH");
+
+			let now = <timestamp::Module<T>>::now().as_();
+			let now_bucket = now / EXPIRY_BUCKET_PERIOD;
+			let first_bucket = Self::next_expiry_bucket();
+			let mut bucket = first_bucket;
+			let mut expired = Vec::new();
+			let mut buckets = Vec::new();
+			// Each visited bucket takes from `limit` like a purged activation, so empty buckets are walked in bounds
+			while bucket <= now_bucket && buckets.len() < limit as usize && expired.len() < limit as usize {
+				let mut expiring = Self::expiring(bucket);
+				let count = expiring
+					.iter()
+					.take(limit as usize - expired.len())
+					.take_while(|(expires, _)| *expires <= now)
+					.count();
+				expired.extend(expiring.drain(..count).map(|(_, id)| id));
+				// Only a past bucket is complete, activations may still be added to the current one
+				let done = expiring.is_empty() && bucket < now_bucket;
+				buckets.push((bucket, expiring));
+				if !done {
+					break;
+				}
+				bucket += 1;
+			}
+			ensure!(!expired.is_empty() || bucket != first_bucket, "no expired doughnuts to purge");
+			let used = rstd::cmp::max(expired.len(), buckets.len());
+
+			for (bucket, expiring) in buckets {
+				if expiring.is_empty() {
+					<Expiring<T>>::remove(bucket);
+				} else {
+					<Expiring<T>>::insert(bucket, expiring);
+				}
+			}
+			<NextExpiryBucket<T>>::put(bucket);
+			<PendingExpiries<T>>::mutate(|pending| *pending = pending.saturating_sub(expired.len() as u32));
+			let reward = Self::purge_reward();
+			for id in &expired {
+				<Activated<T>>::remove(id);
+				if let Some((holder, deposit)) = <ActivationDeposits<T>>::take(id) {
+					let paid = reward.min(deposit);
+					let _ = T::Currency::repatriate_reserved(&holder, &purger, paid);
+					T::Currency::unreserve(&holder, deposit - paid);
+				}
+			}
+			T::RefundWeight::refund_weight(&purger, weights::PER_PURGE * (limit as usize - used) as Weight);
+
+			Self::deposit_event(RawEvent::DoughnutsPurged(purger, expired.len() as u32));
+			Ok(())
+		}
+
+		/// Set the deposit reserved for each activation and the reward paid from it for purging the activation
+		/// once the doughnut expires (root only)
+		pub fn set_activation_deposit(deposit: BalanceOf<T>, purge_reward: BalanceOf<T>) -> Result {
+			ensure!(!deposit.is_zero(), "activation deposit must be greater than zero");
+			ensure!(purge_reward <= deposit, "purge reward can not exceed the activation deposit");
+			<ActivationDeposit<T>>::put(deposit);
+			<PurgeReward<T>>::put(purge_reward);
+			Ok(())
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where <T as system::Trait>::AccountId, <T as system::Trait>::Hash {
+		/// A doughnut was activated: (certificate_hash, issuer, holder)
+		DoughnutActivated(Hash, AccountId, AccountId),
+		/// Activations of expired doughnuts were purged: (purger, count)
+		DoughnutsPurged(AccountId, u32),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Doughnut {
+		/// Doughnuts activated by their holder, by certificate hash
+		pub Activated get(activated): map T::Hash => bool;
+		/// The amount a holder must reserve for each activation
+		pub ActivationDeposit get(activation_deposit) config(): BalanceOf<T>;
+		/// The amount paid from an activation deposit to whoever purges the activation after the doughnut expires
+		pub PurgeReward get(purge_reward) config(): BalanceOf<T>;
+		/// Deposits held for activations: certificate_hash => (holder, amount)
+		ActivationDeposits get(activation_deposits): map T::Hash => Option<(T::AccountId, BalanceOf<T>)>;
+		/// Activated certificate hashes by expiry bucket, ordered by the expiry of their doughnut:
+		/// expires / EXPIRY_BUCKET_PERIOD => [(expires, certificate_hash)]
+		pub Expiring get(expiring): map u64 => Vec<(u64, T::Hash)>;
+		/// The oldest expiry bucket which may hold activations
+		pub NextExpiryBucket get(next_expiry_bucket): u64;
+		/// The number of activations not purged yet
+		PendingExpiries get(pending_expiries): u32;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Return `Ok` iff the doughnut with `certificate` may be used on-chain.
+	/// Doughnuts with an activation window must have been activated.
+	pub fn ensure_usable(certificate: &Certificate<T::AccountId>) -> Result {
+		if certificate.activation_window().is_some() && !Self::activated(T::Hashing::hash_of(certificate)) {
+			return Err("doughnut has not been activated");
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,138 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Test utilities
+
+#![cfg(test)]
+
+use crate::{GenesisConfig, Module, RefundWeight, Trait, Weight};
+use parity_codec::{Decode, Encode};
+use primitives::{Blake2Hasher, H256};
+use runtime_io;
+use runtime_primitives::traits::{Lazy, Verify};
+use runtime_primitives::BuildStorage;
+use runtime_primitives::{
+	testing::{Digest, DigestItem, Header},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use support::impl_outer_origin;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+/// A signature which is valid iff it holds `true`
+#[derive(Encode, Decode, Serialize, Deserialize, Clone, PartialEq, Eq, Default, Debug)]
+pub struct Signature(pub bool);
+
+impl Verify for Signature {
+	type Signer = u64;
+	fn verify<L: Lazy<[u8]>>(&self, _msg: L, _signer: &Self::Signer) -> bool {
+		self.0
+	}
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Test;
+impl system::Trait for Test {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Digest = Digest;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<u64>;
+	type Header = Header;
+	type Event = ();
+	type Log = DigestItem;
+	type Signature = Signature;
+}
+
+impl timestamp::Trait for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+}
+
+impl generic_asset::Trait for Test {
+	type Balance = u64;
+	type AssetId = u32;
+	type Event = ();
+	type OnTransferPayload = ();
+}
+
+thread_local! {
+	static REFUNDS: RefCell<Vec<(u64, Weight)>> = RefCell::new(Vec::new());
+}
+
+/// Records weight refunds for inspection by tests
+pub struct RecordRefunds;
+
+impl RecordRefunds {
+	pub fn refunds() -> Vec<(u64, Weight)> {
+		REFUNDS.with(|r| r.borrow().clone())
+	}
+}
+
+impl RefundWeight<u64> for RecordRefunds {
+	fn refund_weight(who: &u64, weight: Weight) {
+		REFUNDS.with(|r| r.borrow_mut().push((*who, weight)));
+	}
+}
+
+impl Trait for Test {
+	type Event = ();
+	type Signature = Signature;
+	type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	type RefundWeight = RecordRefunds;
+}
+
+pub const SPENDING_ASSET_ID: u32 = 16_001;
+pub const ACTIVATION_DEPOSIT: u64 = 10;
+pub const PURGE_REWARD: u64 = 3;
+
+pub type Doughnuts = Module<Test>;
+pub type GenericAsset = generic_asset::Module<Test>;
+
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build() -> runtime_io::TestExternalities<Blake2Hasher> {
+		REFUNDS.with(|r| r.borrow_mut().clear());
+		let (mut t, mut c) = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let _ = generic_asset::GenesisConfig::<Test> {
+			staking_asset_id: 16_000,
+			spending_asset_id: SPENDING_ASSET_ID,
+			assets: vec![SPENDING_ASSET_ID],
+			endowed_accounts: vec![1, 2],
+			endowments: vec![],
+			genesis_locks: vec![],
+			create_asset_stake: 10,
+			initial_balance: 100,
+			next_asset_id: 17_000,
+		}
+		.assimilate_storage(&mut t, &mut c);
+		let _ = GenesisConfig::<Test> {
+			activation_deposit: ACTIVATION_DEPOSIT,
+			purge_reward: PURGE_REWARD,
+		}
+		.assimilate_storage(&mut t, &mut c);
+
+		t.into()
+	}
+}
@@ -0,0 +1,236 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{
+	Doughnuts, ExtBuilder, GenericAsset, Origin, RecordRefunds, Signature, Test, ACTIVATION_DEPOSIT, PURGE_REWARD,
+	SPENDING_ASSET_ID,
+};
+use cennznet_primitives::{DoughnutSignature, ACTIVATION_WINDOW_KEY};
+use parity_codec::Encode;
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok, StorageValue};
+
+const ISSUER: u64 = 1;
+const HOLDER: u64 = 2;
+
+fn doughnut(activation_window: Option<u64>) -> Doughnut<u64, Signature> {
+	let mut permissions = vec![("cennznet".encode(), vec![])];
+	if let Some(window) = activation_window {
+		permissions.push((ACTIVATION_WINDOW_KEY.encode(), window.encode()));
+	}
+	Doughnut {
+		certificate: Certificate {
+			expires: 1000,
+			version: 0,
+			holder: HOLDER,
+			not_before: 100,
+			permissions,
+			issuer: ISSUER,
+		},
+		signature: DoughnutSignature::Single(Signature(true)),
+	}
+}
+
+fn set_now(now: u64) {
+	<timestamp::Now<Test>>::put(now);
+}
+
+#[test]
+fn doughnut_without_activation_window_is_usable() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		let d = doughnut(None);
+		assert_ok!(Doughnuts::ensure_usable(&d.certificate));
+		set_now(100);
+		assert_noop!(
+			Doughnuts::activate_doughnut(Origin::signed(HOLDER), d),
+			"doughnut does not require activation"
+		);
+	});
+}
+
+#[test]
+fn doughnut_is_usable_once_activated_within_window() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		let d = doughnut(Some(50));
+		assert_noop!(Doughnuts::ensure_usable(&d.certificate), "doughnut has not been activated");
+
+		// not before
+		set_now(99);
+		assert_noop!(
+			Doughnuts::activate_doughnut(Origin::signed(HOLDER), d.clone()),
+			"doughnut is not valid yet"
+		);
+
+		set_now(150);
+		assert_noop!(
+			Doughnuts::activate_doughnut(Origin::signed(ISSUER), d.clone()),
+			"sender is not the doughnut holder"
+		);
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), d.clone()));
+		assert_ok!(Doughnuts::ensure_usable(&d.certificate));
+		assert_noop!(
+			Doughnuts::activate_doughnut(Origin::signed(HOLDER), d),
+			"doughnut is already activated"
+		);
+	});
+}
+
+#[test]
+fn doughnut_can_not_be_activated_after_window() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		let mut d = doughnut(Some(50));
+		set_now(151);
+		assert_noop!(
+			Doughnuts::activate_doughnut(Origin::signed(HOLDER), d.clone()),
+			"doughnut activation window has passed"
+		);
+
+		set_now(120);
+		d.signature = DoughnutSignature::Single(Signature(false));
+		assert_noop!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), d), "invalid signature");
+
+		d.signature = DoughnutSignature::Single(Signature(true));
+		d.certificate.expires = 120 + MAX_ACTIVATION_LIFETIME + 1;
+		assert_noop!(
+			Doughnuts::activate_doughnut(Origin::signed(HOLDER), d.clone()),
+			"doughnut expires too far in the future to be activated"
+		);
+		d.certificate.expires = 120 + MAX_ACTIVATION_LIFETIME;
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), d));
+	});
+}
+
+#[test]
+fn activation_reserves_deposit_from_holder() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		set_now(120);
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), doughnut(Some(50))));
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &HOLDER), ACTIVATION_DEPOSIT);
+		assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &HOLDER), 100 - ACTIVATION_DEPOSIT);
+
+		let mut poor = doughnut(Some(50));
+		poor.certificate.holder = 3;
+		assert_noop!(
+			Doughnuts::activate_doughnut(Origin::signed(3), poor),
+			"Not enough funds to pay the activation deposit"
+		);
+	});
+}
+
+#[test]
+fn purge_expired_doughnuts_removes_expired_activations_and_rewards_purger() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		let early = doughnut(Some(50));
+		let mut late = doughnut(Some(50));
+		late.certificate.expires = 2000;
+		set_now(120);
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), late.clone()));
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), early.clone()));
+		let early_id = <Test as system::Trait>::Hashing::hash_of(&early.certificate);
+		let late_id = <Test as system::Trait>::Hashing::hash_of(&late.certificate);
+		assert_eq!(Doughnuts::expiring(0), vec![(1000, early_id), (2000, late_id)]);
+
+		assert_noop!(
+			Doughnuts::purge_expired_doughnuts(Origin::signed(ISSUER), 0),
+			"purge limit must be between 1 and MAX_PURGE_BATCH"
+		);
+		assert_noop!(
+			Doughnuts::purge_expired_doughnuts(Origin::signed(ISSUER), 10),
+			"no expired doughnuts to purge"
+		);
+
+		set_now(1000);
+		assert_ok!(Doughnuts::purge_expired_doughnuts(Origin::signed(ISSUER), 10));
+		assert!(!Doughnuts::activated(early_id));
+		assert!(Doughnuts::activated(late_id));
+		assert_eq!(Doughnuts::activation_deposits(early_id), None);
+		assert_eq!(Doughnuts::expiring(0), vec![(2000, late_id)]);
+		assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &ISSUER), 100 + PURGE_REWARD);
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &HOLDER), ACTIVATION_DEPOSIT);
+		assert_eq!(
+			GenericAsset::free_balance(&SPENDING_ASSET_ID, &HOLDER),
+			100 - ACTIVATION_DEPOSIT - PURGE_REWARD
+		);
+	});
+}
+
+#[test]
+fn purge_refunds_weight_of_unused_limit() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		set_now(120);
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), doughnut(Some(50))));
+		assert_eq!(
+			Doughnuts::call_weight(&Call::purge_expired_doughnuts(10)),
+			weights::PURGE_BASE + 10 * weights::PER_PURGE
+		);
+
+		set_now(1000);
+		assert_ok!(Doughnuts::purge_expired_doughnuts(Origin::signed(ISSUER), 10));
+		assert_eq!(RecordRefunds::refunds(), vec![(ISSUER, 9 * weights::PER_PURGE)]);
+	});
+}
+
+#[test]
+fn set_activation_deposit_bounds_purge_reward() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		assert_noop!(
+			Doughnuts::set_activation_deposit(5, 6),
+			"purge reward can not exceed the activation deposit"
+		);
+		assert_noop!(
+			Doughnuts::set_activation_deposit(0, 0),
+			"activation deposit must be greater than zero"
+		);
+		assert_ok!(Doughnuts::set_activation_deposit(5, 5));
+		assert_eq!(Doughnuts::activation_deposit(), 5);
+		assert_eq!(Doughnuts::purge_reward(), 5);
+	});
+}
+
+#[test]
+fn purge_walks_expiry_buckets_oldest_first() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		let mut early = doughnut(Some(50));
+		let mut late = doughnut(Some(50));
+		late.certificate.expires = 3 * EXPIRY_BUCKET_PERIOD + 10;
+		set_now(120);
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), late.clone()));
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), early.clone()));
+		let late_id = <Test as system::Trait>::Hashing::hash_of(&late.certificate);
+		assert_eq!(Doughnuts::expiring(3), vec![(late.certificate.expires, late_id)]);
+
+		// Purging the early activation walks one bucket, then empty buckets take from the limit
+		set_now(3 * EXPIRY_BUCKET_PERIOD + 10);
+		assert_ok!(Doughnuts::purge_expired_doughnuts(Origin::signed(ISSUER), 2));
+		assert_eq!(Doughnuts::next_expiry_bucket(), 2);
+		assert!(Doughnuts::activated(late_id));
+		assert_ok!(Doughnuts::purge_expired_doughnuts(Origin::signed(ISSUER), 2));
+		assert!(!Doughnuts::activated(late_id));
+		assert_eq!(Doughnuts::expiring(3), vec![]);
+		assert_eq!(Doughnuts::next_expiry_bucket(), 3);
+
+		// With nothing left to purge, the next activation moves purges up to the current bucket
+		early.certificate.not_before = 10 * EXPIRY_BUCKET_PERIOD;
+		early.certificate.expires = 11 * EXPIRY_BUCKET_PERIOD;
+		set_now(10 * EXPIRY_BUCKET_PERIOD);
+		assert_ok!(Doughnuts::activate_doughnut(Origin::signed(HOLDER), early));
+		assert_eq!(Doughnuts::next_expiry_bucket(), 10);
+	});
+}
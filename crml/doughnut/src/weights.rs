@@ -0,0 +1,65 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Weights of the doughnut calls
+//!
+//! A call's weight is its worst case count of storage reads and writes, in the same units as the generic asset
+//! weights so the runtime charges `PerWeight` for each. A purge is weighed by its `limit`, and the weight of the
+//! entries it finds unexpired is refunded after it executes.
+
+use crate::{Call, Module, Trait};
+
+/// The execution cost of a call in weight units
+pub type Weight = u64;
+
+/// The weight of reading a storage item
+pub const READ: Weight = 1;
+
+/// The weight of writing a storage item
+pub const WRITE: Weight = 3;
+
+/// The weight of activating a doughnut, including reserving the activation deposit
+pub const ACTIVATE: Weight = 7 * READ + 7 * WRITE;
+
+/// The weight of a purge, excluding the activations it purges
+pub const PURGE_BASE: Weight = 3 * READ + 2 * WRITE;
+
+/// The weight of purging one activation and settling its deposit, or of walking past an empty expiry bucket
+pub const PER_PURGE: Weight = 6 * READ + 6 * WRITE;
+
+/// The weight of setting the activation deposit and purge reward
+pub const SETTING: Weight = 2 * WRITE;
+
+/// Refunds the fee paid for weight a call did not use
+pub trait RefundWeight<AccountId> {
+	/// Refund `who` the fee of `weight` units of the current extrinsic's weight
+	fn refund_weight(who: &AccountId, weight: Weight);
+}
+
+impl<AccountId> RefundWeight<AccountId> for () {
+	fn refund_weight(_: &AccountId, _: Weight) {}
+}
+
+impl<T: Trait> Module<T> {
+	/// The worst case weight of `call`. Purges are weighed by their `limit`.
+	pub fn call_weight(call: &Call<T>) -> Weight {
+		match call {
+			Call::activate_doughnut(..) => ACTIVATE,
+			Call::purge_expired_doughnuts(limit) => PURGE_BASE + PER_PURGE * Weight::from(*limit),
+			Call::set_activation_deposit(..) => SETTING,
+			Call::__PhantomItem(_, never) => match *never {},
+		}
+	}
+}
@@ -0,0 +1,245 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Escrow module.
+//!
+//! A depositor escrows an amount of any asset for a beneficiary, with an arbiter to settle disputes.
+//! The amount is reserved on the depositor until the escrow is settled:
+//! - the depositor releases it to the beneficiary, or the beneficiary refunds it to the depositor
+//! - either party disputes the escrow, after which only the arbiter can release or refund it
+//! - the escrow times out undisputed and is refunded to the depositor at the end of its timeout block
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use generic_asset::ReserveIdentifier;
+use parity_codec::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::traits::Zero;
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap, StorageValue};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The reservation escrowed amounts are held under on the depositor
+pub const ESCROW_RESERVE_ID: ReserveIdentifier = *b"escrow  ";
+/// The max. number of escrows which may time out at the same block
+pub const MAX_ESCROWS_TIMING_OUT_PER_BLOCK: usize = 100;
+
+pub type EscrowId = u32;
+
+pub trait Trait: generic_asset::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+/// An amount held for a beneficiary until it is released or refunded
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Escrow<AccountId, AssetId, Balance, BlockNumber> {
+	/// The account the amount is reserved on
+	pub depositor: AccountId,
+	/// The account the amount is released to
+	pub beneficiary: AccountId,
+	/// The account which settles a dispute
+	pub arbiter: AccountId,
+	/// The asset escrowed
+	pub asset_id: AssetId,
+	/// The amount escrowed
+	pub amount: Balance,
+	/// The block at the end of which the escrow is refunded, unless it is disputed
+	pub timeout: BlockNumber,
+	/// Whether the escrow is disputed and awaits the arbiter
+	pub disputed: bool,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Escrow `amount` of `asset_id` for `beneficiary`, with `arbiter` to settle a dispute.
+		/// The escrow is refunded to the sender `timeout` blocks from now unless it is settled or disputed first.
+		pub fn create_escrow(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			#[compact] amount: T::Balance,
+			beneficiary: T::AccountId,
+			arbiter: T::AccountId,
+			#[compact] timeout: T::BlockNumber
+		) -> Result {
+			let depositor = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), "Escrow amount must be greater than zero");
+			ensure!(!timeout.is_zero(), "Escrow timeout must be greater than zero");
+			let timeout = <system::Module<T>>::block_number() + timeout;
+			let mut timing_out = Self::escrows_timing_out(timeout);
+			ensure!(
+				timing_out.len() < MAX_ESCROWS_TIMING_OUT_PER_BLOCK,
+				"Too many escrows time out at the block"
+			);
+			let escrow_id = Self::next_escrow_id();
+			let next_escrow_id = escrow_id.checked_add(1).ok_or_else(|| "No new escrow id is available")?;
+
+			<generic_asset::Module<T>>::reserve_named(&ESCROW_RESERVE_ID, &asset_id, &depositor, amount)?;
+			<NextEscrowId<T>>::put(next_escrow_id);
+			timing_out.push(escrow_id);
+			<EscrowsTimingOut<T>>::insert(timeout, timing_out);
+			<Escrows<T>>::insert(
+				escrow_id,
+				Escrow {
+					depositor: depositor.clone(),
+					beneficiary: beneficiary.clone(),
+					arbiter,
+					asset_id,
+					amount,
+					timeout,
+					disputed: false,
+				},
+			);
+
+			Self::deposit_event(RawEvent::EscrowCreated(escrow_id, depositor, beneficiary, asset_id, amount));
+			Ok(())
+		}
+
+		/// Release an undisputed escrow to its beneficiary, the sender must be its depositor.
+		pub fn release(origin, #[compact] escrow_id: EscrowId) -> Result {
+			let depositor = ensure_signed(origin)?;
+			let escrow = Self::escrow(escrow_id).ok_or_else(|| "Escrow does not exist")?;
+			ensure!(escrow.depositor == depositor, "Only the depositor can release an escrow");
+			ensure!(!escrow.disputed, "Escrow is disputed, only the arbiter can settle it");
+
+			let timeout = escrow.timeout;
+			Self::settle(escrow_id, escrow, true)?;
+			Self::cancel_timeout(escrow_id, timeout);
+			Ok(())
+		}
+
+		/// Refund an undisputed escrow to its depositor, the sender must be its beneficiary.
+		pub fn refund(origin, #[compact] escrow_id: EscrowId) -> Result {
+			let beneficiary = ensure_signed(origin)?;
+			let escrow = Self::escrow(escrow_id).ok_or_else(|| "Escrow does not exist")?;
+			ensure!(escrow.beneficiary == beneficiary, "Only the beneficiary can refund an escrow");
+			ensure!(!escrow.disputed, "Escrow is disputed, only the arbiter can settle it");
+
+			let timeout = escrow.timeout;
+			Self::settle(escrow_id, escrow, false)?;
+			Self::cancel_timeout(escrow_id, timeout);
+			Ok(())
+		}
+
+		/// Dispute an escrow, the sender must be its depositor or beneficiary.
+		/// A disputed escrow no longer times out, only its arbiter can settle it.
+		pub fn dispute(origin, #[compact] escrow_id: EscrowId) -> Result {
+			let sender = ensure_signed(origin)?;
+			let mut escrow = Self::escrow(escrow_id).ok_or_else(|| "Escrow does not exist")?;
+			ensure!(
+				escrow.depositor == sender || escrow.beneficiary == sender,
+				"Only the depositor or beneficiary can dispute an escrow"
+			);
+			ensure!(!escrow.disputed, "Escrow is already disputed");
+
+			Self::cancel_timeout(escrow_id, escrow.timeout);
+			escrow.disputed = true;
+			<Escrows<T>>::insert(escrow_id, escrow);
+
+			Self::deposit_event(RawEvent::EscrowDisputed(escrow_id, sender));
+			Ok(())
+		}
+
+		/// Settle a disputed escrow, releasing it to its beneficiary or refunding it to its depositor.
+		/// The sender must be its arbiter.
+		pub fn resolve_dispute(origin, #[compact] escrow_id: EscrowId, release: bool) -> Result {
+			let arbiter = ensure_signed(origin)?;
+			let escrow = Self::escrow(escrow_id).ok_or_else(|| "Escrow does not exist")?;
+			ensure!(escrow.arbiter == arbiter, "Only the arbiter can resolve a dispute");
+			ensure!(escrow.disputed, "Escrow is not disputed");
+
+			Self::settle(escrow_id, escrow, release)
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			for escrow_id in <EscrowsTimingOut<T>>::take(n) {
+				if let Some(escrow) = Self::escrow(escrow_id) {
+					let _ = Self::settle(escrow_id, escrow, false);
+				}
+			}
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+		<T as generic_asset::Trait>::AssetId,
+		<T as generic_asset::Trait>::Balance
+	{
+		/// An escrow was created: (escrow id, depositor, beneficiary, asset id, amount)
+		EscrowCreated(EscrowId, AccountId, AccountId, AssetId, Balance),
+		/// An escrow was released to its beneficiary (escrow id)
+		EscrowReleased(EscrowId),
+		/// An escrow was refunded to its depositor (escrow id)
+		EscrowRefunded(EscrowId),
+		/// An escrow was disputed: (escrow id, disputing party)
+		EscrowDisputed(EscrowId, AccountId),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Escrow {
+		/// The id of the next escrow to be created
+		pub NextEscrowId get(next_escrow_id): EscrowId;
+		/// Open escrows: escrow id => escrow
+		pub Escrows get(escrow): map EscrowId => Option<Escrow<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>>;
+		/// The undisputed escrows which time out at the end of a block
+		EscrowsTimingOut get(escrows_timing_out): map T::BlockNumber => Vec<EscrowId>;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	fn cancel_timeout(escrow_id: EscrowId, timeout: T::BlockNumber) {
+		<EscrowsTimingOut<T>>::mutate(timeout, |timing_out| timing_out.retain(|id| *id != escrow_id));
+	}
+
+	/// Close an escrow, releasing its amount to the beneficiary or refunding it to the depositor
+	fn settle(
+		escrow_id: EscrowId,
+		escrow: Escrow<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>,
+		release: bool,
+	) -> Result {
+		if release {
+			<generic_asset::Module<T>>::repatriate_reserved_named(
+				&ESCROW_RESERVE_ID,
+				&escrow.asset_id,
+				&escrow.depositor,
+				&escrow.beneficiary,
+				escrow.amount,
+			)?;
+		} else {
+			<generic_asset::Module<T>>::unreserve_named(
+				&ESCROW_RESERVE_ID,
+				&escrow.asset_id,
+				&escrow.depositor,
+				escrow.amount,
+			);
+		}
+		<Escrows<T>>::remove(escrow_id);
+
+		if release {
+			Self::deposit_event(RawEvent::EscrowReleased(escrow_id));
+		} else {
+			Self::deposit_event(RawEvent::EscrowRefunded(escrow_id));
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,143 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{
+	Escrows, ExtBuilder, GenericAsset, Origin, System, ARBITER, BENEFICIARY, DEPOSITOR, SPENDING_ASSET_ID,
+};
+use runtime_io::with_externalities;
+use runtime_primitives::traits::OnFinalize;
+use support::{assert_noop, assert_ok};
+
+fn free_balance_of(who: u64) -> u64 {
+	GenericAsset::free_balance(&SPENDING_ASSET_ID, &who)
+}
+
+fn create_escrow() {
+	assert_ok!(Escrows::create_escrow(
+		Origin::signed(DEPOSITOR),
+		SPENDING_ASSET_ID,
+		40,
+		BENEFICIARY,
+		ARBITER,
+		10
+	));
+}
+
+#[test]
+fn create_escrow_reserves_the_amount() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_noop!(
+			Escrows::create_escrow(Origin::signed(DEPOSITOR), SPENDING_ASSET_ID, 0, BENEFICIARY, ARBITER, 10),
+			"Escrow amount must be greater than zero"
+		);
+		assert_noop!(
+			Escrows::create_escrow(Origin::signed(DEPOSITOR), SPENDING_ASSET_ID, 40, BENEFICIARY, ARBITER, 0),
+			"Escrow timeout must be greater than zero"
+		);
+		create_escrow();
+
+		assert_eq!(free_balance_of(DEPOSITOR), 60);
+		assert_eq!(
+			GenericAsset::reserves(&SPENDING_ASSET_ID, &DEPOSITOR),
+			vec![(b"escrow".to_vec(), 40)]
+		);
+		assert_eq!(Escrows::escrow(0).map(|escrow| escrow.timeout), Some(11));
+		assert_eq!(Escrows::escrows_timing_out(11), vec![0]);
+	});
+}
+
+#[test]
+fn depositor_releases_and_beneficiary_refunds() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		create_escrow();
+		create_escrow();
+
+		assert_noop!(
+			Escrows::release(Origin::signed(BENEFICIARY), 0),
+			"Only the depositor can release an escrow"
+		);
+		assert_ok!(Escrows::release(Origin::signed(DEPOSITOR), 0));
+		assert_eq!(free_balance_of(BENEFICIARY), 40);
+
+		assert_noop!(
+			Escrows::refund(Origin::signed(DEPOSITOR), 1),
+			"Only the beneficiary can refund an escrow"
+		);
+		assert_ok!(Escrows::refund(Origin::signed(BENEFICIARY), 1));
+		assert_eq!(free_balance_of(DEPOSITOR), 60);
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &DEPOSITOR), 0);
+
+		assert_eq!(Escrows::escrow(0), None);
+		assert!(Escrows::escrows_timing_out(11).is_empty());
+		assert_noop!(Escrows::release(Origin::signed(DEPOSITOR), 0), "Escrow does not exist");
+	});
+}
+
+#[test]
+fn undisputed_escrow_is_refunded_at_its_timeout() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		create_escrow();
+
+		Escrows::on_finalize(10);
+		assert_eq!(free_balance_of(DEPOSITOR), 60);
+		Escrows::on_finalize(11);
+		assert_eq!(free_balance_of(DEPOSITOR), 100);
+		assert_eq!(Escrows::escrow(0), None);
+	});
+}
+
+#[test]
+fn disputed_escrow_is_settled_by_the_arbiter_only() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		create_escrow();
+
+		assert_noop!(
+			Escrows::resolve_dispute(Origin::signed(ARBITER), 0, true),
+			"Escrow is not disputed"
+		);
+		assert_noop!(
+			Escrows::dispute(Origin::signed(ARBITER), 0),
+			"Only the depositor or beneficiary can dispute an escrow"
+		);
+		assert_ok!(Escrows::dispute(Origin::signed(BENEFICIARY), 0));
+		assert_noop!(Escrows::dispute(Origin::signed(DEPOSITOR), 0), "Escrow is already disputed");
+		assert_noop!(
+			Escrows::release(Origin::signed(DEPOSITOR), 0),
+			"Escrow is disputed, only the arbiter can settle it"
+		);
+
+		// A disputed escrow does not time out
+		Escrows::on_finalize(11);
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &DEPOSITOR), 40);
+
+		assert_noop!(
+			Escrows::resolve_dispute(Origin::signed(BENEFICIARY), 0, true),
+			"Only the arbiter can resolve a dispute"
+		);
+		assert_ok!(Escrows::resolve_dispute(Origin::signed(ARBITER), 0, true));
+		assert_eq!(free_balance_of(BENEFICIARY), 40);
+		assert_eq!(free_balance_of(DEPOSITOR), 60);
+		assert_eq!(Escrows::escrow(0), None);
+	});
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Fee budget module.
+//!
+//! This module allows an account to set fee budgets for each of its delegates (doughnut holders).
+//! Fees for a delegated transaction are paid by the doughnut issuer, and are debited from the
+//! delegate's budget. A delegated transaction is rejected once the delegate's budget is exhausted.
+//! Budgets are reset at the start of every budget period.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_codec::{Codec, Decode, Encode};
+use runtime_primitives::traits::{As, CheckedAdd, MaybeSerializeDebug, Member, SimpleArithmetic, Zero};
+use support::{
+	decl_event, decl_module, decl_storage, dispatch::Result, ensure, Parameter, StorageDoubleMap, StorageValue,
+};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+pub trait Trait: system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	/// The fee balance type, this should match the balance of the fee asset
+	type Balance: Parameter + Member + SimpleArithmetic + Codec + Default + Copy + As<u64> + MaybeSerializeDebug;
+}
+
+/// A delegate's fee budget
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Budget<Balance, BlockNumber> {
+	/// The max. amount of fees the delegate may spend in a budget period
+	pub limit: Balance,
+	/// The amount of fees spent in the current budget period
+	pub spent: Balance,
+	/// The block number the current budget period started at
+	pub period_start: BlockNumber,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Set the fee budget of `delegate` for transactions delegated by the sender.
+		/// This starts a new budget period for the delegate.
+		pub fn set_budget(origin, delegate: T::AccountId, #[compact] limit: T::Balance) -> Result {
+			let issuer = ensure_signed(origin)?;
+
+			<Budgets<T>>::insert(&issuer, &delegate, Budget {
+				limit,
+				spent: Zero::zero(),
+				period_start: <system::Module<T>>::block_number(),
+			});
+
+			Self::deposit_event(RawEvent::BudgetSet(issuer, delegate, limit));
+			Ok(())
+		}
+
+		/// Remove the fee budget of `delegate` for transactions delegated by the sender
+		pub fn remove_budget(origin, delegate: T::AccountId) -> Result {
+			let issuer = ensure_signed(origin)?;
+			ensure!(<Budgets<T>>::get(&issuer, &delegate).is_some(), "Budget does not exist");
+
+			<Budgets<T>>::remove(&issuer, &delegate);
+
+			Self::deposit_event(RawEvent::BudgetRemoved(issuer, delegate));
+			Ok(())
+		}
+
+		/// Set the length of a budget period in blocks (root only)
+		pub fn set_budget_period(period: T::BlockNumber) -> Result {
+			ensure!(!period.is_zero(), "Budget period must be greater than zero");
+			<BudgetPeriod<T>>::put(period);
+			Ok(())
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where <T as system::Trait>::AccountId, <T as Trait>::Balance {
+		/// A fee budget was set: (issuer, delegate, limit)
+		BudgetSet(AccountId, AccountId, Balance),
+		/// A fee budget was removed: (issuer, delegate)
+		BudgetRemoved(AccountId, AccountId),
+		/// A delegate has spent their whole fee budget for the period: (issuer, delegate)
+		BudgetExhausted(AccountId, AccountId),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as FeeBudget {
+		/// Fee budgets: (issuer, delegate) => budget
+		pub Budgets get(budget): double_map T::AccountId, twox_128(T::AccountId) => Option<Budget<T::Balance, T::BlockNumber>>;
+		/// The length of a budget period in blocks
+		pub BudgetPeriod get(budget_period) config(): T::BlockNumber;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Debit `amount` of fees from the budget `issuer` has set for `delegate`.
+	/// Delegates without a budget are not limited.
+	pub fn debit(issuer: &T::AccountId, delegate: &T::AccountId, amount: T::Balance) -> Result {
+		let mut budget = match <Budgets<T>>::get(issuer, delegate) {
+			Some(budget) => budget,
+			None => return Ok(()),
+		};
+
+		let now = <system::Module<T>>::block_number();
+		let period = Self::budget_period();
+		if !period.is_zero() && now >= budget.period_start + period {
+			// Periods stay aligned with the block the budget was set at
+			budget.period_start = now - (now - budget.period_start) % period;
+			budget.spent = Zero::zero();
+		}
+
+		let spent = budget.spent.checked_add(&amount).ok_or_else(|| "fee budget overflow")?;
+		ensure!(spent <= budget.limit, "delegate fee budget exhausted");
+
+		if spent == budget.limit {
+			Self::deposit_event(RawEvent::BudgetExhausted(issuer.clone(), delegate.clone()));
+		}
+		budget.spent = spent;
+		<Budgets<T>>::insert(issuer, delegate, budget);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{ExtBuilder, FeeBudget, Origin, System};
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok};
+
+const ISSUER: u64 = 1;
+const DELEGATE: u64 = 2;
+
+#[test]
+fn debit_without_budget_is_unlimited() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(FeeBudget::debit(&ISSUER, &DELEGATE, u64::max_value()));
+		assert_eq!(FeeBudget::budget(&ISSUER, &DELEGATE), None);
+	});
+}
+
+#[test]
+fn debit_rejects_once_budget_is_exhausted() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(FeeBudget::set_budget(Origin::signed(ISSUER), DELEGATE, 100));
+
+		assert_ok!(FeeBudget::debit(&ISSUER, &DELEGATE, 60));
+		assert_ok!(FeeBudget::debit(&ISSUER, &DELEGATE, 40));
+		assert_noop!(FeeBudget::debit(&ISSUER, &DELEGATE, 1), "delegate fee budget exhausted");
+		assert_eq!(FeeBudget::budget(&ISSUER, &DELEGATE).unwrap().spent, 100);
+
+		// Budgets are per issuer
+		assert_ok!(FeeBudget::debit(&DELEGATE, &DELEGATE, 1));
+	});
+}
+
+#[test]
+fn budget_resets_each_period() {
+	with_externalities(&mut ExtBuilder::default().budget_period(10).build(), || {
+		System::set_block_number(1);
+		assert_ok!(FeeBudget::set_budget(Origin::signed(ISSUER), DELEGATE, 100));
+		assert_ok!(FeeBudget::debit(&ISSUER, &DELEGATE, 100));
+
+		System::set_block_number(10);
+		assert_noop!(FeeBudget::debit(&ISSUER, &DELEGATE, 1), "delegate fee budget exhausted");
+
+		System::set_block_number(25);
+		assert_ok!(FeeBudget::debit(&ISSUER, &DELEGATE, 1));
+		assert_eq!(
+			FeeBudget::budget(&ISSUER, &DELEGATE),
+			Some(Budget {
+				limit: 100,
+				spent: 1,
+				period_start: 21,
+			})
+		);
+	});
+}
+
+#[test]
+fn remove_budget_works() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_noop!(FeeBudget::remove_budget(Origin::signed(ISSUER), DELEGATE), "Budget does not exist");
+		assert_ok!(FeeBudget::set_budget(Origin::signed(ISSUER), DELEGATE, 0));
+		assert_noop!(FeeBudget::debit(&ISSUER, &DELEGATE, 1), "delegate fee budget exhausted");
+
+		assert_ok!(FeeBudget::remove_budget(Origin::signed(ISSUER), DELEGATE));
+		assert_ok!(FeeBudget::debit(&ISSUER, &DELEGATE, 1));
+	});
+}
+
+#[test]
+fn set_budget_period_rejects_zero() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_noop!(FeeBudget::set_budget_period(0), "Budget period must be greater than zero");
+		assert_ok!(FeeBudget::set_budget_period(5));
+		assert_eq!(FeeBudget::budget_period(), 5);
+	});
+}
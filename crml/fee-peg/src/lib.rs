@@ -0,0 +1,163 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Fee peg module.
+//!
+//! Keeps the real world cost of transaction fees roughly constant as the fee asset price moves.
+//! An oracle account reports the amount of fee asset worth one fiat unit, and every `AdjustmentPeriod`
+//! blocks the pegged fees are moved towards their fiat target, by at most `MaxStep` of their current cost.
+//! Governance may disable the peg to set fees directly through the fees module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use cennznet_primitives::fixed_point::{mul_div, Rounding};
+use fees::AssetOf;
+use rstd::prelude::*;
+use runtime_primitives::{
+	traits::{As, Zero},
+	Permill,
+};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageValue};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+pub trait Trait: fees::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	/// Converts fee amounts to and from `u128` for the peg math
+	type AsBalance: From<AssetOf<Self>> + Into<AssetOf<Self>> + As<u128>;
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Report the amount of fee asset worth one fiat unit (oracle only)
+		pub fn report_price(origin, #[compact] price: AssetOf<T>) -> Result {
+			let reporter = ensure_signed(origin)?;
+			ensure!(Self::oracle() == Some(reporter), "Only the oracle can report the fee asset price");
+			ensure!(!price.is_zero(), "Price must be greater than zero");
+
+			<Price<T>>::put(price);
+			Self::deposit_event(RawEvent::PriceReported(price));
+			Ok(())
+		}
+
+		/// Set the account which reports the fee asset price (root only)
+		pub fn set_oracle(oracle: T::AccountId) -> Result {
+			<Oracle<T>>::put(oracle);
+			Ok(())
+		}
+
+		/// Set the fiat target of a fee in milli fiat units, a zero target stops pegging the fee (root only)
+		pub fn set_target_fee(fee: <T as fees::Trait>::Fee, #[compact] target: u64) -> Result {
+			let mut targets: Vec<_> = Self::target_fees().into_iter().filter(|(f, _)| *f != fee).collect();
+			if target > 0 {
+				targets.push((fee, target));
+			}
+			<TargetFees<T>>::put(targets);
+			Ok(())
+		}
+
+		/// Set the maximum fraction a fee may change by in each adjustment (root only)
+		pub fn set_max_step(max_step: Permill) -> Result {
+			<MaxStep<T>>::put(max_step);
+			Ok(())
+		}
+
+		/// Enable or disable the peg, when disabled fees are only changed by governance (root only)
+		pub fn set_enabled(enabled: bool) -> Result {
+			<Enabled<T>>::put(enabled);
+			Ok(())
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			let period = Self::adjustment_period();
+			if Self::enabled() && !period.is_zero() && (n % period).is_zero() {
+				Self::adjust_fees();
+			}
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T>
+	where
+		Amount = AssetOf<T>,
+	{
+		/// The oracle reported a new fee asset price (fee asset per fiat unit)
+		PriceReported(Amount),
+		/// Pegged fees were adjusted at the fee asset price
+		FeesAdjusted(Amount),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as FeePeg {
+		/// Whether pegged fees are adjusted automatically
+		pub Enabled get(enabled) config(): bool;
+		/// The account which reports the fee asset price
+		pub Oracle get(oracle) config(): Option<T::AccountId>;
+		/// The amount of fee asset worth one fiat unit, as last reported by the oracle
+		pub Price get(price): AssetOf<T>;
+		/// The fiat cost of each pegged fee, in milli fiat units
+		pub TargetFees get(target_fees) config(): Vec<(<T as fees::Trait>::Fee, u64)>;
+		/// The maximum fraction a fee may change by in each adjustment
+		pub MaxStep get(max_step) config(): Permill;
+		/// The number of blocks between fee adjustments
+		pub AdjustmentPeriod get(adjustment_period) config(): T::BlockNumber;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Move each pegged fee towards its fiat target at the reported price.
+	/// Nothing is adjusted until the oracle has reported a price.
+	fn adjust_fees() {
+		let price = Self::price();
+		if price.is_zero() {
+			return;
+		}
+
+		for (fee, target) in Self::target_fees() {
+			let current = <fees::Module<T>>::fee_registry(fee.clone());
+			let _ = <fees::Module<T>>::set_fee(fee, Self::next_fee(current, Self::pegged_fee(price, target)));
+		}
+		Self::deposit_event(RawEvent::FeesAdjusted(price));
+	}
+
+	/// The fee amount worth `target` milli fiat units at `price`
+	pub fn pegged_fee(price: AssetOf<T>, target: u64) -> AssetOf<T> {
+		let price: u128 = Into::<T::AsBalance>::into(price).as_();
+		let fee = mul_div(price, target.into(), 1_000, Rounding::Down).unwrap_or_else(u128::max_value);
+		let fee: T::AsBalance = As::sa(fee);
+		fee.into()
+	}
+
+	/// Step from the `current` fee towards the `pegged` fee, by at most `MaxStep` of the current fee.
+	/// A zero fee is set to its pegged amount directly.
+	pub fn next_fee(current: AssetOf<T>, pegged: AssetOf<T>) -> AssetOf<T> {
+		if current.is_zero() {
+			return pegged;
+		}
+		let max_step = Self::max_step() * current;
+		if pegged > current {
+			pegged.min(current + max_step)
+		} else {
+			pegged.max(current - max_step)
+		}
+	}
+}
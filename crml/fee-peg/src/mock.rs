@@ -0,0 +1,146 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Test utilities
+
+#![cfg(test)]
+
+use crate::{GenesisConfig, Module, Trait};
+use parity_codec::{Decode, Encode};
+use primitives::{Blake2Hasher, H256};
+use runtime_io;
+use runtime_primitives::traits::{Lazy, Verify};
+use runtime_primitives::{BuildStorage, Permill};
+use runtime_primitives::{
+	testing::{Digest, DigestItem, Header},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use serde::{Deserialize, Serialize};
+use support::{impl_outer_event, impl_outer_origin};
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod fee_peg {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		fee_peg<T>,
+	}
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Debug)]
+pub struct Signature;
+
+impl Verify for Signature {
+	type Signer = u64;
+	fn verify<L: Lazy<[u8]>>(&self, _msg: L, _signer: &Self::Signer) -> bool {
+		true
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode)]
+pub enum MockFee {
+	Base,
+	Bytes,
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Test;
+impl system::Trait for Test {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Digest = Digest;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<u64>;
+	type Header = Header;
+	type Event = TestEvent;
+	type Log = DigestItem;
+	type Signature = Signature;
+}
+
+impl generic_asset::Trait for Test {
+	type Balance = u64;
+	type AssetId = u32;
+	type Event = ();
+	type OnTransferPayload = ();
+}
+
+impl fees::Trait for Test {
+	type Event = ();
+	type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	type OnFeeCharged = ();
+	type BuyFeeAsset = ();
+	type Fee = MockFee;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type AsBalance = u64;
+}
+
+pub type System = system::Module<Test>;
+pub type FeePeg = Module<Test>;
+pub type Fees = fees::Module<Test>;
+
+pub struct ExtBuilder {
+	base_fee: u64,
+	enabled: bool,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			base_fee: 1_000,
+			enabled: true,
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn base_fee(mut self, base_fee: u64) -> Self {
+		self.base_fee = base_fee;
+		self
+	}
+	pub fn enabled(mut self, enabled: bool) -> Self {
+		self.enabled = enabled;
+		self
+	}
+	pub fn build(self) -> runtime_io::TestExternalities<Blake2Hasher> {
+		let (mut t, mut c) = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let _ = fees::GenesisConfig::<Test> {
+			_genesis_phantom_data: Default::default(),
+			fee_registry: vec![(MockFee::Base, self.base_fee), (MockFee::Bytes, 1)],
+		}
+		.assimilate_storage(&mut t, &mut c);
+		let _ = GenesisConfig::<Test> {
+			enabled: self.enabled,
+			oracle: Some(1),
+			target_fees: vec![(MockFee::Base, 10)],
+			max_step: Permill::from_percent(10),
+			adjustment_period: 10,
+		}
+		.assimilate_storage(&mut t, &mut c);
+
+		t.into()
+	}
+}
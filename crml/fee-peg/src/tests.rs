@@ -0,0 +1,113 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{ExtBuilder, FeePeg, Fees, MockFee, Origin, System, TestEvent};
+use runtime_io::with_externalities;
+use runtime_primitives::traits::OnFinalize;
+use support::{assert_noop, assert_ok};
+
+fn fee_peg_events() -> Vec<RawEvent<u64>> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			TestEvent::fee_peg(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn only_the_oracle_can_report_a_price() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_noop!(
+			FeePeg::report_price(Origin::signed(2), 5_000),
+			"Only the oracle can report the fee asset price"
+		);
+		assert_noop!(FeePeg::report_price(Origin::signed(1), 0), "Price must be greater than zero");
+
+		assert_ok!(FeePeg::report_price(Origin::signed(1), 5_000));
+		assert_eq!(FeePeg::price(), 5_000);
+		assert_eq!(fee_peg_events(), vec![RawEvent::PriceReported(5_000)]);
+	});
+}
+
+#[test]
+fn fees_step_towards_their_peg_each_period() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		// 10 milli fiat units at 5_000 per fiat unit
+		assert_ok!(FeePeg::report_price(Origin::signed(1), 5_000));
+		assert_eq!(FeePeg::pegged_fee(5_000, 10), 50);
+
+		// not an adjustment block
+		<FeePeg as OnFinalize<u64>>::on_finalize(9);
+		assert_eq!(Fees::fee_registry(MockFee::Base), 1_000);
+
+		// at most 10% per adjustment
+		<FeePeg as OnFinalize<u64>>::on_finalize(10);
+		assert_eq!(Fees::fee_registry(MockFee::Base), 900);
+		<FeePeg as OnFinalize<u64>>::on_finalize(20);
+		assert_eq!(Fees::fee_registry(MockFee::Base), 810);
+
+		// unpegged fees are left alone
+		assert_eq!(Fees::fee_registry(MockFee::Bytes), 1);
+	});
+}
+
+#[test]
+fn fees_stop_at_their_peg() {
+	with_externalities(&mut ExtBuilder::default().base_fee(52).build(), || {
+		assert_ok!(FeePeg::report_price(Origin::signed(1), 5_000));
+		<FeePeg as OnFinalize<u64>>::on_finalize(10);
+		assert_eq!(Fees::fee_registry(MockFee::Base), 50);
+		<FeePeg as OnFinalize<u64>>::on_finalize(20);
+		assert_eq!(Fees::fee_registry(MockFee::Base), 50);
+	});
+}
+
+#[test]
+fn fees_are_not_adjusted_without_a_price_or_when_disabled() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		<FeePeg as OnFinalize<u64>>::on_finalize(10);
+		assert_eq!(Fees::fee_registry(MockFee::Base), 1_000);
+	});
+
+	with_externalities(&mut ExtBuilder::default().enabled(false).build(), || {
+		assert_ok!(FeePeg::report_price(Origin::signed(1), 5_000));
+		<FeePeg as OnFinalize<u64>>::on_finalize(10);
+		assert_eq!(Fees::fee_registry(MockFee::Base), 1_000);
+
+		assert_ok!(FeePeg::set_enabled(true));
+		<FeePeg as OnFinalize<u64>>::on_finalize(20);
+		assert_eq!(Fees::fee_registry(MockFee::Base), 900);
+	});
+}
+
+#[test]
+fn set_target_fee_works() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(FeePeg::set_target_fee(MockFee::Bytes, 1));
+		assert_ok!(FeePeg::set_target_fee(MockFee::Base, 20));
+		assert_eq!(FeePeg::target_fees(), vec![(MockFee::Bytes, 1), (MockFee::Base, 20)]);
+
+		// a zero target stops pegging the fee
+		assert_ok!(FeePeg::set_target_fee(MockFee::Bytes, 0));
+		assert_eq!(FeePeg::target_fees(), vec![(MockFee::Base, 20)]);
+	});
+}
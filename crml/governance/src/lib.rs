@@ -0,0 +1,255 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Governance module.
+//!
+//! Staking asset holders propose runtime parameter changes and vote on them in referenda.
+//! A proposal reserves `ProposalDeposit` of the proposer's staking asset until its referendum ends.
+//! A vote is weighted by the voter's free staking asset balance at the start of the snapshot period the
+//! referendum was proposed in, see `generic_asset::Module::voting_weight_at`, and is locked until the
+//! referendum ends. Votes are tallied as they are cast, a voter may remove its vote once the referendum ends.
+//! A referendum passes when its ayes outweigh its nays and its turnout reaches the quorum, and the proposal
+//! is then dispatched as root. The runtime decides which proposals are parameter changes governance may make.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use generic_asset::StakingAssetCurrency;
+use parity_codec::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::traits::{One, Zero};
+use support::traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons};
+use support::{
+	decl_event, decl_module, decl_storage, dispatch::Result, ensure, Dispatchable, Parameter, StorageMap, StorageValue,
+};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The lock held on voters' staking asset balances until the referenda they voted in end
+pub const GOVERNANCE_LOCK_ID: LockIdentifier = *b"govvote ";
+/// The max. number of referenda which may be open at once
+pub const MAX_OPEN_REFERENDA: u32 = 16;
+/// Identifies the proposal deposit in the proposer's reserved balance
+pub const PROPOSAL_RESERVE_ID: generic_asset::ReserveIdentifier = *b"govprop ";
+
+pub type ReferendumIndex = u32;
+
+pub trait Trait: generic_asset::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	/// A call which may be proposed and dispatched as root once its referendum passes
+	type Proposal: Parameter + Dispatchable<Origin = Self::Origin>;
+	/// Decides which proposals governance may make
+	type ProposalFilter: ProposalFilter<Self::Proposal>;
+}
+
+/// Decides which proposals may be put to a referendum
+pub trait ProposalFilter<Proposal> {
+	/// Whether `proposal` may be put to a referendum
+	fn allows(proposal: &Proposal) -> bool;
+}
+
+/// An open referendum
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Referendum<AccountId, Proposal, Balance, BlockNumber> {
+	/// The call dispatched if the referendum passes
+	pub proposal: Proposal,
+	/// The account which proposed the referendum
+	pub proposer: AccountId,
+	/// The staking asset reserved from the proposer until the referendum ends
+	pub deposit: Balance,
+	/// The block the referendum was proposed at, votes are weighted by the balances at its snapshot
+	pub proposed: BlockNumber,
+	/// The block the referendum ends at
+	pub end: BlockNumber,
+	/// The vote weight in favour
+	pub ayes: Balance,
+	/// The vote weight against
+	pub nays: Balance,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Put `proposal` to a referendum ending after the voting period, the sender must hold the staking asset.
+		pub fn propose(origin, proposal: Box<T::Proposal>) -> Result {
+			let proposer = ensure_signed(origin)?;
+			ensure!(T::ProposalFilter::allows(&proposal), "proposal is not a governable parameter change");
+			ensure!(!Self::voting_weight(&proposer).is_zero(), "proposer does not hold the staking asset");
+			let open_referenda = Self::open_referenda();
+			ensure!(open_referenda < MAX_OPEN_REFERENDA, "too many referenda are open");
+
+			let referendum_id = Self::next_referendum_id();
+			let next_referendum_id = referendum_id.checked_add(1).ok_or_else(|| "No new referendum id is available")?;
+			let deposit = Self::proposal_deposit();
+			let staking_asset_id = <generic_asset::Module<T>>::staking_asset_id();
+			<generic_asset::Module<T>>::reserve_named(&PROPOSAL_RESERVE_ID, &staking_asset_id, &proposer, deposit)?;
+
+			let now = <system::Module<T>>::block_number();
+			let end = now + Self::voting_period();
+			<NextReferendumId<T>>::put(next_referendum_id);
+			<OpenReferenda<T>>::put(open_referenda + 1);
+			<ReferendaEnding<T>>::mutate(end, |ending| ending.push(referendum_id));
+			<Referenda<T>>::insert(
+				referendum_id,
+				Referendum {
+					proposal: *proposal,
+					proposer: proposer.clone(),
+					deposit,
+					proposed: now,
+					end,
+					ayes: Zero::zero(),
+					nays: Zero::zero(),
+				},
+			);
+
+			Self::deposit_event(RawEvent::Proposed(referendum_id, proposer));
+			Ok(())
+		}
+
+		/// Vote for or against a referendum with the sender's free staking asset balance as of the referendum's
+		/// snapshot, which is locked until the referendum ends. Voting again replaces the sender's previous vote.
+		pub fn vote(origin, #[compact] referendum_id: ReferendumIndex, aye: bool) -> Result {
+			let voter = ensure_signed(origin)?;
+			let mut referendum = Self::referendum(referendum_id).ok_or_else(|| "referendum is not open")?;
+			let weight = <generic_asset::Module<T>>::voting_weight_at(&voter, referendum.proposed)
+				.ok_or_else(|| "referendum snapshot is no longer available")?;
+			ensure!(!weight.is_zero(), "voter does not hold the staking asset");
+
+			match Self::vote_of((referendum_id, voter.clone())) {
+				Some((true, previous)) => referendum.ayes -= previous,
+				Some((false, previous)) => referendum.nays -= previous,
+				None => {}
+			}
+			if aye {
+				referendum.ayes += weight;
+			} else {
+				referendum.nays += weight;
+			}
+			// Locked through the end block, so the weight can not be moved and voted again before the tally
+			<StakingAssetCurrency<T>>::extend_lock(
+				GOVERNANCE_LOCK_ID,
+				&voter,
+				weight,
+				referendum.end + One::one(),
+				WithdrawReasons::all(),
+			);
+			<Votes<T>>::insert((referendum_id, voter.clone()), (aye, weight));
+			<Referenda<T>>::insert(referendum_id, referendum);
+
+			Self::deposit_event(RawEvent::Voted(referendum_id, voter, aye, weight));
+			Ok(())
+		}
+
+		/// Set the number of blocks referenda are open for and the turnout they need to pass (root only).
+		/// Open referenda keep their end block.
+		pub fn set_voting_parameters(voting_period: T::BlockNumber, quorum: T::Balance) -> Result {
+			ensure!(!voting_period.is_zero(), "voting period must be greater than zero");
+			<VotingPeriod<T>>::put(voting_period);
+			<Quorum<T>>::put(quorum);
+			Ok(())
+		}
+
+		/// Remove the sender's vote from a referendum which has ended.
+		pub fn remove_vote(origin, #[compact] referendum_id: ReferendumIndex) -> Result {
+			let voter = ensure_signed(origin)?;
+			ensure!(!<Referenda<T>>::exists(referendum_id), "referendum is still open");
+			ensure!(<Votes<T>>::exists((referendum_id, voter.clone())), "voter did not vote in the referendum");
+			<Votes<T>>::remove((referendum_id, voter));
+			Ok(())
+		}
+
+		/// Set the staking asset reserved from the proposer of each referendum (root only).
+		pub fn set_proposal_deposit(#[compact] deposit: T::Balance) -> Result {
+			<ProposalDeposit<T>>::put(deposit);
+			Ok(())
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			for referendum_id in <ReferendaEnding<T>>::take(n) {
+				Self::close(referendum_id);
+			}
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where <T as system::Trait>::AccountId, <T as generic_asset::Trait>::Balance {
+		/// A referendum was opened: (referendum id, proposer)
+		Proposed(ReferendumIndex, AccountId),
+		/// A vote was cast: (referendum id, voter, aye, weight)
+		Voted(ReferendumIndex, AccountId, bool, Balance),
+		/// A referendum passed and its proposal was dispatched: (referendum id, dispatch succeeded)
+		Enacted(ReferendumIndex, bool),
+		/// A referendum did not pass (referendum id)
+		Rejected(ReferendumIndex),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Governance {
+		/// The number of blocks a referendum is open for
+		pub VotingPeriod get(voting_period) config(): T::BlockNumber;
+		/// The total vote weight a referendum needs to pass
+		pub Quorum get(quorum) config(): T::Balance;
+		/// The staking asset reserved from the proposer of a referendum until it ends
+		pub ProposalDeposit get(proposal_deposit) config(): T::Balance;
+		/// The id of the next referendum to be opened
+		pub NextReferendumId get(next_referendum_id): ReferendumIndex;
+		/// The number of open referenda
+		pub OpenReferenda get(open_referenda): u32;
+		/// Open referenda: referendum id => referendum
+		pub Referenda get(referendum): map ReferendumIndex => Option<Referendum<T::AccountId, T::Proposal, T::Balance, T::BlockNumber>>;
+		/// Votes, kept until their voter removes them: (referendum id, voter) => (aye, weight)
+		pub Votes get(vote_of): map (ReferendumIndex, T::AccountId) => Option<(bool, T::Balance)>;
+		/// The referenda which end at the end of a block
+		ReferendaEnding get(referenda_ending): map T::BlockNumber => Vec<ReferendumIndex>;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The vote weight of `who`: its free staking asset balance
+	pub fn voting_weight(who: &T::AccountId) -> T::Balance {
+		<StakingAssetCurrency<T>>::free_balance(who)
+	}
+
+	/// Tally a referendum which has ended and dispatch its proposal if it passed
+	fn close(referendum_id: ReferendumIndex) {
+		let referendum = match <Referenda<T>>::take(referendum_id) {
+			Some(referendum) => referendum,
+			None => return,
+		};
+		<OpenReferenda<T>>::mutate(|open| *open = open.saturating_sub(1));
+		let staking_asset_id = <generic_asset::Module<T>>::staking_asset_id();
+		<generic_asset::Module<T>>::unreserve_named(
+			&PROPOSAL_RESERVE_ID,
+			&staking_asset_id,
+			&referendum.proposer,
+			referendum.deposit,
+		);
+
+		// Voters' locks expire by themselves after the referendum ends
+		let turnout = referendum.ayes + referendum.nays;
+		if referendum.ayes > referendum.nays && turnout >= Self::quorum() {
+			let ok = referendum.proposal.dispatch(system::RawOrigin::Root.into()).is_ok();
+			Self::deposit_event(RawEvent::Enacted(referendum_id, ok));
+		} else {
+			Self::deposit_event(RawEvent::Rejected(referendum_id));
+		}
+	}
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Test utilities
+
+#![cfg(test)]
+
+use crate::{GenesisConfig, Module, ProposalFilter, Trait};
+use parity_codec::{Decode, Encode};
+use primitives::{Blake2Hasher, H256};
+use runtime_io;
+use runtime_primitives::traits::{Lazy, Verify};
+use runtime_primitives::BuildStorage;
+use runtime_primitives::{
+	testing::{Digest, DigestItem, Header},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use serde::{Deserialize, Serialize};
+use support::{impl_outer_dispatch, impl_outer_origin};
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		generic_asset::GenericAsset,
+	}
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Debug)]
+pub struct Signature;
+
+impl Verify for Signature {
+	type Signer = u64;
+	fn verify<L: Lazy<[u8]>>(&self, _msg: L, _signer: &Self::Signer) -> bool {
+		true
+	}
+}
+
+pub const STAKING_ASSET_ID: u32 = 16_000;
+pub const SPENDING_ASSET_ID: u32 = 16_001;
+pub const VOTING_PERIOD: u64 = 10;
+pub const QUORUM: u64 = 50;
+pub const PROPOSAL_DEPOSIT: u64 = 10;
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Test;
+impl system::Trait for Test {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Digest = Digest;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<u64>;
+	type Header = Header;
+	type Event = ();
+	type Log = DigestItem;
+	type Signature = Signature;
+}
+
+impl generic_asset::Trait for Test {
+	type Balance = u64;
+	type AssetId = u32;
+	type Event = ();
+	type OnTransferPayload = ();
+}
+
+/// Only the generic asset creation stake may be governed
+pub struct CreateAssetStakeOnly;
+
+impl ProposalFilter<Call> for CreateAssetStakeOnly {
+	fn allows(proposal: &Call) -> bool {
+		match proposal {
+			Call::GenericAsset(generic_asset::Call::set_create_asset_stake(..)) => true,
+			_ => false,
+		}
+	}
+}
+
+impl Trait for Test {
+	type Event = ();
+	type Proposal = Call;
+	type ProposalFilter = CreateAssetStakeOnly;
+}
+
+pub type GenericAsset = generic_asset::Module<Test>;
+pub type Governance = Module<Test>;
+pub type System = system::Module<Test>;
+
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	/// Accounts 1, 2 and 3 hold 100 of the staking asset, account 4 holds none
+	pub fn build() -> runtime_io::TestExternalities<Blake2Hasher> {
+		let (mut t, mut c) = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let _ = generic_asset::GenesisConfig::<Test> {
+			staking_asset_id: STAKING_ASSET_ID,
+			spending_asset_id: SPENDING_ASSET_ID,
+			assets: vec![STAKING_ASSET_ID, SPENDING_ASSET_ID],
+			endowed_accounts: vec![1, 2, 3],
+			endowments: vec![],
+			genesis_locks: vec![],
+			create_asset_stake: 10,
+			initial_balance: 100,
+			next_asset_id: 17_000,
+		}
+		.assimilate_storage(&mut t, &mut c);
+		let _ = GenesisConfig::<Test> {
+			voting_period: VOTING_PERIOD,
+			quorum: QUORUM,
+			proposal_deposit: PROPOSAL_DEPOSIT,
+		}
+		.assimilate_storage(&mut t, &mut c);
+
+		t.into()
+	}
+}
@@ -0,0 +1,176 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{
+	Call, ExtBuilder, GenericAsset, Governance, Origin, System, PROPOSAL_DEPOSIT, STAKING_ASSET_ID, VOTING_PERIOD,
+};
+use generic_asset::VOTING_SNAPSHOT_PERIOD;
+use runtime_io::with_externalities;
+use runtime_primitives::traits::OnFinalize;
+use support::{assert_noop, assert_ok};
+
+fn set_stake(stake: u64) -> Box<Call> {
+	Box::new(Call::GenericAsset(generic_asset::Call::set_create_asset_stake(stake)))
+}
+
+fn end_referendum() {
+	let end = System::block_number() + VOTING_PERIOD;
+	System::set_block_number(end);
+	Governance::on_finalize(end);
+}
+
+#[test]
+fn propose_is_validated() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		assert_noop!(
+			Governance::propose(
+				Origin::signed(1),
+				Box::new(Call::GenericAsset(generic_asset::Call::set_dust_collector(None)))
+			),
+			"proposal is not a governable parameter change"
+		);
+		assert_noop!(
+			Governance::propose(Origin::signed(4), set_stake(1)),
+			"proposer does not hold the staking asset"
+		);
+		for i in 0..MAX_OPEN_REFERENDA {
+			assert_ok!(Governance::propose(Origin::signed(1 + (i % 2) as u64), set_stake(1)));
+		}
+		assert_noop!(
+			Governance::propose(Origin::signed(3), set_stake(1)),
+			"too many referenda are open"
+		);
+	});
+}
+
+#[test]
+fn propose_reserves_a_deposit_until_the_referendum_ends() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Governance::propose(Origin::signed(1), set_stake(5)));
+		assert_eq!(GenericAsset::reserved_balance(&STAKING_ASSET_ID, &1), PROPOSAL_DEPOSIT);
+		assert_eq!(GenericAsset::free_balance(&STAKING_ASSET_ID, &1), 100 - PROPOSAL_DEPOSIT);
+
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), STAKING_ASSET_ID, 4, 100 - PROPOSAL_DEPOSIT + 1));
+		assert_noop!(Governance::propose(Origin::signed(2), set_stake(5)), "not enough free funds");
+
+		end_referendum();
+		assert_eq!(GenericAsset::reserved_balance(&STAKING_ASSET_ID, &1), 0);
+		assert_eq!(GenericAsset::free_balance(&STAKING_ASSET_ID, &1), 100);
+	});
+}
+
+#[test]
+fn passed_referendum_is_enacted_as_root() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Governance::propose(Origin::signed(1), set_stake(5)));
+		assert_eq!(Governance::referendum(0).map(|referendum| referendum.end), Some(1 + VOTING_PERIOD));
+
+		assert_ok!(Governance::vote(Origin::signed(1), 0, true));
+		assert_ok!(Governance::vote(Origin::signed(2), 0, false));
+		assert_ok!(Governance::vote(Origin::signed(3), 0, true));
+		assert_noop!(Governance::vote(Origin::signed(4), 0, true), "voter does not hold the staking asset");
+		assert_eq!(Governance::referendum(0).map(|referendum| (referendum.ayes, referendum.nays)), Some((200, 100)));
+
+		// Vote weight is locked until the referendum has ended
+		assert!(GenericAsset::transfer(Origin::signed(1), STAKING_ASSET_ID, 4, 10).is_err());
+
+		end_referendum();
+		assert_eq!(GenericAsset::create_asset_stake(), 5);
+		assert_eq!(Governance::referendum(0), None);
+		assert_eq!(Governance::open_referenda(), 0);
+		assert_noop!(Governance::vote(Origin::signed(1), 0, true), "referendum is not open");
+
+		// Votes are left to their voters to remove
+		assert_eq!(Governance::vote_of((0, 1)), Some((true, 100)));
+		assert_ok!(Governance::remove_vote(Origin::signed(1), 0));
+		assert_eq!(Governance::vote_of((0, 1)), None);
+		assert_noop!(Governance::remove_vote(Origin::signed(1), 0), "voter did not vote in the referendum");
+
+		System::set_block_number(System::block_number() + 1);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), STAKING_ASSET_ID, 4, 10));
+	});
+}
+
+#[test]
+fn referendum_without_majority_or_quorum_is_rejected() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Governance::propose(Origin::signed(1), set_stake(5)));
+		assert_ok!(Governance::vote(Origin::signed(1), 0, true));
+		assert_ok!(Governance::vote(Origin::signed(2), 0, false));
+		end_referendum();
+		assert_eq!(GenericAsset::create_asset_stake(), 10);
+
+		// Account 1 moves away all but 40 of its stake, which is below the quorum from the next snapshot period
+		System::set_block_number(System::block_number() + 1);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), STAKING_ASSET_ID, 4, 60));
+		System::set_block_number(VOTING_SNAPSHOT_PERIOD);
+		assert_ok!(Governance::propose(Origin::signed(1), set_stake(5)));
+		assert_ok!(Governance::vote(Origin::signed(1), 1, true));
+		end_referendum();
+		assert_eq!(GenericAsset::create_asset_stake(), 10);
+	});
+}
+
+#[test]
+fn voting_again_replaces_the_previous_vote() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Governance::propose(Origin::signed(1), set_stake(5)));
+		assert_ok!(Governance::vote(Origin::signed(1), 0, false));
+		assert_ok!(Governance::vote(Origin::signed(1), 0, true));
+		assert_eq!(Governance::referendum(0).map(|referendum| (referendum.ayes, referendum.nays)), Some((100, 0)));
+		assert_eq!(Governance::vote_of((0, 1)), Some((true, 100)));
+
+		end_referendum();
+		assert_eq!(GenericAsset::create_asset_stake(), 5);
+	});
+}
+
+#[test]
+fn votes_are_weighted_by_the_balance_at_the_referendum_snapshot() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Governance::propose(Origin::signed(1), set_stake(5)));
+		assert_ok!(Governance::vote(Origin::signed(2), 0, false));
+
+		// Stake moved after the snapshot can not be voted again
+		System::set_block_number(2);
+		assert_ok!(GenericAsset::transfer(Origin::signed(3), STAKING_ASSET_ID, 4, 100));
+		assert_noop!(Governance::vote(Origin::signed(4), 0, true), "voter does not hold the staking asset");
+		assert_ok!(Governance::vote(Origin::signed(3), 0, true));
+		assert_eq!(Governance::referendum(0).map(|referendum| (referendum.ayes, referendum.nays)), Some((100, 100)));
+
+		end_referendum();
+		assert_eq!(GenericAsset::create_asset_stake(), 10);
+	});
+}
+
+#[test]
+fn remove_vote_is_refused_while_the_referendum_is_open() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Governance::propose(Origin::signed(1), set_stake(5)));
+		assert_ok!(Governance::vote(Origin::signed(1), 0, true));
+		assert_noop!(Governance::remove_vote(Origin::signed(1), 0), "referendum is still open");
+	});
+}
@@ -0,0 +1,140 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Integrity module.
+//!
+//! Runs cheap runtime invariant checks, intended for canary monitoring after runtime upgrades.
+//! Checks run every `CheckPeriod` blocks or on demand via a root call, and an `IntegrityViolated`
+//! event is deposited for each failed check.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use rstd::prelude::*;
+use runtime_primitives::traits::Zero;
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, StorageMap, StorageValue};
+
+mod mock;
+mod tests;
+
+pub trait Trait: generic_asset::Trait + fees::Trait {
+	/// The overarching event type.
+	type Event: From<Event> + Into<<Self as system::Trait>::Event>;
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event() = default;
+
+		/// Run the integrity checks now (root only)
+		pub fn assert_runtime_integrity() -> Result {
+			Self::check_integrity();
+			Ok(())
+		}
+
+		/// Set the number of blocks between automatic integrity checks, zero disables them (root only)
+		pub fn set_check_period(period: T::BlockNumber) -> Result {
+			<CheckPeriod<T>>::put(period);
+			Ok(())
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			let period = Self::check_period();
+			if !period.is_zero() && (n % period).is_zero() {
+				Self::check_integrity();
+			}
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event {
+		/// All integrity checks passed
+		IntegrityChecked,
+		/// An integrity check failed (description)
+		IntegrityViolated(Vec<u8>),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Integrity {
+		/// The number of blocks between automatic integrity checks
+		pub CheckPeriod get(check_period) config(): T::BlockNumber;
+		/// Fee types which must have a non-zero cost
+		pub RequiredFees get(required_fees) config(): Vec<<T as fees::Trait>::Fee>;
+		/// The next asset id seen by the last check, it may never decrease
+		pub LastNextAssetId get(last_next_asset_id): T::AssetId;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Run all integrity checks, depositing an event for each violation.
+	/// Returns the number of violations.
+	pub fn check_integrity() -> usize {
+		let violations: Vec<&'static str> = [
+			Self::check_next_asset_id(),
+			Self::check_core_assets(),
+			Self::check_fees(),
+		]
+		.iter()
+		.filter_map(|result| result.err())
+		.collect();
+
+		if violations.is_empty() {
+			Self::deposit_event(Event::IntegrityChecked);
+		}
+		for violation in violations.iter() {
+			let description = <generic_asset::Module<T>>::bounded_event_data(violation.as_bytes().to_vec());
+			Self::deposit_event(Event::IntegrityViolated(description));
+		}
+
+		violations.len()
+	}
+
+	/// The next asset id must never decrease
+	fn check_next_asset_id() -> Result {
+		let next_asset_id = <generic_asset::Module<T>>::next_asset_id();
+		let last_next_asset_id = Self::last_next_asset_id();
+		<LastNextAssetId<T>>::put(next_asset_id.max(last_next_asset_id));
+
+		if next_asset_id < last_next_asset_id {
+			Err("next asset id decreased")
+		} else {
+			Ok(())
+		}
+	}
+
+	/// The staking and spending assets must exist
+	fn check_core_assets() -> Result {
+		if !<generic_asset::TotalIssuance<T>>::exists(<generic_asset::Module<T>>::staking_asset_id()) {
+			return Err("staking asset does not exist");
+		}
+		if !<generic_asset::TotalIssuance<T>>::exists(<generic_asset::Module<T>>::spending_asset_id()) {
+			return Err("spending asset does not exist");
+		}
+		Ok(())
+	}
+
+	/// Required fees must have a non-zero cost
+	fn check_fees() -> Result {
+		if Self::required_fees()
+			.into_iter()
+			.any(|fee| <fees::Module<T>>::fee_registry(fee).is_zero())
+		{
+			Err("required fee is zero")
+		} else {
+			Ok(())
+		}
+	}
+}
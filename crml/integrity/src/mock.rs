@@ -0,0 +1,154 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Test utilities
+
+#![cfg(test)]
+
+use crate::{GenesisConfig, Module, Trait};
+use parity_codec::{Decode, Encode};
+use primitives::{Blake2Hasher, H256};
+use runtime_io;
+use runtime_primitives::traits::{Lazy, Verify};
+use runtime_primitives::BuildStorage;
+use runtime_primitives::{
+	testing::{Digest, DigestItem, Header},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use serde::{Deserialize, Serialize};
+use support::{impl_outer_event, impl_outer_origin};
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod integrity {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		integrity,
+	}
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Debug)]
+pub struct Signature;
+
+impl Verify for Signature {
+	type Signer = u64;
+	fn verify<L: Lazy<[u8]>>(&self, _msg: L, _signer: &Self::Signer) -> bool {
+		true
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode)]
+pub enum MockFee {
+	Base,
+	Bytes,
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Test;
+impl system::Trait for Test {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Digest = Digest;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<u64>;
+	type Header = Header;
+	type Event = TestEvent;
+	type Log = DigestItem;
+	type Signature = Signature;
+}
+
+impl generic_asset::Trait for Test {
+	type Balance = u64;
+	type AssetId = u32;
+	type Event = ();
+	type OnTransferPayload = ();
+}
+
+impl fees::Trait for Test {
+	type Event = ();
+	type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	type OnFeeCharged = ();
+	type BuyFeeAsset = ();
+	type Fee = MockFee;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+}
+
+pub type System = system::Module<Test>;
+pub type Integrity = Module<Test>;
+pub type GenericAsset = generic_asset::Module<Test>;
+
+pub struct ExtBuilder {
+	assets: Vec<u32>,
+	base_fee: u64,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			assets: vec![16_000, 16_001],
+			base_fee: 1,
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn assets(mut self, assets: Vec<u32>) -> Self {
+		self.assets = assets;
+		self
+	}
+	pub fn base_fee(mut self, base_fee: u64) -> Self {
+		self.base_fee = base_fee;
+		self
+	}
+	pub fn build(self) -> runtime_io::TestExternalities<Blake2Hasher> {
+		let (mut t, mut c) = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let _ = generic_asset::GenesisConfig::<Test> {
+			staking_asset_id: 16_000,
+			spending_asset_id: 16_001,
+			assets: self.assets,
+			endowed_accounts: vec![1],
+			endowments: vec![],
+			genesis_locks: vec![],
+			create_asset_stake: 10,
+			initial_balance: 100,
+			next_asset_id: 17_000,
+		}
+		.assimilate_storage(&mut t, &mut c);
+		let _ = fees::GenesisConfig::<Test> {
+			_genesis_phantom_data: Default::default(),
+			fee_registry: vec![(MockFee::Base, self.base_fee), (MockFee::Bytes, 1)],
+		}
+		.assimilate_storage(&mut t, &mut c);
+		let _ = GenesisConfig::<Test> {
+			check_period: 10,
+			required_fees: vec![MockFee::Base, MockFee::Bytes],
+		}
+		.assimilate_storage(&mut t, &mut c);
+
+		t.into()
+	}
+}
@@ -0,0 +1,85 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{ExtBuilder, GenericAsset, Integrity, System, Test, TestEvent};
+use runtime_io::with_externalities;
+use runtime_primitives::traits::OnFinalize;
+use support::assert_ok;
+
+fn integrity_events() -> Vec<Event> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			TestEvent::integrity(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn healthy_runtime_passes_checks() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_ok!(Integrity::assert_runtime_integrity());
+		assert_eq!(integrity_events(), vec![Event::IntegrityChecked]);
+		assert_eq!(Integrity::last_next_asset_id(), 17_000);
+	});
+}
+
+#[test]
+fn missing_core_asset_is_a_violation() {
+	with_externalities(&mut ExtBuilder::default().assets(vec![16_000]).build(), || {
+		assert_eq!(Integrity::check_integrity(), 1);
+		assert_eq!(integrity_events(), vec![Event::IntegrityViolated(b"spending asset does not exist".to_vec())]);
+	});
+}
+
+#[test]
+fn zero_required_fee_is_a_violation() {
+	with_externalities(&mut ExtBuilder::default().base_fee(0).build(), || {
+		assert_eq!(Integrity::check_integrity(), 1);
+		assert_eq!(integrity_events(), vec![Event::IntegrityViolated(b"required fee is zero".to_vec())]);
+	});
+}
+
+#[test]
+fn decreasing_next_asset_id_is_a_violation() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_eq!(Integrity::check_integrity(), 0);
+		<generic_asset::NextAssetId<Test>>::put(16_999);
+		assert_eq!(Integrity::check_integrity(), 1);
+		assert_eq!(GenericAsset::next_asset_id(), 16_999);
+		assert_eq!(Integrity::last_next_asset_id(), 17_000);
+	});
+}
+
+#[test]
+fn checks_run_every_check_period() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		<Integrity as OnFinalize<u64>>::on_finalize(9);
+		assert!(integrity_events().is_empty());
+
+		<Integrity as OnFinalize<u64>>::on_finalize(10);
+		assert_eq!(integrity_events(), vec![Event::IntegrityChecked]);
+
+		assert_ok!(Integrity::set_check_period(0));
+		<Integrity as OnFinalize<u64>>::on_finalize(20);
+		assert_eq!(integrity_events(), vec![Event::IntegrityChecked]);
+	});
+}
@@ -0,0 +1,203 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Multisig module.
+//!
+//! Lets a set of signatories register an M-of-N multisig account. Any signatory may propose a call to be made
+//! from the multisig account, e.g. a generic asset transfer, which is dispatched once `threshold` signatories
+//! have approved it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_codec::{Decode, Encode};
+use primitives::crypto::{UncheckedFrom, UncheckedInto};
+use rstd::{marker::PhantomData, prelude::*};
+use runtime_primitives::traits::Hash;
+use support::{
+	decl_event, decl_module, decl_storage, dispatch::Result, ensure, Dispatchable, Parameter, StorageDoubleMap,
+	StorageMap,
+};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The max. number of signatories of a multisig account
+pub const MAX_SIGNATORIES: usize = 16;
+
+pub trait Trait: system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	/// A call which may be proposed and dispatched from a multisig account
+	type Proposal: Parameter + Dispatchable<Origin = Self::Origin>;
+	/// Generates the address of a multisig account
+	type MultisigAddressGenerator: MultisigAddressFor<Self::AccountId>;
+}
+
+/// A function that generates an `AccountId` for a multisig account from its signatories and threshold
+pub trait MultisigAddressFor<AccountId: Sized> {
+	fn multisig_address_for(signatories: &[AccountId], threshold: u32) -> AccountId;
+}
+
+/// A multisig address generator implementation
+pub struct MultisigAddressGenerator<T: Trait>(PhantomData<T>);
+
+impl<T: Trait> MultisigAddressFor<T::AccountId> for MultisigAddressGenerator<T>
+where
+	T::AccountId: UncheckedFrom<T::Hash>,
+{
+	/// Generates a multisig address for the given (sorted) signatories and threshold
+	fn multisig_address_for(signatories: &[T::AccountId], threshold: u32) -> T::AccountId {
+		let mut buf = Vec::new();
+		buf.extend_from_slice(b"multisig:");
+		buf.extend_from_slice(&signatories.encode());
+		buf.extend_from_slice(&threshold.encode());
+
+		T::Hashing::hash(&buf[..]).unchecked_into()
+	}
+}
+
+/// The signatories of a multisig account and the number of them required to approve a proposal
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MultisigConfig<AccountId> {
+	pub signatories: Vec<AccountId>,
+	pub threshold: u32,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Register a multisig account of `signatories` which requires `threshold` of them to approve a proposal.
+		/// The order of `signatories` does not matter, the same set and threshold always yields the same address.
+		pub fn register(origin, signatories: Vec<T::AccountId>, threshold: u32) -> Result {
+			let _ = ensure_signed(origin)?;
+			let mut signatories = signatories;
+			signatories.sort_by(|a, b| a.encode().cmp(&b.encode()));
+			signatories.dedup();
+			ensure!(signatories.len() >= 2, "multisig requires at least two distinct signatories");
+			ensure!(signatories.len() <= MAX_SIGNATORIES, "multisig has too many signatories");
+			ensure!(
+				threshold > 0 && threshold as usize <= signatories.len(),
+				"multisig threshold must be between one and the number of signatories"
+			);
+
+			let multisig = T::MultisigAddressGenerator::multisig_address_for(&signatories, threshold);
+			ensure!(!<Multisigs<T>>::exists(&multisig), "multisig is already registered");
+			<Multisigs<T>>::insert(&multisig, MultisigConfig { signatories: signatories.clone(), threshold });
+
+			Self::deposit_event(RawEvent::Registered(multisig, signatories, threshold));
+			Ok(())
+		}
+
+		/// Propose a call to be made from `multisig`, the proposer approves it implicitly.
+		pub fn propose(origin, multisig: T::AccountId, proposal: Box<T::Proposal>) -> Result {
+			let proposer = ensure_signed(origin)?;
+			let config = Self::ensure_signatory(&multisig, &proposer)?;
+			let proposal_hash = T::Hashing::hash_of(&proposal);
+			ensure!(!<Proposals<T>>::exists(&multisig, &proposal_hash), "proposal is already pending");
+
+			<Proposals<T>>::insert(&multisig, &proposal_hash, *proposal);
+			<Approvals<T>>::insert(&multisig, &proposal_hash, vec![proposer.clone()]);
+			Self::deposit_event(RawEvent::Proposed(multisig.clone(), proposer, proposal_hash));
+
+			if config.threshold <= 1 {
+				Self::execute(multisig, proposal_hash);
+			}
+			Ok(())
+		}
+
+		/// Approve a pending proposal of `multisig`, it is dispatched once the threshold is reached.
+		pub fn approve(origin, multisig: T::AccountId, proposal_hash: T::Hash) -> Result {
+			let who = ensure_signed(origin)?;
+			let config = Self::ensure_signatory(&multisig, &who)?;
+			ensure!(<Proposals<T>>::exists(&multisig, &proposal_hash), "proposal is not pending");
+			let mut approvals = Self::approvals(&multisig, &proposal_hash);
+			ensure!(!approvals.contains(&who), "proposal is already approved by the signatory");
+
+			approvals.push(who.clone());
+			let count = approvals.len() as u32;
+			<Approvals<T>>::insert(&multisig, &proposal_hash, approvals);
+			Self::deposit_event(RawEvent::Approved(multisig.clone(), who, proposal_hash, count));
+
+			if count >= config.threshold {
+				Self::execute(multisig, proposal_hash);
+			}
+			Ok(())
+		}
+
+		/// Cancel a pending proposal of `multisig`, only its proposer may cancel it.
+		pub fn cancel(origin, multisig: T::AccountId, proposal_hash: T::Hash) -> Result {
+			let who = ensure_signed(origin)?;
+			let approvals = Self::approvals(&multisig, &proposal_hash);
+			ensure!(!approvals.is_empty(), "proposal is not pending");
+			ensure!(approvals[0] == who, "only the proposer may cancel a proposal");
+
+			<Proposals<T>>::remove(&multisig, &proposal_hash);
+			<Approvals<T>>::remove(&multisig, &proposal_hash);
+			Self::deposit_event(RawEvent::Cancelled(multisig, proposal_hash));
+			Ok(())
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where <T as system::Trait>::AccountId, <T as system::Trait>::Hash {
+		/// A multisig account was registered: (multisig, signatories, threshold)
+		Registered(AccountId, Vec<AccountId>, u32),
+		/// A call was proposed: (multisig, proposer, proposal_hash)
+		Proposed(AccountId, AccountId, Hash),
+		/// A proposal was approved: (multisig, signatory, proposal_hash, approvals)
+		Approved(AccountId, AccountId, Hash, u32),
+		/// A proposal was cancelled by its proposer: (multisig, proposal_hash)
+		Cancelled(AccountId, Hash),
+		/// A proposal reached its threshold and was dispatched: (multisig, proposal_hash, succeeded)
+		Executed(AccountId, Hash, bool),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Multisig {
+		/// The config of each registered multisig account
+		pub Multisigs get(multisig): map T::AccountId => Option<MultisigConfig<T::AccountId>>;
+		/// Pending proposals of a multisig account, by proposal hash
+		pub Proposals get(proposal): double_map T::AccountId, twox_128(T::Hash) => Option<T::Proposal>;
+		/// The signatories which approved a pending proposal, the proposer first
+		pub Approvals get(approvals): double_map T::AccountId, twox_128(T::Hash) => Vec<T::AccountId>;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Return the config of `multisig` iff `who` is one of its signatories
+	fn ensure_signatory(
+		multisig: &T::AccountId,
+		who: &T::AccountId,
+	) -> rstd::result::Result<MultisigConfig<T::AccountId>, &'static str> {
+		let config = Self::multisig(multisig).ok_or_else(|| "multisig is not registered")?;
+		ensure!(config.signatories.contains(who), "sender is not a signatory of the multisig");
+		Ok(config)
+	}
+
+	/// Dispatch a pending proposal from `multisig` and clear it.
+	/// The outcome is reported in an event, a failing proposal does not fail the final approval.
+	fn execute(multisig: T::AccountId, proposal_hash: T::Hash) {
+		if let Some(proposal) = <Proposals<T>>::take(&multisig, &proposal_hash) {
+			<Approvals<T>>::remove(&multisig, &proposal_hash);
+			let ok = proposal.dispatch(system::RawOrigin::Signed(multisig.clone()).into()).is_ok();
+			Self::deposit_event(RawEvent::Executed(multisig, proposal_hash, ok));
+		}
+	}
+}
@@ -0,0 +1,132 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{Call, ExtBuilder, GenericAsset, Multisig, Origin, Test};
+use cennznet_primitives::AccountId;
+use primitives::H256;
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok};
+
+const ASSET_ID: u32 = 16000;
+
+fn account(id: u64) -> AccountId {
+	H256::from_low_u64_be(id).unchecked_into()
+}
+
+/// Register a 2-of-3 multisig of accounts 1, 2 and 3 holding 100 of `ASSET_ID`
+fn two_of_three() -> AccountId {
+	assert_ok!(Multisig::register(
+		Origin::signed(account(1)),
+		vec![account(1), account(2), account(3)],
+		2
+	));
+	let multisig = multisig_of(&[1, 2, 3], 2);
+	GenericAsset::force_set_balance(&ASSET_ID, &multisig, 100, 0);
+	multisig
+}
+
+fn multisig_of(ids: &[u64], threshold: u32) -> AccountId {
+	let mut signatories: Vec<AccountId> = ids.iter().map(|id| account(*id)).collect();
+	signatories.sort_by(|a, b| a.encode().cmp(&b.encode()));
+	<Test as Trait>::MultisigAddressGenerator::multisig_address_for(&signatories, threshold)
+}
+
+fn transfer(to: u64, amount: u128) -> Box<Call> {
+	Box::new(Call::GenericAsset(generic_asset::Call::transfer(ASSET_ID, account(to), amount)))
+}
+
+#[test]
+fn register_should_validate_signatories_and_threshold() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		assert_noop!(
+			Multisig::register(Origin::signed(account(1)), vec![account(1), account(1)], 1),
+			"multisig requires at least two distinct signatories"
+		);
+		assert_noop!(
+			Multisig::register(Origin::signed(account(1)), vec![account(1), account(2)], 3),
+			"multisig threshold must be between one and the number of signatories"
+		);
+		assert_noop!(
+			Multisig::register(Origin::signed(account(1)), vec![account(1), account(2)], 0),
+			"multisig threshold must be between one and the number of signatories"
+		);
+
+		assert_ok!(Multisig::register(Origin::signed(account(1)), vec![account(2), account(1)], 2));
+		assert_eq!(
+			Multisig::multisig(&multisig_of(&[1, 2], 2)).map(|config| config.threshold),
+			Some(2)
+		);
+		assert_noop!(
+			Multisig::register(Origin::signed(account(2)), vec![account(1), account(2)], 2),
+			"multisig is already registered"
+		);
+	});
+}
+
+#[test]
+fn proposal_should_be_dispatched_once_threshold_is_reached() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		let multisig = two_of_three();
+		let proposal = transfer(4, 40);
+		let proposal_hash = <Test as system::Trait>::Hashing::hash_of(&proposal);
+
+		assert_noop!(
+			Multisig::propose(Origin::signed(account(4)), multisig.clone(), proposal.clone()),
+			"sender is not a signatory of the multisig"
+		);
+		assert_ok!(Multisig::propose(Origin::signed(account(1)), multisig.clone(), proposal.clone()));
+		assert_noop!(
+			Multisig::propose(Origin::signed(account(2)), multisig.clone(), proposal),
+			"proposal is already pending"
+		);
+		assert_noop!(
+			Multisig::approve(Origin::signed(account(1)), multisig.clone(), proposal_hash),
+			"proposal is already approved by the signatory"
+		);
+		assert_eq!(GenericAsset::free_balance(&ASSET_ID, &account(4)), 0);
+
+		assert_ok!(Multisig::approve(Origin::signed(account(3)), multisig.clone(), proposal_hash));
+		assert_eq!(GenericAsset::free_balance(&ASSET_ID, &account(4)), 40);
+		assert_eq!(GenericAsset::free_balance(&ASSET_ID, &multisig), 60);
+		assert_eq!(Multisig::proposal(&multisig, &proposal_hash), None);
+		assert!(Multisig::approvals(&multisig, &proposal_hash).is_empty());
+	});
+}
+
+#[test]
+fn cancel_should_only_be_allowed_for_the_proposer() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		let multisig = two_of_three();
+		let proposal = transfer(4, 40);
+		let proposal_hash = <Test as system::Trait>::Hashing::hash_of(&proposal);
+		assert_ok!(Multisig::propose(Origin::signed(account(1)), multisig.clone(), proposal));
+
+		assert_noop!(
+			Multisig::cancel(Origin::signed(account(2)), multisig.clone(), proposal_hash),
+			"only the proposer may cancel a proposal"
+		);
+		assert_ok!(Multisig::cancel(Origin::signed(account(1)), multisig.clone(), proposal_hash));
+		assert_noop!(
+			Multisig::approve(Origin::signed(account(2)), multisig.clone(), proposal_hash),
+			"proposal is not pending"
+		);
+		assert_eq!(GenericAsset::free_balance(&ASSET_ID, &multisig), 100);
+	});
+}
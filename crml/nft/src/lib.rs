@@ -0,0 +1,208 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Non-fungible token module.
+//!
+//! Collections of unique tokens live alongside generic asset's fungible assets. A collection id is taken from the
+//! generic asset id space, so it never names a fungible asset. Each token of a collection has a single owner and an
+//! optional metadata URI. Like a fungible asset, a collection has permission owners:
+//! - the mint owner mints new tokens to any account
+//! - the burn owner burns any token of the collection
+//! - the update owner updates token metadata URIs and the collection's permissions
+//!
+//! A token's owner may transfer it to another account.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use generic_asset::{Owner, PermissionsV1};
+use rstd::prelude::*;
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The max. length of a token's metadata URI in bytes
+pub const MAX_TOKEN_URI_LENGTH: usize = 256;
+
+pub type TokenId = u32;
+
+pub trait Trait: generic_asset::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Create a collection with `permissions`, its id is the next generic asset id.
+		/// The generic asset creation stake is reserved from the sender as for a fungible asset.
+		pub fn create_collection(origin, permissions: PermissionsV1<T::AccountId>) -> Result {
+			let creator = ensure_signed(origin)?;
+			let collection_id = <generic_asset::Module<T>>::allocate_asset_id(&creator)?;
+			<Collections<T>>::insert(collection_id, &permissions);
+
+			Self::deposit_event(RawEvent::CollectionCreated(collection_id, creator, permissions));
+			Ok(())
+		}
+
+		/// Mint a new token of a collection to `owner`, optionally with a metadata `uri`.
+		/// The origin must have `mint` permission.
+		pub fn mint(origin, #[compact] collection_id: T::AssetId, owner: T::AccountId, uri: Option<Vec<u8>>) -> Result {
+			let origin = ensure_signed(origin)?;
+			let permissions = Self::collection(collection_id).ok_or_else(|| "Collection does not exist")?;
+			ensure!(is_owner(&permissions.mint, &origin), "Origin does not have permission to mint a token");
+			ensure!(
+				uri.as_ref().map_or(true, |uri| uri.len() <= MAX_TOKEN_URI_LENGTH),
+				"Token URI is too long"
+			);
+			let token_id = Self::next_token_id(collection_id);
+			let next_token_id = token_id.checked_add(1).ok_or_else(|| "No new token id is available")?;
+
+			<NextTokenId<T>>::insert(collection_id, next_token_id);
+			<TokenOwner<T>>::insert((collection_id, token_id), &owner);
+			<TokenCount<T>>::mutate((collection_id, owner.clone()), |count| *count += 1);
+			if let Some(uri) = uri {
+				<TokenUri<T>>::insert((collection_id, token_id), uri);
+			}
+
+			Self::deposit_event(RawEvent::Minted(collection_id, token_id, owner));
+			Ok(())
+		}
+
+		/// Transfer a token to `to`, the origin must be its owner.
+		pub fn transfer(
+			origin,
+			#[compact] collection_id: T::AssetId,
+			#[compact] token_id: TokenId,
+			to: T::AccountId
+		) -> Result {
+			let from = ensure_signed(origin)?;
+			let owner = Self::token_owner((collection_id, token_id)).ok_or_else(|| "Token does not exist")?;
+			ensure!(owner == from, "Only the token owner can transfer a token");
+
+			<TokenOwner<T>>::insert((collection_id, token_id), &to);
+			<TokenCount<T>>::mutate((collection_id, from.clone()), |count| *count -= 1);
+			<TokenCount<T>>::mutate((collection_id, to.clone()), |count| *count += 1);
+
+			Self::deposit_event(RawEvent::Transferred(collection_id, token_id, from, to));
+			Ok(())
+		}
+
+		/// Burn a token of a collection. The origin must have `burn` permission.
+		pub fn burn(origin, #[compact] collection_id: T::AssetId, #[compact] token_id: TokenId) -> Result {
+			let origin = ensure_signed(origin)?;
+			let permissions = Self::collection(collection_id).ok_or_else(|| "Collection does not exist")?;
+			ensure!(is_owner(&permissions.burn, &origin), "Origin does not have permission to burn a token");
+			let owner = Self::token_owner((collection_id, token_id)).ok_or_else(|| "Token does not exist")?;
+
+			<TokenOwner<T>>::remove((collection_id, token_id));
+			<TokenUri<T>>::remove((collection_id, token_id));
+			<TokenCount<T>>::mutate((collection_id, owner.clone()), |count| *count -= 1);
+
+			Self::deposit_event(RawEvent::Burned(collection_id, token_id, owner));
+			Ok(())
+		}
+
+		/// Set or remove the metadata URI of a token. The origin must have `update` permission.
+		pub fn set_token_uri(
+			origin,
+			#[compact] collection_id: T::AssetId,
+			#[compact] token_id: TokenId,
+			uri: Option<Vec<u8>>
+		) -> Result {
+			let origin = ensure_signed(origin)?;
+			let permissions = Self::collection(collection_id).ok_or_else(|| "Collection does not exist")?;
+			ensure!(is_owner(&permissions.update, &origin), "Origin does not have permission to update a token");
+			ensure!(<TokenOwner<T>>::exists((collection_id, token_id)), "Token does not exist");
+			ensure!(
+				uri.as_ref().map_or(true, |uri| uri.len() <= MAX_TOKEN_URI_LENGTH),
+				"Token URI is too long"
+			);
+
+			match uri {
+				Some(ref uri) => <TokenUri<T>>::insert((collection_id, token_id), uri),
+				None => <TokenUri<T>>::remove((collection_id, token_id)),
+			}
+
+			Self::deposit_event(RawEvent::TokenUriUpdated(collection_id, token_id, uri));
+			Ok(())
+		}
+
+		/// Update the permissions of a collection. The origin must have `update` permission.
+		pub fn update_permission(
+			origin,
+			#[compact] collection_id: T::AssetId,
+			permissions: PermissionsV1<T::AccountId>
+		) -> Result {
+			let origin = ensure_signed(origin)?;
+			let current = Self::collection(collection_id).ok_or_else(|| "Collection does not exist")?;
+			ensure!(
+				is_owner(&current.update, &origin),
+				"Origin does not have permission to update the collection permissions"
+			);
+
+			<Collections<T>>::insert(collection_id, &permissions);
+
+			Self::deposit_event(RawEvent::PermissionUpdated(collection_id, permissions));
+			Ok(())
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+		<T as generic_asset::Trait>::AssetId,
+		Permissions = PermissionsV1<<T as system::Trait>::AccountId>
+	{
+		/// A collection was created: (collection id, creator, permissions)
+		CollectionCreated(AssetId, AccountId, Permissions),
+		/// A token was minted: (collection id, token id, owner)
+		Minted(AssetId, TokenId, AccountId),
+		/// A token was transferred: (collection id, token id, from, to)
+		Transferred(AssetId, TokenId, AccountId, AccountId),
+		/// A token was burned: (collection id, token id, last owner)
+		Burned(AssetId, TokenId, AccountId),
+		/// A token's metadata URI was set, `None` if removed: (collection id, token id, uri)
+		TokenUriUpdated(AssetId, TokenId, Option<Vec<u8>>),
+		/// A collection's permissions were updated: (collection id, permissions)
+		PermissionUpdated(AssetId, Permissions),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Nft {
+		/// The permissions of each collection, a collection exists iff it has permissions
+		pub Collections get(collection): map T::AssetId => Option<PermissionsV1<T::AccountId>>;
+		/// The id of the next token to be minted in a collection
+		pub NextTokenId get(next_token_id): map T::AssetId => TokenId;
+		/// The owner of each token: (collection id, token id) => owner
+		pub TokenOwner get(token_owner): map (T::AssetId, TokenId) => Option<T::AccountId>;
+		/// The metadata URI of a token: (collection id, token id) => uri
+		pub TokenUri get(token_uri): map (T::AssetId, TokenId) => Option<Vec<u8>>;
+		/// The number of tokens of a collection an account owns: (collection id, account) => count
+		pub TokenCount get(token_count): map (T::AssetId, T::AccountId) => u32;
+	}
+}
+
+/// Whether `who` is the permission owner `owner`
+fn is_owner<AccountId: PartialEq>(owner: &Owner<AccountId>, who: &AccountId) -> bool {
+	match owner {
+		Owner::Address(account) => account == who,
+		Owner::None => false,
+	}
+}
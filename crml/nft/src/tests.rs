@@ -0,0 +1,145 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{ExtBuilder, GenericAsset, Nft, Origin, CREATOR, HOLDER};
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok};
+
+const COLLECTION_ID: u32 = 17_000;
+
+fn create_collection() {
+	assert_ok!(Nft::create_collection(
+		Origin::signed(CREATOR),
+		PermissionsV1 {
+			update: Owner::Address(CREATOR),
+			mint: Owner::Address(CREATOR),
+			burn: Owner::Address(CREATOR),
+		}
+	));
+}
+
+#[test]
+fn collection_takes_the_next_asset_id() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		create_collection();
+		assert!(Nft::collection(COLLECTION_ID).is_some());
+		assert_eq!(GenericAsset::next_asset_id(), COLLECTION_ID + 1);
+		assert_eq!(GenericAsset::total_issuance(COLLECTION_ID), 0);
+	});
+}
+
+#[test]
+fn create_collection_reserves_the_asset_creation_stake() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		create_collection();
+		assert_eq!(GenericAsset::reserved_balance(&16_000, &CREATOR), 10);
+		assert_eq!(GenericAsset::creator_stakes(&CREATOR), (10, vec![(COLLECTION_ID, 10)]));
+
+		assert_noop!(
+			Nft::create_collection(
+				Origin::signed(HOLDER),
+				PermissionsV1 {
+					update: Owner::Address(HOLDER),
+					mint: Owner::Address(HOLDER),
+					burn: Owner::Address(HOLDER),
+				}
+			),
+			"not enough free funds"
+		);
+		assert_eq!(GenericAsset::next_asset_id(), COLLECTION_ID + 1);
+	});
+}
+
+#[test]
+fn mint_transfer_and_burn_tokens() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		create_collection();
+		assert_noop!(
+			Nft::mint(Origin::signed(HOLDER), COLLECTION_ID, HOLDER, None),
+			"Origin does not have permission to mint a token"
+		);
+		assert_noop!(
+			Nft::mint(Origin::signed(CREATOR), COLLECTION_ID, HOLDER, Some(vec![0; MAX_TOKEN_URI_LENGTH + 1])),
+			"Token URI is too long"
+		);
+		assert_ok!(Nft::mint(Origin::signed(CREATOR), COLLECTION_ID, HOLDER, Some(b"ipfs://token0".to_vec())));
+		assert_ok!(Nft::mint(Origin::signed(CREATOR), COLLECTION_ID, HOLDER, None));
+		assert_eq!(Nft::token_owner((COLLECTION_ID, 0)), Some(HOLDER));
+		assert_eq!(Nft::token_uri((COLLECTION_ID, 0)), Some(b"ipfs://token0".to_vec()));
+		assert_eq!(Nft::token_count((COLLECTION_ID, HOLDER)), 2);
+
+		assert_noop!(
+			Nft::transfer(Origin::signed(CREATOR), COLLECTION_ID, 0, CREATOR),
+			"Only the token owner can transfer a token"
+		);
+		assert_ok!(Nft::transfer(Origin::signed(HOLDER), COLLECTION_ID, 0, CREATOR));
+		assert_eq!(Nft::token_owner((COLLECTION_ID, 0)), Some(CREATOR));
+		assert_eq!(Nft::token_count((COLLECTION_ID, HOLDER)), 1);
+		assert_eq!(Nft::token_count((COLLECTION_ID, CREATOR)), 1);
+
+		assert_noop!(
+			Nft::burn(Origin::signed(HOLDER), COLLECTION_ID, 1),
+			"Origin does not have permission to burn a token"
+		);
+		assert_ok!(Nft::burn(Origin::signed(CREATOR), COLLECTION_ID, 0));
+		assert_eq!(Nft::token_owner((COLLECTION_ID, 0)), None);
+		assert_eq!(Nft::token_uri((COLLECTION_ID, 0)), None);
+		assert_eq!(Nft::token_count((COLLECTION_ID, CREATOR)), 0);
+		assert_noop!(Nft::burn(Origin::signed(CREATOR), COLLECTION_ID, 0), "Token does not exist");
+	});
+}
+
+#[test]
+fn update_owner_sets_token_uris_and_permissions() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		create_collection();
+		assert_ok!(Nft::mint(Origin::signed(CREATOR), COLLECTION_ID, HOLDER, None));
+
+		assert_noop!(
+			Nft::set_token_uri(Origin::signed(HOLDER), COLLECTION_ID, 0, Some(b"uri".to_vec())),
+			"Origin does not have permission to update a token"
+		);
+		assert_noop!(
+			Nft::set_token_uri(Origin::signed(CREATOR), COLLECTION_ID, 1, Some(b"uri".to_vec())),
+			"Token does not exist"
+		);
+		assert_ok!(Nft::set_token_uri(Origin::signed(CREATOR), COLLECTION_ID, 0, Some(b"uri".to_vec())));
+		assert_eq!(Nft::token_uri((COLLECTION_ID, 0)), Some(b"uri".to_vec()));
+		assert_ok!(Nft::set_token_uri(Origin::signed(CREATOR), COLLECTION_ID, 0, None));
+		assert_eq!(Nft::token_uri((COLLECTION_ID, 0)), None);
+
+		let permissions = PermissionsV1 {
+			update: Owner::Address(CREATOR),
+			mint: Owner::Address(HOLDER),
+			burn: Owner::None,
+		};
+		assert_noop!(
+			Nft::update_permission(Origin::signed(HOLDER), COLLECTION_ID, permissions.clone()),
+			"Origin does not have permission to update the collection permissions"
+		);
+		assert_ok!(Nft::update_permission(Origin::signed(CREATOR), COLLECTION_ID, permissions));
+		assert_ok!(Nft::mint(Origin::signed(HOLDER), COLLECTION_ID, HOLDER, None));
+		assert_eq!(Nft::token_owner((COLLECTION_ID, 1)), Some(HOLDER));
+		assert_noop!(
+			Nft::burn(Origin::signed(CREATOR), COLLECTION_ID, 0),
+			"Origin does not have permission to burn a token"
+		);
+	});
+}
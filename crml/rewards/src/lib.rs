@@ -22,11 +22,9 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use cennznet_primitives::fixed_point::{mul_div, Rounding, SCALE};
 use fees::OnFeeCharged;
-use runtime_primitives::{
-	traits::{As, CheckedAdd, CheckedMul, One},
-	Permill,
-};
+use runtime_primitives::{traits::As, Permill};
 use session::OnSessionChange;
 use staking::CurrentEraReward;
 use support::{decl_module, decl_storage, dispatch::Result, traits::Currency, StorageValue};
@@ -36,7 +34,10 @@ mod tests;
 
 type AmountOf<T> = <<T as staking::Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
-pub trait Trait: staking::Trait {}
+pub trait Trait: staking::Trait {
+	/// Converts amounts to and from `u128` for the reward curve math
+	type AsBalance: From<AmountOf<Self>> + Into<AmountOf<Self>> + As<u128>;
+}
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
@@ -45,32 +46,32 @@ decl_module! {
 		/// `s` is storage / CPU ratio; k is empty_block / CCC ratio; `qmax` is target transaction count in a block;
 		/// `cost` is the estimated average spending tokens cost per transaction.
 		pub fn set_parameters(#[compact] s: AmountOf<T>, #[compact] k: AmountOf<T>, #[compact] qmax: AmountOf<T>, #[compact] cost: AmountOf<T>) -> Result {
-			let s_plus_one = s + One::one();
+			let to_u128 = |amount: AmountOf<T>| -> u128 { Into::<T::AsBalance>::into(amount).as_() };
+			let (s, k, qmax, cost) = (to_u128(s), to_u128(k), to_u128(qmax), to_u128(cost));
+			let s_plus_one = s.checked_add(1).ok_or_else(|| "block reward calculation overflow")?;
 
-			// block_reward = (s_plus_one + k) * qmax / (s_plus_one * qmax + k)
+			// block_reward = (s_plus_one + k) * qmax * cost / (s_plus_one * qmax + k)
 			let block_reward_divident = s_plus_one
-				.checked_add(&k)
-				.and_then(|x| x.checked_mul(&qmax))
+				.checked_add(k)
+				.and_then(|x| x.checked_mul(qmax))
 				.ok_or_else(|| "block reward calculation overflow")?;
 			// Given s/k/qmax are all integers, if (s_plus_one + k) * qmax doesn't overflow,
 			// (s_plus_one * qmax + k) cannot overflow, as the former one is always larger.
 			let reward_divisor = s_plus_one * qmax + k;
-			let block_reward = (block_reward_divident / reward_divisor)
-				.checked_mul(&cost)
+			let block_reward = mul_div(block_reward_divident, cost, reward_divisor, Rounding::Down)
 				.ok_or_else(|| "block reward calculation overflow")?;
 
 			// fee_reward_multiplier = s_plus_one * qmax * 1_000_000 / (s_plus_one * qmax + k)
-			let fee_reward_multiplier_divident = s_plus_one
-				.checked_mul(&qmax)
-				.and_then(|x| x.checked_mul(&<AmountOf<T>>::sa(1_000_000)))
+			let fee_reward_multiplier_mill = mul_div(s_plus_one * qmax, SCALE, reward_divisor, Rounding::Down)
 				.ok_or_else(|| "fee reward multiplier calculation overflow")?;
-			let fee_reward_multiplier_mill = fee_reward_multiplier_divident / reward_divisor;
+			let block_reward: T::AsBalance = As::sa(block_reward);
+			let block_reward: AmountOf<T> = block_reward.into();
 
 			<BlockReward<T>>::put(block_reward);
 			<FeeRewardMultiplier<T>>::put(
 				// `fee_reward_multiplier_bill` cannot overflow u32, since (s_plus_one * qmax)/(s_plus_one * qmax + k)
 				// always smaller than 1.
-				Permill::from_parts(fee_reward_multiplier_mill.as_() as u32),
+				Permill::from_parts(fee_reward_multiplier_mill as u32),
 			);
 
 			Ok(())
@@ -89,6 +89,7 @@ impl generic_asset::Trait for Test {
 	type Balance = u128;
 	type AssetId = u32;
 	type Event = ();
+	type OnTransferPayload = ();
 }
 impl consensus::Trait for Test {
 	type Log = DigestItem;
@@ -113,7 +114,9 @@ impl staking::Trait for Test {
 	type Reward = ();
 }
 
-impl Trait for Test {}
+impl Trait for Test {
+	type AsBalance = u128;
+}
 
 pub type Rewards = Module<Test>;
 pub type Staking = staking::Module<Test>;
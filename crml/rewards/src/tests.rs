@@ -40,7 +40,8 @@ fn set_reward_parameters_works() {
 			assert_ok!(Rewards::set_parameters(s, k, m, cost));
 
 			let s_plus_one = s + 1;
-			assert_eq!(Rewards::block_reward(), (s_plus_one + k) * m / (s_plus_one * m + k) * 7);
+			assert_eq!(Rewards::block_reward(), (s_plus_one + k) * m * 7 / (s_plus_one * m + k));
+			assert_eq!(Rewards::block_reward(), 186);
 			assert_eq!(
 				Rewards::fee_reward_multiplier(),
 				Permill::from_parts((s_plus_one * m * 1_000_000 / (s_plus_one * m + k)) as u32,)
@@ -72,11 +73,9 @@ fn set_reward_parameters_should_fail_if_overflow() {
 			block_reward_overflow
 		);
 
-		// (s_plus_one * qmax * 1_000_000) overflows
-		assert_noop!(
-			Rewards::set_parameters(2, 1, u128::max_value() / 10_000, 1),
-			"fee reward multiplier calculation overflow"
-		);
+		// (s_plus_one * qmax * 1_000_000) overflows, the multiplier is still calculated exactly
+		assert_ok!(Rewards::set_parameters(2, 1, u128::max_value() / 10_000, 1));
+		assert_eq!(Rewards::fee_reward_multiplier(), Permill::from_parts(999_999));
 	});
 }
 
@@ -0,0 +1,258 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Standing orders module.
+//!
+//! A payer sets up a standing order to transfer a fixed amount of any asset to a recipient every `interval`
+//! blocks until an end block. Due payments are made at the start of their block as ordinary transfers.
+//! A payment which can not be made, e.g. because the payer's balance is too low, is skipped and the order
+//! carries on, until `MAX_FAILED_PAYMENTS` payments in a row have failed and the order lapses.
+//! The payer may cancel a standing order at any time.
+//!
+//! Creating an order reserves `OrderDeposit` of the spending asset until the order ends. Orders pay at
+//! least `MinOrderInterval` blocks apart and at most `MAX_ORDERS_DUE_PER_BLOCK` payments are due at any
+//! block, a payment rescheduled into a full block is deferred to the next block with room.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_codec::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::traits::{One, Zero};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap, StorageValue};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The max. number of open standing orders of a payer
+pub const MAX_ORDERS_PER_PAYER: usize = 16;
+/// The max. number of standing order payments due at a block
+pub const MAX_ORDERS_DUE_PER_BLOCK: usize = 100;
+/// The max. number of blocks a payment is deferred by when its block is full, the order lapses after that
+pub const MAX_PAYMENT_DEFERRAL: u32 = 10;
+/// The number of payments in a row which may fail before a standing order lapses
+pub const MAX_FAILED_PAYMENTS: u32 = 3;
+/// Identifies the order deposit in the payer's reserved balance
+pub const STANDING_ORDER_RESERVE_ID: generic_asset::ReserveIdentifier = *b"standord";
+
+pub type OrderId = u32;
+
+pub trait Trait: generic_asset::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+/// A recurring transfer
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct StandingOrder<AccountId, AssetId, Balance, BlockNumber> {
+	/// The account paying
+	pub payer: AccountId,
+	/// The account paid
+	pub recipient: AccountId,
+	/// The asset paid
+	pub asset_id: AssetId,
+	/// The amount of each payment
+	pub amount: Balance,
+	/// The number of blocks between payments
+	pub interval: BlockNumber,
+	/// The block the next payment is due at
+	pub next_payment: BlockNumber,
+	/// No payments are made after this block
+	pub end: BlockNumber,
+	/// The amount of spending asset reserved from the payer until the order ends
+	pub deposit: Balance,
+	/// The number of payments in a row which have failed
+	pub failed_payments: u32,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Pay `amount` of `asset_id` to `recipient` every `interval` blocks, from `interval` blocks from now
+		/// until `end`.
+		pub fn create_order(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			recipient: T::AccountId,
+			#[compact] amount: T::Balance,
+			#[compact] interval: T::BlockNumber,
+			#[compact] end: T::BlockNumber
+		) -> Result {
+			let payer = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), "Standing order amount must be greater than zero");
+			ensure!(!interval.is_zero(), "Standing order interval must be greater than zero");
+			ensure!(interval >= Self::min_order_interval(), "Standing order interval is below the minimum");
+			let next_payment = <system::Module<T>>::block_number() + interval;
+			ensure!(next_payment <= end, "Standing order ends before its first payment");
+			let mut payer_orders = Self::payer_orders(&payer);
+			ensure!(payer_orders.len() < MAX_ORDERS_PER_PAYER, "Payer has too many standing orders");
+			ensure!(
+				Self::orders_due(next_payment).len() < MAX_ORDERS_DUE_PER_BLOCK,
+				"Too many standing orders are due at the block"
+			);
+
+			let order_id = Self::next_order_id();
+			let next_order_id = order_id.checked_add(1).ok_or_else(|| "No new standing order id is available")?;
+			let deposit = Self::order_deposit();
+			let spending_asset_id = <generic_asset::Module<T>>::spending_asset_id();
+			<generic_asset::Module<T>>::reserve_named(&STANDING_ORDER_RESERVE_ID, &spending_asset_id, &payer, deposit)?;
+
+			<NextOrderId<T>>::put(next_order_id);
+			payer_orders.push(order_id);
+			<PayerOrders<T>>::insert(&payer, payer_orders);
+			<OrdersDue<T>>::mutate(next_payment, |due| due.push(order_id));
+			<Orders<T>>::insert(
+				order_id,
+				StandingOrder {
+					payer: payer.clone(),
+					recipient: recipient.clone(),
+					asset_id,
+					amount,
+					interval,
+					next_payment,
+					end,
+					deposit,
+					failed_payments: 0,
+				},
+			);
+
+			Self::deposit_event(RawEvent::OrderCreated(order_id, payer, recipient, asset_id, amount));
+			Ok(())
+		}
+
+		/// Cancel a standing order, the sender must be its payer.
+		pub fn cancel_order(origin, #[compact] order_id: OrderId) -> Result {
+			let payer = ensure_signed(origin)?;
+			let order = Self::order(order_id).ok_or_else(|| "Standing order does not exist")?;
+			ensure!(order.payer == payer, "Only the payer can cancel a standing order");
+
+			// The order is left in its due queue and skipped when the queue is taken
+			Self::remove_order(order_id, &order);
+
+			Self::deposit_event(RawEvent::OrderCancelled(order_id));
+			Ok(())
+		}
+
+		fn on_initialize(n: T::BlockNumber) {
+			for order_id in <OrdersDue<T>>::take(n) {
+				if let Some(order) = Self::order(order_id) {
+					Self::pay(order_id, order, n);
+				}
+			}
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+		<T as generic_asset::Trait>::AssetId,
+		<T as generic_asset::Trait>::Balance
+	{
+		/// A standing order was created: (order id, payer, recipient, asset id, amount)
+		OrderCreated(OrderId, AccountId, AccountId, AssetId, Balance),
+		/// A standing order payment was made (order id)
+		PaymentMade(OrderId),
+		/// A standing order payment could not be made and was skipped (order id)
+		PaymentSkipped(OrderId),
+		/// The last payment of a standing order was due (order id)
+		OrderCompleted(OrderId),
+		/// A standing order was cancelled by its payer (order id)
+		OrderCancelled(OrderId),
+		/// A standing order ended early, because its payments kept failing or no block near its next payment
+		/// had room (order id)
+		OrderLapsed(OrderId),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as StandingOrders {
+		/// The id of the next standing order to be created
+		pub NextOrderId get(next_order_id): OrderId;
+		/// Open standing orders: order id => order
+		pub Orders get(order): map OrderId => Option<StandingOrder<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>>;
+		/// The open standing orders of a payer
+		pub PayerOrders get(payer_orders): map T::AccountId => Vec<OrderId>;
+		/// The standing orders with a payment due at the start of a block
+		OrdersDue get(orders_due): map T::BlockNumber => Vec<OrderId>;
+		/// The amount of spending asset reserved from the payer of each standing order
+		pub OrderDeposit get(order_deposit) config(): T::Balance;
+		/// The min. number of blocks between payments of a standing order
+		pub MinOrderInterval get(min_order_interval) config(): T::BlockNumber;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Make the payment of standing order `order_id` due at block `now`, then schedule its next payment
+	fn pay(
+		order_id: OrderId,
+		mut order: StandingOrder<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>,
+		now: T::BlockNumber,
+	) {
+		let paid = <generic_asset::Module<T>>::make_transfer_with_event(
+			&order.asset_id,
+			&order.payer,
+			&order.recipient,
+			order.amount,
+		);
+		if paid.is_ok() {
+			order.failed_payments = 0;
+			Self::deposit_event(RawEvent::PaymentMade(order_id));
+		} else {
+			order.failed_payments += 1;
+			Self::deposit_event(RawEvent::PaymentSkipped(order_id));
+			if order.failed_payments >= MAX_FAILED_PAYMENTS {
+				Self::remove_order(order_id, &order);
+				Self::deposit_event(RawEvent::OrderLapsed(order_id));
+				return;
+			}
+		}
+
+		let mut next_payment = now + order.interval;
+		if next_payment > order.end {
+			Self::remove_order(order_id, &order);
+			Self::deposit_event(RawEvent::OrderCompleted(order_id));
+			return;
+		}
+		let mut deferred = 0;
+		while Self::orders_due(next_payment).len() >= MAX_ORDERS_DUE_PER_BLOCK {
+			deferred += 1;
+			next_payment = next_payment + One::one();
+			if deferred > MAX_PAYMENT_DEFERRAL || next_payment > order.end {
+				Self::remove_order(order_id, &order);
+				Self::deposit_event(RawEvent::OrderLapsed(order_id));
+				return;
+			}
+		}
+		order.next_payment = next_payment;
+		<OrdersDue<T>>::mutate(next_payment, |due| due.push(order_id));
+		<Orders<T>>::insert(order_id, order);
+	}
+
+	/// Remove standing order `order_id` and return its deposit to the payer
+	fn remove_order(order_id: OrderId, order: &StandingOrder<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>) {
+		let spending_asset_id = <generic_asset::Module<T>>::spending_asset_id();
+		<generic_asset::Module<T>>::unreserve_named(
+			&STANDING_ORDER_RESERVE_ID,
+			&spending_asset_id,
+			&order.payer,
+			order.deposit,
+		);
+		<Orders<T>>::remove(order_id);
+		<PayerOrders<T>>::mutate(&order.payer, |orders| orders.retain(|id| *id != order_id));
+	}
+}
@@ -0,0 +1,251 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{ExtBuilder, GenericAsset, Origin, StandingOrders, System, PAYER, RECIPIENT, SPENDING_ASSET_ID};
+use runtime_io::with_externalities;
+use runtime_primitives::traits::OnInitialize;
+use support::{assert_noop, assert_ok};
+
+fn run_to_block(n: u64) {
+	for block in System::block_number() + 1..=n {
+		System::set_block_number(block);
+		StandingOrders::on_initialize(block);
+	}
+}
+
+fn balance_of(who: u64) -> u64 {
+	GenericAsset::free_balance(&SPENDING_ASSET_ID, &who)
+}
+
+fn deposit_of(who: u64) -> u64 {
+	GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &who)
+}
+
+#[test]
+fn create_order_is_validated() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_noop!(
+			StandingOrders::create_order(Origin::signed(PAYER), SPENDING_ASSET_ID, RECIPIENT, 0, 10, 100),
+			"Standing order amount must be greater than zero"
+		);
+		assert_noop!(
+			StandingOrders::create_order(Origin::signed(PAYER), SPENDING_ASSET_ID, RECIPIENT, 5, 0, 100),
+			"Standing order interval must be greater than zero"
+		);
+		assert_noop!(
+			StandingOrders::create_order(Origin::signed(PAYER), SPENDING_ASSET_ID, RECIPIENT, 5, 4, 100),
+			"Standing order interval is below the minimum"
+		);
+		assert_noop!(
+			StandingOrders::create_order(Origin::signed(PAYER), SPENDING_ASSET_ID, RECIPIENT, 5, 10, 10),
+			"Standing order ends before its first payment"
+		);
+		assert_ok!(GenericAsset::reward(&SPENDING_ASSET_ID, &PAYER, 100));
+		for _ in 0..MAX_ORDERS_PER_PAYER {
+			assert_ok!(StandingOrders::create_order(
+				Origin::signed(PAYER),
+				SPENDING_ASSET_ID,
+				RECIPIENT,
+				5,
+				10,
+				100
+			));
+		}
+		assert_noop!(
+			StandingOrders::create_order(Origin::signed(PAYER), SPENDING_ASSET_ID, RECIPIENT, 5, 10, 100),
+			"Payer has too many standing orders"
+		);
+	});
+}
+
+#[test]
+fn standing_order_pays_every_interval_until_its_end() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(StandingOrders::create_order(
+			Origin::signed(PAYER),
+			SPENDING_ASSET_ID,
+			RECIPIENT,
+			10,
+			5,
+			16
+		));
+		assert_eq!(StandingOrders::payer_orders(PAYER), vec![0]);
+		assert_eq!(StandingOrders::orders_due(6), vec![0]);
+		assert_eq!(balance_of(PAYER), 90);
+		assert_eq!(deposit_of(PAYER), 10);
+
+		run_to_block(5);
+		assert_eq!(balance_of(RECIPIENT), 0);
+		run_to_block(6);
+		assert_eq!(balance_of(RECIPIENT), 10);
+		assert_eq!(StandingOrders::order(0).map(|order| order.next_payment), Some(11));
+
+		run_to_block(16);
+		assert_eq!(balance_of(RECIPIENT), 30);
+		// The deposit is returned when the order completes
+		assert_eq!(balance_of(PAYER), 70);
+		assert_eq!(deposit_of(PAYER), 0);
+		assert_eq!(StandingOrders::order(0), None);
+		assert!(StandingOrders::payer_orders(PAYER).is_empty());
+
+		run_to_block(30);
+		assert_eq!(balance_of(RECIPIENT), 30);
+	});
+}
+
+#[test]
+fn standing_order_skips_payments_the_payer_can_not_afford() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(StandingOrders::create_order(
+			Origin::signed(PAYER),
+			SPENDING_ASSET_ID,
+			RECIPIENT,
+			60,
+			5,
+			100
+		));
+
+		run_to_block(6);
+		assert_eq!(balance_of(RECIPIENT), 60);
+		// The payer only has 30 left beside the deposit
+		run_to_block(11);
+		assert_eq!(balance_of(RECIPIENT), 60);
+		assert_eq!(StandingOrders::order(0).map(|order| order.next_payment), Some(16));
+		assert_eq!(StandingOrders::order(0).map(|order| order.failed_payments), Some(1));
+
+		assert_ok!(GenericAsset::transfer(Origin::signed(RECIPIENT), SPENDING_ASSET_ID, PAYER, 30));
+		run_to_block(16);
+		assert_eq!(balance_of(RECIPIENT), 90);
+		assert_eq!(balance_of(PAYER), 0);
+		assert_eq!(StandingOrders::order(0).map(|order| order.failed_payments), Some(0));
+	});
+}
+
+#[test]
+fn standing_order_lapses_after_too_many_failed_payments() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(StandingOrders::create_order(
+			Origin::signed(PAYER),
+			SPENDING_ASSET_ID,
+			RECIPIENT,
+			1_000,
+			5,
+			100
+		));
+
+		run_to_block(1 + 5 * (MAX_FAILED_PAYMENTS as u64 - 1));
+		assert!(StandingOrders::order(0).is_some());
+		run_to_block(1 + 5 * MAX_FAILED_PAYMENTS as u64);
+		assert_eq!(StandingOrders::order(0), None);
+		assert!(StandingOrders::payer_orders(PAYER).is_empty());
+		assert_eq!(balance_of(PAYER), 100);
+		assert_eq!(deposit_of(PAYER), 0);
+		assert!(StandingOrders::orders_due(6 + 5 * MAX_FAILED_PAYMENTS as u64).is_empty());
+	});
+}
+
+#[test]
+fn create_order_fails_without_the_deposit() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::transfer(Origin::signed(PAYER), SPENDING_ASSET_ID, RECIPIENT, 95));
+		assert_noop!(
+			StandingOrders::create_order(Origin::signed(PAYER), SPENDING_ASSET_ID, RECIPIENT, 1, 5, 100),
+			"not enough free funds"
+		);
+	});
+}
+
+#[test]
+fn orders_due_at_a_block_are_capped() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		// Fill block 11 with orders paying every 10 blocks
+		let payers = (MAX_ORDERS_DUE_PER_BLOCK / MAX_ORDERS_PER_PAYER + 1) as u64;
+		for payer in 10..10 + payers {
+			assert_ok!(GenericAsset::reward(&SPENDING_ASSET_ID, &payer, 1_000));
+		}
+		for i in 0..MAX_ORDERS_DUE_PER_BLOCK {
+			let payer = 10 + (i / MAX_ORDERS_PER_PAYER) as u64;
+			assert_ok!(StandingOrders::create_order(
+				Origin::signed(payer),
+				SPENDING_ASSET_ID,
+				RECIPIENT,
+				1,
+				10,
+				100
+			));
+		}
+		assert_noop!(
+			StandingOrders::create_order(Origin::signed(PAYER), SPENDING_ASSET_ID, RECIPIENT, 1, 10, 100),
+			"Too many standing orders are due at the block"
+		);
+
+		// A payment rescheduled into block 11 is deferred to block 12
+		let order_id = StandingOrders::next_order_id();
+		assert_ok!(StandingOrders::create_order(
+			Origin::signed(PAYER),
+			SPENDING_ASSET_ID,
+			RECIPIENT,
+			1,
+			5,
+			100
+		));
+		run_to_block(6);
+		assert_eq!(StandingOrders::order(order_id).map(|order| order.next_payment), Some(12));
+		assert_eq!(StandingOrders::orders_due(12), vec![order_id]);
+	});
+}
+
+#[test]
+fn cancel_order_stops_payments() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(StandingOrders::create_order(
+			Origin::signed(PAYER),
+			SPENDING_ASSET_ID,
+			RECIPIENT,
+			10,
+			5,
+			100
+		));
+
+		assert_noop!(
+			StandingOrders::cancel_order(Origin::signed(RECIPIENT), 0),
+			"Only the payer can cancel a standing order"
+		);
+		assert_ok!(StandingOrders::cancel_order(Origin::signed(PAYER), 0));
+		assert_eq!(balance_of(PAYER), 100);
+		assert_eq!(deposit_of(PAYER), 0);
+		assert_noop!(
+			StandingOrders::cancel_order(Origin::signed(PAYER), 0),
+			"Standing order does not exist"
+		);
+		assert!(StandingOrders::payer_orders(PAYER).is_empty());
+
+		run_to_block(6);
+		assert_eq!(balance_of(RECIPIENT), 0);
+		assert!(StandingOrders::orders_due(6).is_empty());
+	});
+}
@@ -0,0 +1,202 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Subscriptions module.
+//!
+//! A payer subscribes to a merchant, authorizing it to claim a fixed amount of any asset once every `period`
+//! blocks. The merchant claims each payment from the payer's free balance, so it can never take more than
+//! one payment per period, more than the subscription's total cap or anything after its expiry block.
+//! Payments the merchant has not claimed yet remain claimable until expiry. Either party may cancel.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_codec::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::traits::Zero;
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap, StorageValue};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The max. number of open subscriptions of a payer
+pub const MAX_SUBSCRIPTIONS_PER_PAYER: usize = 16;
+
+pub type SubscriptionId = u32;
+
+pub trait Trait: generic_asset::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+/// A merchant's authorization to claim recurring payments from a payer
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Subscription<AccountId, AssetId, Balance, BlockNumber> {
+	/// The account paying
+	pub payer: AccountId,
+	/// The account claiming payments
+	pub merchant: AccountId,
+	/// The asset paid
+	pub asset_id: AssetId,
+	/// The amount of each payment
+	pub amount: Balance,
+	/// The number of blocks between payments
+	pub period: BlockNumber,
+	/// The max. total amount which may be claimed
+	pub cap: Balance,
+	/// The total amount claimed so far
+	pub claimed: Balance,
+	/// The block the next payment may be claimed from
+	pub next_payment: BlockNumber,
+	/// No payments may be claimed from this block
+	pub expiry: BlockNumber,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Authorize `merchant` to claim `amount` of `asset_id` every `period` blocks, from now until `expiry`,
+		/// up to `cap` in total.
+		pub fn create_subscription(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			merchant: T::AccountId,
+			#[compact] amount: T::Balance,
+			#[compact] period: T::BlockNumber,
+			#[compact] cap: T::Balance,
+			#[compact] expiry: T::BlockNumber
+		) -> Result {
+			let payer = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), "Subscription amount must be greater than zero");
+			ensure!(!period.is_zero(), "Subscription period must be greater than zero");
+			ensure!(cap >= amount, "Subscription cap is less than one payment");
+			let now = <system::Module<T>>::block_number();
+			ensure!(expiry > now, "Subscription expires before its first payment");
+			let mut payer_subscriptions = Self::payer_subscriptions(&payer);
+			ensure!(
+				payer_subscriptions.len() < MAX_SUBSCRIPTIONS_PER_PAYER,
+				"Payer has too many subscriptions"
+			);
+
+			let subscription_id = Self::next_subscription_id();
+			let next_subscription_id = subscription_id
+				.checked_add(1)
+				.ok_or_else(|| "No new subscription id is available")?;
+			<NextSubscriptionId<T>>::put(next_subscription_id);
+			payer_subscriptions.push(subscription_id);
+			<PayerSubscriptions<T>>::insert(&payer, payer_subscriptions);
+			<Subscriptions<T>>::insert(
+				subscription_id,
+				Subscription {
+					payer: payer.clone(),
+					merchant: merchant.clone(),
+					asset_id,
+					amount,
+					period,
+					cap,
+					claimed: Zero::zero(),
+					next_payment: now,
+					expiry,
+				},
+			);
+
+			Self::deposit_event(RawEvent::SubscriptionCreated(subscription_id, payer, merchant, asset_id, amount));
+			Ok(())
+		}
+
+		/// Cancel a subscription, the sender must be its payer or merchant.
+		pub fn cancel(origin, #[compact] subscription_id: SubscriptionId) -> Result {
+			let sender = ensure_signed(origin)?;
+			let subscription = Self::subscription(subscription_id).ok_or_else(|| "Subscription does not exist")?;
+			ensure!(
+				subscription.payer == sender || subscription.merchant == sender,
+				"Only the payer or merchant can cancel a subscription"
+			);
+
+			Self::remove_subscription(subscription_id, &subscription.payer);
+
+			Self::deposit_event(RawEvent::SubscriptionCancelled(subscription_id));
+			Ok(())
+		}
+
+		/// Claim the next payment of a subscription, the sender must be its merchant.
+		/// Each call claims one payment, payments from past periods which were not claimed may be claimed
+		/// in turn.
+		pub fn claim_payment(origin, #[compact] subscription_id: SubscriptionId) -> Result {
+			let merchant = ensure_signed(origin)?;
+			let mut subscription = Self::subscription(subscription_id).ok_or_else(|| "Subscription does not exist")?;
+			ensure!(subscription.merchant == merchant, "Only the merchant can claim a subscription payment");
+			let now = <system::Module<T>>::block_number();
+			ensure!(now < subscription.expiry, "Subscription has expired");
+			ensure!(now >= subscription.next_payment, "Subscription payment is not due yet");
+
+			<generic_asset::Module<T>>::make_transfer_with_event(
+				&subscription.asset_id,
+				&subscription.payer,
+				&merchant,
+				subscription.amount,
+			)?;
+			subscription.claimed += subscription.amount;
+			subscription.next_payment += subscription.period;
+			Self::deposit_event(RawEvent::PaymentClaimed(subscription_id, subscription.amount));
+
+			let cap_reached = subscription.cap - subscription.claimed < subscription.amount;
+			if cap_reached || subscription.next_payment >= subscription.expiry {
+				Self::remove_subscription(subscription_id, &subscription.payer);
+				Self::deposit_event(RawEvent::SubscriptionCompleted(subscription_id));
+			} else {
+				<Subscriptions<T>>::insert(subscription_id, subscription);
+			}
+			Ok(())
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+		<T as generic_asset::Trait>::AssetId,
+		<T as generic_asset::Trait>::Balance
+	{
+		/// A subscription was created: (subscription id, payer, merchant, asset id, amount)
+		SubscriptionCreated(SubscriptionId, AccountId, AccountId, AssetId, Balance),
+		/// A subscription payment was claimed by the merchant: (subscription id, amount)
+		PaymentClaimed(SubscriptionId, Balance),
+		/// The last payment of a subscription was claimed (subscription id)
+		SubscriptionCompleted(SubscriptionId),
+		/// A subscription was cancelled by its payer or merchant (subscription id)
+		SubscriptionCancelled(SubscriptionId),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Subscriptions {
+		/// The id of the next subscription to be created
+		pub NextSubscriptionId get(next_subscription_id): SubscriptionId;
+		/// Open subscriptions: subscription id => subscription
+		pub Subscriptions get(subscription): map SubscriptionId => Option<Subscription<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>>;
+		/// The open subscriptions of a payer
+		pub PayerSubscriptions get(payer_subscriptions): map T::AccountId => Vec<SubscriptionId>;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	fn remove_subscription(subscription_id: SubscriptionId, payer: &T::AccountId) {
+		<Subscriptions<T>>::remove(subscription_id);
+		<PayerSubscriptions<T>>::mutate(payer, |subscriptions| subscriptions.retain(|id| *id != subscription_id));
+	}
+}
@@ -0,0 +1,180 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{ExtBuilder, GenericAsset, Origin, Subscriptions, System, MERCHANT, PAYER, SPENDING_ASSET_ID};
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok};
+
+fn balance_of(who: u64) -> u64 {
+	GenericAsset::free_balance(&SPENDING_ASSET_ID, &who)
+}
+
+#[test]
+fn create_subscription_is_validated() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_noop!(
+			Subscriptions::create_subscription(Origin::signed(PAYER), SPENDING_ASSET_ID, MERCHANT, 0, 10, 50, 100),
+			"Subscription amount must be greater than zero"
+		);
+		assert_noop!(
+			Subscriptions::create_subscription(Origin::signed(PAYER), SPENDING_ASSET_ID, MERCHANT, 5, 0, 50, 100),
+			"Subscription period must be greater than zero"
+		);
+		assert_noop!(
+			Subscriptions::create_subscription(Origin::signed(PAYER), SPENDING_ASSET_ID, MERCHANT, 5, 10, 4, 100),
+			"Subscription cap is less than one payment"
+		);
+		assert_noop!(
+			Subscriptions::create_subscription(Origin::signed(PAYER), SPENDING_ASSET_ID, MERCHANT, 5, 10, 50, 1),
+			"Subscription expires before its first payment"
+		);
+		for _ in 0..MAX_SUBSCRIPTIONS_PER_PAYER {
+			assert_ok!(Subscriptions::create_subscription(
+				Origin::signed(PAYER),
+				SPENDING_ASSET_ID,
+				MERCHANT,
+				5,
+				10,
+				50,
+				100
+			));
+		}
+		assert_noop!(
+			Subscriptions::create_subscription(Origin::signed(PAYER), SPENDING_ASSET_ID, MERCHANT, 5, 10, 50, 100),
+			"Payer has too many subscriptions"
+		);
+	});
+}
+
+#[test]
+fn merchant_claims_one_payment_per_period_up_to_the_cap() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Subscriptions::create_subscription(
+			Origin::signed(PAYER),
+			SPENDING_ASSET_ID,
+			MERCHANT,
+			10,
+			5,
+			25,
+			100
+		));
+		assert_eq!(Subscriptions::payer_subscriptions(PAYER), vec![0]);
+
+		assert_noop!(
+			Subscriptions::claim_payment(Origin::signed(PAYER), 0),
+			"Only the merchant can claim a subscription payment"
+		);
+		assert_ok!(Subscriptions::claim_payment(Origin::signed(MERCHANT), 0));
+		assert_eq!(balance_of(MERCHANT), 10);
+		assert_noop!(
+			Subscriptions::claim_payment(Origin::signed(MERCHANT), 0),
+			"Subscription payment is not due yet"
+		);
+
+		// An unclaimed past period may be caught up on, but no more than the cap
+		System::set_block_number(11);
+		assert_ok!(Subscriptions::claim_payment(Origin::signed(MERCHANT), 0));
+		assert_eq!(balance_of(MERCHANT), 20);
+		assert_eq!(Subscriptions::subscription(0), None);
+		assert!(Subscriptions::payer_subscriptions(PAYER).is_empty());
+		assert_noop!(
+			Subscriptions::claim_payment(Origin::signed(MERCHANT), 0),
+			"Subscription does not exist"
+		);
+		assert_eq!(balance_of(PAYER), 80);
+	});
+}
+
+#[test]
+fn no_payment_can_be_claimed_after_expiry() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Subscriptions::create_subscription(
+			Origin::signed(PAYER),
+			SPENDING_ASSET_ID,
+			MERCHANT,
+			10,
+			5,
+			100,
+			8
+		));
+
+		System::set_block_number(8);
+		assert_noop!(Subscriptions::claim_payment(Origin::signed(MERCHANT), 0), "Subscription has expired");
+		assert_eq!(balance_of(MERCHANT), 0);
+	});
+}
+
+#[test]
+fn failed_payment_can_be_claimed_again() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		assert_ok!(Subscriptions::create_subscription(
+			Origin::signed(PAYER),
+			SPENDING_ASSET_ID,
+			MERCHANT,
+			60,
+			5,
+			120,
+			100
+		));
+		assert_ok!(GenericAsset::transfer(Origin::signed(PAYER), SPENDING_ASSET_ID, 3, 50));
+
+		assert!(Subscriptions::claim_payment(Origin::signed(MERCHANT), 0).is_err());
+		assert_eq!(Subscriptions::subscription(0).map(|subscription| subscription.claimed), Some(0));
+
+		assert_ok!(GenericAsset::transfer(Origin::signed(3), SPENDING_ASSET_ID, PAYER, 50));
+		assert_ok!(Subscriptions::claim_payment(Origin::signed(MERCHANT), 0));
+		assert_eq!(balance_of(MERCHANT), 60);
+	});
+}
+
+#[test]
+fn payer_or_merchant_can_cancel() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		System::set_block_number(1);
+		for _ in 0..2 {
+			assert_ok!(Subscriptions::create_subscription(
+				Origin::signed(PAYER),
+				SPENDING_ASSET_ID,
+				MERCHANT,
+				10,
+				5,
+				100,
+				100
+			));
+		}
+
+		assert_noop!(
+			Subscriptions::cancel(Origin::signed(3), 0),
+			"Only the payer or merchant can cancel a subscription"
+		);
+		assert_ok!(Subscriptions::cancel(Origin::signed(PAYER), 0));
+		assert_ok!(Subscriptions::cancel(Origin::signed(MERCHANT), 1));
+		assert!(Subscriptions::payer_subscriptions(PAYER).is_empty());
+		assert_noop!(
+			Subscriptions::claim_payment(Origin::signed(MERCHANT), 0),
+			"Subscription does not exist"
+		);
+		assert_eq!(balance_of(PAYER), 100);
+	});
+}
@@ -111,6 +111,7 @@ pub(super) mod tests {
 		BuildStorage,
 	};
 
+	use generic_asset;
 	use vault;
 
 	impl_outer_origin! {
@@ -152,10 +153,22 @@ pub(super) mod tests {
 	impl device::Trait for Test {
 		type Event = ();
 	}
-	impl inbox::Trait for Test {}
-	impl response::Trait for Test {}
+	impl generic_asset::Trait for Test {
+		type Balance = u64;
+		type AssetId = u32;
+		type Event = ();
+		type OnTransferPayload = ();
+	}
+	impl inbox::Trait for Test {
+		type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	}
+	impl response::Trait for Test {
+		type Event = ();
+	}
 	impl groups::Trait for Test {}
-	impl vault::Trait for Test {}
+	impl vault::Trait for Test {
+		type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	}
 	type E2EE = Module<Test>;
 	type Device = device::Module<Test>;
 	type Response = response::Module<Test>;
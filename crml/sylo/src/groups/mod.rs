@@ -118,6 +118,9 @@ decl_module! {
 			ensure!(invites.len() < INVITES_MAX, "Can not invite more than maximum amount");
 			ensure!(<vault::Vault<T>>::get(&sender).len() < vault::KEYS_MAX, "Can not store more than maximum amount of keys for user's vault");
 
+			// Store group data in the user's vault first, this may fail on the vault's deposit
+			<vault::Module<T>>::upsert(sender.clone(), group_data.0, group_data.1)?;
+
 			let admin: Member<T::AccountId> = Member {
 				user_id: sender.clone(),
 				roles: vec![MemberRoles::Admin],
@@ -147,8 +150,6 @@ decl_module! {
 
 			<MemberDevices<T>>::insert(group_id.clone(), member_devices);
 
-			<vault::Module<T>>::upsert(sender.clone(), group_data.0, group_data.1);
-
 			// Create invites
 			for invite in invites {
 				let _ = Self::create_invite(&group_id, invite);
@@ -281,6 +282,9 @@ decl_module! {
 				"Failed to verify invite"
 			);
 
+			// Store group data in the user's vault first, this may fail on the vault's deposit
+			<vault::Module<T>>::upsert(sender.clone(), group_data.0, group_data.1)?;
+
 			let mut roles = vec![MemberRoles::Member];
 			roles.extend(invite.roles);
 
@@ -309,8 +313,6 @@ decl_module! {
 					.map(|device| (sender.clone(), device))
 					.collect();
 
-			<vault::Module<T>>::upsert(sender.clone(), group_data.0, group_data.1);
-
 			let mut all_devices = <MemberDevices<T>>::get(&group_id);
 			all_devices.extend(member_devices);
 
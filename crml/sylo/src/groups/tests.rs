@@ -29,6 +29,7 @@ mod tests {
 		BuildStorage,
 	};
 
+	use generic_asset;
 	use groups::{
 		device, inbox, system, vault, AcceptPayload, Encode, Group, Invite, Member, MemberRoles, Module, Trait,
 	};
@@ -74,8 +75,18 @@ mod tests {
 	impl device::Trait for Test {
 		type Event = ();
 	}
-	impl inbox::Trait for Test {}
-	impl vault::Trait for Test {}
+	impl generic_asset::Trait for Test {
+		type Balance = u64;
+		type AssetId = u32;
+		type Event = ();
+		type OnTransferPayload = ();
+	}
+	impl inbox::Trait for Test {
+		type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	}
+	impl vault::Trait for Test {
+		type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	}
 	type Groups = Module<Test>;
 	type Vault = vault::Module<Test>;
 
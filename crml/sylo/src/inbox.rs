@@ -13,7 +13,9 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
-use srml_support::{dispatch::Result, dispatch::Vec, StorageMap};
+use runtime_primitives::traits::Zero;
+use srml_support::traits::{Currency, ReservableCurrency};
+use srml_support::{dispatch::Result, dispatch::Vec, StorageMap, StorageValue};
 use system::ensure_signed;
 
 extern crate sr_io;
@@ -30,13 +32,29 @@ extern crate srml_system as system;
 // type String = Vec<u8>;
 
 pub trait Trait: system::Trait {
-	// add code here
+	/// The currency used to hold spam protection deposits for inbox values.
+	type Currency: ReservableCurrency<Self::AccountId>;
 }
 
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		/// Add a value to `peer_id`'s inbox.
+		/// The sender's deposit is held until the recipient deletes the value, or is forfeited
+		/// to the recipient if the value is deleted after the deposit period.
 		fn add_value(origin, peer_id: T::AccountId, value: Vec<u8>) -> Result {
-			ensure_signed(origin)?;
+			let sender = ensure_signed(origin)?;
+
+			let deposit = Self::deposit();
+			if !deposit.is_zero() {
+				T::Currency::reserve(&sender, deposit)
+					.map_err(|_| "Not enough funds to pay the inbox deposit")?;
+
+				let value_id = <NextIndexes<T>>::get(&peer_id);
+				let expiry = <system::Module<T>>::block_number() + Self::deposit_period();
+				<Deposits<T>>::insert((peer_id.clone(), value_id), (sender, deposit, expiry));
+			}
 
 			Self::add(peer_id, value)
 		}
@@ -46,6 +64,12 @@ decl_module! {
 
 			Self::delete(user_id, value_ids)
 		}
+
+		/// Set the inbox deposit and the number of blocks before an unacknowledged deposit is forfeited (root only)
+		fn set_deposit(deposit: BalanceOf<T>, deposit_period: T::BlockNumber) {
+			<Deposit<T>>::put(deposit);
+			<DepositPeriod<T>>::put(deposit_period);
+		}
 	}
 }
 
@@ -54,6 +78,13 @@ decl_storage! {
 		NextIndexes: map(T::AccountId) => u32;
 		AccountValues: map(T::AccountId) => Vec<(T::AccountId, u32)>;
 		Values get(values): map T::AccountId => Vec<(u32, Vec<u8>)>;
+
+		/// The amount a sender must reserve for each value added to an inbox
+		pub Deposit get(deposit) config(): BalanceOf<T>;
+		/// The number of blocks a recipient has to acknowledge a value before its deposit is forfeited to them
+		pub DepositPeriod get(deposit_period) config(): T::BlockNumber;
+		/// Deposits held for inbox values: (recipient, value_id) => (sender, amount, expiry block)
+		Deposits get(deposits): map (T::AccountId, u32) => Option<(T::AccountId, BalanceOf<T>, T::BlockNumber)>;
 	}
 }
 
@@ -95,12 +126,22 @@ impl<T: Trait> Module<T> {
 			.filter(|account_value| !value_ids.contains(&account_value.1))
 			.collect();
 
+		let now = <system::Module<T>>::block_number();
 		let mut values = <Values<T>>::get(&user_id);
 		for id in value_ids {
 			// Remove value from storage
 			if let Some(index) = values.iter().position(|(x, _)| *x == id) {
 				values.remove(index);
 			}
+
+			// Settle the sender's deposit: refund on time, otherwise forfeit to the recipient
+			if let Some((sender, amount, expiry)) = <Deposits<T>>::take((user_id.clone(), id)) {
+				if now < expiry {
+					T::Currency::unreserve(&sender, amount);
+				} else {
+					let _ = T::Currency::repatriate_reserved(&sender, &user_id, amount);
+				}
+			}
 		}
 		<Values<T>>::insert(user_id.clone(), values);
 
@@ -129,6 +170,8 @@ mod tests {
 		BuildStorage,
 	};
 
+	use generic_asset;
+
 	impl_outer_origin! {
 		pub enum Origin for Test {}
 	}
@@ -162,8 +205,20 @@ mod tests {
 		type Log = DigestItem;
 		type Signature = Signature;
 	}
-	impl Trait for Test {}
+	impl generic_asset::Trait for Test {
+		type Balance = u64;
+		type AssetId = u32;
+		type Event = ();
+		type OnTransferPayload = ();
+	}
+	impl Trait for Test {
+		type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	}
 	type Inbox = Module<Test>;
+	type GenericAsset = generic_asset::Module<Test>;
+	type System = system::Module<Test>;
+
+	const SPENDING_ASSET_ID: u32 = 16001;
 
 	// This function basically just builds a genesis storage key/value store according to
 	// our desired mockup.
@@ -175,6 +230,38 @@ mod tests {
 			.into()
 	}
 
+	// Builds a genesis where account 1 holds 100 spending asset and each inbox value requires
+	// a deposit of 10 for 5 blocks.
+	fn new_test_ext_with_deposit() -> sr_io::TestExternalities<Blake2Hasher> {
+		let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap().0;
+		t.extend(
+			generic_asset::GenesisConfig::<Test> {
+				assets: vec![SPENDING_ASSET_ID],
+				initial_balance: 100,
+				endowed_accounts: vec![H256::from_low_u64_be(1)],
+				endowments: vec![],
+				genesis_locks: vec![],
+				next_asset_id: 17000,
+				create_asset_stake: 0,
+				staking_asset_id: 16000,
+				spending_asset_id: SPENDING_ASSET_ID,
+			}
+			.build_storage()
+			.unwrap()
+			.0,
+		);
+		t.extend(
+			GenesisConfig::<Test> {
+				deposit: 10,
+				deposit_period: 5,
+			}
+			.build_storage()
+			.unwrap()
+			.0,
+		);
+		t.into()
+	}
+
 	#[test]
 	fn it_works_adding_values_to_an_inbox() {
 		with_externalities(&mut new_test_ext(), || {
@@ -246,4 +333,69 @@ mod tests {
 			assert_ok!(Inbox::delete_values(Origin::signed(H256::from_low_u64_be(2)), vec![0]));
 		});
 	}
+
+	#[test]
+	fn it_reserves_a_deposit_when_adding_values() {
+		with_externalities(&mut new_test_ext_with_deposit(), || {
+			let sender = H256::from_low_u64_be(1);
+			let recipient = H256::from_low_u64_be(2);
+
+			assert_ok!(Inbox::add_value(Origin::signed(sender), recipient, b"hello, world".to_vec()));
+
+			assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &sender), 90);
+			assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &sender), 10);
+			assert_eq!(Inbox::deposits((recipient, 0)), Some((sender, 10, 5)));
+		});
+	}
+
+	#[test]
+	fn it_fails_adding_values_without_funds_for_the_deposit() {
+		with_externalities(&mut new_test_ext_with_deposit(), || {
+			assert_noop!(
+				Inbox::add_value(
+					Origin::signed(H256::from_low_u64_be(3)),
+					H256::from_low_u64_be(2),
+					b"spam".to_vec()
+				),
+				"Not enough funds to pay the inbox deposit"
+			);
+			assert_eq!(Inbox::inbox(H256::from_low_u64_be(2)), Vec::<Vec<u8>>::new());
+		});
+	}
+
+	#[test]
+	fn it_refunds_the_deposit_when_acknowledged_in_time() {
+		with_externalities(&mut new_test_ext_with_deposit(), || {
+			let sender = H256::from_low_u64_be(1);
+			let recipient = H256::from_low_u64_be(2);
+
+			assert_ok!(Inbox::add_value(Origin::signed(sender), recipient, b"hello, world".to_vec()));
+
+			System::set_block_number(4);
+			assert_ok!(Inbox::delete_values(Origin::signed(recipient), vec![0]));
+
+			assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &sender), 100);
+			assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &sender), 0);
+			assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &recipient), 0);
+			assert_eq!(Inbox::deposits((recipient, 0)), None);
+		});
+	}
+
+	#[test]
+	fn it_forfeits_the_deposit_to_the_recipient_after_expiry() {
+		with_externalities(&mut new_test_ext_with_deposit(), || {
+			let sender = H256::from_low_u64_be(1);
+			let recipient = H256::from_low_u64_be(2);
+
+			assert_ok!(Inbox::add_value(Origin::signed(sender), recipient, b"hello, world".to_vec()));
+
+			System::set_block_number(5);
+			assert_ok!(Inbox::delete_values(Origin::signed(recipient), vec![0]));
+
+			assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &sender), 90);
+			assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &sender), 0);
+			assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &recipient), 10);
+			assert_eq!(Inbox::deposits((recipient, 0)), None);
+		});
+	}
 }
@@ -27,6 +27,8 @@ extern crate srml_support;
 extern crate runtime_primitives;
 extern crate srml_balances as balances;
 extern crate srml_system as system;
+#[cfg(test)]
+extern crate generic_asset;
 
 pub mod device;
 pub mod e2ee;
@@ -16,7 +16,8 @@
 extern crate parity_codec;
 
 use self::parity_codec::{Decode, Encode};
-use srml_support::{dispatch::Result, dispatch::Vec, StorageMap};
+use runtime_primitives::traits::Zero;
+use srml_support::{dispatch::Result, dispatch::Vec, StorageMap, StorageValue};
 use system::ensure_signed;
 extern crate srml_system as system;
 
@@ -24,7 +25,13 @@ extern crate sr_io;
 extern crate runtime_primitives;
 extern crate primitives;
 
-pub trait Trait: system::Trait {}
+/// The max. number of requests which may expire at a block
+pub const MAX_REQUESTS_EXPIRING_PER_BLOCK: usize = 100;
+
+pub trait Trait: system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -42,25 +49,98 @@ impl<T: Encode + Decode> Default for Response<T> {
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
 		fn remove_response(origin, request_id: T::Hash) -> Result {
 			let sender = ensure_signed(origin)?;
 			<Responses<T>>::remove((sender, request_id));
 			Ok(())
 		}
+
+		/// Make a request which may only be answered by `responder`.
+		/// Unanswered requests are removed once the request timeout has passed.
+		fn make_request(origin, request_id: T::Hash, responder: T::AccountId) -> Result {
+			let sender = ensure_signed(origin)?;
+			let key = (sender.clone(), request_id);
+
+			ensure!(!<Requests<T>>::exists(&key), "Request already exists");
+			ensure!(!<Responses<T>>::exists(&key), "Request has already been responded to");
+
+			let timeout = Self::request_timeout();
+			let expiry = <system::Module<T>>::block_number() + timeout;
+			if !timeout.is_zero() {
+				let mut expiring = Self::requests_expiring(expiry);
+				ensure!(expiring.len() < MAX_REQUESTS_EXPIRING_PER_BLOCK, "Too many requests expire at the block");
+				expiring.push(key.clone());
+				<RequestsExpiring<T>>::insert(expiry, expiring);
+			}
+			<Requests<T>>::insert(&key, (responder.clone(), expiry));
+
+			Self::deposit_event(RawEvent::RequestMade(sender, request_id, responder));
+			Ok(())
+		}
+
+		/// Respond to a pending request made by `requester`. Only the requested responder may respond.
+		fn respond(origin, requester: T::AccountId, request_id: T::Hash, response: Response<T::AccountId>) -> Result {
+			let sender = ensure_signed(origin)?;
+			let key = (requester.clone(), request_id);
+
+			let (responder, _) = Self::request(&key).ok_or("Request does not exist")?;
+			ensure!(sender == responder, "Only the requested responder can respond");
+			ensure!(response != Response::None, "Response can not be empty");
+
+			<Requests<T>>::remove(&key);
+			Self::set_response(requester, request_id, response);
+			Ok(())
+		}
+
+		/// Set the number of blocks a request may remain unanswered, zero disables expiry (root only)
+		fn set_request_timeout(timeout: T::BlockNumber) {
+			<RequestTimeout<T>>::put(timeout);
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			for (requester, request_id) in <RequestsExpiring<T>>::take(n) {
+				// The request may have been answered before expiring
+				if <Requests<T>>::exists((requester.clone(), request_id)) {
+					<Requests<T>>::remove((requester.clone(), request_id));
+					Self::deposit_event(RawEvent::RequestExpired(requester, request_id));
+				}
+			}
+		}
 	}
 }
 
+decl_event!(
+	pub enum Event<T> where <T as system::Trait>::Hash, <T as system::Trait>::AccountId {
+		/// A request was made: (requester, request_id, responder)
+		RequestMade(AccountId, Hash, AccountId),
+		/// A response is available: (requester, request_id)
+		ResponseSet(AccountId, Hash),
+		/// A request expired without a response: (requester, request_id)
+		RequestExpired(AccountId, Hash),
+	}
+);
+
 // The data that is stored
 decl_storage! {
 	trait Store for Module<T: Trait> as SyloResponse {
 		Responses get(response): map (T::AccountId, T::Hash /* request_id */) => Response<T::AccountId>;
+
+		/// Pending requests: (requester, request_id) => (responder, expiry block)
+		Requests get(request): map (T::AccountId, T::Hash /* request_id */) => Option<(T::AccountId, T::BlockNumber)>;
+		/// Pending requests which expire at the end of a block
+		RequestsExpiring get(requests_expiring): map T::BlockNumber => Vec<(T::AccountId, T::Hash)>;
+		/// The number of blocks a request may remain unanswered
+		pub RequestTimeout get(request_timeout) config(): T::BlockNumber;
 	}
 }
 
 impl<T: Trait> Module<T> {
 	pub(super) fn set_response(sender: T::AccountId, request_id: T::Hash, response: Response<T::AccountId>) {
 		if response != Response::None {
-			<Responses<T>>::insert((sender, request_id), response);
+			<Responses<T>>::insert((sender.clone(), request_id), response);
+			Self::deposit_event(RawEvent::ResponseSet(sender, request_id));
 		}
 	}
 }
@@ -79,7 +159,7 @@ pub(super) mod tests {
 	// or public keys. `u64` is used as the `AccountId` and no `Signature`s are requried.
 	use self::runtime_primitives::{
 		testing::{Digest, DigestItem, Header},
-		traits::{BlakeTwo256, IdentityLookup},
+		traits::{BlakeTwo256, IdentityLookup, OnFinalize},
 		BuildStorage,
 	};
 
@@ -116,8 +196,11 @@ pub(super) mod tests {
 		type Log = DigestItem;
 		type Signature = Signature;
 	}
-	impl Trait for Test {}
+	impl Trait for Test {
+		type Event = ();
+	}
 	type Responses = Module<Test>;
+	type System = system::Module<Test>;
 
 	// This function basically just builds a genesis storage key/value store according to
 	// our desired mockup.
@@ -161,4 +244,104 @@ pub(super) mod tests {
 			);
 		});
 	}
+
+	#[test]
+	fn should_only_accept_responses_from_the_responder() {
+		with_externalities(&mut new_test_ext(), || {
+			let requester = H256::from_low_u64_be(1);
+			let responder = H256::from_low_u64_be(2);
+			let request_id = H256::from([1; 32]);
+
+			assert_ok!(Responses::make_request(Origin::signed(requester), request_id, responder));
+			assert_noop!(
+				Responses::make_request(Origin::signed(requester), request_id, responder),
+				"Request already exists"
+			);
+			assert_noop!(
+				Responses::respond(
+					Origin::signed(H256::from_low_u64_be(3)),
+					requester,
+					request_id,
+					Response::DeviceId(1)
+				),
+				"Only the requested responder can respond"
+			);
+			assert_noop!(
+				Responses::respond(Origin::signed(responder), requester, request_id, Response::None),
+				"Response can not be empty"
+			);
+
+			assert_ok!(Responses::respond(
+				Origin::signed(responder),
+				requester,
+				request_id,
+				Response::DeviceId(1)
+			));
+			assert_eq!(Responses::request((requester, request_id)), None);
+			assert_eq!(Responses::response((requester, request_id)), Response::DeviceId(1));
+
+			assert_noop!(
+				Responses::respond(Origin::signed(responder), requester, request_id, Response::DeviceId(2)),
+				"Request does not exist"
+			);
+			assert_noop!(
+				Responses::make_request(Origin::signed(requester), request_id, responder),
+				"Request has already been responded to"
+			);
+		});
+	}
+
+	#[test]
+	fn should_remove_unanswered_requests_after_timeout() {
+		with_externalities(&mut new_test_ext(), || {
+			let requester = H256::from_low_u64_be(1);
+			let responder = H256::from_low_u64_be(2);
+			let answered_id = H256::from([1; 32]);
+			let unanswered_id = H256::from([2; 32]);
+
+			assert_ok!(Responses::set_request_timeout(5));
+			System::set_block_number(1);
+			assert_ok!(Responses::make_request(Origin::signed(requester), answered_id, responder));
+			assert_ok!(Responses::make_request(Origin::signed(requester), unanswered_id, responder));
+			assert_eq!(Responses::request((requester, unanswered_id)), Some((responder, 6)));
+
+			assert_ok!(Responses::respond(
+				Origin::signed(responder),
+				requester,
+				answered_id,
+				Response::DeviceId(1)
+			));
+
+			<Responses as OnFinalize<u64>>::on_finalize(5);
+			assert!(Responses::request((requester, unanswered_id)).is_some());
+
+			<Responses as OnFinalize<u64>>::on_finalize(6);
+			assert_eq!(Responses::request((requester, unanswered_id)), None);
+			assert!(Responses::requests_expiring(6).is_empty());
+			assert_eq!(Responses::response((requester, answered_id)), Response::DeviceId(1));
+		});
+	}
+
+	#[test]
+	fn should_cap_requests_expiring_at_a_block() {
+		with_externalities(&mut new_test_ext(), || {
+			let requester = H256::from_low_u64_be(1);
+			let responder = H256::from_low_u64_be(2);
+
+			assert_ok!(Responses::set_request_timeout(5));
+			System::set_block_number(1);
+			for i in 0..MAX_REQUESTS_EXPIRING_PER_BLOCK {
+				let request_id = H256::from_low_u64_be(i as u64);
+				assert_ok!(Responses::make_request(Origin::signed(requester), request_id, responder));
+			}
+			let request_id = H256::from_low_u64_be(MAX_REQUESTS_EXPIRING_PER_BLOCK as u64);
+			assert_noop!(
+				Responses::make_request(Origin::signed(requester), request_id, responder),
+				"Too many requests expire at the block"
+			);
+
+			System::set_block_number(2);
+			assert_ok!(Responses::make_request(Origin::signed(requester), request_id, responder));
+		});
+	}
 }
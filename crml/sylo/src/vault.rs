@@ -13,7 +13,10 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
-use srml_support::{dispatch::Vec, StorageMap};
+use runtime_primitives::traits::Zero;
+use generic_asset::{NamedReservableCurrency, ReserveIdentifier};
+use srml_support::traits::Currency;
+use srml_support::{dispatch::Result, dispatch::Vec, StorageMap, StorageValue};
 use {system, system::ensure_signed};
 
 extern crate sr_io;
@@ -21,20 +24,27 @@ extern crate runtime_primitives;
 extern crate primitives;
 
 pub const KEYS_MAX: usize = 100;
+pub const KEY_SIZE_MAX: usize = 256;
+pub const VALUE_SIZE_MAX: usize = 2048;
+/// The reservation id of vault deposits
+pub const VAULT_RESERVE_ID: ReserveIdentifier = *b"vault   ";
+
+pub trait Trait: system::Trait {
+	/// The currency used to hold deposits for stored values.
+	type Currency: NamedReservableCurrency<Self::AccountId>;
+}
 
-pub trait Trait: system::Trait {}
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
 pub type VaultKey = Vec<u8>;
 pub type VaultValue = Vec<u8>;
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-		fn upsert_value(origin, key: VaultKey, value: VaultValue) {
+		fn upsert_value(origin, key: VaultKey, value: VaultValue) -> Result {
 			let user_id = ensure_signed(origin)?;
 
-			ensure!(<Vault<T>>::get(&user_id).len() < KEYS_MAX, "Can not store more than maximum amount of keys");
-
-			Self::upsert(user_id, key, value);
+			Self::upsert(user_id, key, value)
 		}
 
 		fn delete_values(origin, keys: Vec<VaultKey>) {
@@ -42,25 +52,51 @@ decl_module! {
 
 			Self::delete(user_id, keys);
 		}
+
+		/// Set the deposit reserved for each key stored in a vault (root only)
+		fn set_deposit(deposit: BalanceOf<T>) {
+			<Deposit<T>>::put(deposit);
+		}
 	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as SyloVault {
 		pub Vault get(values): map T::AccountId => Vec<(VaultKey, VaultValue)>;
+
+		/// The amount reserved from a user for each key stored in their vault
+		pub Deposit get(deposit) config(): BalanceOf<T>;
+		/// Deposits held for stored keys: (user_id, key) => amount
+		Deposits get(deposits): map (T::AccountId, VaultKey) => BalanceOf<T>;
 	}
 }
 
 impl<T: Trait> Module<T> {
-	pub fn upsert(user_id: T::AccountId, key: VaultKey, value: VaultValue) {
+	pub fn upsert(user_id: T::AccountId, key: VaultKey, value: VaultValue) -> Result {
+		ensure!(key.len() <= KEY_SIZE_MAX, "Vault key is too large");
+		ensure!(value.len() <= VALUE_SIZE_MAX, "Vault value is too large");
+
 		let mut values = <Vault<T>>::get(&user_id);
 
 		match values.iter().enumerate().find(|(_, item)| item.0 == key) {
-			None => values.push((key, value)),
+			None => {
+				ensure!(values.len() < KEYS_MAX, "Can not store more than maximum amount of keys");
+
+				let deposit = Self::deposit();
+				if !deposit.is_zero() {
+					T::Currency::reserve_named(&VAULT_RESERVE_ID, &user_id, deposit)
+						.map_err(|_| "Not enough funds to pay the vault deposit")?;
+					<Deposits<T>>::insert((user_id.clone(), key.clone()), deposit);
+				}
+
+				values.push((key, value))
+			}
 			Some((i, _)) => values[i] = (key, value),
 		}
 
-		<Vault<T>>::insert(user_id, values)
+		<Vault<T>>::insert(user_id, values);
+
+		Ok(())
 	}
 
 	pub fn delete(user_id: T::AccountId, keys: Vec<VaultKey>) {
@@ -69,6 +105,13 @@ impl<T: Trait> Module<T> {
 			.filter(|item| keys.iter().find(|key_to_remove| &&item.0 == key_to_remove).is_none())
 			.collect();
 
+		for key in keys {
+			let deposit = <Deposits<T>>::take((user_id.clone(), key));
+			if !deposit.is_zero() {
+				T::Currency::unreserve_named(&VAULT_RESERVE_ID, &user_id, deposit);
+			}
+		}
+
 		<Vault<T>>::insert(user_id, remaining_values)
 	}
 }
@@ -91,6 +134,8 @@ mod tests {
 		BuildStorage,
 	};
 
+	use generic_asset;
+
 	impl_outer_origin! {
 		pub enum Origin for Test {}
 	}
@@ -124,8 +169,19 @@ mod tests {
 		type Log = DigestItem;
 		type Signature = Signature;
 	}
-	impl Trait for Test {}
+	impl generic_asset::Trait for Test {
+		type Balance = u64;
+		type AssetId = u32;
+		type Event = ();
+		type OnTransferPayload = ();
+	}
+	impl Trait for Test {
+		type Currency = generic_asset::SpendingAssetCurrency<Test>;
+	}
 	type Vault = Module<Test>;
+	type GenericAsset = generic_asset::Module<Test>;
+
+	const SPENDING_ASSET_ID: u32 = 16001;
 
 	// This function basically just builds a genesis storage key/value store according to
 	// our desired mockup.
@@ -137,6 +193,29 @@ mod tests {
 			.into()
 	}
 
+	// Builds a genesis where account 1 holds 100 spending asset and each key requires a deposit of 10.
+	fn new_test_ext_with_deposit() -> sr_io::TestExternalities<Blake2Hasher> {
+		let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap().0;
+		t.extend(
+			generic_asset::GenesisConfig::<Test> {
+				assets: vec![SPENDING_ASSET_ID],
+				initial_balance: 100,
+				endowed_accounts: vec![H256::from_low_u64_be(1)],
+				endowments: vec![],
+				genesis_locks: vec![],
+				next_asset_id: 17000,
+				create_asset_stake: 0,
+				staking_asset_id: 16000,
+				spending_asset_id: SPENDING_ASSET_ID,
+			}
+			.build_storage()
+			.unwrap()
+			.0,
+		);
+		t.extend(GenesisConfig::<Test> { deposit: 10 }.build_storage().unwrap().0);
+		t.into()
+	}
+
 	#[test]
 	fn should_upsert_values() {
 		with_externalities(&mut new_test_ext(), || {
@@ -227,4 +306,86 @@ mod tests {
 			assert_eq!(Vault::values(H256::from_low_u64_be(1)), vec![]);
 		});
 	}
+
+	#[test]
+	fn should_not_store_oversized_keys_or_values() {
+		with_externalities(&mut new_test_ext(), || {
+			assert_noop!(
+				Vault::upsert_value(
+					Origin::signed(H256::from_low_u64_be(1)),
+					vec![0; KEY_SIZE_MAX + 1],
+					b"1".to_vec()
+				),
+				"Vault key is too large"
+			);
+			assert_noop!(
+				Vault::upsert_value(
+					Origin::signed(H256::from_low_u64_be(1)),
+					b"0".to_vec(),
+					vec![0; VALUE_SIZE_MAX + 1]
+				),
+				"Vault value is too large"
+			);
+		});
+	}
+
+	#[test]
+	fn should_replace_existing_keys_when_vault_is_full() {
+		with_externalities(&mut new_test_ext(), || {
+			for i in 0..KEYS_MAX {
+				assert_ok!(Vault::upsert_value(
+					Origin::signed(H256::from_low_u64_be(1)),
+					(i as u32).encode(),
+					b"1".to_vec()
+				));
+			}
+
+			assert_noop!(
+				Vault::upsert_value(
+					Origin::signed(H256::from_low_u64_be(1)),
+					(KEYS_MAX as u32).encode(),
+					b"1".to_vec()
+				),
+				"Can not store more than maximum amount of keys"
+			);
+
+			assert_ok!(Vault::upsert_value(
+				Origin::signed(H256::from_low_u64_be(1)),
+				0u32.encode(),
+				b"2".to_vec()
+			));
+			assert_eq!(Vault::values(H256::from_low_u64_be(1))[0], (0u32.encode(), b"2".to_vec()));
+		});
+	}
+
+	#[test]
+	fn should_reserve_and_refund_deposits() {
+		with_externalities(&mut new_test_ext_with_deposit(), || {
+			let user = H256::from_low_u64_be(1);
+			let key_0 = b"0".to_vec();
+
+			assert_ok!(Vault::upsert_value(Origin::signed(user), key_0.clone(), b"1".to_vec()));
+			assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &user), 10);
+			assert_eq!(GenericAsset::reserves(&SPENDING_ASSET_ID, &user), vec![(b"vault".to_vec(), 10)]);
+
+			// Replacing a value does not take another deposit
+			assert_ok!(Vault::upsert_value(Origin::signed(user), key_0.clone(), b"2".to_vec()));
+			assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &user), 10);
+
+			assert_ok!(Vault::delete_values(Origin::signed(user), vec![key_0]));
+			assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &user), 0);
+			assert_eq!(GenericAsset::reserves(&SPENDING_ASSET_ID, &user), vec![]);
+			assert_eq!(GenericAsset::free_balance(&SPENDING_ASSET_ID, &user), 100);
+		});
+	}
+
+	#[test]
+	fn should_not_store_values_without_funds_for_the_deposit() {
+		with_externalities(&mut new_test_ext_with_deposit(), || {
+			assert_noop!(
+				Vault::upsert_value(Origin::signed(H256::from_low_u64_be(2)), b"0".to_vec(), b"1".to_vec()),
+				"Not enough funds to pay the vault deposit"
+			);
+		});
+	}
 }
@@ -0,0 +1,229 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Treasury module.
+//!
+//! The treasury holds assets in a keyless account for the network's own spending.
+//! It is funded by:
+//! - a share of the extrinsic fees charged in each block, which would otherwise be burned, in spending asset
+//! - a share of each asset creation stake, which would otherwise stay reserved on the asset's creator, in staking
+//!   asset
+//! Anyone may propose a spend of any asset to a beneficiary by reserving a bond of spending asset. Root approves
+//! a spend, paying it from the treasury and returning the bond, or rejects it, paying the bond into the treasury.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fees::OnFeeCharged;
+use generic_asset::{ModuleIdentifier, ReserveIdentifier};
+use parity_codec::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::{traits::Zero, Permill};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap, StorageValue};
+use system::ensure_signed;
+
+mod mock;
+mod tests;
+
+/// The module identifier of payments out of the treasury
+pub const TREASURY_MODULE_ID: ModuleIdentifier = *b"treasury";
+/// The reservation spend proposal bonds are held under on their proposer
+pub const PROPOSAL_BOND_RESERVE_ID: ReserveIdentifier = *b"treasury";
+/// The seed the treasury account is derived from
+pub const TREASURY_ACCOUNT_SEED: &[u8] = b"cennznet:treasury";
+
+pub type ProposalIndex = u32;
+
+pub trait Trait: generic_asset::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+/// A proposal to pay an amount out of the treasury
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SpendProposal<AccountId, AssetId, Balance> {
+	/// The account which proposed the spend and reserved the bond
+	pub proposer: AccountId,
+	/// The asset to pay
+	pub asset_id: AssetId,
+	/// The amount of the asset to pay
+	pub amount: Balance,
+	/// The account the amount is paid to
+	pub beneficiary: AccountId,
+	/// The bond reserved on the proposer
+	pub bond: Balance,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// Propose paying `amount` of `asset_id` out of the treasury to `beneficiary`.
+		/// The proposal bond is reserved on the sender until the spend is approved or rejected.
+		pub fn propose_spend(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			#[compact] amount: T::Balance,
+			beneficiary: T::AccountId
+		) -> Result {
+			let proposer = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), "Spend amount must be greater than zero");
+			let proposal_id = Self::next_proposal_id();
+			let next_proposal_id = proposal_id.checked_add(1).ok_or_else(|| "No new proposal id is available")?;
+			let bond = Self::proposal_bond();
+
+			<generic_asset::Module<T>>::reserve_named(
+				&PROPOSAL_BOND_RESERVE_ID,
+				&<generic_asset::Module<T>>::spending_asset_id(),
+				&proposer,
+				bond,
+			)?;
+			<NextProposalId<T>>::put(next_proposal_id);
+			<Proposals<T>>::insert(
+				proposal_id,
+				SpendProposal {
+					proposer: proposer.clone(),
+					asset_id,
+					amount,
+					beneficiary: beneficiary.clone(),
+					bond,
+				},
+			);
+
+			Self::deposit_event(RawEvent::Proposed(proposal_id, proposer, asset_id, amount, beneficiary));
+			Ok(())
+		}
+
+		/// Pay a proposed spend out of the treasury and return its bond (root only).
+		pub fn approve_spend(#[compact] proposal_id: ProposalIndex) -> Result {
+			let proposal = Self::proposal(proposal_id).ok_or_else(|| "Spend proposal does not exist")?;
+			let spending_asset_id = <generic_asset::Module<T>>::spending_asset_id();
+
+			<generic_asset::Module<T>>::make_module_transfer(
+				&TREASURY_MODULE_ID,
+				&proposal.asset_id,
+				&Self::account_id(),
+				&proposal.beneficiary,
+				proposal.amount,
+			)?;
+			<generic_asset::Module<T>>::unreserve_named(
+				&PROPOSAL_BOND_RESERVE_ID,
+				&spending_asset_id,
+				&proposal.proposer,
+				proposal.bond,
+			);
+			<Proposals<T>>::remove(proposal_id);
+
+			Self::deposit_event(RawEvent::Awarded(
+				proposal_id,
+				proposal.asset_id,
+				proposal.amount,
+				proposal.beneficiary,
+			));
+			Ok(())
+		}
+
+		/// Reject a proposed spend, paying its bond into the treasury (root only).
+		pub fn reject_spend(#[compact] proposal_id: ProposalIndex) -> Result {
+			let proposal = Self::proposal(proposal_id).ok_or_else(|| "Spend proposal does not exist")?;
+
+			<generic_asset::Module<T>>::repatriate_reserved_named(
+				&PROPOSAL_BOND_RESERVE_ID,
+				&<generic_asset::Module<T>>::spending_asset_id(),
+				&proposal.proposer,
+				&Self::account_id(),
+				proposal.bond,
+			)?;
+			<Proposals<T>>::remove(proposal_id);
+
+			Self::deposit_event(RawEvent::Rejected(proposal_id, proposal.bond));
+			Ok(())
+		}
+
+		/// Set the bond reserved on the proposer of a spend (root only).
+		pub fn set_proposal_bond(#[compact] bond: T::Balance) -> Result {
+			<ProposalBond<T>>::put(bond);
+			Ok(())
+		}
+
+		/// Set the share of the extrinsic fees charged in each block paid into the treasury (root only).
+		pub fn set_fee_share(share: Permill) -> Result {
+			<FeeShare<T>>::put(share);
+			Ok(())
+		}
+
+		/// Set the share of each asset creation stake paid into the treasury (root only).
+		pub fn set_creation_stake_share(share: Permill) -> Result {
+			if share == Permill::zero() {
+				<generic_asset::Module<T>>::set_creation_stake_treasury(None);
+			} else {
+				<generic_asset::Module<T>>::set_creation_stake_treasury(Some((Self::account_id(), share)));
+			}
+			Ok(())
+		}
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+		<T as generic_asset::Trait>::AssetId,
+		<T as generic_asset::Trait>::Balance
+	{
+		/// The treasury's share of a block's fees was paid into it (amount)
+		Deposit(Balance),
+		/// A spend was proposed: (proposal id, proposer, asset id, amount, beneficiary)
+		Proposed(ProposalIndex, AccountId, AssetId, Balance, AccountId),
+		/// A spend was approved and paid: (proposal id, asset id, amount, beneficiary)
+		Awarded(ProposalIndex, AssetId, Balance, AccountId),
+		/// A spend was rejected and its bond paid into the treasury: (proposal id, bond)
+		Rejected(ProposalIndex, Balance),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Treasury {
+		/// The share of the extrinsic fees charged in each block paid into the treasury
+		pub FeeShare get(fee_share): Permill;
+		/// The bond reserved on the proposer of a spend
+		pub ProposalBond get(proposal_bond): T::Balance;
+		/// The id of the next spend proposal
+		pub NextProposalId get(next_proposal_id): ProposalIndex;
+		/// Open spend proposals: proposal id => proposal
+		pub Proposals get(proposal): map ProposalIndex => Option<SpendProposal<T::AccountId, T::AssetId, T::Balance>>;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The treasury account. It is derived from `TREASURY_ACCOUNT_SEED`, so no one holds its key.
+	pub fn account_id() -> T::AccountId {
+		T::AccountId::decode(&mut &runtime_io::blake2_256(TREASURY_ACCOUNT_SEED)[..]).unwrap_or_default()
+	}
+}
+
+impl<T: Trait> OnFeeCharged<T::Balance> for Module<T> {
+	/// Pay the treasury's share of the fees charged in the block into the treasury.
+	/// Charged fees are burned, so the share is issued to the treasury.
+	fn on_fee_charged(fee: &T::Balance) {
+		let share = Self::fee_share() * *fee;
+		if share.is_zero() {
+			return;
+		}
+		let spending_asset_id = <generic_asset::Module<T>>::spending_asset_id();
+		if <generic_asset::Module<T>>::reward(&spending_asset_id, &Self::account_id(), share).is_ok() {
+			Self::deposit_event(RawEvent::Deposit(share));
+		}
+	}
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{ExtBuilder, GenericAsset, Origin, Treasury, BENEFICIARY, PROPOSER, SPENDING_ASSET_ID};
+use generic_asset::{AssetOptions, Owner, PermissionLatest};
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok};
+
+fn free_balance_of(who: u64) -> u64 {
+	GenericAsset::free_balance(&SPENDING_ASSET_ID, &who)
+}
+
+#[test]
+fn fee_share_is_paid_into_the_treasury() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		Treasury::on_fee_charged(&100);
+		assert_eq!(free_balance_of(Treasury::account_id()), 0);
+
+		assert_ok!(Treasury::set_fee_share(Permill::from_percent(20)));
+		Treasury::on_fee_charged(&100);
+		assert_eq!(free_balance_of(Treasury::account_id()), 20);
+		assert_eq!(GenericAsset::total_issuance(&SPENDING_ASSET_ID), 120);
+	});
+}
+
+#[test]
+fn creation_stake_share_is_paid_into_the_treasury() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		assert_ok!(Treasury::set_creation_stake_share(Permill::from_percent(50)));
+		assert_ok!(GenericAsset::create(
+			Origin::signed(PROPOSER),
+			AssetOptions {
+				initial_issuance: 100,
+				permissions: PermissionLatest {
+					update: Owner::Address(PROPOSER),
+					mint: Owner::Address(PROPOSER),
+					burn: Owner::Address(PROPOSER),
+					freeze: Owner::None,
+				},
+			}
+		));
+
+		assert_eq!(GenericAsset::free_balance(&16_000, &Treasury::account_id()), 5);
+		assert_eq!(GenericAsset::reserved_balance(&16_000, &PROPOSER), 5);
+
+		// The staking asset share is spent in the staking asset
+		assert_ok!(Treasury::propose_spend(Origin::signed(PROPOSER), 16_000, 5, BENEFICIARY));
+		assert_ok!(Treasury::approve_spend(0));
+		assert_eq!(GenericAsset::free_balance(&16_000, &BENEFICIARY), 5);
+		assert_eq!(GenericAsset::free_balance(&16_000, &Treasury::account_id()), 0);
+
+		assert_ok!(Treasury::set_creation_stake_share(Permill::zero()));
+		assert_eq!(GenericAsset::creation_stake_treasury(), None);
+	});
+}
+
+#[test]
+fn approved_spend_is_paid_from_the_treasury() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		assert_ok!(Treasury::set_proposal_bond(10));
+		assert_ok!(GenericAsset::transfer(
+			Origin::signed(PROPOSER),
+			SPENDING_ASSET_ID,
+			Treasury::account_id(),
+			50
+		));
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(PROPOSER), SPENDING_ASSET_ID, 0, BENEFICIARY),
+			"Spend amount must be greater than zero"
+		);
+		assert_ok!(Treasury::propose_spend(Origin::signed(PROPOSER), SPENDING_ASSET_ID, 30, BENEFICIARY));
+		assert_ok!(Treasury::propose_spend(Origin::signed(PROPOSER), SPENDING_ASSET_ID, 60, BENEFICIARY));
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &PROPOSER), 20);
+
+		assert_ok!(Treasury::approve_spend(0));
+		assert_eq!(free_balance_of(BENEFICIARY), 30);
+		assert_eq!(free_balance_of(Treasury::account_id()), 20);
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &PROPOSER), 10);
+		assert_eq!(Treasury::proposal(0), None);
+
+		assert_noop!(Treasury::approve_spend(1), "balance too low to send amount");
+		assert_noop!(Treasury::approve_spend(0), "Spend proposal does not exist");
+	});
+}
+
+#[test]
+fn rejected_spend_pays_its_bond_into_the_treasury() {
+	with_externalities(&mut ExtBuilder::build(), || {
+		assert_ok!(Treasury::set_proposal_bond(10));
+		assert_ok!(Treasury::propose_spend(Origin::signed(PROPOSER), SPENDING_ASSET_ID, 30, BENEFICIARY));
+
+		assert_ok!(Treasury::reject_spend(0));
+		assert_eq!(free_balance_of(PROPOSER), 90);
+		assert_eq!(GenericAsset::reserved_balance(&SPENDING_ASSET_ID, &PROPOSER), 0);
+		assert_eq!(free_balance_of(Treasury::account_id()), 10);
+		assert_eq!(Treasury::proposal(0), None);
+	});
+}
@@ -20,7 +20,7 @@ use std::fmt;
 
 use rstd::prelude::*;
 use runtime_io::blake2_256;
-use runtime_primitives::codec::{Compact, Decode, Encode, HasCompact, Input};
+use runtime_primitives::codec::{Compact, Decode, Encode, EncodeAsRef, HasCompact, Input, Output};
 use runtime_primitives::generic::Era;
 use runtime_primitives::traits::{
 	self, BlockNumberToHash, Checkable, CurrentHeight, Doughnuted, Extrinsic, Lookup, MaybeDisplay, Member,
@@ -28,10 +28,40 @@ use runtime_primitives::traits::{
 };
 
 const TRANSACTION_VERSION: u8 = 0b0000_00001;
-const MASK_VERSION: u8 = 0b0000_1111;
+const MASK_VERSION: u8 = 0b0000_0111;
 const BIT_SIGNED: u8 = 0b1000_0000;
 const BIT_DOUGHNUT: u8 = 0b0100_0000;
 const BIT_CENNZ_X: u8 = 0b0010_0000;
+const BIT_FEE_EXCHANGE_FALLBACKS: u8 = 0b0001_0000;
+// Taken from the version number, versions up to 7 remain
+const BIT_TIP: u8 = 0b0000_1000;
+/// The version number mask of `ExtrinsicFormat::V1`, before bits were taken from it
+const MASK_VERSION_V1: u8 = 0b0000_1111;
+
+/// The wire formats `CennznetExtrinsic` has been encoded in, each a superset of the one before.
+/// Blocks must be decoded in the format of the runtime they were authored with.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ExtrinsicFormat {
+	/// Runtime spec versions before 25: signed, doughnut and fee exchange flags and a 4 bit version number
+	V1,
+	/// Runtime spec versions from 25: adds fee exchange fallback and tip flags, leaving a 3 bit version number
+	V2,
+}
+
+impl ExtrinsicFormat {
+	/// The format extrinsics are encoded in
+	pub const LATEST: ExtrinsicFormat = ExtrinsicFormat::V2;
+
+	/// The format of extrinsics in blocks authored with runtime `spec_version`
+	pub fn for_spec_version(spec_version: u32) -> Self {
+		if spec_version < 25 {
+			ExtrinsicFormat::V1
+		} else {
+			ExtrinsicFormat::V2
+		}
+	}
+}
 
 fn encode_with_vec_prefix<T: Encode, F: Fn(&mut Vec<u8>)>(encoder: F) -> Vec<u8> {
 	let size = ::rstd::mem::size_of::<T>();
@@ -55,6 +85,57 @@ fn encode_with_vec_prefix<T: Encode, F: Fn(&mut Vec<u8>)>(encoder: F) -> Vec<u8>
 	v
 }
 
+/// The payload signed by the signer of an extrinsic.
+///
+/// It is the encoded index, call, era and era birth block hash, followed by the encoding of each optional
+/// extension which is present, in the order they are added. Extensions which are absent add nothing, so
+/// adding a new extension does not change the payload of extrinsics which do not use it.
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SigningPayload(Vec<u8>);
+
+impl SigningPayload {
+	/// A payload of the fields every signed extrinsic signs
+	pub fn new<Index, Call: Encode, Hash: Encode>(
+		index: &Compact<Index>,
+		function: &Call,
+		era: Era,
+		block_hash: &Hash,
+	) -> Self
+	where
+		Compact<Index>: Encode,
+	{
+		let mut payload = Vec::new();
+		index.encode_to(&mut payload);
+		function.encode_to(&mut payload);
+		era.encode_to(&mut payload);
+		block_hash.encode_to(&mut payload);
+		SigningPayload(payload)
+	}
+
+	/// Add an optional extension to the payload
+	pub fn with<E: Encode>(mut self, extension: Option<&E>) -> Self {
+		if let Some(extension) = extension {
+			extension.encode_to(&mut self.0);
+		}
+		self
+	}
+
+	/// Call `f` with the bytes to sign: the payload, or its blake2-256 hash if it is longer than 256 bytes
+	pub fn using_signable<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		if self.0.len() > 256 {
+			f(&blake2_256(&self.0)[..])
+		} else {
+			f(&self.0)
+		}
+	}
+
+	/// The bytes to sign
+	pub fn signable(&self) -> Vec<u8> {
+		self.using_signable(|bytes| bytes.to_vec())
+	}
+}
+
 /// A extrinsic right from the external world. This is unchecked and so
 /// can contain a signature.
 #[derive(PartialEq, Eq, Clone)]
@@ -69,6 +150,8 @@ pub struct CennznetExtrinsic<AccountId, Address, Index, Call, Signature, Balance
 	pub doughnut: Option<Doughnut<AccountId, Signature>>,
 	/// Signals fee payment should use the CENNZX-Spot exchange
 	pub fee_exchange: Option<FeeExchange<Balance>>,
+	/// An amount of the spending asset paid to the block author on top of the fee, for priority
+	pub tip: Option<Compact<Balance>>,
 }
 
 /// Definition of something that the external world might want to say; its
@@ -84,6 +167,12 @@ pub struct CheckedCennznetExtrinsic<AccountId, Index, Call, Balance: HasCompact>
 	pub function: Call,
 	/// Signals fee payment should use the CENNZX-Spot exchange
 	pub fee_exchange: Option<FeeExchange<Balance>>,
+	/// The delegate (doughnut holder) who signed on behalf of `signed`, if any
+	pub delegate: Option<AccountId>,
+	/// The certificate of the doughnut the delegate acted under, if any
+	pub certificate: Option<Certificate<AccountId>>,
+	/// An amount of the spending asset paid to the block author on top of the fee, for priority
+	pub tip: Option<Balance>,
 }
 
 impl<AccountId, Index, Call, Balance> traits::Applyable for CheckedCennznetExtrinsic<AccountId, Index, Call, Balance>
@@ -131,6 +220,7 @@ impl<AccountId, Address, Index, Call, Signature, Balance: HasCompact>
 			function,
 			doughnut,
 			fee_exchange: None,
+			tip: None,
 		}
 	}
 
@@ -141,10 +231,30 @@ impl<AccountId, Address, Index, Call, Signature, Balance: HasCompact>
 			function,
 			doughnut: None,
 			fee_exchange: None,
+			tip: None,
 		}
 	}
 }
 
+impl<AccountId: Encode, Address, Index, Call: Encode, Signature: Encode, Balance: HasCompact>
+	CennznetExtrinsic<AccountId, Address, Index, Call, Signature, Balance>
+where
+	Compact<Balance>: Encode,
+{
+	/// The payload the signer of this extrinsic signs, with the signer's `index` and the extrinsic's `era`.
+	/// `block_hash` is the hash of the era's birth block, the genesis hash for an immortal extrinsic.
+	/// The doughnut, fee exchange and tip are signed in that order, when present.
+	pub fn signing_payload<Hash: Encode>(&self, index: &Compact<Index>, era: Era, block_hash: &Hash) -> SigningPayload
+	where
+		Compact<Index>: Encode,
+	{
+		SigningPayload::new(index, &self.function, era, block_hash)
+			.with(self.doughnut.as_ref())
+			.with(self.fee_exchange.as_ref())
+			.with(self.tip.as_ref())
+	}
+}
+
 impl<AccountId: Encode, Address: Encode, Index: Encode, Call: Encode, Signature: Encode, Balance: HasCompact> Extrinsic
 	for CennznetExtrinsic<AccountId, Address, Index, Call, Signature, Balance>
 {
@@ -158,11 +268,12 @@ impl<AccountId, Address, Index, Call, Signature, Context, Hash, BlockNumber, Bal
 where
 	Address: Member + MaybeDisplay,
 	Balance: HasCompact,
+	Compact<Balance>: Encode,
 	Index: Member + MaybeDisplay + SimpleArithmetic,
 	Compact<Index>: Encode,
 	Call: Encode + Member,
-	Signature: Member + traits::Verify<Signer = AccountId> + Encode,
-	AccountId: Member + MaybeDisplay + Encode,
+	Signature: Member + traits::Verify<Signer = AccountId> + Encode + Decode,
+	AccountId: Member + MaybeDisplay + Encode + Decode,
 	BlockNumber: SimpleArithmetic,
 	Hash: Encode,
 	Context: Lookup<Source = Address, Target = AccountId>
@@ -171,55 +282,60 @@ where
 {
 	type Checked = CheckedCennznetExtrinsic<AccountId, Index, Call, Balance>;
 
-	fn check(self, context: &Context) -> Result<Self::Checked, &'static str> {
+	fn check(mut self, context: &Context) -> Result<Self::Checked, &'static str> {
 		// There's no signature so we're done
 		if self.signature.is_none() {
+			// Nobody would pay the tip
+			if self.tip.is_some() {
+				return Err("unsigned extrinsic can not carry a tip");
+			}
 			return Ok(Self::Checked {
 				signed: None,
 				function: self.function,
 				fee_exchange: self.fee_exchange,
+				delegate: None,
+				certificate: None,
+				tip: None,
 			});
 		};
 
-		let (signed, signature, index, era) = self.signature.unwrap();
+		let (signed, signature, index, era) = self.signature.take().unwrap();
 		let h = context
 			.block_number_to_hash(BlockNumber::sa(era.birth(context.current_height().as_())))
 			.ok_or("transaction birth block ancient")?;
 		let mut signed = context.lookup(signed)?;
 
-		let verify_signature = |payload: &[u8]| {
-			if payload.len() > 256 {
-				signature.verify(&blake2_256(payload)[..], &signed)
-			} else {
-				signature.verify(payload, &signed)
-			}
-		};
-
-		// Signature may be standard, contain a doughnut and/or a fee exchange operation
-		let verified = match (&self.doughnut, &self.fee_exchange) {
-			(Some(doughnut), Some(fee_exchange)) => {
-				(&index, &self.function, era, h, doughnut, fee_exchange).using_encoded(verify_signature)
-			}
-			(Some(doughnut), None) => (&index, &self.function, era, h, doughnut).using_encoded(verify_signature),
-			(None, Some(fee_exchange)) => {
-				(&index, &self.function, era, h, fee_exchange).using_encoded(verify_signature)
-			}
-			(None, None) => (&index, &self.function, era, h).using_encoded(verify_signature),
-		};
+		// Signature may be standard, contain a doughnut, a fee exchange operation and/or a tip
+		let verified = self
+			.signing_payload(&index, era, &h)
+			.using_signable(|payload| signature.verify(payload, &signed));
 
 		if !verified {
 			return Err("bad signature in extrinsic");
 		}
 
-		// Doughnuts are signed by their issuer
+		// Doughnuts are signed by their issuer, the extrinsic signer is acting as a delegate.
+		// A re-delegated doughnut acts on behalf of the issuer at the root of its chain.
+		// The validity period is checked by the runtime against on-chain time.
+		let mut delegate = None;
+		let mut certificate = None;
 		if let Some(d) = self.doughnut {
-			signed = d.certificate.issuer;
+			if d.certificate.holder != signed {
+				return Err("extrinsic signer is not the doughnut holder");
+			}
+			d.validate_signatures()?;
+			delegate = Some(signed);
+			signed = d.root_issuer();
+			certificate = Some(d.certificate);
 		}
 
 		Ok(Self::Checked {
 			signed: Some((signed, index.0)),
 			function: self.function,
 			fee_exchange: self.fee_exchange,
+			delegate,
+			certificate,
+			tip: self.tip.map(|tip| tip.0),
 		})
 	}
 }
@@ -233,8 +349,26 @@ where
 	Compact<Index>: Decode,
 	Call: Decode,
 	Balance: HasCompact,
+	Compact<Balance>: Decode,
 {
 	fn decode<I: Input>(input: &mut I) -> Option<Self> {
+		Self::decode_format(ExtrinsicFormat::LATEST, input)
+	}
+}
+
+impl<AccountId, Address, Index, Call, Signature, Balance>
+	CennznetExtrinsic<AccountId, Address, Index, Call, Signature, Balance>
+where
+	AccountId: Decode,
+	Address: Decode,
+	Signature: Decode,
+	Compact<Index>: Decode,
+	Call: Decode,
+	Balance: HasCompact,
+	Compact<Balance>: Decode,
+{
+	/// Decode an extrinsic encoded in `format`, e.g. from a block authored before the latest format
+	pub fn decode_format<I: Input>(format: ExtrinsicFormat, input: &mut I) -> Option<Self> {
 		// This is a little more complicated than usual since the binary format must be compatible
 		// with substrate's generic `Vec<u8>` type. Basically this just means accepting that there
 		// will be a prefix of vector length (we don't need
@@ -246,9 +380,17 @@ where
 		let is_signed = version & BIT_SIGNED != 0;
 		let has_doughnut = version & BIT_DOUGHNUT != 0;
 		let has_fee_exchange = version & BIT_CENNZ_X != 0;
-		let version = version & MASK_VERSION;
+		let (has_fee_exchange_fallbacks, has_tip, version) = match format {
+			// The unused bits were ignored
+			ExtrinsicFormat::V1 => (false, false, version & MASK_VERSION_V1),
+			ExtrinsicFormat::V2 => (
+				version & BIT_FEE_EXCHANGE_FALLBACKS != 0,
+				version & BIT_TIP != 0,
+				version & MASK_VERSION,
+			),
+		};
 
-		if version != TRANSACTION_VERSION {
+		if version != TRANSACTION_VERSION || (has_fee_exchange_fallbacks && !has_fee_exchange) {
 			return None;
 		}
 
@@ -262,16 +404,23 @@ where
 		};
 
 		let fee_exchange = if has_fee_exchange {
-			Some(Decode::decode(input)?)
+			let mut fee_exchange: FeeExchange<Balance> = Decode::decode(input)?;
+			if has_fee_exchange_fallbacks {
+				fee_exchange.fallbacks = Decode::decode(input)?;
+			}
+			Some(fee_exchange)
 		} else {
 			None
 		};
 
+		let tip = if has_tip { Some(Decode::decode(input)?) } else { None };
+
 		Some(CennznetExtrinsic {
 			signature,
 			function,
 			doughnut,
 			fee_exchange,
+			tip,
 		})
 	}
 }
@@ -285,6 +434,7 @@ where
 	Compact<Index>: Encode,
 	Call: Encode,
 	Balance: HasCompact,
+	Compact<Balance>: Encode,
 {
 	fn encode(&self) -> Vec<u8> {
 		encode_with_vec_prefix::<Self, _>(|v| {
@@ -296,8 +446,14 @@ where
 			if self.doughnut.is_some() {
 				version |= BIT_DOUGHNUT;
 			}
-			if self.fee_exchange.is_some() {
+			if let Some(f) = self.fee_exchange.as_ref() {
 				version |= BIT_CENNZ_X;
+				if !f.fallbacks.is_empty() {
+					version |= BIT_FEE_EXCHANGE_FALLBACKS;
+				}
+			}
+			if self.tip.is_some() {
+				version |= BIT_TIP;
 			}
 			v.push(version);
 
@@ -311,6 +467,9 @@ where
 			if let Some(f) = self.fee_exchange.as_ref() {
 				f.encode_to(v);
 			}
+			if let Some(t) = self.tip.as_ref() {
+				t.encode_to(v);
+			}
 		})
 	}
 }
@@ -321,6 +480,7 @@ impl<AccountId: Encode, Address: Encode, Index, Signature: Encode, Call: Encode,
 where
 	Compact<Index>: Encode,
 	Balance: HasCompact,
+	Compact<Balance>: Encode,
 {
 	fn serialize<S>(&self, seq: S) -> Result<S::Ok, S::Error>
 	where
@@ -344,11 +504,12 @@ where
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(
 			f,
-			"CennznetExtrinsic({:?}, {:?}, {:?}, {:?})",
+			"CennznetExtrinsic({:?}, {:?}, {:?}, {:?}, {:?})",
 			self.signature.as_ref().map(|x| (&x.0, &x.2)),
 			self.function,
 			self.doughnut,
-			self.fee_exchange
+			self.fee_exchange,
+			self.tip.as_ref().map(|tip| &tip.0)
 		)
 	}
 }
@@ -366,11 +527,208 @@ pub struct Certificate<AccountId> {
 	pub issuer: AccountId,
 }
 
+/// The permission key of the doughnut a doughnut was re-delegated from, the value is the encoded parent doughnut.
+/// A holder may re-delegate to another account with a subset of the parent's permissions and validity period.
+pub const PARENT_DOUGHNUT_KEY: &str = "parent";
+
+/// The max. number of doughnuts in a delegation chain, including the root
+pub const MAX_DOUGHNUT_CHAIN_LENGTH: usize = 4;
+
+/// The permission key of constraints on a doughnut's use, the value is an encoded `TransferConstraints`.
+/// A doughnut with constraints may only make asset transfers, within the constraints.
+pub const TRANSFER_CONSTRAINTS_KEY: &str = "cennznet:transfer";
+
+/// Constraints on the asset transfers a doughnut holder may make
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct TransferConstraints<AccountId> {
+	/// The max. amount of each transfer, unlimited if `None`
+	pub max_amount: Option<u128>,
+	/// The assets which may be transferred, any if empty
+	pub asset_ids: Vec<u32>,
+	/// The accounts which may be transferred to, any if empty
+	pub destinations: Vec<AccountId>,
+}
+
+impl<AccountId: PartialEq> TransferConstraints<AccountId> {
+	/// Return `Ok` iff a transfer of `amount` of `asset_id` to `to` is within the constraints
+	pub fn check(&self, asset_id: u32, to: &AccountId, amount: u128) -> support::dispatch::Result {
+		if self.max_amount.map_or(false, |max| amount > max) {
+			return Err("doughnut does not permit a transfer of this amount");
+		}
+		if !self.asset_ids.is_empty() && !self.asset_ids.contains(&asset_id) {
+			return Err("doughnut does not permit transfers of this asset");
+		}
+		if !self.destinations.is_empty() && !self.destinations.contains(to) {
+			return Err("doughnut does not permit transfers to this account");
+		}
+		Ok(())
+	}
+}
+
+/// The permission key of a doughnut's activation window, the value is an encoded `u64` of seconds after `not_before`.
+/// A doughnut with an activation window can only be used once its holder has activated it on-chain within the window.
+pub const ACTIVATION_WINDOW_KEY: &str = "activation";
+
+/// The permission key of a read-only doughnut, the value is an encoded `Vec<Vec<u8>>` of the read scopes it grants,
+/// e.g. `b"sylo:inbox"`. A read-only doughnut permits no calls, it only authorizes its holder to privileged reads
+/// on behalf of its issuer.
+pub const READ_ONLY_KEY: &str = "read";
+
+impl<AccountId> Certificate<AccountId> {
+	/// A read-only doughnut certificate granting `holder` the read `scopes` of `issuer`, valid from `not_before`
+	/// until `expires`
+	pub fn read_only(
+		issuer: AccountId,
+		holder: AccountId,
+		not_before: u64,
+		expires: u64,
+		scopes: Vec<Vec<u8>>,
+	) -> Self {
+		Certificate {
+			expires,
+			version: 0,
+			holder,
+			not_before,
+			permissions: vec![(READ_ONLY_KEY.encode(), scopes.encode())],
+			issuer,
+		}
+	}
+
+	/// The read scopes of a read-only doughnut, `None` if it is not read-only or they can not be decoded
+	pub fn read_scopes(&self) -> Option<Vec<Vec<u8>>> {
+		let key = READ_ONLY_KEY.encode();
+		self.permissions
+			.iter()
+			.find(|(k, _)| *k == key)
+			.and_then(|(_, v)| Decode::decode(&mut &v[..]))
+	}
+
+	/// The transfer constraints of the doughnut, if any.
+	/// Returns `Err` if they can not be decoded.
+	pub fn transfer_constraints(&self) -> Result<Option<TransferConstraints<AccountId>>, &'static str>
+	where
+		AccountId: Decode,
+	{
+		let key = TRANSFER_CONSTRAINTS_KEY.encode();
+		match self.permissions.iter().find(|(k, _)| *k == key) {
+			Some((_, v)) => Decode::decode(&mut &v[..])
+				.map(Some)
+				.ok_or("invalid doughnut transfer constraints"),
+			None => Ok(None),
+		}
+	}
+
+	/// The issuer key set of a multi-signature doughnut, if any.
+	/// Returns `Err` if it can not be decoded.
+	pub fn issuer_key_set(&self) -> Result<Option<IssuerKeySet<AccountId>>, &'static str>
+	where
+		AccountId: Decode,
+	{
+		let key = MULTISIG_ISSUER_KEY.encode();
+		match self.permissions.iter().find(|(k, _)| *k == key) {
+			Some((_, v)) => Decode::decode(&mut &v[..])
+				.map(Some)
+				.ok_or("invalid doughnut issuer key set"),
+			None => Ok(None),
+		}
+	}
+
+	/// The activation window of the doughnut in seconds after `not_before`, if it requires activation
+	pub fn activation_window(&self) -> Option<u64> {
+		let key = ACTIVATION_WINDOW_KEY.encode();
+		self.permissions
+			.iter()
+			.find(|(k, _)| *k == key)
+			.and_then(|(_, v)| Decode::decode(&mut &v[..]))
+	}
+
+	/// Return `Ok` iff the doughnut is valid at time `now`, from `not_before` until just before `expires`
+	pub fn validate_period(&self, now: u64) -> support::dispatch::Result {
+		if now < self.not_before {
+			return Err("doughnut is not valid yet");
+		}
+		if now >= self.expires {
+			return Err("doughnut has expired");
+		}
+		Ok(())
+	}
+}
+
+/// The certificate version of a doughnut issued by an M-of-N issuer key set, signed with a
+/// `DoughnutSignature::Multi`. Single-signed doughnut versions count up from 0 and never reach it.
+pub const MULTISIG_DOUGHNUT_VERSION: u32 = 0x8000_0000;
+
+/// The permission key of a multi-signature doughnut's issuer key set, the value is an encoded `IssuerKeySet`.
+/// The doughnut's issuer must be the key set's account and at least `threshold` of its signatories must sign it.
+pub const MULTISIG_ISSUER_KEY: &str = "multisig";
+
+/// The max. number of signatories in an issuer key set
+pub const MAX_ISSUER_SIGNATORIES: usize = 16;
+
+/// An M-of-N key set issuing doughnuts on behalf of its own account, e.g. the officers of a corporate issuer
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct IssuerKeySet<AccountId> {
+	/// The number of signatories which must sign a doughnut
+	pub threshold: u32,
+	/// The accounts which may sign, a signature names its signatory by index
+	pub signatories: Vec<AccountId>,
+}
+
+impl<AccountId: Encode + Decode + PartialEq> IssuerKeySet<AccountId> {
+	/// The account the key set issues doughnuts on behalf of, derived from its threshold and signatories
+	pub fn account_id(&self) -> Option<AccountId> {
+		let entropy = (&b"cennznet:multisig"[..], self.threshold, &self.signatories).using_encoded(blake2_256);
+		Decode::decode(&mut &entropy[..])
+	}
+
+	/// Return `Ok` iff the threshold can be met by distinct signatories
+	pub fn validate(&self) -> support::dispatch::Result {
+		let signatories = self.signatories.len();
+		if self.threshold == 0 || self.threshold as usize > signatories || signatories > MAX_ISSUER_SIGNATORIES {
+			return Err("invalid doughnut issuer key set");
+		}
+		for (i, signatory) in self.signatories.iter().enumerate() {
+			if self.signatories[..i].contains(signatory) {
+				return Err("invalid doughnut issuer key set");
+			}
+		}
+		Ok(())
+	}
+}
+
+/// The signature of a doughnut certificate.
+/// It is encoded without a variant index, the certificate's version determines which variant it decodes as.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum DoughnutSignature<Signature> {
+	/// The issuer's signature
+	Single(Signature),
+	/// Signatures of the issuer key set's signatories: (signatory index, signature)
+	Multi(Vec<(u32, Signature)>),
+}
+
+impl<Signature: Default> Default for DoughnutSignature<Signature> {
+	fn default() -> Self {
+		DoughnutSignature::Single(Default::default())
+	}
+}
+
+impl<Signature: Encode> Encode for DoughnutSignature<Signature> {
+	fn encode_to<T: Output>(&self, dest: &mut T) {
+		match self {
+			DoughnutSignature::Single(signature) => signature.encode_to(dest),
+			DoughnutSignature::Multi(signatures) => signatures.encode_to(dest),
+		}
+	}
+}
+
 #[derive(Clone, Eq, PartialEq, Default, Encode)]
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct Doughnut<AccountId, Signature> {
 	pub certificate: Certificate<AccountId>,
-	pub signature: Signature,
+	pub signature: DoughnutSignature<Signature>,
 }
 
 impl<AccountId, Signature> Decode for Doughnut<AccountId, Signature>
@@ -379,34 +737,156 @@ where
 	Signature: Decode,
 {
 	fn decode<I: Input>(input: &mut I) -> Option<Self> {
-		Some(Doughnut {
-			certificate: Decode::decode(input)?,
-			signature: Decode::decode(input)?,
-		})
+		let certificate: Certificate<AccountId> = Decode::decode(input)?;
+		let signature = if certificate.version == MULTISIG_DOUGHNUT_VERSION {
+			DoughnutSignature::Multi(Decode::decode(input)?)
+		} else {
+			DoughnutSignature::Single(Decode::decode(input)?)
+		};
+		Some(Doughnut { certificate, signature })
 	}
 }
 
 impl<AccountId, Signature> Doughnut<AccountId, Signature>
 where
-	Signature: Verify<Signer = AccountId> + Encode,
-	AccountId: Encode,
+	Signature: Verify<Signer = AccountId> + Encode + Decode,
+	AccountId: Encode + Decode + PartialEq + Clone,
 {
+	/// Validate the doughnut and each doughnut it was re-delegated from at time `now`
 	pub fn validate(&self, now: u64) -> support::dispatch::Result {
-		if self.certificate.expires > now {
-			let valid = self.certificate.not_before <= now;
-			if valid {
-				if self
-					.signature
-					.verify(self.certificate.encode().as_slice(), &self.certificate.issuer)
-				{
-					// TODO: ensure doughnut hasn't been revoked
-					return Ok(());
-				} else {
+		self.validate_chain(Some(now))
+	}
+
+	/// Validate the signatures and delegation chain of the doughnut, but not its validity period.
+	/// A re-delegated doughnut's period is within its parents', so only its own period remains to be validated,
+	/// e.g. with `Certificate::validate_period` against on-chain time.
+	pub fn validate_signatures(&self) -> support::dispatch::Result {
+		self.validate_chain(None)
+	}
+
+	/// Validate the doughnut and each doughnut it was re-delegated from, at time `now` if given
+	fn validate_chain(&self, now: Option<u64>) -> support::dispatch::Result {
+		self.validate_certificate(now)?;
+		let mut doughnut = self.parent()?;
+		let mut child = self.certificate.clone();
+		let mut length = 1;
+		while let Some(parent) = doughnut {
+			length += 1;
+			if length > MAX_DOUGHNUT_CHAIN_LENGTH {
+				return Err("doughnut delegation chain is too long");
+			}
+			Self::ensure_narrows(&child, &parent.certificate)?;
+			parent.validate_certificate(now)?;
+			doughnut = parent.parent()?;
+			child = parent.certificate;
+		}
+		Ok(())
+	}
+
+	/// The doughnut this doughnut was re-delegated from, if any
+	pub fn parent(&self) -> Result<Option<Self>, &'static str> {
+		let key = PARENT_DOUGHNUT_KEY.encode();
+		match self.certificate.permissions.iter().find(|(k, _)| *k == key) {
+			Some((_, v)) => Decode::decode(&mut &v[..])
+				.map(Some)
+				.ok_or("invalid parent doughnut"),
+			None => Ok(None),
+		}
+	}
+
+	/// The issuer at the root of the delegation chain, on whose behalf the holder acts.
+	/// This does not validate the chain.
+	pub fn root_issuer(&self) -> AccountId {
+		let mut issuer = self.certificate.issuer.clone();
+		let mut doughnut = self.parent().unwrap_or(None);
+		let mut length = 1;
+		while let Some(parent) = doughnut {
+			length += 1;
+			if length > MAX_DOUGHNUT_CHAIN_LENGTH {
+				break;
+			}
+			issuer = parent.certificate.issuer.clone();
+			doughnut = parent.parent().unwrap_or(None);
+		}
+		issuer
+	}
+
+	/// A re-delegated certificate must be issued by the parent's holder, fit within the parent's validity
+	/// period and grant no permission the parent does not
+	fn ensure_narrows(child: &Certificate<AccountId>, parent: &Certificate<AccountId>) -> support::dispatch::Result {
+		if child.issuer != parent.holder {
+			return Err("doughnut is not issued by the holder of its parent");
+		}
+		if child.expires > parent.expires || child.not_before < parent.not_before {
+			return Err("doughnut outlives its parent");
+		}
+		// these describe the child itself rather than grant anything
+		let own_keys = [
+			PARENT_DOUGHNUT_KEY.encode(),
+			ACTIVATION_WINDOW_KEY.encode(),
+			MULTISIG_ISSUER_KEY.encode(),
+		];
+		let escalates = child
+			.permissions
+			.iter()
+			.filter(|(k, _)| !own_keys.contains(k))
+			.any(|permission| !parent.permissions.contains(permission));
+		// constraints restrict rather than grant, they must be passed on unchanged
+		let constraints_key = TRANSFER_CONSTRAINTS_KEY.encode();
+		let drops_constraints = parent
+			.permissions
+			.iter()
+			.filter(|(k, _)| *k == constraints_key)
+			.any(|permission| !child.permissions.contains(permission));
+		if escalates || drops_constraints {
+			return Err("doughnut escalates the permissions of its parent");
+		}
+		Ok(())
+	}
+
+	/// Validate the signature and, at time `now` if given, the validity period of this doughnut only
+	fn validate_certificate(&self, now: Option<u64>) -> support::dispatch::Result {
+		if let Some(now) = now {
+			self.certificate.validate_period(now)?;
+		}
+		let payload = self.certificate.encode();
+		match self.signature {
+			DoughnutSignature::Single(ref signature) => {
+				if !signature.verify(payload.as_slice(), &self.certificate.issuer) {
 					return Err("invalid signature");
 				}
 			}
+			DoughnutSignature::Multi(ref signatures) => self.validate_multisig(&payload, signatures)?,
+		}
+		// TODO: ensure doughnut hasn't been revoked
+		Ok(())
+	}
+
+	/// Validate that at least the threshold of the issuer key set's signatories signed `payload`, each once
+	fn validate_multisig(&self, payload: &[u8], signatures: &[(u32, Signature)]) -> support::dispatch::Result {
+		let key_set = self
+			.certificate
+			.issuer_key_set()?
+			.ok_or("multi-signature doughnut has no issuer key set")?;
+		key_set.validate()?;
+		if key_set.account_id().as_ref() != Some(&self.certificate.issuer) {
+			return Err("doughnut issuer is not the account of its key set");
+		}
+		let mut signed = vec![false; key_set.signatories.len()];
+		for (index, signature) in signatures {
+			let index = *index as usize;
+			if index >= signed.len() || signed[index] {
+				return Err("invalid multi-signature");
+			}
+			if !signature.verify(payload, &key_set.signatories[index]) {
+				return Err("invalid signature");
+			}
+			signed[index] = true;
+		}
+		if signatures.len() < key_set.threshold as usize {
+			return Err("doughnut is not signed by enough of its issuer's signatories");
 		}
-		return Err("invalid doughnut");
+		Ok(())
 	}
 	pub fn validate_permission(&self) -> support::dispatch::Result {
 		// not efficient, optimize later
@@ -423,23 +903,69 @@ where
 /// embed within CENNZnet extrinsics.
 /// It specifies input asset ID and the max. input asset to pay. The actual
 /// fee amount to pay is calculated via the fees module and current exchange prices.
-#[derive(PartialEq, Eq, Clone, Encode, Decode)]
+/// Optional fallbacks are tried in order when the asset can not pay within its max. payment.
+#[derive(PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct FeeExchange<Balance: HasCompact> {
 	// TODO: use runtime `AssetId` type instead of `u32` directly
-	/// The asset ID to pay in exchange for fee asset
-	#[codec(compact)]
+	/// The asset ID to pay in exchange for fee asset, compact encoded
 	pub asset_id: u32,
 	/// The max. amount of `asset_id` to pay for the needed fee amount.
-	/// The operation should fail otherwise.
-	#[codec(compact)]
+	/// The operation should fail otherwise. Compact encoded.
 	pub max_payment: Balance,
+	/// Assets to pay with instead, in order, each with its own max. payment.
+	/// Fallbacks of fallbacks are ignored.
+	pub fallbacks: Vec<FeeExchange<Balance>>,
 }
 
 impl<Balance: HasCompact> FeeExchange<Balance> {
 	/// Create a new FeeExchange
 	pub fn new(asset_id: u32, max_payment: Balance) -> Self {
-		Self { asset_id, max_payment }
+		Self {
+			asset_id,
+			max_payment,
+			fallbacks: Vec::new(),
+		}
+	}
+
+	/// Add `fallbacks` to try in order if this asset can not pay the fee
+	pub fn with_fallbacks(mut self, fallbacks: Vec<(u32, Balance)>) -> Self {
+		self.fallbacks = fallbacks
+			.into_iter()
+			.map(|(asset_id, max_payment)| Self::new(asset_id, max_payment))
+			.collect();
+		self
+	}
+
+	/// The assets which may pay the fee and their max. payments, in the order they should be tried
+	pub fn candidates(&self) -> Vec<(u32, Balance)>
+	where
+		Balance: Clone,
+	{
+		rstd::iter::once((self.asset_id, self.max_payment.clone()))
+			.chain(self.fallbacks.iter().map(|f| (f.asset_id, f.max_payment.clone())))
+			.collect()
+	}
+}
+
+/// The fallbacks are only encoded when present, the extrinsic's version byte signals whether they follow.
+/// This keeps fee exchanges without fallbacks binary compatible with their original encoding.
+impl<Balance: HasCompact> Encode for FeeExchange<Balance> {
+	fn encode_to<T: Output>(&self, dest: &mut T) {
+		Compact(self.asset_id).encode_to(dest);
+		<<Balance as HasCompact>::Type as EncodeAsRef<'_, Balance>>::RefType::from(&self.max_payment).encode_to(dest);
+		if !self.fallbacks.is_empty() {
+			self.fallbacks.encode_to(dest);
+		}
+	}
+}
+
+/// Decodes a fee exchange without fallbacks, the extrinsic decodes them when its version byte signals them.
+impl<Balance: HasCompact> Decode for FeeExchange<Balance> {
+	fn decode<I: Input>(input: &mut I) -> Option<Self> {
+		let asset_id = <Compact<u32>>::decode(input)?.0;
+		let max_payment = <<Balance as HasCompact>::Type>::decode(input)?.into();
+		Some(Self::new(asset_id, max_payment))
 	}
 }
 
@@ -466,4 +992,271 @@ mod tests {
 
 		assert_eq!(extrinsic, decoded);
 	}
+
+	#[test]
+	fn it_works_with_fee_exchange_fallbacks() {
+		let mut extrinsic = CennznetExtrinsic::<H256, H256, u32, (), (), u128>::new_unsigned(());
+		extrinsic.fee_exchange = Some(FeeExchange::new(0, 1_000_000));
+		let without_fallbacks = Encode::encode(&extrinsic);
+
+		extrinsic.fee_exchange = Some(FeeExchange::new(0, 1_000_000).with_fallbacks(vec![(1, 500), (2, 700)]));
+		let buf = Encode::encode(&extrinsic);
+		let decoded: CennznetExtrinsic<H256, H256, u32, (), (), u128> = Decode::decode(&mut &buf[..]).unwrap();
+		assert_eq!(extrinsic, decoded);
+		assert_eq!(
+			decoded.fee_exchange.unwrap().candidates(),
+			vec![(0, 1_000_000), (1, 500), (2, 700)]
+		);
+
+		// fee exchanges without fallbacks keep their original encoding
+		assert!(without_fallbacks.ends_with(&(Compact(0u32), Compact(1_000_000u128)).encode()));
+	}
+
+	#[test]
+	fn it_works_with_tip() {
+		let mut extrinsic = CennznetExtrinsic::<H256, H256, u32, (), (), u128>::new_unsigned(());
+		let without_tip = Encode::encode(&extrinsic);
+
+		extrinsic.tip = Some(Compact(500));
+		let buf = Encode::encode(&extrinsic);
+		let decoded: CennznetExtrinsic<H256, H256, u32, (), (), u128> = Decode::decode(&mut &buf[..]).unwrap();
+		assert_eq!(extrinsic, decoded);
+		assert_eq!(buf[1], without_tip[1] | BIT_TIP);
+		assert!(buf.ends_with(&Compact(500u128).encode()));
+	}
+
+	#[test]
+	fn decode_format_follows_the_rules_of_the_format() {
+		type TestExtrinsic = CennznetExtrinsic<H256, H256, u32, (), (), u128>;
+		let mut extrinsic = TestExtrinsic::new_unsigned(());
+		extrinsic.fee_exchange = Some(FeeExchange::new(0, 1_000));
+		let buf = Encode::encode(&extrinsic);
+		assert_eq!(TestExtrinsic::decode_format(ExtrinsicFormat::V1, &mut &buf[..]), Some(extrinsic.clone()));
+		assert_eq!(TestExtrinsic::decode_format(ExtrinsicFormat::V2, &mut &buf[..]), Some(extrinsic.clone()));
+
+		// The fallbacks bit was unused in V1
+		let mut with_unused_bit = buf.clone();
+		with_unused_bit[1] |= BIT_FEE_EXCHANGE_FALLBACKS;
+		assert_eq!(
+			TestExtrinsic::decode_format(ExtrinsicFormat::V1, &mut &with_unused_bit[..]),
+			Some(extrinsic.clone())
+		);
+		assert_eq!(TestExtrinsic::decode_format(ExtrinsicFormat::V2, &mut &with_unused_bit[..]), None);
+
+		// The tip bit was part of the version number in V1
+		extrinsic.tip = Some(Compact(5));
+		let buf = Encode::encode(&extrinsic);
+		assert_eq!(TestExtrinsic::decode_format(ExtrinsicFormat::V1, &mut &buf[..]), None);
+		assert_eq!(TestExtrinsic::decode_format(ExtrinsicFormat::V2, &mut &buf[..]), Some(extrinsic));
+
+		assert_eq!(ExtrinsicFormat::for_spec_version(23), ExtrinsicFormat::V1);
+		assert_eq!(ExtrinsicFormat::for_spec_version(24), ExtrinsicFormat::V1);
+		assert_eq!(ExtrinsicFormat::for_spec_version(25), ExtrinsicFormat::LATEST);
+
+		// A block authored with spec 24 is replayed in V1, where its extrinsics may set the unused bit
+		let format = ExtrinsicFormat::for_spec_version(24);
+		assert!(TestExtrinsic::decode_format(format, &mut &with_unused_bit[..]).is_some());
+		assert_eq!(TestExtrinsic::decode_format(format, &mut &buf[..]), None);
+	}
+
+	/// A signature which is valid iff it holds `true`
+	#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode)]
+	struct TestSignature(bool);
+
+	impl Verify for TestSignature {
+		type Signer = u64;
+		fn verify<L: runtime_primitives::traits::Lazy<[u8]>>(&self, _msg: L, _signer: &u64) -> bool {
+			self.0
+		}
+	}
+
+	fn doughnut(issuer: u64, holder: u64, permissions: Vec<(Vec<u8>, Vec<u8>)>) -> Doughnut<u64, TestSignature> {
+		Doughnut {
+			certificate: Certificate {
+				expires: 100,
+				version: 0,
+				holder,
+				not_before: 0,
+				permissions,
+				issuer,
+			},
+			signature: DoughnutSignature::Single(TestSignature(true)),
+		}
+	}
+
+	fn redelegate(
+		parent: &Doughnut<u64, TestSignature>,
+		holder: u64,
+		mut permissions: Vec<(Vec<u8>, Vec<u8>)>,
+	) -> Doughnut<u64, TestSignature> {
+		permissions.push((PARENT_DOUGHNUT_KEY.encode(), parent.encode()));
+		doughnut(parent.certificate.holder, holder, permissions)
+	}
+
+	#[test]
+	fn doughnut_chain_validates_each_level() {
+		let cennznet = ("cennznet".encode(), vec![]);
+		let root = doughnut(1, 2, vec![cennznet.clone(), ("other".encode(), vec![])]);
+		let child = redelegate(&root, 3, vec![cennznet.clone()]);
+		assert_eq!(child.validate(10), Ok(()));
+		assert_eq!(child.root_issuer(), 1);
+
+		// the parent's signature is checked
+		let mut bad_root = root.clone();
+		bad_root.signature = DoughnutSignature::Single(TestSignature(false));
+		assert_eq!(redelegate(&bad_root, 3, vec![cennznet.clone()]).validate(10), Err("invalid signature"));
+
+		// the child may not outlive the parent
+		let mut short_root = root.clone();
+		short_root.certificate.expires = 50;
+		let child = redelegate(&short_root, 3, vec![cennznet.clone()]);
+		assert_eq!(child.validate(10), Err("doughnut outlives its parent"));
+
+		// the child must be issued by the parent's holder
+		let mut child = redelegate(&root, 3, vec![cennznet.clone()]);
+		child.certificate.issuer = 4;
+		assert_eq!(child.validate(10), Err("doughnut is not issued by the holder of its parent"));
+	}
+
+	#[test]
+	fn doughnut_is_valid_from_not_before_until_expiry() {
+		let mut d = doughnut(1, 2, vec![]);
+		d.certificate.not_before = 10;
+		assert_eq!(d.certificate.validate_period(9), Err("doughnut is not valid yet"));
+		assert_eq!(d.certificate.validate_period(10), Ok(()));
+		assert_eq!(d.certificate.validate_period(99), Ok(()));
+		assert_eq!(d.certificate.validate_period(100), Err("doughnut has expired"));
+		assert_eq!(d.validate(100), Err("doughnut has expired"));
+
+		// signatures are validated regardless of time
+		assert_eq!(d.validate_signatures(), Ok(()));
+		d.signature = DoughnutSignature::Single(TestSignature(false));
+		assert_eq!(d.validate_signatures(), Err("invalid signature"));
+	}
+
+	#[test]
+	fn doughnut_chain_rejects_escalation_and_long_chains() {
+		let cennznet = ("cennznet".encode(), vec![]);
+		let root = doughnut(1, 2, vec![cennznet.clone()]);
+		let child = redelegate(&root, 3, vec![cennznet.clone(), ("other".encode(), vec![])]);
+		assert_eq!(child.validate(10), Err("doughnut escalates the permissions of its parent"));
+
+		// transfer constraints must be passed on
+		let constraints = (
+			TRANSFER_CONSTRAINTS_KEY.encode(),
+			TransferConstraints::<u64> {
+				max_amount: Some(100),
+				asset_ids: vec![],
+				destinations: vec![],
+			}
+			.encode(),
+		);
+		let constrained_root = doughnut(1, 2, vec![cennznet.clone(), constraints.clone()]);
+		let child = redelegate(&constrained_root, 3, vec![cennznet.clone()]);
+		assert_eq!(child.validate(10), Err("doughnut escalates the permissions of its parent"));
+		let child = redelegate(&constrained_root, 3, vec![cennznet.clone(), constraints]);
+		assert_eq!(child.validate(10), Ok(()));
+
+		let mut chain = root;
+		for holder in 3..(2 + MAX_DOUGHNUT_CHAIN_LENGTH as u64) {
+			chain = redelegate(&chain, holder, vec![cennznet.clone()]);
+		}
+		assert_eq!(chain.validate(10), Ok(()));
+		let chain = redelegate(&chain, 10, vec![cennznet]);
+		assert_eq!(chain.validate(10), Err("doughnut delegation chain is too long"));
+	}
+
+	#[test]
+	fn multisig_doughnut_requires_threshold_of_distinct_signatories() {
+		let cennznet = ("cennznet".encode(), vec![]);
+		let key_set = IssuerKeySet {
+			threshold: 2,
+			signatories: vec![10u64, 11, 12],
+		};
+		let issuer = key_set.account_id().unwrap();
+		let mut d = doughnut(issuer, 2, vec![cennznet.clone(), (MULTISIG_ISSUER_KEY.encode(), key_set.encode())]);
+		d.certificate.version = MULTISIG_DOUGHNUT_VERSION;
+		let signed_by = |indices: &[u32]| {
+			DoughnutSignature::Multi(indices.iter().map(|i| (*i, TestSignature(true))).collect())
+		};
+
+		d.signature = signed_by(&[0, 2]);
+		assert_eq!(d.validate(10), Ok(()));
+		assert_eq!(Doughnut::decode(&mut &d.encode()[..]), Some(d.clone()));
+		// the holder may re-delegate it
+		assert_eq!(redelegate(&d, 3, vec![cennznet.clone()]).validate(10), Ok(()));
+
+		d.signature = signed_by(&[1]);
+		assert_eq!(d.validate(10), Err("doughnut is not signed by enough of its issuer's signatories"));
+		d.signature = signed_by(&[1, 1]);
+		assert_eq!(d.validate(10), Err("invalid multi-signature"));
+		d.signature = signed_by(&[1, 3]);
+		assert_eq!(d.validate(10), Err("invalid multi-signature"));
+		d.signature = DoughnutSignature::Multi(vec![(0, TestSignature(true)), (1, TestSignature(false))]);
+		assert_eq!(d.validate(10), Err("invalid signature"));
+
+		// the issuer must be the key set's account
+		d.signature = signed_by(&[0, 1]);
+		d.certificate.issuer = 10;
+		assert_eq!(d.validate(10), Err("doughnut issuer is not the account of its key set"));
+		d.certificate.permissions = vec![cennznet];
+		assert_eq!(d.validate(10), Err("multi-signature doughnut has no issuer key set"));
+
+		let invalid_key_set = |threshold, signatories| IssuerKeySet { threshold, signatories }.validate();
+		assert_eq!(invalid_key_set(0, vec![10u64, 11]), Err("invalid doughnut issuer key set"));
+		assert_eq!(invalid_key_set(3, vec![10u64, 11]), Err("invalid doughnut issuer key set"));
+		assert_eq!(invalid_key_set(2, vec![10u64, 10]), Err("invalid doughnut issuer key set"));
+	}
+
+	#[test]
+	fn single_signed_doughnuts_of_any_version_decode() {
+		for version in 0..3 {
+			let mut d = doughnut(1, 2, vec![("cennznet".encode(), vec![])]);
+			d.certificate.version = version;
+			assert_eq!(Doughnut::decode(&mut &d.encode()[..]), Some(d));
+		}
+	}
+
+	#[test]
+	fn signing_payload_appends_present_extensions_in_order() {
+		let mut extrinsic = CennznetExtrinsic::<u64, u64, u32, u8, TestSignature, u128>::new_unsigned(7);
+		let index = Compact(3u32);
+		let era = Era::immortal();
+		let h = H256::repeat_byte(1);
+		let doughnut = doughnut(1, 2, vec![]);
+		let fee_exchange = FeeExchange::new(0, 1_000_000);
+		let tip = Compact(5u128);
+
+		let payload = |extrinsic: &CennznetExtrinsic<u64, u64, u32, u8, TestSignature, u128>| {
+			extrinsic.signing_payload(&index, era, &h).signable()
+		};
+		assert_eq!(payload(&extrinsic), (&index, &7u8, era, h).encode());
+		extrinsic.fee_exchange = Some(fee_exchange.clone());
+		assert_eq!(payload(&extrinsic), (&index, &7u8, era, h, &fee_exchange).encode());
+		extrinsic.doughnut = Some(doughnut.clone());
+		assert_eq!(payload(&extrinsic), (&index, &7u8, era, h, &doughnut, &fee_exchange).encode());
+		extrinsic.tip = Some(tip);
+		assert_eq!(payload(&extrinsic), (&index, &7u8, era, h, &doughnut, &fee_exchange, &tip).encode());
+		extrinsic.fee_exchange = None;
+		extrinsic.tip = None;
+		assert_eq!(payload(&extrinsic), (&index, &7u8, era, h, &doughnut).encode());
+
+		// long payloads are signed by their hash
+		let long = SigningPayload::new(&index, &vec![0u8; 300], era, &h);
+		assert_eq!(long.signable(), blake2_256(&(&index, &vec![0u8; 300], era, h).encode()).to_vec());
+	}
+
+	#[test]
+	fn read_only_doughnut_grants_scopes_but_no_calls() {
+		let certificate = Certificate::read_only(1u64, 2, 0, 100, vec![b"sylo:inbox".to_vec()]);
+		assert_eq!(certificate.read_scopes(), Some(vec![b"sylo:inbox".to_vec()]));
+		let read_only = Doughnut {
+			certificate,
+			signature: DoughnutSignature::Single(TestSignature(true)),
+		};
+		assert_eq!(read_only.validate(10), Ok(()));
+		assert_eq!(read_only.validate_permission(), Err("no permission"));
+
+		assert_eq!(doughnut(1, 2, vec![("cennznet".encode(), vec![])]).certificate.read_scopes(), None);
+	}
 }
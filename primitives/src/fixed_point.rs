@@ -0,0 +1,177 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Fixed-point arithmetic for balance math
+//!
+//! Ratios are applied as `x * numerator / denominator` over a 256-bit intermediate product, so they
+//! neither overflow before the division nor lose precision by dividing first.
+//! Callers choose the rounding explicitly, protocol math should round in the protocol's favour.
+
+/// The denominator of per-million ratios, the same precision as `Permill` and exchange fee rates
+pub const SCALE: u128 = 1_000_000;
+
+/// How the result of a division is rounded to an integer
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Rounding {
+	/// Towards zero
+	Down,
+	/// Away from zero
+	Up,
+	/// To the nearest integer, halves are rounded up
+	Nearest,
+}
+
+/// Calculate `x * y / denominator` rounded as given.
+/// `None` if `denominator` is zero or the result does not fit in a `u128`.
+pub fn mul_div(x: u128, y: u128, denominator: u128, rounding: Rounding) -> Option<u128> {
+	if denominator == 0 {
+		return None;
+	}
+	let (high, low) = full_mul(x, y);
+	let (quotient, remainder) = full_div(high, low, denominator)?;
+	let round_up = match rounding {
+		Rounding::Down => false,
+		Rounding::Up => remainder > 0,
+		Rounding::Nearest => remainder >= denominator - remainder,
+	};
+	if round_up {
+		quotient.checked_add(1)
+	} else {
+		Some(quotient)
+	}
+}
+
+/// Apply a per-million ratio to `x`, i.e. `x * parts / SCALE`.
+pub fn mul_ratio(x: u128, parts: u128, rounding: Rounding) -> Option<u128> {
+	mul_div(x, parts, SCALE, rounding)
+}
+
+/// Divide `x` by a per-million ratio, i.e. `x * SCALE / parts`.
+pub fn div_ratio(x: u128, parts: u128, rounding: Rounding) -> Option<u128> {
+	mul_div(x, SCALE, parts, rounding)
+}
+
+const LOW_MASK: u128 = u64::max_value() as u128;
+
+/// The 256-bit product of `a` and `b` as (high, low) 128-bit halves
+fn full_mul(a: u128, b: u128) -> (u128, u128) {
+	let (a_high, a_low) = (a >> 64, a & LOW_MASK);
+	let (b_high, b_low) = (b >> 64, b & LOW_MASK);
+
+	let low_low = a_low * b_low;
+	let low_high = a_low * b_high;
+	let high_low = a_high * b_low;
+	let high_high = a_high * b_high;
+
+	// At most 3 * (2^64 - 1), the carry into the high half is the top bits
+	let middle = (low_low >> 64) + (low_high & LOW_MASK) + (high_low & LOW_MASK);
+	let low = (low_low & LOW_MASK) | (middle << 64);
+	let high = high_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+	(high, low)
+}
+
+/// Divide the 256-bit number (high, low) by `divisor`, returning (quotient, remainder).
+/// `None` if the quotient does not fit in a `u128`.
+fn full_div(high: u128, low: u128, divisor: u128) -> Option<(u128, u128)> {
+	if high == 0 {
+		return Some((low / divisor, low % divisor));
+	}
+	if high >= divisor {
+		return None;
+	}
+	// Long division one bit at a time, the remainder is always less than `divisor`
+	let mut remainder = high;
+	let mut quotient = 0u128;
+	for bit in (0..128).rev() {
+		let carry = remainder >> 127;
+		remainder = (remainder << 1) | ((low >> bit) & 1);
+		// With a carry the true remainder is 2^128 more and still less than twice `divisor`
+		if carry == 1 || remainder >= divisor {
+			remainder = remainder.wrapping_sub(divisor);
+			quotient |= 1 << bit;
+		}
+	}
+	Some((quotient, remainder))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn mul_div_rounds_small_values_exactly() {
+		for x in 0..40u128 {
+			for y in 0..40u128 {
+				for denominator in 1..25u128 {
+					let product = x * y;
+					let floor = product / denominator;
+					let remainder = product % denominator;
+					assert_eq!(mul_div(x, y, denominator, Rounding::Down), Some(floor));
+					assert_eq!(
+						mul_div(x, y, denominator, Rounding::Up),
+						Some(if remainder > 0 { floor + 1 } else { floor })
+					);
+					assert_eq!(
+						mul_div(x, y, denominator, Rounding::Nearest),
+						Some(if 2 * remainder >= denominator { floor + 1 } else { floor })
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn mul_div_rounds_halves_up() {
+		assert_eq!(mul_div(5, 1, 2, Rounding::Nearest), Some(3));
+		assert_eq!(mul_div(5, 1, 2, Rounding::Down), Some(2));
+		assert_eq!(mul_div(7, 1, 3, Rounding::Nearest), Some(2));
+		assert_eq!(mul_div(8, 1, 3, Rounding::Nearest), Some(3));
+	}
+
+	#[test]
+	fn mul_div_does_not_overflow_before_dividing() {
+		let max = u128::max_value();
+		assert_eq!(mul_div(max, max, max, Rounding::Down), Some(max));
+		assert_eq!(mul_div(max, 2, 4, Rounding::Down), Some(max / 2));
+		assert_eq!(mul_div(max, 2, 4, Rounding::Up), Some(max / 2 + 1));
+		assert_eq!(mul_div(max, SCALE - 1, SCALE, Rounding::Down), Some(max - max / SCALE - 1));
+		assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90, Rounding::Down), Some(1 << 110));
+		// 3 * (2^128 - 1) / 7 rounded every way
+		let three_max = (max / 7) * 3 + (max % 7) * 3 / 7;
+		assert_eq!(mul_div(max, 3, 7, Rounding::Down), Some(three_max));
+		assert_eq!(mul_div(max, 3, 7, Rounding::Up), Some(three_max + 1));
+	}
+
+	#[test]
+	fn mul_div_fails_on_overflow_or_zero_denominator() {
+		let max = u128::max_value();
+		assert_eq!(mul_div(max, 2, 1, Rounding::Down), None);
+		assert_eq!(mul_div(max, max, max - 1, Rounding::Down), None);
+		assert_eq!(mul_div(max, 1, 1, Rounding::Up), Some(max));
+		assert_eq!(mul_div(max, 3, 2, Rounding::Down), None);
+		assert_eq!(mul_div(1, 1, 0, Rounding::Down), None);
+	}
+
+	#[test]
+	fn ratios_use_per_million_scale() {
+		assert_eq!(mul_ratio(1_000, 1_500_000, Rounding::Down), Some(1_500));
+		assert_eq!(mul_ratio(3, 333_333, Rounding::Down), Some(0));
+		assert_eq!(mul_ratio(3, 333_333, Rounding::Nearest), Some(1));
+		assert_eq!(div_ratio(10, 1_100_000, Rounding::Down), Some(9));
+		assert_eq!(div_ratio(10, 1_100_000, Rounding::Up), Some(10));
+		assert_eq!(div_ratio(10, 0, Rounding::Down), None);
+	}
+}
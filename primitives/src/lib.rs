@@ -26,8 +26,17 @@ use runtime_primitives::{
 };
 
 mod cennznet_extrinsic;
-
-pub use cennznet_extrinsic::{CennznetExtrinsic, CheckedCennznetExtrinsic, FeeExchange};
+pub mod fixed_point;
+pub mod runtime_api;
+#[cfg(feature = "std")]
+pub mod ss58;
+
+pub use cennznet_extrinsic::{
+	CennznetExtrinsic, Certificate, CheckedCennznetExtrinsic, Doughnut, DoughnutSignature, ExtrinsicFormat,
+	FeeExchange, IssuerKeySet, TransferConstraints, ACTIVATION_WINDOW_KEY, MAX_DOUGHNUT_CHAIN_LENGTH,
+	MAX_ISSUER_SIGNATORIES, MULTISIG_DOUGHNUT_VERSION, MULTISIG_ISSUER_KEY, PARENT_DOUGHNUT_KEY, READ_ONLY_KEY,
+	TRANSFER_CONSTRAINTS_KEY,
+};
 
 /// An index to a block.
 pub type BlockNumber = u64;
@@ -0,0 +1,192 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Runtime APIs for CENNZnet modules
+
+use parity_codec::{Codec, Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::{generic::Era, traits::Block as BlockT, Permill};
+use substrate_client::decl_runtime_apis;
+
+/// Whether an account may make a call, and why not otherwise
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum CallVerdict {
+	/// The call is permitted
+	Allowed,
+	/// The account is not the holder of the given doughnut
+	NotDoughnutHolder,
+	/// The doughnut has expired, is not yet valid or has a bad signature (reason)
+	InvalidDoughnut(Vec<u8>),
+	/// The doughnut does not grant permission for the call
+	NoPermission,
+	/// The fee payer can not afford the transaction fee
+	InsufficientFeeBalance,
+	/// The call could not be decoded
+	InvalidCall,
+	/// The call would fail when dispatched, e.g. the account lacks a module permission or the asset is frozen
+	/// (reason)
+	CallFailed(Vec<u8>),
+}
+
+/// An unsigned extrinsic with everything but its signature filled in
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ExtrinsicTemplate<Extrinsic, Index, Hash> {
+	/// The unsigned extrinsic, with a quoted fee exchange when its fee is paid in another asset
+	pub extrinsic: Extrinsic,
+	/// The sender's next index (nonce)
+	pub index: Index,
+	/// The mortal era the extrinsic is valid for
+	pub era: Era,
+	/// The hash of the block the era begins at, which is signed along with the extrinsic
+	pub era_block_hash: Hash,
+}
+
+/// A holder of an asset as listed in a compliance report
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct AssetHolder<AccountId, Balance, BlockNumber, AttestationValue> {
+	/// The holder's account
+	pub who: AccountId,
+	/// The holder's free balance of the asset
+	pub free_balance: Balance,
+	/// The holder's reserved balance of the asset
+	pub reserved_balance: Balance,
+	/// The block the holder's balance of the asset is frozen until, `None` if it is not frozen
+	pub frozen_until: Option<BlockNumber>,
+	/// The value of the queried attestation claim about the holder, `None` if there is no such claim
+	pub attestation: Option<AttestationValue>,
+}
+
+decl_runtime_apis! {
+	/// The API to query generic asset state
+	pub trait GenericAssetApi<AccountId, Lock, AssetId, Balance, Unlock> where
+		AccountId: Codec,
+		Lock: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+		Unlock: Codec,
+	{
+		/// Get a page of the `asset_id` locks on `who`, ordered by expiry.
+		/// Each lock is paired with the name of the module which set it.
+		fn locks(who: AccountId, asset_id: AssetId, page: u32, page_size: u32) -> Vec<(Vec<u8>, Lock)>;
+
+		/// Get the breakdown of the reserved balance of `asset_id` on `who` by the module which reserved it.
+		/// Reserved balance which is not attributed to a module is listed under an empty name.
+		fn reserves(who: AccountId, asset_id: AssetId) -> Vec<(Vec<u8>, Balance)>;
+
+		/// Get the total staking asset `who` has reserved for creating assets, and the stake held for each asset.
+		fn creator_stakes(who: AccountId) -> (Balance, Vec<(AssetId, Balance)>);
+
+		/// Get the free balance of `asset_id` on `who`.
+		fn asset_balance(asset_id: AssetId, who: AccountId) -> Balance;
+
+		/// Get the total issuance of `asset_id`.
+		fn total_issuance(asset_id: AssetId) -> Balance;
+
+		/// Get the ids of all assets, in order of creation.
+		fn registered_assets() -> Vec<AssetId>;
+
+		/// Get the governance voting weight of `who` as of the start of the snapshot period containing `block`:
+		/// its free staking asset balance, including locked but not reserved balance.
+		/// `None` if `block` is in the future or older than the snapshot history.
+		fn voting_weight_at(who: AccountId, block: u64) -> Option<Balance>;
+
+		/// Get the future unlocks of every asset on `who` ordered by block: lock expiries, vesting ends,
+		/// mint cooldowns and staking unbonding chunks.
+		fn unlock_timeline(who: AccountId) -> Vec<Unlock>;
+	}
+
+	/// The API to query fee suggestions
+	pub trait FeeApi<Balance> where
+		Balance: Codec,
+	{
+		/// Get the fee at each of `percentiles` of the fees charged over recent blocks.
+		/// Zero when no fees have been charged.
+		fn fee_percentiles(percentiles: Vec<Permill>) -> Vec<Balance>;
+
+		/// Get the fee `extrinsic` would be charged, and the amount of its fee exchange asset which would be
+		/// sold on the CENNZX-Spot exchange to pay it and any tip (zero without a fee exchange): `(fee, payment)`.
+		/// Returns the reason the fee could not be paid otherwise.
+		fn query_fee_exchange_info(extrinsic: <Block as BlockT>::Extrinsic) -> Result<(Balance, Balance), Vec<u8>>;
+	}
+
+	/// The API to query whether a call would be authorized
+	pub trait PermissionApi<AccountId, Doughnut> where
+		AccountId: Codec,
+		Doughnut: Codec,
+	{
+		/// Check whether `account` may make `call`, optionally acting as the holder of `doughnut`.
+		/// `call` is the SCALE encoded runtime call: its module and method index followed by its arguments.
+		/// It is checked like a submitted extrinsic, including its dispatch.
+		fn can_call(account: AccountId, call: Vec<u8>, doughnut: Option<Doughnut>) -> CallVerdict;
+
+		/// Check whether `account` may read `scope`, e.g. `b"sylo:inbox"`, as the holder of the read-only `doughnut`.
+		/// Read-only doughnuts permit no calls, they let privileged read endpoints authenticate their holder.
+		fn can_read(account: AccountId, scope: Vec<u8>, doughnut: Doughnut) -> CallVerdict;
+	}
+
+	/// The API to query attestation claims
+	pub trait AttestationApi<AccountId, Topic, Value> where
+		AccountId: Codec,
+		Topic: Codec,
+		Value: Codec,
+	{
+		/// Get every claim made about `holder` as `(issuer, topic, value)`.
+		fn claims(holder: AccountId) -> Vec<(AccountId, Topic, Value)>;
+
+		/// Get the value of the claim `issuer` made about `holder` on `topic`, `None` if there is no such claim.
+		fn claim(holder: AccountId, issuer: AccountId, topic: Topic) -> Option<Value>;
+	}
+
+	/// The API for asset issuers to report on the holders of their assets
+	pub trait ComplianceApi<AccountId, AssetId, Topic, Holder> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Topic: Codec,
+		Holder: Codec,
+	{
+		/// Get the number of accounts holding a non-zero balance of `asset_id` and a page of them,
+		/// each with its freeze status and the claim `issuer` made about it on `topic`.
+		/// Holders are in no particular order and may move between pages from one block to the next.
+		fn asset_holders(
+			asset_id: AssetId,
+			issuer: AccountId,
+			topic: Topic,
+			page: u32,
+			page_size: u32,
+		) -> (u32, Vec<Holder>);
+	}
+
+	/// The API to help wallets construct extrinsics
+	pub trait ExtrinsicApi<AccountId, Call, AssetId, Index, Hash> where
+		AccountId: Codec,
+		Call: Codec,
+		AssetId: Codec,
+		Index: Codec,
+		Hash: Codec,
+	{
+		/// Build an extrinsic of `call` from `sender`, paying its fee in `asset_id`, which only needs signing:
+		/// sign its signing payload for `index`, `era` and `era_block_hash`, then set the signature.
+		/// The fee exchange max. payment allows for prices moving against the quote by a small margin.
+		/// Returns the reason the fee could not be quoted otherwise.
+		fn extrinsic_template(
+			sender: AccountId,
+			call: Call,
+			asset_id: AssetId,
+		) -> Result<ExtrinsicTemplate<<Block as BlockT>::Extrinsic, Index, Hash>, Vec<u8>>;
+	}
+}
@@ -0,0 +1,206 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! CENNZnet SS58 addresses
+//!
+//! An address is the base58 encoding of a network prefix byte, the 32 byte account id and a 2 byte
+//! checksum. Each CENNZnet network has its own prefix, so an address formatted for one network is
+//! rejected by the other instead of funds being sent to it.
+
+use crate::AccountId;
+use base58::{FromBase58, ToBase58};
+use blake2_rfc::blake2b::Blake2b;
+use std::fmt;
+
+/// The SS58 prefix of CENNZnet mainnet addresses
+pub const MAINNET_SS58_PREFIX: u8 = 21;
+/// The SS58 prefix of CENNZnet testnet (Kauri, Rimu and development chain) addresses
+pub const TESTNET_SS58_PREFIX: u8 = 22;
+
+const ACCOUNT_ID_LENGTH: usize = 32;
+const CHECKSUM_LENGTH: usize = 2;
+
+/// A CENNZnet network with its own address format
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Network {
+	/// CENNZnet mainnet
+	Mainnet,
+	/// The CENNZnet testnets and development chains
+	Testnet,
+}
+
+impl Network {
+	/// The SS58 prefix of the network's addresses
+	pub fn ss58_prefix(self) -> u8 {
+		match self {
+			Network::Mainnet => MAINNET_SS58_PREFIX,
+			Network::Testnet => TESTNET_SS58_PREFIX,
+		}
+	}
+
+	/// The network using SS58 prefix `prefix`, if any
+	pub fn from_ss58_prefix(prefix: u8) -> Option<Self> {
+		match prefix {
+			MAINNET_SS58_PREFIX => Some(Network::Mainnet),
+			TESTNET_SS58_PREFIX => Some(Network::Testnet),
+			_ => None,
+		}
+	}
+}
+
+impl fmt::Display for Network {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Network::Mainnet => write!(f, "CENNZnet mainnet"),
+			Network::Testnet => write!(f, "CENNZnet testnet"),
+		}
+	}
+}
+
+/// Why an address could not be decoded
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AddressError {
+	/// The address is not base58
+	BadBase58,
+	/// The address does not hold an account id
+	BadLength,
+	/// The address checksum does not match, it is mistyped
+	BadChecksum,
+	/// The address is not for a CENNZnet network
+	UnknownPrefix(u8),
+	/// The address is for another CENNZnet network
+	WrongNetwork {
+		/// The network the address was expected for
+		expected: Network,
+		/// The network the address is for
+		found: Network,
+	},
+}
+
+impl fmt::Display for AddressError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			AddressError::BadBase58 => write!(f, "address is not valid base58"),
+			AddressError::BadLength => write!(f, "address has the wrong length for an account"),
+			AddressError::BadChecksum => write!(f, "address checksum does not match, check it for typos"),
+			AddressError::UnknownPrefix(prefix) => {
+				write!(f, "address prefix {} is not a CENNZnet network, it is for another chain", prefix)
+			}
+			AddressError::WrongNetwork { expected, found } => {
+				write!(f, "address is for {}, it can not be used on {}", found, expected)
+			}
+		}
+	}
+}
+
+impl std::error::Error for AddressError {}
+
+fn checksum(data: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+	let mut context = Blake2b::new(64);
+	context.update(b"SS58PRE");
+	context.update(data);
+	let hash = context.finalize();
+	let mut checksum = [0u8; CHECKSUM_LENGTH];
+	checksum.copy_from_slice(&hash.as_bytes()[..CHECKSUM_LENGTH]);
+	checksum
+}
+
+/// Format `account` as an address of `network`
+pub fn encode_address(account: &AccountId, network: Network) -> String {
+	let mut data = Vec::with_capacity(1 + ACCOUNT_ID_LENGTH + CHECKSUM_LENGTH);
+	data.push(network.ss58_prefix());
+	data.extend_from_slice(account.as_ref());
+	let checksum = checksum(&data);
+	data.extend_from_slice(&checksum);
+	data.to_base58()
+}
+
+/// Decode an address of any CENNZnet network, returning its network and account id
+pub fn decode_any_address(address: &str) -> Result<(Network, AccountId), AddressError> {
+	let data = address.from_base58().map_err(|_| AddressError::BadBase58)?;
+	if data.len() != 1 + ACCOUNT_ID_LENGTH + CHECKSUM_LENGTH {
+		return Err(AddressError::BadLength);
+	}
+	let (payload, checksum_bytes) = data.split_at(1 + ACCOUNT_ID_LENGTH);
+	if checksum(payload) != checksum_bytes {
+		return Err(AddressError::BadChecksum);
+	}
+	let network = Network::from_ss58_prefix(payload[0]).ok_or(AddressError::UnknownPrefix(payload[0]))?;
+	let mut account = [0u8; ACCOUNT_ID_LENGTH];
+	account.copy_from_slice(&payload[1..]);
+	Ok((network, AccountId::from_raw(account)))
+}
+
+/// Decode an address, which must be for `network`
+pub fn decode_address(address: &str, network: Network) -> Result<AccountId, AddressError> {
+	match decode_any_address(address)? {
+		(found, account) if found == network => Ok(account),
+		(found, _) => Err(AddressError::WrongNetwork { expected: network, found }),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn address_round_trips_on_its_network_only() {
+		let account = AccountId::from_raw([7u8; 32]);
+		let mainnet_address = encode_address(&account, Network::Mainnet);
+		let testnet_address = encode_address(&account, Network::Testnet);
+		assert_ne!(mainnet_address, testnet_address);
+
+		assert_eq!(decode_address(&mainnet_address, Network::Mainnet), Ok(account.clone()));
+		assert_eq!(decode_address(&testnet_address, Network::Testnet), Ok(account.clone()));
+		assert_eq!(
+			decode_address(&testnet_address, Network::Mainnet),
+			Err(AddressError::WrongNetwork {
+				expected: Network::Mainnet,
+				found: Network::Testnet
+			})
+		);
+		assert_eq!(decode_any_address(&mainnet_address), Ok((Network::Mainnet, account)));
+	}
+
+	#[test]
+	fn generic_substrate_addresses_are_rejected() {
+		// Alice on the CENNZnet testnets and on generic Substrate chains
+		let address = "3H7kkjQBQB33tvAi3D1wGCBw9QM18khQbqcttikMYBNaHimY";
+		let account = decode_address(address, Network::Testnet).unwrap();
+		assert_eq!(encode_address(&account, Network::Testnet), address);
+		assert_eq!(
+			decode_any_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+			Err(AddressError::UnknownPrefix(42))
+		);
+	}
+
+	#[test]
+	fn malformed_addresses_are_rejected() {
+		let account = AccountId::from_raw([7u8; 32]);
+		let address = encode_address(&account, Network::Testnet);
+
+		assert_eq!(decode_any_address("0OIl"), Err(AddressError::BadBase58));
+		assert_eq!(decode_any_address(&address[..address.len() - 4]), Err(AddressError::BadLength));
+		let mut data = address.from_base58().unwrap();
+		data[5] ^= 1;
+		assert_eq!(decode_any_address(&data.to_base58()), Err(AddressError::BadChecksum));
+
+		let mut data = vec![2u8];
+		data.extend_from_slice(account.as_ref());
+		let checksum = checksum(&data);
+		data.extend_from_slice(&checksum);
+		assert_eq!(decode_any_address(&data.to_base58()), Err(AddressError::UnknownPrefix(2)));
+	}
+}
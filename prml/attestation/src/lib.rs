@@ -60,8 +60,8 @@ pub trait Trait: system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 }
 
-type AttestationTopic = U256;
-type AttestationValue = U256;
+pub type AttestationTopic = U256;
+pub type AttestationValue = U256;
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
@@ -86,8 +86,15 @@ decl_module! {
 		/// Remove a claim, only the original issuer can remove a claim
 		pub fn remove_claim(origin, holder: T::AccountId, topic: AttestationTopic) -> Result {
 			let issuer = ensure_signed(origin)?;
-			<Issuers<T>>::mutate(&holder,|issuers| issuers.retain(|vec_issuer| *vec_issuer != issuer));
-			<Topics<T>>::mutate((holder.clone(), issuer.clone()),|topics| topics.retain(|vec_topic| *vec_topic != topic));
+			let mut topics = <Topics<T>>::get((holder.clone(), issuer.clone()));
+			topics.retain(|vec_topic| *vec_topic != topic);
+			// Only forget the issuer once it has no claims left about the holder
+			if topics.is_empty() {
+				<Issuers<T>>::mutate(&holder, |issuers| issuers.retain(|vec_issuer| *vec_issuer != issuer));
+				<Topics<T>>::remove((holder.clone(), issuer.clone()));
+			} else {
+				<Topics<T>>::insert((holder.clone(), issuer.clone()), topics);
+			}
 			<Values<T>>::remove((holder.clone(), issuer.clone(), topic));
 
 			Self::deposit_event(RawEvent::ClaimRemoved(holder, issuer, topic));
@@ -127,6 +134,28 @@ decl_storage! {
 }
 
 impl<T: Trait> Module<T> {
+	/// Get the value of the claim `issuer` made about `holder` on `topic`, if any.
+	pub fn claim(holder: &T::AccountId, issuer: &T::AccountId, topic: AttestationTopic) -> Option<AttestationValue> {
+		let key = (holder.clone(), issuer.clone());
+		if <Topics<T>>::get(&key).contains(&topic) {
+			Some(<Values<T>>::get((key.0, key.1, topic)))
+		} else {
+			None
+		}
+	}
+
+	/// Get every claim made about `holder` as `(issuer, topic, value)`, ordered by issuer then topic as set.
+	pub fn claims(holder: &T::AccountId) -> Vec<(T::AccountId, AttestationTopic, AttestationValue)> {
+		let mut claims = Vec::new();
+		for issuer in <Issuers<T>>::get(holder) {
+			for topic in <Topics<T>>::get((holder.clone(), issuer.clone())) {
+				let value = <Values<T>>::get((holder.clone(), issuer.clone(), topic));
+				claims.push((issuer.clone(), topic, value));
+			}
+		}
+		claims
+	}
+
 	fn create_claim(
 		holder: T::AccountId,
 		issuer: T::AccountId,
@@ -18,15 +18,17 @@
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use parity_codec::Codec;
+use parity_codec::{Codec, Decode, Encode};
+use rstd::{prelude::*, result};
 use runtime_primitives::traits::{As, CheckedAdd, CheckedSub, MaybeDebug, Zero};
+use runtime_primitives::Permill;
 use support::{
 	additional_traits::ChargeFee,
 	decl_event, decl_fee, decl_module, decl_storage,
 	dispatch::Result,
 	for_each_tuple,
 	traits::{Currency, ExistenceRequirement, WithdrawReason},
-	StorageMap,
+	Parameter, StorageMap,
 };
 use system;
 
@@ -71,24 +73,50 @@ macro_rules! impl_fee_charged {
 
 for_each_tuple!(impl_fee_charged);
 
+/// The number of recent blocks whose charged fees are kept for fee suggestions
+pub const FEE_HISTORY_BLOCKS: u32 = 50;
+
 pub type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
+/// The asset sold to buy fee asset
+pub type ExchangeAssetOf<T> =
+	<<T as Trait>::BuyFeeAsset as BuyFeeAsset<<T as system::Trait>::AccountId, BalanceOf<T>>>::AssetId;
+
 /// A trait which enables buying some fee asset using another asset.
 /// It is targeted at the CENNZX Spot exchange and the CennznetExtrinsic format.
 pub trait BuyFeeAsset<AccountId, Balance> {
 	type FeeExchange;
+	/// The id of an asset sold to buy fee asset
+	type AssetId: Parameter;
 	/// Buy `amount` of fee asset for `who` using asset info from `fee_exchange.
+	/// Returns the asset sold and the amount of it sold.
 	/// Note: It does not charge the fee asset, that is left to a `ChargeFee` implementation
-	fn buy_fee_asset(who: &AccountId, amount: Balance, fee_exchange: &Self::FeeExchange) -> Result;
+	fn buy_fee_asset(
+		who: &AccountId,
+		amount: Balance,
+		fee_exchange: &Self::FeeExchange,
+	) -> result::Result<(Self::AssetId, Balance), &'static str>;
 }
 
-impl<AccountId, Balance> BuyFeeAsset<AccountId, Balance> for () {
+impl<AccountId, Balance: Default> BuyFeeAsset<AccountId, Balance> for () {
 	type FeeExchange = ();
-	fn buy_fee_asset(_: &AccountId, _: Balance, _: &Self::FeeExchange) -> Result {
-		Ok(())
+	type AssetId = ();
+	fn buy_fee_asset(_: &AccountId, _: Balance, _: &Self::FeeExchange) -> result::Result<((), Balance), &'static str> {
+		Ok(((), Default::default()))
 	}
 }
 
+/// The fee charged by an extrinsic, as kept in the fee history
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FeeRecord<Balance, AssetId> {
+	/// The fee charged
+	pub fee: Balance,
+	/// The fee exchange which bought the fee asset, if any: (asset sold, amount sold, fee asset bought).
+	/// The amount sold over the fee asset bought is the exchange rate paid.
+	pub exchange: Option<(AssetId, Balance, Balance)>,
+}
+
 pub trait Trait: system::Trait {
 	/// The overarching event type.
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
@@ -114,19 +142,26 @@ decl_module! {
 			let extrinsic_count = <system::Module<T>>::extrinsic_count();
 			// the accumulated fee of the whole block
 			let mut block_fee = <AssetOf<T>>::sa(0);
+			let mut fees = Vec::new();
 			(0..extrinsic_count).for_each(|index| {
 				// Deposit `Charged` event if some amount of fee charged.
 				let fee = <CurrentTransactionFee<T>>::take(index);
+				let exchange = <CurrentFeeExchange<T>>::take(index);
 				if !fee.is_zero() {
 					block_fee += fee;
+					fees.push(FeeRecord { fee, exchange });
 					Self::deposit_event(RawEvent::Charged(index, fee));
 				}
 			});
 			T::OnFeeCharged::on_fee_charged(&block_fee);
+
+			// Overwrite the fees of the block which just left the history window
+			let slot = (<system::Module<T>>::block_number().as_() % u64::from(FEE_HISTORY_BLOCKS)) as u32;
+			<FeeHistory<T>>::insert(slot, fees);
 		}
 
 		/// Set a new associated cost for the given fee type
-		fn set_fee(fee: T::Fee, new_amount: AssetOf<T>) {
+		pub fn set_fee(fee: T::Fee, new_amount: AssetOf<T>) {
 			FeeRegistry::<T>::mutate(fee, |amount| *amount = new_amount);
 	}
 }
@@ -153,6 +188,44 @@ decl_storage! {
 		///
 		/// All records should be removed at finalise stage.
 		CurrentTransactionFee get(current_transaction_fee): map u32 => AssetOf<T>;
+
+		/// The fee exchange of each extrinsic which bought its fee asset: extrinsic index => (asset sold,
+		/// amount sold, fee asset bought). All records are removed at finalise stage.
+		CurrentFeeExchange get(current_fee_exchange): map u32 => Option<(ExchangeAssetOf<T>, AssetOf<T>, AssetOf<T>)>;
+
+		/// The fees charged by the extrinsics of the last `FEE_HISTORY_BLOCKS` blocks, with the fee exchange
+		/// each paid them with, keyed by block number modulo `FEE_HISTORY_BLOCKS`.
+		FeeHistory get(fee_history): map u32 => Vec<FeeRecord<AssetOf<T>, ExchangeAssetOf<T>>>;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Get the fee at each of `percentiles` of the fees charged over the last `FEE_HISTORY_BLOCKS` blocks,
+	/// e.g. for wallets to suggest a maximum fee payment. Zero when no fees have been charged.
+	pub fn fee_percentiles(percentiles: &[Permill]) -> Vec<AssetOf<T>> {
+		let mut fees: Vec<AssetOf<T>> = (0..FEE_HISTORY_BLOCKS)
+			.flat_map(Self::fee_history)
+			.map(|record| record.fee)
+			.collect();
+		if fees.is_empty() {
+			return percentiles.iter().map(|_| Zero::zero()).collect();
+		}
+		fees.sort();
+		let last = (fees.len() - 1) as u64;
+		percentiles.iter().map(|p| fees[(*p * last) as usize]).collect()
+	}
+
+	/// Buy `amount` of fee asset for `who` with `fee_exchange`, recording the exchange in the fee history
+	/// of the current extrinsic.
+	pub fn buy_fee_asset(
+		who: &T::AccountId,
+		amount: AssetOf<T>,
+		fee_exchange: &<T::BuyFeeAsset as BuyFeeAsset<T::AccountId, AssetOf<T>>>::FeeExchange,
+	) -> Result {
+		let extrinsic_index = <system::Module<T>>::extrinsic_index().ok_or_else(|| "no extrinsic index found")?;
+		let (asset_id, sold) = T::BuyFeeAsset::buy_fee_asset(who, amount, fee_exchange)?;
+		<CurrentFeeExchange<T>>::insert(extrinsic_index, (asset_id, sold, amount));
+		Ok(())
 	}
 }
 
@@ -17,7 +17,7 @@
 //! Test utilities
 #![cfg(test)]
 
-use crate::{system, GenesisConfig, Module, OnFeeCharged, Trait};
+use crate::{system, BuyFeeAsset, GenesisConfig, Module, OnFeeCharged, Trait};
 use generic_asset::SpendingAssetCurrency;
 use parity_codec::{Decode, Encode};
 use primitives::{Blake2Hasher, H256};
@@ -83,6 +83,7 @@ impl generic_asset::Trait for Test {
 	type Balance = u64;
 	type AssetId = u32;
 	type Event = TestEvent;
+	type OnTransferPayload = ();
 }
 
 pub trait OnFeeChargedMockTrait: system::Trait {}
@@ -107,11 +108,22 @@ impl<T: OnFeeChargedMockTrait> OnFeeCharged<u64> for OnFeeChargedMockModule<T> {
 
 impl OnFeeChargedMockTrait for Test {}
 
+/// Buys fee asset with the asset id given as the fee exchange, selling twice the amount bought
+pub struct BuyFeeAssetMock;
+
+impl BuyFeeAsset<u64, u64> for BuyFeeAssetMock {
+	type FeeExchange = u32;
+	type AssetId = u32;
+	fn buy_fee_asset(_: &u64, amount: u64, asset_id: &u32) -> rstd::result::Result<(u32, u64), &'static str> {
+		Ok((*asset_id, 2 * amount))
+	}
+}
+
 impl Trait for Test {
 	type Event = TestEvent;
 	type Currency = SpendingAssetCurrency<Test>;
 	type OnFeeCharged = OnFeeChargedMock;
-	type BuyFeeAsset = ();
+	type BuyFeeAsset = BuyFeeAssetMock;
 	type Fee = MockFee;
 }
 
@@ -141,6 +153,8 @@ impl ExtBuilder {
 			spending_asset_id: 16_001,
 			assets: vec![16_001],
 			endowed_accounts: vec![0],
+			endowments: vec![],
+			genesis_locks: vec![],
 			create_asset_stake: 10,
 			initial_balance: u64::max_value(),
 			next_asset_id: 10_000,
@@ -119,3 +119,55 @@ fn on_finalize_should_work() {
 		assert_eq!(OnFeeChargedMock::amount(), 2);
 	});
 }
+
+#[test]
+fn fee_percentiles_should_use_recent_block_fees() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let percentiles = [Permill::from_percent(0), Permill::from_percent(50), Permill::from_percent(100)];
+		assert_eq!(Fees::fee_percentiles(&percentiles), vec![0, 0, 0]);
+
+		for (index, fee) in [4, 1, 3, 2, 5].iter().enumerate() {
+			System::set_extrinsic_index(index as u32);
+			assert_ok!(Fees::charge_fee(&0, *fee));
+			System::note_applied_extrinsic(&Ok(()), 1);
+		}
+		System::note_finished_extrinsics();
+		System::set_block_number(1);
+		<Fees as OnFinalize<u64>>::on_finalize(1);
+		assert_eq!(Fees::fee_percentiles(&percentiles), vec![1, 3, 5]);
+
+		// The block's fees are dropped once it leaves the history window
+		System::set_block_number(1 + u64::from(FEE_HISTORY_BLOCKS));
+		<Fees as OnFinalize<u64>>::on_finalize(1 + u64::from(FEE_HISTORY_BLOCKS));
+		assert_eq!(Fees::fee_percentiles(&percentiles), vec![0, 0, 0]);
+	});
+}
+
+#[test]
+fn fee_history_should_record_the_fee_exchange() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		System::set_extrinsic_index(0);
+		assert_ok!(Fees::charge_fee(&0, 3));
+		System::note_applied_extrinsic(&Ok(()), 1);
+		System::set_extrinsic_index(1);
+		assert_ok!(Fees::buy_fee_asset(&0, 5, &16_000));
+		assert_eq!(Fees::current_fee_exchange(1), Some((16_000, 10, 5)));
+		assert_ok!(Fees::charge_fee(&0, 5));
+		System::note_applied_extrinsic(&Ok(()), 1);
+		System::note_finished_extrinsics();
+		System::set_block_number(1);
+		<Fees as OnFinalize<u64>>::on_finalize(1);
+
+		assert_eq!(Fees::current_fee_exchange(1), None);
+		assert_eq!(
+			Fees::fee_history(1),
+			vec![
+				FeeRecord { fee: 3, exchange: None },
+				FeeRecord {
+					fee: 5,
+					exchange: Some((16_000, 10, 5))
+				},
+			]
+		);
+	});
+}
@@ -0,0 +1,193 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Benchmarks of the generic asset calls against their weights
+//!
+//! Run with `cargo test -p prml-generic-asset --release benchmarks -- --ignored`.
+//! Each benchmark times a call as its locks, recipients or entries grow and compares its time per unit of weight
+//! with that of a plain transfer. A call whose time per unit of weight exceeds the transfer's by more than
+//! `MAX_TIME_PER_WEIGHT_RATIO` is underweight, and the benchmark fails until its weight in `weights.rs` is raised.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{ExtBuilder, GenericAsset, Origin, Test};
+use runtime_io::with_externalities;
+use std::time::Instant;
+use support::assert_ok;
+use support::traits::{LockableCurrency, WithdrawReasons};
+
+/// The number of times each call is timed
+const RUNS: u32 = 100;
+
+/// The most a call's time per unit of weight may exceed a plain transfer's
+const MAX_TIME_PER_WEIGHT_RATIO: u128 = 3;
+
+/// The asset created for minting and burning
+const ASSET_ID: u32 = 1000;
+
+/// Time `RUNS` calls, returning the average time per unit of `weight` in nanoseconds
+fn time_per_weight<F: FnMut()>(weight: Weight, mut call: F) -> u128 {
+	let start = Instant::now();
+	for _ in 0..RUNS {
+		call();
+	}
+	start.elapsed().as_nanos() / u128::from(RUNS) / u128::from(weight.max(1))
+}
+
+/// The time per unit of weight of a plain transfer, the reference every call is compared with
+fn reference_time_per_weight() -> u128 {
+	with_externalities(&mut new_bench_ext(), || {
+		let weight = GenericAsset::call_weight(&1, &Call::<Test>::transfer(16000, 2, 1));
+		time_per_weight(weight, || {
+			assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 1));
+		})
+	})
+}
+
+/// Time `RUNS` calls and fail if the call is underweight compared with a plain transfer
+fn bench<F: FnMut()>(name: &str, reference: u128, weight: Weight, call: F) {
+	let measured = time_per_weight(weight, call);
+	assert!(
+		measured <= reference.max(1) * MAX_TIME_PER_WEIGHT_RATIO,
+		"{} is underweight: {} ns per unit of weight {}, a transfer takes {} ns",
+		name,
+		measured,
+		weight,
+		reference
+	);
+}
+
+fn new_bench_ext() -> runtime_io::TestExternalities<primitives::Blake2Hasher> {
+	ExtBuilder::default().free_balance((16000, 1, u64::max_value() / 2)).build()
+}
+
+/// Create `ASSET_ID` with every permission held by account 1
+fn create_asset() {
+	assert_ok!(GenericAsset::create(
+		Origin::signed(1),
+		AssetOptions {
+			initial_issuance: u64::max_value() / 2,
+			permissions: PermissionLatest {
+				update: Owner::Address(1),
+				mint: Owner::Address(1),
+				burn: Owner::Address(1),
+				freeze: Owner::None,
+			},
+		}
+	));
+}
+
+#[test]
+#[ignore]
+fn benchmarks_create() {
+	let reference = reference_time_per_weight();
+	with_externalities(&mut new_bench_ext(), || {
+		let call = Call::<Test>::create(AssetOptions {
+			initial_issuance: 100,
+			permissions: Default::default(),
+		});
+		assert_ok!(GenericAsset::set_max_asset_creations_per_block(RUNS));
+		bench("create", reference, GenericAsset::call_weight(&1, &call), create_asset);
+	});
+}
+
+#[test]
+#[ignore]
+fn benchmarks_transfer_by_locks() {
+	let reference = reference_time_per_weight();
+	for locks in [0u8, 10, 50].iter() {
+		with_externalities(&mut new_bench_ext(), || {
+			for i in 0..*locks {
+				let id = [b'b', b'e', b'n', b'c', b'h', b' ', b' ', i];
+				StakingAssetCurrency::<Test>::set_lock(id, &1, 10, u64::max_value(), WithdrawReasons::all());
+			}
+			let call = Call::<Test>::transfer(16000, 2, 1);
+			let name = format!("transfer with {} locks", locks);
+			bench(&name, reference, GenericAsset::call_weight(&1, &call), || {
+				assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 1));
+			});
+		});
+	}
+}
+
+#[test]
+#[ignore]
+fn benchmarks_transfer_batch_by_recipients() {
+	let reference = reference_time_per_weight();
+	for recipients in [1u64, 10, 100].iter() {
+		with_externalities(&mut new_bench_ext(), || {
+			let transfers: Vec<(u64, u64)> = (0..*recipients).map(|i| (i + 2, 1)).collect();
+			let call = Call::<Test>::transfer_batch(16000, transfers.clone());
+			let name = format!("transfer_batch to {} recipients", recipients);
+			bench(&name, reference, GenericAsset::call_weight(&1, &call), || {
+				assert_ok!(GenericAsset::transfer_batch(Origin::signed(1), 16000, transfers.clone()));
+			});
+		});
+	}
+}
+
+#[test]
+#[ignore]
+fn benchmarks_mint_and_burn() {
+	let reference = reference_time_per_weight();
+	with_externalities(&mut new_bench_ext(), || {
+		create_asset();
+		let call = Call::<Test>::mint(ASSET_ID, 2, 1);
+		bench("mint", reference, GenericAsset::call_weight(&1, &call), || {
+			assert_ok!(GenericAsset::mint(Origin::signed(1), ASSET_ID, 2, 1));
+		});
+		let call = Call::<Test>::burn(ASSET_ID, 2, 1);
+		bench("burn", reference, GenericAsset::call_weight(&1, &call), || {
+			assert_ok!(GenericAsset::burn(Origin::signed(1), ASSET_ID, 2, 1));
+		});
+	});
+}
+
+#[test]
+#[ignore]
+fn benchmarks_mint_batch_by_entries() {
+	let reference = reference_time_per_weight();
+	for entries in [1u64, 10, 100].iter() {
+		with_externalities(&mut new_bench_ext(), || {
+			create_asset();
+			let mints: Vec<(u64, u64)> = (0..*entries).map(|i| (i + 2, 1)).collect();
+			let call = Call::<Test>::mint_batch(ASSET_ID, mints.clone());
+			let name = format!("mint_batch to {} accounts", entries);
+			bench(&name, reference, GenericAsset::call_weight(&1, &call), || {
+				assert_ok!(GenericAsset::mint_batch(Origin::signed(1), ASSET_ID, mints.clone()));
+			});
+		});
+	}
+}
+
+#[test]
+#[ignore]
+fn benchmarks_update_permission() {
+	let reference = reference_time_per_weight();
+	with_externalities(&mut new_bench_ext(), || {
+		create_asset();
+		let permissions = PermissionLatest {
+			update: Owner::Address(1),
+			mint: Owner::Address(2),
+			burn: Owner::Address(2),
+			freeze: Owner::None,
+		};
+		let call = Call::<Test>::update_permission(ASSET_ID, permissions.clone());
+		bench("update_permission", reference, GenericAsset::call_weight(&1, &call), || {
+			assert_ok!(GenericAsset::update_permission(Origin::signed(1), ASSET_ID, permissions.clone()));
+		});
+	});
+}
@@ -79,7 +79,26 @@
 //! must have update permissions.
 //! - `mint`: Mint an asset, increases its total issuance. The origin of this call must have mint permissions.
 //! - `burn`: Burn an asset, decreases its total issuance. The origin of this call must have burn permissions.
-//! - `create_reserved`: Create a new kind of reserved asset. The origin of this call must be root.
+//! - `create_reserved`: Create a new kind of reserved asset, crediting its initial issuance to a beneficiary.
+//! The origin of this call must be root.
+//! - `freeze_asset`: Freeze an asset, halting its transfers, minting, burning and reserving. The origin of this call
+//! must have freeze permissions.
+//! - `thaw_asset`: Thaw a frozen asset. The origin of this call must have freeze permissions.
+//! - `freeze_account`: Freeze an account's balance of an asset, so it can neither send nor receive the asset.
+//! The origin of this call must have freeze permissions.
+//! - `unfreeze_account`: Unfreeze an account's balance of an asset. The origin of this call must have freeze
+//! permissions.
+//! - `set_create_asset_stake`, `set_staking_asset_id`, `set_spending_asset_id`: Set the module parameters.
+//! The origin of these calls must be root.
+//! - `transfer_locked`: Transfer some balance to another account on condition that it is claimed with the
+//! preimage of a hashlock before a timelock, e.g. as one leg of a cross-chain atomic swap. The hashlock may be
+//! a Blake2-256, SHA-256 or Keccak-256 hash, so it can be shared with a Bitcoin or Ethereum HTLC.
+//! - `claim_locked_transfer`, `refund_locked_transfer`: Complete a locked transfer with its preimage, or return
+//! it to the sender once its timelock has passed.
+//! - `migrate_storage`: Migrate the module's storage to the latest layout, with the balances of the listed
+//! accounts. Enacted with a runtime upgrade changing the layout. The origin of this call must be root.
+//!
+//! A call failing for lack of permission emits `PermissionDenied` with the asset, account and permission.
 //!
 //! ### Public Functions
 //!
@@ -91,6 +110,8 @@
 //! This will not emit the `Transferred` event.
 //! - `make_transfer_with_event`: Transfer some liquid free balance from one account to another.
 //! This will emit the `Transferred` event.
+//! - `make_module_transfer`: Transfer some liquid free balance between accounts on behalf of a runtime module.
+//! This will emit the `ModuleTransfer` event.
 //! - `reserve`: Moves an amount from free balance to reserved balance.
 //! - `unreserve`: Move up to an amount from reserved balance to free balance. This function cannot fail.
 //! - `slash`: Deduct up to an amount from the combined balance of `who`, preferring to deduct from the
@@ -99,9 +120,10 @@
 //! - `slash_reserved`: Deduct up to an amount from reserved balance of an account. This function cannot fail.
 //! - `repatriate_reserved`: Move up to an amount from reserved balance of an account to free balance of another
 //! account.
-//! - `check_permission`: Check permission to perform burn, mint or update.
+//! - `check_permission`: Check permission to perform burn, mint, update or freeze.
 //! - `ensure_can_withdraw`: Check if the account is able to make a withdrawal of the given amount
 //!	for the given reason.
+//! - `migrate`: Migrate the module's storage to the latest layout. Run by the root `migrate_storage` call.
 //!
 //! ### Usage
 //!
@@ -152,10 +174,12 @@
 use parity_codec::{Decode, Encode, HasCompact};
 
 use runtime_primitives::traits::{
-	CheckedAdd, CheckedSub, MaybeSerializeDebug, Member, One, Saturating, SimpleArithmetic, Zero,
+	As, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDebug, Member, One, Saturating, SimpleArithmetic, Zero,
 };
+use runtime_primitives::Permill;
 
 use rstd::prelude::*;
+use rstd::collections::btree_map::BTreeMap;
 use rstd::{cmp, result, convert::TryInto};
 use support::dispatch::Result;
 use support::{
@@ -166,12 +190,17 @@ use support::{
 	},
 	Parameter, StorageDoubleMap, StorageMap, StorageValue,
 };
+use sha2::{Digest, Sha256};
 use system::ensure_signed;
 
+mod benchmarks;
+mod migration;
 mod mock;
 mod tests;
+pub mod weights;
 
 pub use self::imbalances::{NegativeImbalance, PositiveImbalance};
+pub use self::weights::Weight;
 
 pub trait Trait: system::Trait {
 	type Balance: Parameter
@@ -182,6 +211,23 @@ pub trait Trait: system::Trait {
 		+ MaybeSerializeDebug + From<u32> + TryInto<u128>;
 	type AssetId: Parameter + Member + SimpleArithmetic + Default + Copy + From<u32> + Into<u64>;
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	/// Receives the payloads of `transfer_with_payload` calls.
+	type OnTransferPayload: OnTransferPayload<Self::AccountId, Self::AssetId, Self::Balance>;
+}
+
+/// A handler of payloads sent along with transfers, e.g. to notify a merchant module or contract of a payment
+pub trait OnTransferPayload<AccountId, AssetId, Balance> {
+	/// Whether `who` has a handler registered for transfer payloads
+	fn has_handler(who: &AccountId) -> bool;
+	/// Deliver the `payload` of a completed transfer of `amount` from `from` to `to`
+	fn on_transfer_payload(asset_id: &AssetId, from: &AccountId, to: &AccountId, amount: Balance, payload: &[u8]);
+}
+
+impl<AccountId, AssetId, Balance> OnTransferPayload<AccountId, AssetId, Balance> for () {
+	fn has_handler(_: &AccountId) -> bool {
+		false
+	}
+	fn on_transfer_payload(_: &AssetId, _: &AccountId, _: &AccountId, _: Balance, _: &[u8]) {}
 }
 
 pub trait Subtrait: system::Trait {
@@ -238,29 +284,74 @@ pub struct PermissionsV1<AccountId> {
 	pub burn: Owner<AccountId>,
 }
 
+/// Asset permissions, with an owner who can freeze the asset
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq, Eq)]
+pub struct PermissionsV2<AccountId> {
+	/// Who have permission to update asset permission
+	pub update: Owner<AccountId>,
+	/// Who have permission to mint new asset
+	pub mint: Owner<AccountId>,
+	/// Who have permission to burn asset
+	pub burn: Owner<AccountId>,
+	/// Who have permission to freeze and thaw asset
+	pub freeze: Owner<AccountId>,
+}
+
 /// Versioned asset permission
 #[cfg_attr(feature = "std", derive(Debug))]
 #[derive(Clone, Encode, Decode, PartialEq, Eq)]
 pub enum PermissionVersions<AccountId> {
 	V1(PermissionsV1<AccountId>),
+	V2(PermissionsV2<AccountId>),
+}
+
+/// Versioned layout of the module's storage.
+/// A chain's storage is migrated one version at a time to the latest layout by the root `migrate_storage` call.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StorageLayout {
+	/// Storage from before its layout was versioned
+	Unversioned,
+	/// The layout when versioning was introduced
+	V1,
+	/// Free and reserved balances and frozen amounts are kept together in `AccountStore`
+	V2,
+	/// The holders of each asset are indexed in `HolderCount`, `HolderAt` and `HolderPosition`
+	V3,
+}
+
+impl StorageLayout {
+	/// The layout the module's code reads and writes
+	pub const LATEST: StorageLayout = StorageLayout::V3;
+}
+
+impl Default for StorageLayout {
+	fn default() -> Self {
+		StorageLayout::Unversioned
+	}
 }
 
 /// Asset permission types
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
 pub enum PermissionType {
-	/// Permission to update asset permission
+	/// Permission to burn asset
 	Burn,
 	/// Permission to mint new asset
 	Mint,
-	/// Permission to burn asset
+	/// Permission to update asset permission
 	Update,
+	/// Permission to freeze and thaw asset
+	Freeze,
 }
 
 /// Alias to latest asset permissions
-pub type PermissionLatest<AccountId> = PermissionsV1<AccountId>;
+pub type PermissionLatest<AccountId> = PermissionsV2<AccountId>;
 
 impl<AccountId> Default for PermissionVersions<AccountId> {
 	fn default() -> Self {
-		PermissionVersions::V1(Default::default())
+		PermissionVersions::V2(Default::default())
 	}
 }
 
@@ -274,10 +365,44 @@ impl<AccountId> Default for PermissionsV1<AccountId> {
 	}
 }
 
+impl<AccountId> Default for PermissionsV2<AccountId> {
+	fn default() -> Self {
+		PermissionsV2 {
+			update: Owner::None,
+			mint: Owner::None,
+			burn: Owner::None,
+			freeze: Owner::None,
+		}
+	}
+}
+
+impl<AccountId> PermissionsV2<AccountId> {
+	/// Whether no account holds any of the permissions
+	pub fn is_empty(&self) -> bool {
+		match (&self.update, &self.mint, &self.burn, &self.freeze) {
+			(Owner::None, Owner::None, Owner::None, Owner::None) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Assets created before `freeze` existed have no freeze owner, the update owner may grant it.
+impl<AccountId> From<PermissionsV1<AccountId>> for PermissionsV2<AccountId> {
+	fn from(v1: PermissionsV1<AccountId>) -> Self {
+		PermissionsV2 {
+			update: v1.update,
+			mint: v1.mint,
+			burn: v1.burn,
+			freeze: Owner::None,
+		}
+	}
+}
+
 impl<AccountId> Into<PermissionLatest<AccountId>> for PermissionVersions<AccountId> {
 	fn into(self) -> PermissionLatest<AccountId> {
 		match self {
-			PermissionVersions::V1(v1) => v1,
+			PermissionVersions::V1(v1) => v1.into(),
+			PermissionVersions::V2(v2) => v2,
 		}
 	}
 }
@@ -285,7 +410,7 @@ impl<AccountId> Into<PermissionLatest<AccountId>> for PermissionVersions<Account
 /// Converts the latest permission to other version.
 impl<AccountId> Into<PermissionVersions<AccountId>> for PermissionLatest<AccountId> {
 	fn into(self) -> PermissionVersions<AccountId> {
-		PermissionVersions::V1(self)
+		PermissionVersions::V2(self)
 	}
 }
 
@@ -293,6 +418,8 @@ decl_fee!(
 	pub enum Fee {
 		/// A fee for creating a new account
 		Transfer,
+		/// A fee per unit of call weight
+		PerWeight,
 	}
 );
 
@@ -300,27 +427,22 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		fn deposit_event<T>() = default;
 
+		fn on_initialize(n: T::BlockNumber) {
+			<SupplyDeltas<T>>::kill();
+			<AssetCreations<T>>::kill();
+			for (asset_id, who) in <AccountFreezesExpiring<T>>::take(n) {
+				<FrozenAccounts<T>>::remove(&asset_id, &who);
+				Self::deposit_event(RawEvent::AccountFreezeExpired(asset_id, who));
+			}
+			for transfer_id in <ScheduledTransfersDue<T>>::take(n) {
+				Self::execute_scheduled_transfer(transfer_id);
+			}
+		}
+
 		/// Create a new kind of asset.
 		fn create(origin, options: AssetOptions<T::Balance, T::AccountId>) -> Result {
 			let origin = ensure_signed(origin)?;
-			let id = Self::next_asset_id();
-
-			let permissions: PermissionVersions<T::AccountId> = options.permissions.clone().into();
-
-			// The last available id serves as the overflow mark and won't be used.
-			let next_id = id.checked_add(&One::one()).ok_or_else(||"No new assets id available.")?;
-
-			// Force to reserve cennz.
-			Self::reserve(&Self::staking_asset_id(), &origin, Self::create_asset_stake())?;
-
-			<NextAssetId<T>>::put(next_id);
-			<TotalIssuance<T>>::insert(id, &options.initial_issuance);
-			<FreeBalance<T>>::insert(&id, &origin, options.initial_issuance);
-			<Permissions<T>>::insert(&id, permissions);
-
-			Self::deposit_event(RawEvent::Created(id, origin, options));
-
-			Ok(())
+			Self::create_staked(&origin, &origin, options).map(|_| ())
 		}
 
 		/// Transfer some liquid free balance to another account.
@@ -339,41 +461,53 @@ decl_module! {
 		) -> Result {
 			let origin = ensure_signed(origin)?;
 
+			ensure!(
+				Self::keeps_renounced(&asset_id, &new_permission),
+				"A renounced permission can not be restored."
+			);
 			let permissions: PermissionVersions<T::AccountId> = new_permission.into();
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Update,
+				"Origin does not have enough permission to update permissions.",
+			)?;
 
-			if Self::check_permission(&asset_id, &origin, &PermissionType::Update) {
-				<Permissions<T>>::insert(asset_id, &permissions);
+			<Permissions<T>>::insert(asset_id, &permissions);
 
-				Self::deposit_event(RawEvent::PermissionUpdated(asset_id, permissions.into()));
+			Self::deposit_event(RawEvent::PermissionUpdated(asset_id, permissions.into()));
 
-				Ok(())
-			} else {
-				return Err("Origin does not have enough permission to update permissions.");
-			}
+			Ok(())
 		}
 
 		/// Mints an asset, increases its total issuance.
 		/// The origin must have `mint` permissions.
 		fn mint(origin, #[compact] asset_id: T::AssetId, to: T::AccountId, amount: T::Balance) -> Result {
 			let origin = ensure_signed(origin)?;
-			if Self::check_permission(&asset_id, &origin, &PermissionType::Mint) {
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Mint,
+				"The origin does not have permission to mint an asset, Permission error.",
+			)?;
+			Self::ensure_not_frozen(&asset_id)?;
+			Self::ensure_account_not_frozen(&asset_id, &to)?;
 
-				let original_free_balance = Self::free_balance(&asset_id, &to);
-				let current_total_issuance = <TotalIssuance<T>>::get(asset_id);
-				let new_total_issuance = current_total_issuance.checked_add(&amount)
-					.ok_or_else(|| "total_issuance got overflow after minting.")?;
-				let value = original_free_balance.checked_add(&amount)
-					.ok_or_else(|| "free balance got overflow after minting.")?;
+			let original_free_balance = Self::free_balance(&asset_id, &to);
+			let current_total_issuance = <TotalIssuance<T>>::get(asset_id);
+			let new_total_issuance = current_total_issuance.checked_add(&amount)
+				.ok_or_else(|| "total_issuance got overflow after minting.")?;
+			let value = original_free_balance.checked_add(&amount)
+				.ok_or_else(|| "free balance got overflow after minting.")?;
 
-				<TotalIssuance<T>>::insert(asset_id, new_total_issuance);
-				Self::set_free_balance(&asset_id, &to, value);
+			<TotalIssuance<T>>::insert(asset_id, new_total_issuance);
+			Self::note_supply_change(&asset_id, amount, Zero::zero());
+			Self::set_free_balance(&asset_id, &to, value);
+			Self::record_mint_batch(&asset_id, &to, amount);
 
-				Self::deposit_event(RawEvent::Minted(asset_id, to, amount));
+			Self::deposit_event(RawEvent::Minted(asset_id, to, amount));
 
-				Ok(())
-			} else {
-				return Err("The origin does not have permission to mint an asset, Permission error.");
-			}
+			Ok(())
 		}
 
 		/// Burns an asset, decreases its total issuance.
@@ -381,206 +515,1631 @@ decl_module! {
 		fn burn(origin, #[compact] asset_id: T::AssetId, to: T::AccountId, amount: T::Balance) -> Result {
 			let origin = ensure_signed(origin)?;
 
-			if Self::check_permission(&asset_id, &origin, &PermissionType::Burn) {
-				let original_free_balance = Self::free_balance(&asset_id, &to);
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Burn,
+				"The origin does not have permission to burn an asset, Permission error.",
+			)?;
+			Self::ensure_not_frozen(&asset_id)?;
+			let original_free_balance = Self::free_balance(&asset_id, &to);
 
-				let current_total_issuance = <TotalIssuance<T>>::get(asset_id);
-				let new_total_issuance = current_total_issuance.checked_sub(&amount)
-					.ok_or_else(|| "total_issuance got underflow after burning")?;
-				let value = original_free_balance.checked_sub(&amount)
-					.ok_or_else(|| "free_balance got underflow after burning")?;
+			let current_total_issuance = <TotalIssuance<T>>::get(asset_id);
+			let new_total_issuance = current_total_issuance.checked_sub(&amount)
+				.ok_or_else(|| "total_issuance got underflow after burning")?;
+			let value = original_free_balance.checked_sub(&amount)
+				.ok_or_else(|| "free_balance got underflow after burning")?;
 
-				<TotalIssuance<T>>::insert(asset_id, new_total_issuance);
+			<TotalIssuance<T>>::insert(asset_id, new_total_issuance);
+			Self::note_supply_change(&asset_id, Zero::zero(), amount);
 
-				Self::set_free_balance(&asset_id, &to, value);
+			Self::set_free_balance(&asset_id, &to, value);
 
-				Self::deposit_event(RawEvent::Burned(asset_id, to, amount));
+			Self::deposit_event(RawEvent::Burned(asset_id, to, amount));
 
-				Ok(())
-			} else {
-				return Err("The origin does not have permission to burn an asset, Permission error.");
-			}
+			Ok(())
 		}
 
-		/// Can be used to create reserved tokens.
+		/// Can be used to create reserved tokens, the initial issuance is credited to `beneficiary`.
+		/// An asset without permissions can never be minted, burned or updated, so empty permissions
+		/// are refused unless `allow_empty_permissions` is set.
 		/// Requires Root call.
-		fn create_reserved(asset_id: T::AssetId, options: AssetOptions<T::Balance, T::AccountId>) -> Result {
-			Self::create_asset(Some(asset_id), None, options)
-		}
-	}
-}
+		fn create_reserved(
+			asset_id: T::AssetId,
+			beneficiary: T::AccountId,
+			options: AssetOptions<T::Balance, T::AccountId>,
+			allow_empty_permissions: bool
+		) -> Result {
+			ensure!(
+				allow_empty_permissions || !options.permissions.is_empty(),
+				"Reserved asset permissions are empty."
+			);
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Debug))]
-pub struct BalanceLock<Balance, BlockNumber> {
-	pub id: LockIdentifier,
-	pub amount: Balance,
-	pub until: BlockNumber,
-	pub reasons: WithdrawReasons,
-}
+			Self::create_asset(Some(asset_id), Some(beneficiary.clone()), options.clone())?;
 
-decl_storage! {
-	trait Store for Module<T: Trait> as GenericAsset {
-		/// Total issuance of a given asset.
-		pub TotalIssuance get(total_issuance) build(|config: &GenesisConfig<T>| {
-			let issuance = config.initial_balance * (config.endowed_accounts.len() as u32).into();
-			config.assets.iter().map(|id| (id.clone(), issuance)).collect::<Vec<_>>()
-		}): map T::AssetId => T::Balance;
+			Self::deposit_event(RawEvent::ReservedAssetCreated(asset_id, beneficiary, options));
 
-		/// The free balance of a given asset under an account.
-		pub FreeBalance: double_map T::AssetId, twox_128(T::AccountId) => T::Balance;
+			Ok(())
+		}
 
-		/// The reserved balance of a given asset under an account.
-		pub ReservedBalance: double_map T::AssetId, twox_128(T::AccountId) => T::Balance;
+		/// Transfer `schedule.locked` to another account, vesting under `schedule`.
+		/// The receiver may only have one vesting schedule per asset.
+		fn vested_transfer(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			to: T::AccountId,
+			schedule: VestingSchedule<T::Balance, T::BlockNumber>
+		) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(!schedule.locked.is_zero(), "cannot transfer zero amount");
+			ensure!(!schedule.per_block.is_zero(), "vesting schedule must unlock some balance per block");
+			ensure!(
+				<Vesting<T>>::get(&asset_id, &to).is_none(),
+				"receiver already has a vesting schedule for this asset"
+			);
 
-		/// Next available ID for user-created asset.
-		pub NextAssetId get(next_asset_id) config(): T::AssetId;
+			Self::make_transfer_with_event(&asset_id, &origin, &to, schedule.locked)?;
+			<Vesting<T>>::insert(&asset_id, &to, schedule);
 
-		/// Permission options for a given asset.
-		pub Permissions get(get_permission): map T::AssetId => PermissionVersions<T::AccountId>;
+			Ok(())
+		}
 
-		pub CreateAssetStakes get(create_asset_stake) config(): T::Balance;
+		/// Transfer some liquid free balance to another account and deliver `payload` to the receiver's
+		/// registered payload handler.
+		pub fn transfer_with_payload(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			to: T::AccountId,
+			#[compact] amount: T::Balance,
+			payload: Vec<u8>
+		) {
+			let origin = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), "cannot transfer zero amount");
+			ensure!(payload.len() <= TRANSFER_PAYLOAD_MAX, "transfer payload is too long");
+			ensure!(T::OnTransferPayload::has_handler(&to), "receiver has no transfer payload handler");
+			Self::make_transfer_with_event(&asset_id, &origin, &to, amount)?;
+			T::OnTransferPayload::on_transfer_payload(&asset_id, &origin, &to, amount, &payload);
+		}
 
-		/// Any liquidity locks on some account balances.
-		pub Locks get(locks): map T::AccountId => Vec<BalanceLock<T::Balance, T::BlockNumber>>;
+		/// Allow `spender` to transfer up to `amount` of the origin's asset with `transfer_from`.
+		/// Replaces any existing allowance of `spender`.
+		pub fn approve(origin, #[compact] asset_id: T::AssetId, spender: T::AccountId, #[compact] amount: T::Balance) {
+			let owner = ensure_signed(origin)?;
+			<Approvals<T>>::insert(&(asset_id, owner.clone()), &spender, amount);
+			Self::deposit_event(RawEvent::Approved(asset_id, owner, spender, amount));
+		}
 
-		/// Staking Asset ID.
-		pub StakingAssetId get(staking_asset_id) config(): T::AssetId;
+		/// Transfer `amount` of `owner`'s asset to `to`, spending the allowance `owner` approved for the origin.
+		pub fn transfer_from(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			owner: T::AccountId,
+			to: T::AccountId,
+			#[compact] amount: T::Balance
+		) {
+			let spender = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), "cannot transfer zero amount");
+			let key = (asset_id, owner.clone());
+			let allowance = <Approvals<T>>::get(&key, &spender)
+				.checked_sub(&amount)
+				.ok_or_else(|| "transfer amount exceeds allowance")?;
+
+			Self::make_transfer_with_event(&asset_id, &owner, &to, amount)?;
+			if allowance.is_zero() {
+				<Approvals<T>>::remove(&key, &spender);
+			} else {
+				<Approvals<T>>::insert(&key, &spender, allowance);
+			}
+		}
 
-		/// Spending Asset ID.
-		pub SpendingAssetId get(spending_asset_id) config(): T::AssetId;
-	}
-	add_extra_genesis {
-		config(assets): Vec<T::AssetId>;
-		config(initial_balance): T::Balance;
-		config(endowed_accounts): Vec<T::AccountId>;
+		/// Remove the allowance the origin approved for `spender`.
+		pub fn revoke_approval(origin, #[compact] asset_id: T::AssetId, spender: T::AccountId) {
+			let owner = ensure_signed(origin)?;
+			let key = (asset_id, owner.clone());
+			ensure!(!<Approvals<T>>::get(&key, &spender).is_zero(), "no approval to revoke");
+			<Approvals<T>>::remove(&key, &spender);
+			Self::deposit_event(RawEvent::ApprovalRevoked(asset_id, owner, spender));
+		}
 
-		build(|
-			storage: &mut runtime_primitives::StorageOverlay,
-			_: &mut runtime_primitives::ChildrenStorageOverlay,
-			config: &GenesisConfig<T>| {
-			config.assets.iter().for_each(|asset_id| {
-				config.endowed_accounts.iter().for_each(|account_id| {
-					storage.insert(
-						<FreeBalance<T>>::key_for(asset_id, account_id),
-						<T::Balance as parity_codec::Encode>::encode(&config.initial_balance)
-					);
-				});
-			});
-		});
-	}
-}
+		/// Transfer an asset to many accounts in one call.
+		/// All transfers are checked before any is made, so either all are made or none are.
+		pub fn transfer_batch(origin, #[compact] asset_id: T::AssetId, transfers: Vec<(T::AccountId, T::Balance)>) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(!transfers.is_empty(), "batch must have at least one transfer");
+			ensure!(transfers.len() <= TRANSFER_BATCH_MAX, "too many transfers in batch");
+
+			let legs: Vec<_> = transfers
+				.into_iter()
+				.map(|(to, amount)| (asset_id, origin.clone(), to, amount))
+				.collect();
+			Self::make_transfers(&legs)
+		}
 
-decl_event!(
-	pub enum Event<T> where
-		<T as system::Trait>::AccountId,
-		<T as Trait>::Balance,
-		<T as Trait>::AssetId,
-		AssetOptions = AssetOptions<<T as Trait>::Balance, <T as system::Trait>::AccountId>
-	{
-		/// Asset created (asset_id, creator, asset_options).
-		Created(AssetId, AccountId, AssetOptions),
-		/// Asset transfer succeeded (asset_id, from, to, amount).
-		Transferred(AssetId, AccountId, AccountId, Balance),
-		/// Asset permission updated (asset_id, new_permissions).
-		PermissionUpdated(AssetId, PermissionLatest<AccountId>),
-		/// New asset minted (asset_id, account, amount).
-		Minted(AssetId, AccountId, Balance),
-		/// Asset burned (asset_id, account, amount).
-		Burned(AssetId, AccountId, Balance),
-	}
-);
+		/// Settle many transfers of any assets in one call, every leg must be sent by the origin.
+		/// All legs are checked before any is transferred, so either all legs settle or none do.
+		fn settle(origin, legs: Vec<(T::AssetId, T::AccountId, T::AccountId, T::Balance)>) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(!legs.is_empty(), "settlement must have at least one leg");
+			ensure!(legs.len() <= SETTLE_LEGS_MAX, "too many settlement legs");
+			ensure!(legs.iter().all(|(_, from, ..)| *from == origin), "settlement legs must be sent by the origin");
 
-impl<T: Trait> Module<T> {
-	// PUBLIC IMMUTABLES
+			Self::make_transfers(&legs)
+		}
 
-	/// Get an account's total balance of an asset kind.
-	pub fn total_balance(asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
-		Self::free_balance(asset_id, who) + Self::reserved_balance(asset_id, who)
-	}
+		/// Mints an asset to many accounts at once, increases its total issuance by the sum of the amounts.
+		/// The origin must have `mint` permissions.
+		fn mint_batch(origin, #[compact] asset_id: T::AssetId, mints: Vec<(T::AccountId, T::Balance)>) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(mints.len() <= MINT_BATCH_MAX, "too many mints in batch");
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Mint,
+				"The origin does not have permission to mint an asset, Permission error.",
+			)?;
+			Self::ensure_not_frozen(&asset_id)?;
+
+			let mut new_total_issuance = <TotalIssuance<T>>::get(asset_id);
+			let mut new_balances = BTreeMap::new();
+			for (to, amount) in mints.iter() {
+				Self::ensure_account_not_frozen(&asset_id, to)?;
+				new_total_issuance = new_total_issuance.checked_add(amount)
+					.ok_or_else(|| "total_issuance got overflow after minting.")?;
+				let balance = new_balances.entry(to.clone()).or_insert_with(|| Self::free_balance(&asset_id, to));
+				*balance = balance.checked_add(amount)
+					.ok_or_else(|| "free balance got overflow after minting.")?;
+			}
 
-	/// Get an account's free balance of an asset kind.
-	pub fn free_balance(asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
-		<FreeBalance<T>>::get(asset_id, who)
-	}
+			<TotalIssuance<T>>::insert(asset_id, new_total_issuance);
+			for (to, value) in new_balances.iter() {
+				Self::set_free_balance(&asset_id, to, *value);
+			}
+			for (to, amount) in mints {
+				Self::note_supply_change(&asset_id, amount, Zero::zero());
+				Self::record_mint_batch(&asset_id, &to, amount);
+				Self::deposit_event(RawEvent::Minted(asset_id, to, amount));
+			}
 
-	/// Get an account's reserved balance of an asset kind.
-	pub fn reserved_balance(asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
-		<ReservedBalance<T>>::get(asset_id, who)
-	}
+			Ok(())
+		}
 
-	/// Creates an asset.
-	///
-	/// # Arguments
-	/// * `asset_id`: An ID of a reserved asset.
-	/// If not provided, a user-generated asset will be created with the next available ID.
-	/// * `from_account`: The initiator account of this call
-	/// * `asset_options`: Asset creation options.
-	///
-	pub fn create_asset(
-		asset_id: Option<T::AssetId>,
-		from_account: Option<T::AccountId>,
-		options: AssetOptions<T::Balance, T::AccountId>,
-	) -> Result {
-		let asset_id = if let Some(asset_id) = asset_id {
-			ensure!(!<TotalIssuance<T>>::exists(&asset_id), "Asset id already taken.");
-			ensure!(asset_id < Self::next_asset_id(), "Asset id not available.");
-			asset_id
-		} else {
-			let asset_id = Self::next_asset_id();
-			let next_id = asset_id
-				.checked_add(&One::one())
-				.ok_or_else(|| "No new user asset id available.")?;
-			<NextAssetId<T>>::put(next_id);
-			asset_id
-		};
+		/// Burns an asset from many accounts at once, decreases its total issuance by the sum of the amounts.
+		/// The origin must have `burn` permissions.
+		fn burn_batch(origin, #[compact] asset_id: T::AssetId, burns: Vec<(T::AccountId, T::Balance)>) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(burns.len() <= MINT_BATCH_MAX, "too many burns in batch");
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Burn,
+				"The origin does not have permission to burn an asset, Permission error.",
+			)?;
+			Self::ensure_not_frozen(&asset_id)?;
+
+			let mut new_total_issuance = <TotalIssuance<T>>::get(asset_id);
+			let mut new_balances = BTreeMap::new();
+			for (to, amount) in burns.iter() {
+				new_total_issuance = new_total_issuance.checked_sub(amount)
+					.ok_or_else(|| "total_issuance got underflow after burning")?;
+				let balance = new_balances.entry(to.clone()).or_insert_with(|| Self::free_balance(&asset_id, to));
+				*balance = balance.checked_sub(amount)
+					.ok_or_else(|| "free_balance got underflow after burning")?;
+			}
 
-		let account_id = from_account.unwrap_or_else(Default::default);
-		let permissions: PermissionVersions<T::AccountId> = options.permissions.clone().into();
+			<TotalIssuance<T>>::insert(asset_id, new_total_issuance);
+			for (to, value) in new_balances.iter() {
+				Self::set_free_balance(&asset_id, to, *value);
+			}
+			for (to, amount) in burns {
+				Self::note_supply_change(&asset_id, Zero::zero(), amount);
+				Self::deposit_event(RawEvent::Burned(asset_id, to, amount));
+			}
 
-		<TotalIssuance<T>>::insert(asset_id, &options.initial_issuance);
-		<FreeBalance<T>>::insert(&asset_id, &account_id, options.initial_issuance);
-		<Permissions<T>>::insert(&asset_id, permissions);
+			Ok(())
+		}
 
-		Self::deposit_event(RawEvent::Created(asset_id, account_id, options));
+		/// Sets the number of blocks freshly minted balances of an asset can not be transferred for.
+		/// The origin must have `update` permission. Zero disables the cooldown for future mints.
+		fn set_mint_cooldown(origin, #[compact] asset_id: T::AssetId, cooldown: T::BlockNumber) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Update,
+				"Origin does not have enough permission to update the mint cooldown.",
+			)?;
 
-		Ok(())
-	}
+			<MintCooldown<T>>::insert(asset_id, cooldown);
 
-	/// Transfer some liquid free balance from one account to another.
-	/// This will not emit the `Transferred` event.
-	pub fn make_transfer(asset_id: &T::AssetId, from: &T::AccountId, to: &T::AccountId, amount: T::Balance) -> Result {
-		let new_balance = Self::free_balance(asset_id, from)
-			.checked_sub(&amount)
-			.ok_or_else(|| "balance too low to send amount")?;
-		Self::ensure_can_withdraw(asset_id, from, amount, WithdrawReason::Transfer, new_balance)?;
+			Self::deposit_event(RawEvent::MintCooldownUpdated(asset_id, cooldown));
 
-		if from != to {
-			<FreeBalance<T>>::mutate(asset_id, from, |balance| *balance -= amount);
-			<FreeBalance<T>>::mutate(asset_id, to, |balance| *balance += amount);
+			Ok(())
 		}
 
-		Ok(())
-	}
+		/// Sets the smallest amount of an asset which can be transferred, zero allows any amount.
+		/// The origin must have `update` permission.
+		fn set_min_transfer_amount(origin, #[compact] asset_id: T::AssetId, #[compact] amount: T::Balance) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Update,
+				"Origin does not have enough permission to update the minimum transfer amount.",
+			)?;
 
-	/// Transfer some liquid free balance from one account to another.
-	/// This will emit the `Transferred` event.
-	pub fn make_transfer_with_event(
-		asset_id: &T::AssetId,
+			<MinTransferAmount<T>>::insert(asset_id, amount);
+
+			Self::deposit_event(RawEvent::MinTransferAmountUpdated(asset_id, amount));
+
+			Ok(())
+		}
+
+		/// Sets the minimum balance of an asset an account may hold, zero disables dust collection.
+		/// Accounts left with a smaller non-zero free balance and no reserved balance are reaped.
+		/// The origin must have `update` permission.
+		fn set_minimum_balance(origin, #[compact] asset_id: T::AssetId, #[compact] amount: T::Balance) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Update,
+				"Origin does not have enough permission to update the minimum balance.",
+			)?;
+
+			<MinimumBalance<T>>::insert(asset_id, amount);
+
+			Self::deposit_event(RawEvent::MinimumBalanceUpdated(asset_id, amount));
+
+			Ok(())
+		}
+
+		/// Sets the sender's velocity limit on an asset: at most `limit` may be transferred out every `period` blocks.
+		/// `None` removes the limit. Imposing or tightening a limit takes effect immediately, while raising or
+		/// removing it only takes effect after `VELOCITY_LIMIT_INCREASE_DELAY` blocks, so a compromised key can not
+		/// lift the limit before the owner can react.
+		fn set_velocity_limit(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			limit: Option<(T::Balance, T::BlockNumber)>
+		) -> Result {
+			let origin = ensure_signed(origin)?;
+			if let Some((_, period)) = limit {
+				ensure!(!period.is_zero(), "velocity limit period must be greater than zero");
+			}
+
+			let now = <system::Module<T>>::block_number();
+			let current = Self::velocity_limit(&asset_id, &origin);
+			let tightens = match (&current, &limit) {
+				(None, _) => true,
+				(Some(current), Some((limit, period))) => *limit <= current.limit && *period >= current.period,
+				(Some(_), None) => false,
+			};
+
+			if tightens {
+				<PendingVelocityLimits<T>>::remove(&asset_id, &origin);
+				match limit {
+					Some((limit, period)) => <VelocityLimits<T>>::insert(&asset_id, &origin, VelocityLimit {
+						limit,
+						period,
+						spent: current.as_ref().map(|c| c.spent).unwrap_or_else(Zero::zero),
+						period_start: current.as_ref().map(|c| c.period_start).unwrap_or(now),
+					}),
+					None => <VelocityLimits<T>>::remove(&asset_id, &origin),
+				}
+				Self::deposit_event(RawEvent::VelocityLimitSet(asset_id, origin, limit));
+			} else {
+				let effective_at = now + T::BlockNumber::sa(VELOCITY_LIMIT_INCREASE_DELAY);
+				<PendingVelocityLimits<T>>::insert(&asset_id, &origin, (limit, effective_at));
+				Self::deposit_event(RawEvent::VelocityLimitIncreaseScheduled(asset_id, origin, limit, effective_at));
+			}
+
+			Ok(())
+		}
+
+		/// Sets the account which receives reaped dust, `None` burns it.
+		/// Requires Root call.
+		fn set_dust_collector(collector: Option<T::AccountId>) -> Result {
+			match collector {
+				Some(collector) => <DustCollector<T>>::put(collector),
+				None => <DustCollector<T>>::kill(),
+			}
+			Ok(())
+		}
+
+		/// Sets or removes the co-signer of an asset. While set, `mint`, `burn`, `update_permission` and
+		/// `set_co_signer` calls for the asset must be delegated to the co-signer by doughnut.
+		/// The origin must have `update` permission.
+		fn set_co_signer(origin, #[compact] asset_id: T::AssetId, co_signer: Option<T::AccountId>) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Update,
+				"Origin does not have enough permission to update the co-signer.",
+			)?;
+
+			match co_signer {
+				Some(ref co_signer) => <CoSigner<T>>::insert(asset_id, co_signer),
+				None => <CoSigner<T>>::remove(asset_id),
+			}
+
+			Self::deposit_event(RawEvent::CoSignerUpdated(asset_id, co_signer));
+
+			Ok(())
+		}
+
+		/// Sets which incoming transfers the origin refuses, by asset or by sender.
+		/// Empty lists accept all transfers.
+		fn set_receive_rules(origin, denied_assets: Vec<T::AssetId>, denied_senders: Vec<T::AccountId>) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(
+				denied_assets.len() <= RECEIVE_RULES_MAX && denied_senders.len() <= RECEIVE_RULES_MAX,
+				"Too many receive rules."
+			);
+
+			if denied_assets.is_empty() && denied_senders.is_empty() {
+				<ReceiveRules<T>>::remove(&origin);
+			} else {
+				<ReceiveRules<T>>::insert(&origin, (denied_assets, denied_senders));
+			}
+
+			Ok(())
+		}
+
+		/// Sets or removes the link to an asset's off-chain metadata document.
+		/// The origin must have `update` permission.
+		fn set_metadata_link(origin, #[compact] asset_id: T::AssetId, link: Option<MetadataLink<T::Hash>>) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Update,
+				"Origin does not have enough permission to update the metadata link.",
+			)?;
+
+			match link {
+				Some(ref link) => {
+					ensure!(!link.uri.is_empty(), "Metadata URI can not be empty");
+					ensure!(link.uri.len() <= METADATA_URI_MAX, "Metadata URI is too long");
+					<MetadataLinks<T>>::insert(asset_id, link);
+				}
+				None => <MetadataLinks<T>>::remove(asset_id),
+			}
+
+			let link = link.map(|link| MetadataLink {
+				uri: Self::bounded_event_data(link.uri),
+				hash: link.hash,
+			});
+			Self::deposit_event(RawEvent::MetadataLinkUpdated(asset_id, link));
+
+			Ok(())
+		}
+
+		/// Sets the amount above which transfers of an asset raise a `LargeTransfer` event,
+		/// `None` stops watching the asset.
+		/// Requires Root call.
+		fn set_large_transfer_threshold(asset_id: T::AssetId, threshold: Option<T::Balance>) -> Result {
+			match threshold {
+				Some(threshold) => <LargeTransferThreshold<T>>::insert(asset_id, threshold),
+				None => <LargeTransferThreshold<T>>::remove(asset_id),
+			}
+			Ok(())
+		}
+
+		/// Create a new kind of asset for `creator`, reserving the creation stake from the sender.
+		/// The initial issuance goes to `creator`, which must accept transfers from the sender under its receive
+		/// rules. The stake is recorded in `CreatorStakes` under the sender.
+		fn create_for(origin, creator: T::AccountId, options: AssetOptions<T::Balance, T::AccountId>) -> Result {
+			let sponsor = ensure_signed(origin)?;
+			let asset_id = Self::create_staked(&sponsor, &creator, options)?;
+			Self::deposit_event(RawEvent::CreationSponsored(asset_id, sponsor, creator));
+			Ok(())
+		}
+
+		/// Irreversibly give up `permission` on an asset, leaving it without an owner.
+		/// Renouncing `Mint` fixes the asset's supply, renouncing every permission makes the asset immutable.
+		/// The origin must hold the permission or the `update` permission, and `confirm` must be set.
+		fn renounce_permission(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			permission: PermissionType,
+			confirm: bool
+		) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(confirm, "Renouncing a permission is irreversible and must be confirmed.");
+			if !Self::check_permission(&asset_id, &origin, &PermissionType::Update) {
+				Self::ensure_permission(
+					&asset_id,
+					&origin,
+					permission,
+					"Origin does not have enough permission to renounce the permission.",
+				)?;
+			}
+
+			let mut permissions: PermissionLatest<T::AccountId> = Self::get_permission(&asset_id).into();
+			match permission {
+				PermissionType::Burn => permissions.burn = Owner::None,
+				PermissionType::Mint => permissions.mint = Owner::None,
+				PermissionType::Update => permissions.update = Owner::None,
+				PermissionType::Freeze => permissions.freeze = Owner::None,
+			}
+			let permissions: PermissionVersions<T::AccountId> = permissions.into();
+			<Permissions<T>>::insert(&asset_id, permissions);
+			<RenouncedPermissions<T>>::mutate(&asset_id, |renounced| {
+				if !renounced.contains(&permission) {
+					renounced.push(permission);
+				}
+			});
+
+			Self::deposit_event(RawEvent::PermissionRenounced(asset_id, permission));
+
+			Ok(())
+		}
+
+		/// Freeze an asset: halt its transfers, minting, burning and reserving until it is thawed.
+		/// The origin must have `freeze` permission.
+		fn freeze_asset(origin, #[compact] asset_id: T::AssetId) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Freeze,
+				"The origin does not have permission to freeze an asset, Permission error.",
+			)?;
+			ensure!(!Self::is_frozen(&asset_id), "asset is already frozen");
+
+			<Frozen<T>>::insert(&asset_id, true);
+			Self::deposit_event(RawEvent::AssetFrozen(asset_id));
+
+			Ok(())
+		}
+
+		/// Thaw a frozen asset.
+		/// The origin must have `freeze` permission.
+		fn thaw_asset(origin, #[compact] asset_id: T::AssetId) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Freeze,
+				"The origin does not have permission to thaw an asset, Permission error.",
+			)?;
+			ensure!(Self::is_frozen(&asset_id), "asset is not frozen");
+
+			<Frozen<T>>::remove(&asset_id);
+			Self::deposit_event(RawEvent::AssetThawed(asset_id));
+
+			Ok(())
+		}
+
+		/// Freeze the `asset_id` balance of `who` for `period` blocks, so it can neither send nor receive the asset.
+		/// The period may not exceed `MaxFreezePeriod`, only root may extend the freeze after that.
+		/// The origin must have `freeze` permission.
+		fn freeze_account(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			who: T::AccountId,
+			#[compact] period: T::BlockNumber
+		) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Freeze,
+				"The origin does not have permission to freeze an account, Permission error.",
+			)?;
+			ensure!(!period.is_zero(), "freeze period must be greater than zero");
+			ensure!(period <= Self::max_freeze_period(), "freeze period exceeds the maximum");
+			ensure!(!Self::is_account_frozen(&asset_id, &who), "account is already frozen");
+
+			let until = <system::Module<T>>::block_number() + period;
+			Self::schedule_account_thaw(&asset_id, &who, until)?;
+			<FrozenAccounts<T>>::insert(&asset_id, &who, until);
+			Self::deposit_event(RawEvent::AccountFrozen(asset_id, who, until));
+
+			Ok(())
+		}
+
+		/// Unfreeze the `asset_id` balance of `who`.
+		/// The origin must have `freeze` permission.
+		fn unfreeze_account(origin, #[compact] asset_id: T::AssetId, who: T::AccountId) -> Result {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_permission(
+				&asset_id,
+				&origin,
+				PermissionType::Freeze,
+				"The origin does not have permission to unfreeze an account, Permission error.",
+			)?;
+			let until = Self::account_frozen_until(&asset_id, &who).ok_or_else(|| "account is not frozen")?;
+
+			<FrozenAccounts<T>>::remove(&asset_id, &who);
+			Self::unschedule_account_thaw(&asset_id, &who, until);
+			Self::deposit_event(RawEvent::AccountUnfrozen(asset_id, who));
+
+			Ok(())
+		}
+
+		/// Keep the frozen `asset_id` balance of `who` frozen until block `until`.
+		/// Requires Root call.
+		fn extend_account_freeze(
+			#[compact] asset_id: T::AssetId,
+			who: T::AccountId,
+			#[compact] until: T::BlockNumber
+		) -> Result {
+			let frozen_until = Self::account_frozen_until(&asset_id, &who).ok_or_else(|| "account is not frozen")?;
+			ensure!(until > frozen_until, "an account freeze can only be extended");
+
+			Self::schedule_account_thaw(&asset_id, &who, until)?;
+			Self::unschedule_account_thaw(&asset_id, &who, frozen_until);
+			<FrozenAccounts<T>>::insert(&asset_id, &who, until);
+			Self::deposit_event(RawEvent::AccountFreezeExtended(asset_id, who, until));
+
+			Ok(())
+		}
+
+		/// Sets the max. number of blocks a freeze owner may freeze an account for.
+		/// Requires Root call.
+		fn set_max_freeze_period(#[compact] period: T::BlockNumber) -> Result {
+			<MaxFreezePeriod<T>>::put(period);
+			Ok(())
+		}
+
+		/// Sets the stake reserved from the creator of a new asset.
+		/// Requires Root call.
+		fn set_create_asset_stake(#[compact] stake: T::Balance) -> Result {
+			<CreateAssetStakes<T>>::put(stake);
+			Ok(())
+		}
+
+		/// Sets the asset used for staking, which must have been issued.
+		/// Requires Root call.
+		fn set_staking_asset_id(#[compact] asset_id: T::AssetId) -> Result {
+			ensure!(!Self::total_issuance(&asset_id).is_zero(), "asset has not been issued");
+			<StakingAssetId<T>>::put(asset_id);
+			Ok(())
+		}
+
+		/// Sets the asset used for payments such as fees, which must have been issued.
+		/// Requires Root call.
+		fn set_spending_asset_id(#[compact] asset_id: T::AssetId) -> Result {
+			ensure!(!Self::total_issuance(&asset_id).is_zero(), "asset has not been issued");
+			<SpendingAssetId<T>>::put(asset_id);
+			Ok(())
+		}
+
+		/// Transfer `amount` of `asset_id` to `to` at the start of block `execute_at`.
+		/// The amount is reserved from the sender until then.
+		pub fn schedule_transfer(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			to: T::AccountId,
+			#[compact] amount: T::Balance,
+			#[compact] execute_at: T::BlockNumber
+		) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), "cannot transfer zero amount");
+			ensure!(execute_at > <system::Module<T>>::block_number(), "scheduled transfer must be in the future");
+			let mut due = Self::scheduled_transfers_due(execute_at);
+			ensure!(due.len() < SCHEDULED_TRANSFERS_PER_BLOCK_MAX, "too many transfers are scheduled for the block");
+			let transfer_id = Self::next_scheduled_transfer_id();
+			let next_transfer_id = transfer_id
+				.checked_add(1)
+				.ok_or_else(|| "No new scheduled transfer id is available")?;
+
+			Self::reserve_named(&SCHEDULED_TRANSFER_RESERVE_ID, &asset_id, &origin, amount)?;
+			<NextScheduledTransferId<T>>::put(next_transfer_id);
+			due.push(transfer_id);
+			<ScheduledTransfersDue<T>>::insert(execute_at, due);
+			<ScheduledTransfers<T>>::insert(
+				transfer_id,
+				ScheduledTransfer {
+					asset_id,
+					from: origin.clone(),
+					to: to.clone(),
+					amount,
+					execute_at,
+				},
+			);
+
+			Self::deposit_event(RawEvent::TransferScheduled(transfer_id, asset_id, origin, to, amount, execute_at));
+			Ok(())
+		}
+
+		/// Cancel a scheduled transfer before it is executed and release its reserved amount.
+		/// The origin must be the sender of the transfer.
+		pub fn cancel_scheduled_transfer(origin, #[compact] transfer_id: ScheduledTransferId) -> Result {
+			let origin = ensure_signed(origin)?;
+			let transfer = Self::scheduled_transfer(transfer_id).ok_or_else(|| "scheduled transfer does not exist")?;
+			ensure!(transfer.from == origin, "only the sender can cancel a scheduled transfer");
+
+			<ScheduledTransfers<T>>::remove(transfer_id);
+			<ScheduledTransfersDue<T>>::mutate(transfer.execute_at, |due| due.retain(|id| *id != transfer_id));
+			Self::unreserve_named(&SCHEDULED_TRANSFER_RESERVE_ID, &transfer.asset_id, &origin, transfer.amount);
+
+			Self::deposit_event(RawEvent::ScheduledTransferCancelled(transfer_id));
+			Ok(())
+		}
+
+		/// Sets the max. number of user assets and collections which may be created in a block.
+		/// Requires Root call.
+		fn set_max_asset_creations_per_block(limit: u32) -> Result {
+			<MaxAssetCreationsPerBlock<T>>::put(limit);
+			Ok(())
+		}
+
+		/// Transfer `amount` of `asset_id` to `to` on condition that it is claimed with the preimage of `hashlock`
+		/// before block `timelock`. The amount is reserved from the sender until it is claimed, or refunded from
+		/// `timelock` on. The transfer is checked like an ordinary transfer when it is locked.
+		/// A sender may lock one transfer under a hashlock, others may use the same hashlock, so a hashlock made
+		/// public by one leg of a swap can not be taken to block the other leg.
+		pub fn transfer_locked(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			to: T::AccountId,
+			#[compact] amount: T::Balance,
+			hashlock: T::Hash,
+			hash_algorithm: HashAlgorithm,
+			#[compact] timelock: T::BlockNumber
+		) -> Result {
+			let origin = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), "cannot transfer zero amount");
+			ensure!(timelock > <system::Module<T>>::block_number(), "timelock must be in the future");
+			ensure!(!<LockedTransfers<T>>::exists(&origin, &hashlock), "hashlock is already in use");
+			Self::ensure_can_transfer(&asset_id, &origin, &to, amount)?;
+
+			Self::reserve_named(&LOCKED_TRANSFER_RESERVE_ID, &asset_id, &origin, amount)?;
+			Self::note_velocity_spend(&asset_id, &origin, amount);
+			<LockedTransfers<T>>::insert(
+				&origin,
+				&hashlock,
+				LockedTransfer {
+					asset_id,
+					from: origin.clone(),
+					to: to.clone(),
+					amount,
+					hash_algorithm,
+					timelock,
+				},
+			);
+
+			Self::deposit_event(RawEvent::TransferLocked(hashlock, asset_id, origin, to, amount, timelock));
+			Ok(())
+		}
+
+		/// Complete the transfer locked by `from` under the hash of `preimage`, before its timelock.
+		/// Anyone knowing the preimage may claim, the amount always goes to the transfer's receiver.
+		/// The receiver must still accept the transfer, it may have been frozen or changed its receive rules since.
+		pub fn claim_locked_transfer(origin, from: T::AccountId, preimage: Vec<u8>) -> Result {
+			ensure_signed(origin)?;
+			ensure!(preimage.len() <= PREIMAGE_MAX, "preimage is too long");
+			let (hashlock, transfer) = HashAlgorithm::all()
+				.iter()
+				.map(|algorithm| (*algorithm, Self::hashlock_of(*algorithm, &preimage)))
+				.filter_map(|(algorithm, hashlock)| {
+					Self::locked_transfer(&from, &hashlock)
+						.filter(|transfer| transfer.hash_algorithm == algorithm)
+						.map(|transfer| (hashlock, transfer))
+				})
+				.next()
+				.ok_or_else(|| "locked transfer does not exist")?;
+			ensure!(
+				<system::Module<T>>::block_number() < transfer.timelock,
+				"locked transfer's timelock has passed"
+			);
+			Self::ensure_can_receive(&transfer.asset_id, &transfer.from, &transfer.to)?;
+			Self::ensure_can_create(&transfer.asset_id, &transfer.to, transfer.amount)?;
+
+			<LockedTransfers<T>>::remove(&from, &hashlock);
+			Self::repatriate_reserved_named(
+				&LOCKED_TRANSFER_RESERVE_ID,
+				&transfer.asset_id,
+				&transfer.from,
+				&transfer.to,
+				transfer.amount,
+			)?;
+
+			Self::deposit_event(RawEvent::LockedTransferClaimed(from, hashlock, preimage));
+			Ok(())
+		}
+
+		/// Return the transfer locked by `from` under `hashlock` to it, once its timelock has passed.
+		pub fn refund_locked_transfer(origin, from: T::AccountId, hashlock: T::Hash) -> Result {
+			ensure_signed(origin)?;
+			let transfer = Self::locked_transfer(&from, &hashlock).ok_or_else(|| "locked transfer does not exist")?;
+			ensure!(
+				<system::Module<T>>::block_number() >= transfer.timelock,
+				"locked transfer's timelock has not passed"
+			);
+
+			<LockedTransfers<T>>::remove(&from, &hashlock);
+			Self::unreserve_named(&LOCKED_TRANSFER_RESERVE_ID, &transfer.asset_id, &transfer.from, transfer.amount);
+
+			Self::deposit_event(RawEvent::LockedTransferRefunded(from, hashlock));
+			Ok(())
+		}
+
+		/// Migrate the module's storage to the latest layout, and the balances of `accounts` with it.
+		/// Enacted with the runtime upgrade which changes the layout, more batches of accounts may follow it.
+		/// Hashed maps can not be iterated on-chain, so the accounts are listed off-chain.
+		/// Requires Root call.
+		fn migrate_storage(accounts: Vec<(T::AssetId, T::AccountId)>) -> Result {
+			ensure!(accounts.len() <= MIGRATE_ACCOUNTS_MAX, "too many accounts to migrate");
+			Self::migrate(&accounts);
+			Ok(())
+		}
+	}
+}
+
+/// The maximum length of a transfer payload in bytes
+pub const TRANSFER_PAYLOAD_MAX: usize = 256;
+
+/// The maximum number of transfers in a batch transfer
+pub const TRANSFER_BATCH_MAX: usize = 1000;
+
+/// The maximum number of entries in a batch mint or burn
+pub const MINT_BATCH_MAX: usize = 1000;
+
+/// The maximum number of accounts migrated by one `migrate_storage` call
+pub const MIGRATE_ACCOUNTS_MAX: usize = 1000;
+
+/// The maximum number of legs in a settlement
+pub const SETTLE_LEGS_MAX: usize = 16;
+
+/// The maximum number of denied assets or senders in an account's receive rules
+pub const RECEIVE_RULES_MAX: usize = 32;
+
+/// The maximum length of an asset metadata URI
+pub const METADATA_URI_MAX: usize = 256;
+
+/// The maximum length of variable-length data embedded in an event, longer data is truncated
+pub const EVENT_DATA_MAX: usize = 128;
+
+/// A link to an off-chain asset metadata document, e.g. a logo or legal terms.
+/// Clients should check the document they fetch from `uri` against `hash`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MetadataLink<Hash> {
+	pub uri: Vec<u8>,
+	pub hash: Hash,
+}
+
+/// An identifier for a reservation, by convention reservation ids are module names padded with spaces
+/// e.g. `b"vault   "`
+pub type ReserveIdentifier = [u8; 8];
+
+/// An identifier for the runtime module making a module transfer, by convention module names padded with spaces
+/// e.g. `b"bounties"`
+pub type ModuleIdentifier = [u8; 8];
+
+/// The reservation id of the stake held for creating an asset
+pub const CREATE_ASSET_RESERVE_ID: ReserveIdentifier = *b"gasset  ";
+
+/// The lock id of balances locked at genesis
+pub const GENESIS_LOCK_ID: LockIdentifier = *b"genesis ";
+
+/// The default max. number of blocks an account may be frozen for by a freeze owner
+pub const DEFAULT_MAX_FREEZE_PERIOD: u64 = 100_800;
+
+/// The maximum number of account freezes which may expire at the same block
+pub const ACCOUNT_FREEZES_PER_BLOCK_MAX: usize = 100;
+
+/// The reservation id of the amounts held for scheduled transfers
+pub const SCHEDULED_TRANSFER_RESERVE_ID: ReserveIdentifier = *b"schedule";
+
+/// The maximum number of transfers which may be scheduled for the same block
+pub const SCHEDULED_TRANSFERS_PER_BLOCK_MAX: usize = 100;
+
+/// The default max. number of user assets and collections which may be created in a block
+pub const DEFAULT_MAX_ASSET_CREATIONS_PER_BLOCK: u32 = 10;
+
+/// The reservation id of the amounts held for locked transfers
+pub const LOCKED_TRANSFER_RESERVE_ID: ReserveIdentifier = *b"htlc    ";
+
+/// The max. length of the preimage of a locked transfer's hashlock
+pub const PREIMAGE_MAX: usize = 64;
+
+pub type ScheduledTransferId = u32;
+
+/// A transfer to be executed at the start of a future block, its amount is reserved from the sender until then
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ScheduledTransfer<AssetId, AccountId, Balance, BlockNumber> {
+	pub asset_id: AssetId,
+	pub from: AccountId,
+	pub to: AccountId,
+	pub amount: Balance,
+	pub execute_at: BlockNumber,
+}
+
+/// The hash function of a locked transfer's hashlock
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum HashAlgorithm {
+	/// Blake2-256, the hash of CENNZnet
+	Blake2_256,
+	/// SHA-256, as used by Bitcoin and Ethereum HTLCs
+	Sha256,
+	/// Keccak-256, as computed natively by Ethereum contracts
+	Keccak256,
+}
+
+impl HashAlgorithm {
+	/// Every supported hash function
+	pub fn all() -> [HashAlgorithm; 3] {
+		[HashAlgorithm::Blake2_256, HashAlgorithm::Sha256, HashAlgorithm::Keccak256]
+	}
+
+	/// The 32 byte hash of `data`
+	pub fn hash(self, data: &[u8]) -> [u8; 32] {
+		match self {
+			HashAlgorithm::Blake2_256 => runtime_io::blake2_256(data),
+			HashAlgorithm::Sha256 => {
+				let mut hash = [0u8; 32];
+				hash.copy_from_slice(&Sha256::digest(data));
+				hash
+			}
+			HashAlgorithm::Keccak256 => runtime_io::keccak_256(data),
+		}
+	}
+}
+
+/// A transfer which the receiver gets by revealing the preimage of its hashlock before its timelock,
+/// its amount is reserved from the sender until it is claimed or refunded
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct LockedTransfer<AssetId, AccountId, Balance, BlockNumber> {
+	pub asset_id: AssetId,
+	pub from: AccountId,
+	pub to: AccountId,
+	pub amount: Balance,
+	/// The hash function the preimage is hashed with to match the hashlock
+	pub hash_algorithm: HashAlgorithm,
+	/// The transfer may be refunded to the sender from this block, and no longer claimed
+	pub timelock: BlockNumber,
+}
+
+/// A batch of minted balance which can not be transferred until the cooldown has passed
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MintBatch<Balance, BlockNumber> {
+	pub amount: Balance,
+	pub until: BlockNumber,
+}
+
+/// A balance which unlocks linearly, `per_block` at a time from `starting_block`
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct VestingSchedule<Balance, BlockNumber> {
+	pub locked: Balance,
+	pub per_block: Balance,
+	pub starting_block: BlockNumber,
+}
+
+impl<Balance: SimpleArithmetic + Copy, BlockNumber: SimpleArithmetic + Copy> VestingSchedule<Balance, BlockNumber> {
+	/// The amount still locked at block `n`
+	pub fn locked_at(&self, n: BlockNumber) -> Balance {
+		if n <= self.starting_block {
+			return self.locked;
+		}
+		let vested = self.per_block.saturating_mul(Balance::sa((n - self.starting_block).as_()));
+		self.locked.saturating_sub(vested)
+	}
+}
+
+/// The number of blocks in a voting snapshot period
+pub const VOTING_SNAPSHOT_PERIOD: u64 = 14_400;
+
+/// The number of past voting snapshot periods for which voting weights can be queried
+pub const VOTING_SNAPSHOT_HISTORY: u64 = 28;
+
+/// The number of blocks before a raised or removed velocity limit takes effect
+pub const VELOCITY_LIMIT_INCREASE_DELAY: u64 = 14_400;
+
+/// A self-imposed limit on the amount of an asset an account may transfer out each period
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct VelocityLimit<Balance, BlockNumber> {
+	/// The max. amount which may be transferred out in a period
+	pub limit: Balance,
+	/// The length of a period in blocks
+	pub period: BlockNumber,
+	/// The amount transferred out in the current period
+	pub spent: Balance,
+	/// The block number the current period started at
+	pub period_start: BlockNumber,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct BalanceLock<Balance, BlockNumber> {
+	pub id: LockIdentifier,
+	pub amount: Balance,
+	pub until: BlockNumber,
+	pub reasons: WithdrawReasons,
+	/// A human readable explanation of the lock, if provided by the locking module
+	pub metadata: Option<Vec<u8>>,
+}
+
+/// The balance of an asset under an account, kept in a single storage item so a transfer reads and writes one
+/// item per account
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct AccountData<Balance> {
+	/// The balance which may be transferred, reserved or locked
+	pub free: Balance,
+	/// The balance reserved by modules, which can not be transferred
+	pub reserved: Balance,
+	/// The largest lock on `free` restricting withdrawals other than transaction payment
+	pub misc_frozen: Balance,
+	/// The largest lock on `free` restricting transaction payment
+	pub fee_frozen: Balance,
+}
+
+impl<Balance: Copy + Ord + Zero> AccountData<Balance> {
+	/// The amount `free` can not be withdrawn below for `reason`, unless the locks holding it have expired
+	pub fn frozen(&self, reason: WithdrawReason) -> Balance {
+		if reason == WithdrawReason::TransactionPayment {
+			self.fee_frozen
+		} else {
+			self.misc_frozen
+		}
+	}
+
+	/// Set the frozen amounts to the largest of `locks` for each kind of withdrawal
+	fn freeze<BlockNumber>(&mut self, locks: &[BalanceLock<Balance, BlockNumber>]) {
+		let misc_reasons = [WithdrawReason::Transfer, WithdrawReason::Reserve, WithdrawReason::Fee];
+		self.misc_frozen = Zero::zero();
+		self.fee_frozen = Zero::zero();
+		for lock in locks {
+			if misc_reasons.iter().any(|reason| lock.reasons.contains(*reason)) {
+				self.misc_frozen = self.misc_frozen.max(lock.amount);
+			}
+			if lock.reasons.contains(WithdrawReason::TransactionPayment) {
+				self.fee_frozen = self.fee_frozen.max(lock.amount);
+			}
+		}
+	}
+}
+
+/// Why a balance is scheduled to become available
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum UnlockReason {
+	/// The balance lock with this id expires
+	Lock(LockIdentifier),
+	/// A vesting schedule has fully vested, its balance unlocks linearly until then
+	Vesting,
+	/// A batch of minted balance leaves its transfer cooldown
+	MintCooldown,
+	/// A staking unbonding chunk can be withdrawn, the block is estimated from the unbonding era
+	Unbonding,
+}
+
+/// An amount of an asset which becomes available at a future block
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Unlock<AssetId, Balance, BlockNumber> {
+	pub asset_id: AssetId,
+	pub amount: Balance,
+	pub at: BlockNumber,
+	pub reason: UnlockReason,
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as GenericAsset {
+		/// Total issuance of a given asset.
+		pub TotalIssuance get(total_issuance) build(|config: &GenesisConfig<T>| {
+			let issuance = config.initial_balance * (config.endowed_accounts.len() as u32).into();
+			let mut total_issuance: BTreeMap<T::AssetId, T::Balance> =
+				config.assets.iter().map(|id| (*id, issuance)).collect();
+			for (asset_id, _, amount) in config.endowments.iter() {
+				let total = total_issuance.entry(*asset_id).or_insert_with(Zero::zero);
+				*total = total.saturating_add(*amount);
+			}
+			total_issuance.into_iter().collect::<Vec<_>>()
+		}): map T::AssetId => T::Balance;
+
+		/// The free and reserved balances and frozen amounts of a given asset under an account.
+		pub AccountStore: double_map T::AssetId, twox_128(T::AccountId) => Option<AccountData<T::Balance>>;
+
+		/// Free balances from before balances were kept in `AccountStore`.
+		/// They are read until the account's balance of the asset is next written, which moves them.
+		pub FreeBalance: double_map T::AssetId, twox_128(T::AccountId) => T::Balance;
+
+		/// Reserved balances from before balances were kept in `AccountStore`, moved along with `FreeBalance`.
+		pub ReservedBalance: double_map T::AssetId, twox_128(T::AccountId) => T::Balance;
+
+		/// The number of accounts holding a non-zero free or reserved balance of an asset.
+		pub HolderCount get(holder_count): map T::AssetId => u32;
+		/// The holders of an asset by position, from zero to the holder count: (asset_id, position) => holder
+		HolderAt: map (T::AssetId, u32) => T::AccountId;
+		/// The position of a holder of an asset in `HolderAt`
+		HolderPosition: double_map T::AssetId, twox_128(T::AccountId) => Option<u32>;
+
+		/// The ids of all assets, in order of creation.
+		pub RegisteredAssets get(registered_assets) build(|config: &GenesisConfig<T>| {
+			let mut assets = config.assets.clone();
+			for (asset_id, _, _) in config.endowments.iter() {
+				if !assets.contains(asset_id) {
+					assets.push(*asset_id);
+				}
+			}
+			assets
+		}): Vec<T::AssetId>;
+
+		/// Next available ID for user-created asset.
+		pub NextAssetId get(next_asset_id) config(): T::AssetId;
+
+		/// Permission options for a given asset.
+		pub Permissions get(get_permission): map T::AssetId => PermissionVersions<T::AccountId>;
+
+		/// The permissions irreversibly renounced on an asset.
+		pub RenouncedPermissions get(renounced_permissions): map T::AssetId => Vec<PermissionType>;
+		/// Whether an asset is frozen by its freeze owner, halting transfers, minting, burning and reserving.
+		pub Frozen get(is_frozen): map T::AssetId => bool;
+		/// The block an account's balance of an asset is frozen until by the asset's freeze owner, it can neither
+		/// send nor receive the asset until then.
+		pub FrozenAccounts get(account_frozen_until): double_map T::AssetId, twox_128(T::AccountId) => Option<T::BlockNumber>;
+		/// The account freezes which expire at the start of a block.
+		pub AccountFreezesExpiring get(account_freezes_expiring): map T::BlockNumber => Vec<(T::AssetId, T::AccountId)>;
+		/// The max. number of blocks a freeze owner may freeze an account for.
+		pub MaxFreezePeriod get(max_freeze_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_MAX_FREEZE_PERIOD);
+
+		pub CreateAssetStakes get(create_asset_stake) config(): T::Balance;
+
+		/// Staking asset locks from before locks were kept per asset in `AssetLocks`.
+		/// They are read until the account's staking asset locks are next updated, which moves them.
+		pub Locks: map T::AccountId => Vec<BalanceLock<T::Balance, T::BlockNumber>>;
+
+		/// Any liquidity locks on the balance of an asset under an account, ordered by `until`.
+		pub AssetLocks: double_map T::AssetId, twox_128(T::AccountId) => Vec<BalanceLock<T::Balance, T::BlockNumber>>;
+
+		/// Staking Asset ID.
+		pub StakingAssetId get(staking_asset_id) config(): T::AssetId;
+
+		/// Spending Asset ID.
+		pub SpendingAssetId get(spending_asset_id) config(): T::AssetId;
+
+		/// The number of blocks freshly minted balances of an asset can not be transferred for.
+		pub MintCooldown get(mint_cooldown): map T::AssetId => T::BlockNumber;
+
+		/// Links to the off-chain metadata document of each asset.
+		pub MetadataLinks get(metadata_link): map T::AssetId => Option<MetadataLink<T::Hash>>;
+
+		/// The smallest amount of an asset which can be transferred.
+		pub MinTransferAmount get(min_transfer_amount): map T::AssetId => T::Balance;
+
+		/// The staking asset reserved by each creator for creating assets: (asset_id, stake).
+		pub CreatorStakes get(creator_stakes_of): map T::AccountId => Vec<(T::AssetId, T::Balance)>;
+
+		/// The smallest non-zero free balance of an asset an account may be left with before it is reaped.
+		pub MinimumBalance get(minimum_balance): map T::AssetId => T::Balance;
+
+		/// The account which receives reaped dust, if unset dust is burned.
+		pub DustCollector get(dust_collector): Option<T::AccountId>;
+
+		/// The amount of an owner's asset each spender may transfer with `transfer_from`.
+		/// Key: `(asset_id, owner), spender`
+		pub Approvals get(approvals): double_map (T::AssetId, T::AccountId), twox_128(T::AccountId) => T::Balance;
+
+		/// The account which must co-sign sensitive operations on an asset.
+		pub CoSigner get(co_signer): map T::AssetId => Option<T::AccountId>;
+
+		/// The assets and senders each account refuses incoming transfers of: (denied_assets, denied_senders).
+		pub ReceiveRules get(receive_rules): map T::AccountId => (Vec<T::AssetId>, Vec<T::AccountId>);
+
+		/// The total issuance changes of each asset in this block: (asset_id, increase, decrease).
+		/// Cleared at the start of every block.
+		pub SupplyDeltas get(supply_deltas): Vec<(T::AssetId, T::Balance, T::Balance)>;
+
+		/// The max. number of user assets and collections which may be created in a block.
+		pub MaxAssetCreationsPerBlock get(max_asset_creations_per_block): u32 = DEFAULT_MAX_ASSET_CREATIONS_PER_BLOCK;
+
+		/// The number of user assets and collections created in this block.
+		/// Cleared at the start of every block.
+		pub AssetCreations get(asset_creations): u32;
+
+		/// The layout of the module's storage, chains created with this code start on the latest layout.
+		pub StorageVersion get(storage_version) build(|_: &GenesisConfig<T>| StorageLayout::LATEST): StorageLayout;
+
+		/// The treasury account paid a share of each asset creation stake, and that share.
+		/// The rest of the stake stays reserved on the staker.
+		pub CreationStakeTreasury get(creation_stake_treasury): Option<(T::AccountId, Permill)>;
+
+		/// Transfers of an asset above this amount raise a `LargeTransfer` event.
+		pub LargeTransferThreshold get(large_transfer_threshold): map T::AssetId => Option<T::Balance>;
+
+		/// Minted balances of an asset under an account which are still cooling down.
+		pub MintBatches get(mint_batches): double_map T::AssetId, twox_128(T::AccountId) => Vec<MintBatch<T::Balance, T::BlockNumber>>;
+
+		/// The staking asset balance of an account at the start of each snapshot period in which it changed:
+		/// (period, balance), oldest first.
+		pub VotingSnapshots get(voting_snapshots): map T::AccountId => Vec<(u64, T::Balance)>;
+
+		/// The velocity limit an account has set on its outgoing transfers of an asset.
+		pub VelocityLimits: double_map T::AssetId, twox_128(T::AccountId) => Option<VelocityLimit<T::Balance, T::BlockNumber>>;
+
+		/// Raised or removed velocity limits waiting to take effect: (new_limit, effective_at).
+		pub PendingVelocityLimits get(pending_velocity_limit): double_map T::AssetId, twox_128(T::AccountId) => Option<(Option<(T::Balance, T::BlockNumber)>, T::BlockNumber)>;
+
+		/// The vesting schedule of an asset under an account.
+		pub Vesting get(vesting): double_map T::AssetId, twox_128(T::AccountId) => Option<VestingSchedule<T::Balance, T::BlockNumber>>;
+
+		/// The part of an account's reserved balance of an asset held by each named reservation.
+		pub ReservedByPurpose get(reserved_by_purpose): double_map T::AssetId, twox_128(T::AccountId) => Vec<(ReserveIdentifier, T::Balance)>;
+
+		/// The id of the next scheduled transfer.
+		pub NextScheduledTransferId get(next_scheduled_transfer_id): ScheduledTransferId;
+
+		/// Transfers which have been scheduled and not yet executed or cancelled.
+		pub ScheduledTransfers get(scheduled_transfer): map ScheduledTransferId => Option<ScheduledTransfer<T::AssetId, T::AccountId, T::Balance, T::BlockNumber>>;
+
+		/// The scheduled transfers to execute at the start of a block.
+		pub ScheduledTransfersDue get(scheduled_transfers_due): map T::BlockNumber => Vec<ScheduledTransferId>;
+
+		/// Locked transfers which have not been claimed or refunded, by sender and hashlock.
+		pub LockedTransfers get(locked_transfer): double_map T::AccountId, blake2_256(T::Hash) => Option<LockedTransfer<T::AssetId, T::AccountId, T::Balance, T::BlockNumber>>;
+	}
+	add_extra_genesis {
+		config(assets): Vec<T::AssetId>;
+		config(initial_balance): T::Balance;
+		config(endowed_accounts): Vec<T::AccountId>;
+		/// Individual endowments (asset_id, account_id, amount), added on top of `initial_balance`.
+		config(endowments): Vec<(T::AssetId, T::AccountId, T::Balance)>;
+		/// Balances locked from genesis (account_id, amount, until), at most one lock per account.
+		config(genesis_locks): Vec<(T::AccountId, T::Balance, T::BlockNumber)>;
+
+		build(|
+			storage: &mut runtime_primitives::StorageOverlay,
+			_: &mut runtime_primitives::ChildrenStorageOverlay,
+			config: &GenesisConfig<T>| {
+			let mut balances: BTreeMap<(T::AssetId, T::AccountId), T::Balance> = BTreeMap::new();
+			config.assets.iter().for_each(|asset_id| {
+				config.endowed_accounts.iter().for_each(|account_id| {
+					balances.insert((*asset_id, account_id.clone()), config.initial_balance);
+				});
+			});
+			config.endowments.iter().for_each(|(asset_id, account_id, amount)| {
+				let balance = balances.entry((*asset_id, account_id.clone())).or_insert_with(Zero::zero);
+				*balance = balance.saturating_add(*amount);
+			});
+			let mut accounts: BTreeMap<(T::AssetId, T::AccountId), AccountData<T::Balance>> = balances
+				.iter()
+				.map(|(key, balance)| (key.clone(), AccountData { free: *balance, ..Default::default() }))
+				.collect();
+
+			let mut holder_counts: BTreeMap<T::AssetId, u32> = BTreeMap::new();
+			balances.iter().filter(|(_, balance)| !balance.is_zero()).for_each(|((asset_id, account_id), _)| {
+				let count = holder_counts.entry(*asset_id).or_insert(0);
+				storage.insert(<HolderAt<T>>::key_for(&(*asset_id, *count)), parity_codec::Encode::encode(account_id));
+				storage.insert(<HolderPosition<T>>::key_for(asset_id, account_id), parity_codec::Encode::encode(count));
+				*count += 1;
+			});
+			holder_counts.iter().for_each(|(asset_id, count)| {
+				storage.insert(<HolderCount<T>>::key_for(asset_id), parity_codec::Encode::encode(count));
+			});
+
+			config.genesis_locks.iter().for_each(|(account_id, amount, until)| {
+				let lock = BalanceLock {
+					id: GENESIS_LOCK_ID,
+					amount: *amount,
+					until: *until,
+					reasons: WithdrawReasons::all(),
+					metadata: None,
+				};
+				let account = accounts.entry((config.staking_asset_id, account_id.clone())).or_default();
+				account.freeze(&[lock.clone()]);
+				storage.insert(
+					<AssetLocks<T>>::key_for(&config.staking_asset_id, account_id),
+					parity_codec::Encode::encode(&vec![lock])
+				);
+			});
+			accounts.iter().for_each(|((asset_id, account_id), account)| {
+				storage.insert(<AccountStore<T>>::key_for(asset_id, account_id), parity_codec::Encode::encode(account));
+			});
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as system::Trait>::AccountId,
+		<T as system::Trait>::BlockNumber,
+		<T as system::Trait>::Hash,
+		<T as Trait>::Balance,
+		<T as Trait>::AssetId,
+		AssetOptions = AssetOptions<<T as Trait>::Balance, <T as system::Trait>::AccountId>
+	{
+		/// Asset created (asset_id, creator, asset_options).
+		Created(AssetId, AccountId, AssetOptions),
+		/// Reserved asset created by root (asset_id, beneficiary, asset_options).
+		ReservedAssetCreated(AssetId, AccountId, AssetOptions),
+		/// Asset transfer succeeded (asset_id, from, to, amount).
+		Transferred(AssetId, AccountId, AccountId, Balance),
+		/// Asset permission updated (asset_id, new_permissions).
+		PermissionUpdated(AssetId, PermissionLatest<AccountId>),
+		/// New asset minted (asset_id, account, amount).
+		Minted(AssetId, AccountId, Balance),
+		/// Asset burned (asset_id, account, amount).
+		Burned(AssetId, AccountId, Balance),
+		/// Asset mint cooldown updated (asset_id, cooldown).
+		MintCooldownUpdated(AssetId, BlockNumber),
+		/// Asset metadata link updated, `None` if removed (asset_id, metadata_link).
+		MetadataLinkUpdated(AssetId, Option<MetadataLink<Hash>>),
+		/// Asset minimum transfer amount updated (asset_id, amount).
+		MinTransferAmountUpdated(AssetId, Balance),
+		/// Asset co-signer updated (asset_id, co_signer).
+		CoSignerUpdated(AssetId, Option<AccountId>),
+		/// Allowance approved (asset_id, owner, spender, amount).
+		Approved(AssetId, AccountId, AccountId, Balance),
+		/// Allowance revoked (asset_id, owner, spender).
+		ApprovalRevoked(AssetId, AccountId, AccountId),
+		/// Velocity limit set, `None` if removed (asset_id, account, (limit, period)).
+		VelocityLimitSet(AssetId, AccountId, Option<(Balance, BlockNumber)>),
+		/// Velocity limit raise or removal scheduled (asset_id, account, (limit, period), effective_at).
+		VelocityLimitIncreaseScheduled(AssetId, AccountId, Option<(Balance, BlockNumber)>, BlockNumber),
+		/// A transfer exceeded the asset's large transfer threshold (asset_id, from, to, amount).
+		LargeTransfer(AssetId, AccountId, AccountId, Balance),
+		/// Asset minimum balance updated (asset_id, amount).
+		MinimumBalanceUpdated(AssetId, Balance),
+		/// An account's balance below the asset's minimum balance was collected (asset_id, account, amount).
+		DustReaped(AssetId, AccountId, Balance),
+		/// Data in the following event was truncated to `EVENT_DATA_MAX` bytes (original_length).
+		EventTruncated(u32),
+		/// Asset creation stake paid by a sponsor (asset_id, sponsor, creator).
+		CreationSponsored(AssetId, AccountId, AccountId),
+		/// A permission was irreversibly renounced (asset_id, permission).
+		PermissionRenounced(AssetId, PermissionType),
+		/// An asset was frozen (asset_id).
+		AssetFrozen(AssetId),
+		/// An asset was thawed (asset_id).
+		AssetThawed(AssetId),
+		/// An account's balance of an asset was frozen (asset_id, who, until).
+		AccountFrozen(AssetId, AccountId, BlockNumber),
+		/// An account's balance of an asset was unfrozen (asset_id, who).
+		AccountUnfrozen(AssetId, AccountId),
+		/// A runtime module moved an asset between accounts for its own accounting (module, asset_id, from, to, amount).
+		ModuleTransfer(ModuleIdentifier, AssetId, AccountId, AccountId, Balance),
+		/// A transfer was scheduled (transfer_id, asset_id, from, to, amount, execute_at).
+		TransferScheduled(ScheduledTransferId, AssetId, AccountId, AccountId, Balance, BlockNumber),
+		/// A scheduled transfer was executed (transfer_id).
+		ScheduledTransferExecuted(ScheduledTransferId),
+		/// A scheduled transfer could not be made and its amount was returned to the sender (transfer_id).
+		ScheduledTransferFailed(ScheduledTransferId),
+		/// A scheduled transfer was cancelled by its sender (transfer_id).
+		ScheduledTransferCancelled(ScheduledTransferId),
+		/// An account freeze was extended by root (asset_id, who, until).
+		AccountFreezeExtended(AssetId, AccountId, BlockNumber),
+		/// An account freeze expired and the account was unfrozen (asset_id, who).
+		AccountFreezeExpired(AssetId, AccountId),
+		/// An account was denied a permissioned operation on an asset (asset_id, who, permission).
+		PermissionDenied(AssetId, AccountId, PermissionType),
+		/// A transfer was locked under a hashlock (hashlock, asset_id, from, to, amount, timelock).
+		TransferLocked(Hash, AssetId, AccountId, AccountId, Balance, BlockNumber),
+		/// A locked transfer was claimed by revealing its preimage (from, hashlock, preimage).
+		LockedTransferClaimed(AccountId, Hash, Vec<u8>),
+		/// A locked transfer's timelock passed and its amount was returned to the sender (from, hashlock).
+		LockedTransferRefunded(AccountId, Hash),
+		/// The module's storage was migrated to a new layout (layout).
+		StorageMigrated(StorageLayout),
+	}
+);
+
+impl<T: Trait> Module<T> {
+	// PUBLIC IMMUTABLES
+
+	/// Get an account's total balance of an asset kind.
+	pub fn total_balance(asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::free_balance(asset_id, who) + Self::reserved_balance(asset_id, who)
+	}
+
+	/// Get an account's free balance of an asset kind.
+	pub fn free_balance(asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::account(asset_id, who).free
+	}
+
+	/// Get an account's reserved balance of an asset kind.
+	pub fn reserved_balance(asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::account(asset_id, who).reserved
+	}
+
+	/// Get an account's balances of an asset kind.
+	/// Balances not yet moved to `AccountStore` are read from `FreeBalance`, `ReservedBalance` and the locks.
+	pub fn account(asset_id: &T::AssetId, who: &T::AccountId) -> AccountData<T::Balance> {
+		<AccountStore<T>>::get(asset_id, who).unwrap_or_else(|| Self::legacy_account(asset_id, who))
+	}
+
+	/// Creates an asset.
+	///
+	/// # Arguments
+	/// * `asset_id`: An ID of a reserved asset.
+	/// If not provided, a user-generated asset will be created with the next available ID.
+	/// * `from_account`: The initiator account of this call
+	/// * `asset_options`: Asset creation options.
+	///
+	pub fn create_asset(
+		asset_id: Option<T::AssetId>,
+		from_account: Option<T::AccountId>,
+		options: AssetOptions<T::Balance, T::AccountId>,
+	) -> Result {
+		let asset_id = if let Some(asset_id) = asset_id {
+			ensure!(!<TotalIssuance<T>>::exists(&asset_id), "Asset id already taken.");
+			ensure!(asset_id < Self::next_asset_id(), "Asset id not available.");
+			asset_id
+		} else {
+			let asset_id = Self::next_asset_id();
+			let next_id = asset_id
+				.checked_add(&One::one())
+				.ok_or_else(|| "No new user asset id available.")?;
+			<NextAssetId<T>>::put(next_id);
+			asset_id
+		};
+
+		let account_id = from_account.unwrap_or_else(Default::default);
+		let permissions: PermissionVersions<T::AccountId> = options.permissions.clone().into();
+
+		<RegisteredAssets<T>>::mutate(|assets| assets.push(asset_id));
+		<TotalIssuance<T>>::insert(asset_id, &options.initial_issuance);
+		Self::note_supply_change(&asset_id, options.initial_issuance, Zero::zero());
+		Self::mutate_account(&asset_id, &account_id, |account| account.free = options.initial_issuance);
+		Self::note_holder(&asset_id, &account_id);
+		<Permissions<T>>::insert(&asset_id, permissions);
+
+		Self::deposit_event(RawEvent::Created(asset_id, account_id, options));
+
+		Ok(())
+	}
+
+	/// Take the next user asset id for use by another module, e.g. as the id of a non-fungible collection,
+	/// so that it never names a fungible asset.
+	/// The asset creation stake is reserved from `staker` as for `create`, and the allocation counts towards
+	/// the asset creations of the block.
+	pub fn allocate_asset_id(staker: &T::AccountId) -> result::Result<T::AssetId, &'static str> {
+		Self::ensure_can_create_asset()?;
+		let asset_id = Self::next_asset_id();
+		let next_id = asset_id
+			.checked_add(&One::one())
+			.ok_or_else(|| "No new user asset id available.")?;
+		Self::reserve_creation_stake(staker, asset_id)?;
+		<NextAssetId<T>>::put(next_id);
+		<AssetCreations<T>>::mutate(|creations| *creations += 1);
+		Ok(asset_id)
+	}
+
+	/// The hashlock `preimage` matches when hashed with `algorithm`
+	pub fn hashlock_of(algorithm: HashAlgorithm, preimage: &[u8]) -> T::Hash {
+		let mut hashlock = T::Hash::default();
+		for (byte, digest_byte) in hashlock.as_mut().iter_mut().zip(algorithm.hash(preimage).iter()) {
+			*byte = *digest_byte;
+		}
+		hashlock
+	}
+
+	/// Pay `share` of each future asset creation stake to `treasury`, or stop paying a share with `None`
+	pub fn set_creation_stake_treasury(treasury: Option<(T::AccountId, Permill)>) {
+		match treasury {
+			Some(treasury) => <CreationStakeTreasury<T>>::put(treasury),
+			None => <CreationStakeTreasury<T>>::kill(),
+		}
+	}
+
+	/// Return `Ok` if another user asset may be created in this block
+	pub fn ensure_can_create_asset() -> Result {
+		ensure!(
+			Self::asset_creations() < Self::max_asset_creations_per_block(),
+			"too many assets have been created in this block"
+		);
+		Ok(())
+	}
+
+	/// Reserve the asset creation stake for the new asset `id` from `staker`, paying the treasury's share out of
+	/// the reservation, and record the rest in `CreatorStakes`.
+	fn reserve_creation_stake(staker: &T::AccountId, id: T::AssetId) -> Result {
+		let staking_asset_id = Self::staking_asset_id();
+		let mut stake = Self::create_asset_stake();
+		Self::reserve_named(&CREATE_ASSET_RESERVE_ID, &staking_asset_id, staker, stake)?;
+		// The treasury's share of the stake is paid out of the reservation, unless the treasury is frozen
+		if let Some((treasury, share)) = Self::creation_stake_treasury() {
+			let paid = share * stake;
+			if Self::repatriate_reserved_named(&CREATE_ASSET_RESERVE_ID, &staking_asset_id, staker, &treasury, paid)
+				.is_ok()
+			{
+				stake -= paid;
+			}
+		}
+		<CreatorStakes<T>>::mutate(staker, |stakes| stakes.push((id, stake)));
+		Ok(())
+	}
+
+	/// Create a new user asset with the next available id, reserving the creation stake from `staker`
+	/// and giving the initial issuance to `creator`. Returns the new asset id.
+	fn create_staked(
+		staker: &T::AccountId,
+		creator: &T::AccountId,
+		options: AssetOptions<T::Balance, T::AccountId>,
+	) -> result::Result<T::AssetId, &'static str> {
+		Self::ensure_can_create_asset()?;
+		let id = Self::next_asset_id();
+		if staker != creator {
+			Self::ensure_can_receive(&id, staker, creator)?;
+		}
+
+		let permissions: PermissionVersions<T::AccountId> = options.permissions.clone().into();
+
+		// The last available id serves as the overflow mark and won't be used.
+		let next_id = id.checked_add(&One::one()).ok_or_else(|| "No new assets id available.")?;
+
+		// Force to reserve cennz.
+		Self::reserve_creation_stake(staker, id)?;
+
+		<NextAssetId<T>>::put(next_id);
+		<AssetCreations<T>>::mutate(|creations| *creations += 1);
+		<RegisteredAssets<T>>::mutate(|assets| assets.push(id));
+		<TotalIssuance<T>>::insert(id, &options.initial_issuance);
+		Self::note_supply_change(&id, options.initial_issuance, Zero::zero());
+		Self::mutate_account(&id, creator, |account| account.free = options.initial_issuance);
+		Self::note_holder(&id, creator);
+		<Permissions<T>>::insert(&id, permissions);
+
+		Self::deposit_event(RawEvent::Created(id, creator.clone(), options));
+
+		Ok(id)
+	}
+
+	/// Transfer some liquid free balance from one account to another.
+	/// This will not emit the `Transferred` event.
+	pub fn make_transfer(asset_id: &T::AssetId, from: &T::AccountId, to: &T::AccountId, amount: T::Balance) -> Result {
+		let new_balance = Self::ensure_can_transfer(asset_id, from, to, amount)?;
+
+		if from != to {
+			// `new_balance` was read and checked above, write it rather than reading the balance again
+			Self::set_free_balance(asset_id, from, new_balance);
+			Self::snapshot_voting_weight(asset_id, to);
+			Self::mutate_account(asset_id, to, |account| account.free += amount);
+			Self::note_holder(asset_id, to);
+			Self::watch_transfer(asset_id, from, to, amount);
+			Self::note_velocity_spend(asset_id, from, amount);
+			Self::reap_dust(asset_id, from);
+		}
+
+		Ok(())
+	}
+
+	/// Check that `amount` may be transferred from `from` to `to`, returning the sender's free balance after the
+	/// transfer. Every user transfer path makes these checks.
+	fn ensure_can_transfer(
+		asset_id: &T::AssetId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> result::Result<T::Balance, &'static str> {
+		ensure!(
+			amount >= Self::min_transfer_amount(asset_id),
+			"transfer amount is below the asset's minimum transfer amount"
+		);
+		Self::ensure_not_frozen(asset_id)?;
+		Self::ensure_can_receive(asset_id, from, to)?;
+		let new_balance = Self::free_balance(asset_id, from)
+			.checked_sub(&amount)
+			.ok_or_else(|| "balance too low to send amount")?;
+		Self::ensure_can_withdraw(asset_id, from, amount, WithdrawReason::Transfer, new_balance)?;
+		Self::ensure_mint_cooldown_passed(asset_id, from, new_balance)?;
+		Self::ensure_within_velocity_limit(asset_id, from, amount)?;
+		Self::ensure_can_create(asset_id, to, amount)?;
+		Ok(new_balance)
+	}
+
+	/// Transfer some liquid free balance from one account to another on behalf of the runtime `module`,
+	/// e.g. to fund or rebalance module owned accounts. It is not dispatchable, so no transaction fee is paid.
+	/// This will emit the `ModuleTransfer` event rather than `Transferred`.
+	///
+	/// Locks, mint cooldowns, minimum balances and refused incoming transfers still apply. Velocity limits,
+	/// minimum transfer amounts and large transfer watches are for user transfers and do not.
+	pub fn make_module_transfer(
+		module: &ModuleIdentifier,
+		asset_id: &T::AssetId,
 		from: &T::AccountId,
 		to: &T::AccountId,
 		amount: T::Balance,
 	) -> Result {
+		ensure!(!amount.is_zero(), "cannot transfer zero amount");
+		Self::ensure_not_frozen(asset_id)?;
+		Self::ensure_can_receive(asset_id, from, to)?;
 		let new_balance = Self::free_balance(asset_id, from)
 			.checked_sub(&amount)
 			.ok_or_else(|| "balance too low to send amount")?;
 		Self::ensure_can_withdraw(asset_id, from, amount, WithdrawReason::Transfer, new_balance)?;
+		Self::ensure_mint_cooldown_passed(asset_id, from, new_balance)?;
+		Self::ensure_can_create(asset_id, to, amount)?;
+
+		if from != to {
+			Self::set_free_balance(asset_id, from, new_balance);
+			Self::snapshot_voting_weight(asset_id, to);
+			Self::mutate_account(asset_id, to, |account| account.free += amount);
+			Self::note_holder(asset_id, to);
+
+			Self::deposit_event(RawEvent::ModuleTransfer(*module, *asset_id, from.clone(), to.clone(), amount));
+			Self::reap_dust(asset_id, from);
+		}
+
+		Ok(())
+	}
+
+	/// Transfer some liquid free balance from one account to another.
+	/// This will emit the `Transferred` event.
+	pub fn make_transfer_with_event(
+		asset_id: &T::AssetId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> Result {
+		let new_balance = Self::ensure_can_transfer(asset_id, from, to, amount)?;
+
+		if from != to {
+			// `new_balance` was read and checked above, write it rather than reading the balance again
+			Self::set_free_balance(asset_id, from, new_balance);
+			Self::snapshot_voting_weight(asset_id, to);
+			Self::mutate_account(asset_id, to, |account| account.free += amount);
+			Self::note_holder(asset_id, to);
+
+			Self::deposit_event(RawEvent::Transferred(*asset_id, from.clone(), to.clone(), amount));
+			Self::watch_transfer(asset_id, from, to, amount);
+			Self::note_velocity_spend(asset_id, from, amount);
+			Self::reap_dust(asset_id, from);
+		}
 
-		if from != to {
-			<FreeBalance<T>>::mutate(asset_id, from, |balance| *balance -= amount);
-			<FreeBalance<T>>::mutate(asset_id, to, |balance| *balance += amount);
+		Ok(())
+	}
 
-			Self::deposit_event(RawEvent::Transferred(*asset_id, from.clone(), to.clone(), amount));
+	/// Make many transfers as one, emitting `Transferred` for each.
+	/// Every transfer is checked against the balances all of them leave before any is written, so either all are
+	/// made or none are. Senders' dust is reaped once all are made.
+	fn make_transfers(transfers: &[(T::AssetId, T::AccountId, T::AccountId, T::Balance)]) -> Result {
+		let mut debits: BTreeMap<(T::AssetId, T::AccountId), T::Balance> = BTreeMap::new();
+		let mut credits: BTreeMap<(T::AssetId, T::AccountId), T::Balance> = BTreeMap::new();
+		for (asset_id, from, to, amount) in transfers.iter() {
+			ensure!(!amount.is_zero(), "cannot transfer zero amount");
+			ensure!(
+				*amount >= Self::min_transfer_amount(asset_id),
+				"transfer amount is below the asset's minimum transfer amount"
+			);
+			Self::ensure_not_frozen(asset_id)?;
+			Self::ensure_can_receive(asset_id, from, to)?;
+			if from == to {
+				continue;
+			}
+			let debit = debits.entry((*asset_id, from.clone())).or_insert_with(Zero::zero);
+			*debit = debit.checked_add(amount).ok_or_else(|| "transfer amount got overflow")?;
+			let credit = credits.entry((*asset_id, to.clone())).or_insert_with(Zero::zero);
+			*credit = credit.checked_add(amount).ok_or_else(|| "transfer amount got overflow")?;
+		}
+		for ((asset_id, from), debit) in debits.iter() {
+			// Credits to a sender are not counted, it may not spend what it receives within the same call
+			let new_balance = Self::free_balance(asset_id, from)
+				.checked_sub(debit)
+				.ok_or_else(|| "balance too low to send amount")?;
+			Self::ensure_can_withdraw(asset_id, from, *debit, WithdrawReason::Transfer, new_balance)?;
+			Self::ensure_mint_cooldown_passed(asset_id, from, new_balance)?;
+			Self::ensure_within_velocity_limit(asset_id, from, *debit)?;
+		}
+		for ((asset_id, to), credit) in credits.iter() {
+			Self::ensure_can_create(asset_id, to, *credit)?;
+		}
+
+		for (asset_id, from, to, amount) in transfers.iter() {
+			if from != to {
+				Self::set_free_balance(asset_id, from, Self::free_balance(asset_id, from) - *amount);
+				Self::snapshot_voting_weight(asset_id, to);
+				Self::mutate_account(asset_id, to, |account| account.free += *amount);
+				Self::note_holder(asset_id, to);
+
+				Self::deposit_event(RawEvent::Transferred(*asset_id, from.clone(), to.clone(), *amount));
+				Self::watch_transfer(asset_id, from, to, *amount);
+				Self::note_velocity_spend(asset_id, from, *amount);
+			}
+		}
+		for (asset_id, from) in debits.keys() {
+			Self::reap_dust(asset_id, from);
 		}
 
 		Ok(())
@@ -588,15 +2147,17 @@ impl<T: Trait> Module<T> {
 
 	/// Move `amount` from free balance to reserved balance.
 	///
-	/// If the free balance is lower than `amount`, then no funds will be moved and an `Err` will
-	/// be returned. This is different behavior than `unreserve`.
+	/// If the free balance is lower than `amount`, or locks or vesting hold it, then no funds will be moved
+	/// and an `Err` will be returned. This is different behavior than `unreserve`.
 	pub fn reserve(asset_id: &T::AssetId, who: &T::AccountId, amount: T::Balance) -> Result {
+		Self::ensure_not_frozen(asset_id)?;
 		// Do we need to consider that this is an atomic transaction?
 		let original_reserve_balance = Self::reserved_balance(asset_id, who);
 		let original_free_balance = Self::free_balance(asset_id, who);
 		if original_free_balance < amount {
 			return Err("not enough free funds");
 		}
+		Self::ensure_can_withdraw(asset_id, who, amount, WithdrawReason::Reserve, original_free_balance - amount)?;
 		let new_reserve_balance = original_reserve_balance + amount;
 		Self::set_reserved_balance(asset_id, who, new_reserve_balance);
 		let new_free_balance = original_free_balance - amount;
@@ -604,6 +2165,15 @@ impl<T: Trait> Module<T> {
 		Ok(())
 	}
 
+	/// Whether `amount` of the free balance of an asset under an account can be reserved,
+	/// i.e. it is available and not held by a lock or vesting schedule.
+	pub fn can_reserve(asset_id: &T::AssetId, who: &T::AccountId, amount: T::Balance) -> bool {
+		let free_balance = Self::free_balance(asset_id, who);
+		!Self::is_frozen(asset_id)
+			&& free_balance >= amount
+			&& Self::ensure_can_withdraw(asset_id, who, amount, WithdrawReason::Reserve, free_balance - amount).is_ok()
+	}
+
 	/// Moves up to `amount` from reserved balance to free balance. This function cannot fail.
 	///
 	/// As many assets up to `amount` will be moved as possible. If the reserve balance of `who`
@@ -619,6 +2189,111 @@ impl<T: Trait> Module<T> {
 		amount - actual
 	}
 
+	/// Move `amount` from free balance to reserved balance, attributing it to the reservation `id`.
+	///
+	/// Fails in the same way as `reserve`.
+	pub fn reserve_named(
+		id: &ReserveIdentifier,
+		asset_id: &T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> Result {
+		Self::reserve(asset_id, who, amount)?;
+		<ReservedByPurpose<T>>::mutate(asset_id, who, |reserves| {
+			match reserves.iter_mut().find(|(reserve_id, _)| reserve_id == id) {
+				Some((_, reserved)) => *reserved += amount,
+				None => reserves.push((*id, amount)),
+			}
+		});
+		Ok(())
+	}
+
+	/// Moves up to `amount` held by the reservation `id` from reserved balance to free balance.
+	/// This function cannot fail.
+	///
+	/// If less than `amount` is held by the reservation, then the remaining amount will be returned.
+	pub fn unreserve_named(
+		id: &ReserveIdentifier,
+		asset_id: &T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> T::Balance {
+		let actual = Self::release_named(id, asset_id, who, amount);
+		amount - actual + Self::unreserve(asset_id, who, actual)
+	}
+
+	/// Move up to `amount` held by the reservation `id` on `who` to the free balance of `beneficiary`.
+	///
+	/// If less than `amount` is held by the reservation, then the remaining amount will be returned.
+	pub fn repatriate_reserved_named(
+		id: &ReserveIdentifier,
+		asset_id: &T::AssetId,
+		who: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: T::Balance,
+	) -> rstd::result::Result<T::Balance, &'static str> {
+		if beneficiary != who {
+			Self::ensure_account_not_frozen(asset_id, beneficiary)?;
+		}
+		let actual = Self::release_named(id, asset_id, who, amount);
+		Ok(amount - actual + Self::repatriate_reserved(asset_id, who, beneficiary, actual)?)
+	}
+
+	/// Take up to `amount` off the reservation `id` on `who`, returning the amount taken.
+	/// The reserved balance itself is left for the caller to move.
+	fn release_named(
+		id: &ReserveIdentifier,
+		asset_id: &T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> T::Balance {
+		let mut reserves = <ReservedByPurpose<T>>::get(asset_id, who);
+		let actual = match reserves.iter_mut().find(|(reserve_id, _)| reserve_id == id) {
+			Some((_, reserved)) => {
+				let actual = rstd::cmp::min(*reserved, amount);
+				*reserved -= actual;
+				actual
+			}
+			None => return Zero::zero(),
+		};
+
+		reserves.retain(|(_, reserved)| !reserved.is_zero());
+		if reserves.is_empty() {
+			<ReservedByPurpose<T>>::remove(asset_id, who);
+		} else {
+			<ReservedByPurpose<T>>::insert(asset_id, who, reserves);
+		}
+		actual
+	}
+
+	/// The breakdown of an account's reserved balance of an asset by the module which reserved it.
+	/// Any reserved balance not attributed to a named reservation is listed under an empty name.
+	pub fn reserves(asset_id: &T::AssetId, who: &T::AccountId) -> Vec<(Vec<u8>, T::Balance)> {
+		let mut unattributed = Self::reserved_balance(asset_id, who);
+		let mut breakdown: Vec<(Vec<u8>, T::Balance)> = Vec::new();
+		for (id, reserved) in Self::reserved_by_purpose(asset_id, who) {
+			// reserved balance may have been slashed without its reservation being updated
+			let reserved = rstd::cmp::min(reserved, unattributed);
+			unattributed -= reserved;
+			if !reserved.is_zero() {
+				breakdown.push((lock_module_name(&id), reserved));
+			}
+		}
+		if !unattributed.is_zero() {
+			breakdown.push((Vec::new(), unattributed));
+		}
+		breakdown
+	}
+
+	/// The total staking asset `who` has reserved for creating assets, and the stake held for each asset.
+	pub fn creator_stakes(who: &T::AccountId) -> (T::Balance, Vec<(T::AssetId, T::Balance)>) {
+		let stakes = Self::creator_stakes_of(who);
+		let total = stakes
+			.iter()
+			.fold(Zero::zero(), |total: T::Balance, (_, stake)| total.saturating_add(*stake));
+		(total, stakes)
+	}
+
 	/// Deduct up to `amount` from the combined balance of `who`, preferring to deduct from the
 	/// free balance. This function cannot fail.
 	///
@@ -637,14 +2312,23 @@ impl<T: Trait> Module<T> {
 	}
 
 	/// Adds up to `amount` to the free balance of `who`.
+	/// Fails if `who` is frozen for the asset.
 	pub fn reward(asset_id: &T::AssetId, who: &T::AccountId, amount: T::Balance) -> Result {
+		Self::ensure_account_not_frozen(asset_id, who)?;
 		let original_free_balance = Self::free_balance(asset_id, who);
 		let new_free_balance = original_free_balance + amount;
 		Self::set_free_balance(asset_id, who, new_free_balance);
 		<TotalIssuance<T>>::mutate(asset_id, |x| *x = x.saturating_add(amount));
+		Self::note_supply_change(asset_id, amount, Zero::zero());
 		Ok(())
 	}
 
+	/// Reduces the total issuance of an asset by `amount`, for balance removed without an imbalance.
+	pub fn decrease_total_issuance(asset_id: &T::AssetId, amount: T::Balance) {
+		<TotalIssuance<T>>::mutate(asset_id, |x| *x = x.saturating_sub(amount));
+		Self::note_supply_change(asset_id, Zero::zero(), amount);
+	}
+
 	/// Deducts up to `amount` from reserved balance of `who`. This function cannot fail.
 	///
 	/// As much funds up to `amount` will be deducted as possible. If the reserve balance of `who`
@@ -662,8 +2346,8 @@ impl<T: Trait> Module<T> {
 	}
 
 	/// Move up to `amount` from reserved balance of account `who` to free balance of account
-	/// `beneficiary`. `beneficiary` must exist for this to succeed. If it does not, `Err` will be
-	/// returned.
+	/// `beneficiary`. `beneficiary` must exist and not be frozen for the asset for this to succeed.
+	/// If it does not, `Err` will be returned.
 	///
 	/// As much funds up to `amount` will be moved as possible. If this is less than `amount`, then
 	/// the `remaining` would be returned, else `Zero::zero()`.
@@ -673,6 +2357,9 @@ impl<T: Trait> Module<T> {
 		beneficiary: &T::AccountId,
 		amount: T::Balance,
 	) -> rstd::result::Result<T::Balance, &'static str> {
+		if beneficiary != who {
+			Self::ensure_account_not_frozen(asset_id, beneficiary)?;
+		}
 		let b = Self::reserved_balance(asset_id, who);
 		let slash = rstd::cmp::min(b, amount);
 
@@ -685,7 +2372,80 @@ impl<T: Trait> Module<T> {
 		Ok(amount - slash)
 	}
 
-	/// Check permission to perform burn, mint or update.
+	/// Execute a due scheduled transfer. Its amount is released to the sender and transferred as an ordinary
+	/// transfer, so it is left with the sender if the transfer is not allowed at this time.
+	fn execute_scheduled_transfer(transfer_id: ScheduledTransferId) {
+		let transfer = match <ScheduledTransfers<T>>::take(transfer_id) {
+			Some(transfer) => transfer,
+			None => return,
+		};
+		Self::unreserve_named(&SCHEDULED_TRANSFER_RESERVE_ID, &transfer.asset_id, &transfer.from, transfer.amount);
+		if Self::make_transfer_with_event(&transfer.asset_id, &transfer.from, &transfer.to, transfer.amount).is_ok() {
+			Self::deposit_event(RawEvent::ScheduledTransferExecuted(transfer_id));
+		} else {
+			Self::deposit_event(RawEvent::ScheduledTransferFailed(transfer_id));
+		}
+	}
+
+	/// An asset may not be transferred, minted, burned or reserved while it is frozen
+	fn ensure_not_frozen(asset_id: &T::AssetId) -> Result {
+		ensure!(!Self::is_frozen(asset_id), "asset is frozen");
+		Ok(())
+	}
+
+	/// Whether the `asset_id` balance of `who` is frozen
+	pub fn is_account_frozen(asset_id: &T::AssetId, who: &T::AccountId) -> bool {
+		Self::account_frozen_until(asset_id, who).is_some()
+	}
+
+	/// Queue the freeze of `who`'s `asset_id` balance to expire at the start of block `until`
+	fn schedule_account_thaw(asset_id: &T::AssetId, who: &T::AccountId, until: T::BlockNumber) -> Result {
+		let mut expiring = Self::account_freezes_expiring(until);
+		ensure!(expiring.len() < ACCOUNT_FREEZES_PER_BLOCK_MAX, "too many account freezes expire at the block");
+		expiring.push((*asset_id, who.clone()));
+		<AccountFreezesExpiring<T>>::insert(until, expiring);
+		Ok(())
+	}
+
+	fn unschedule_account_thaw(asset_id: &T::AssetId, who: &T::AccountId, until: T::BlockNumber) {
+		<AccountFreezesExpiring<T>>::mutate(until, |expiring| {
+			expiring.retain(|(expiring_asset_id, expiring_who)| expiring_asset_id != asset_id || expiring_who != who)
+		});
+	}
+
+	/// A frozen account may neither send nor receive the asset
+	fn ensure_account_not_frozen(asset_id: &T::AssetId, who: &T::AccountId) -> Result {
+		ensure!(!Self::is_account_frozen(asset_id, who), "account is frozen");
+		Ok(())
+	}
+
+	/// Whether the supply of an asset is fixed, i.e. its mint permission has been renounced.
+	pub fn is_fixed_supply(asset_id: &T::AssetId) -> bool {
+		Self::renounced_permissions(asset_id).contains(&PermissionType::Mint)
+	}
+
+	/// Whether the update permission on an asset has been renounced and no other permission has an owner,
+	/// so nothing about it can change.
+	pub fn is_immutable(asset_id: &T::AssetId) -> bool {
+		// Without an update owner, permissions without an owner can never be granted again
+		let permissions: PermissionLatest<T::AccountId> = Self::get_permission(asset_id).into();
+		Self::renounced_permissions(asset_id).contains(&PermissionType::Update) && permissions.is_empty()
+	}
+
+	/// Whether `permissions` leaves every renounced permission of an asset without an owner.
+	fn keeps_renounced(asset_id: &T::AssetId, permissions: &PermissionLatest<T::AccountId>) -> bool {
+		Self::renounced_permissions(asset_id).iter().all(|permission| {
+			let owner = match permission {
+				PermissionType::Burn => &permissions.burn,
+				PermissionType::Mint => &permissions.mint,
+				PermissionType::Update => &permissions.update,
+				PermissionType::Freeze => &permissions.freeze,
+			};
+			*owner == Owner::None
+		})
+	}
+
+	/// Check permission to perform burn, mint, update or freeze.
 	///
 	/// # Arguments
 	/// * `asset_id`:  A `T::AssetId` type that contains the `asset_id`, which has the permission embedded.
@@ -718,10 +2478,32 @@ impl<T: Trait> Module<T> {
 					..
 				},
 			) => account == *who,
+			(
+				PermissionType::Freeze,
+				PermissionLatest {
+					freeze: Owner::Address(account),
+					..
+				},
+			) => account == *who,
 			_ => false,
 		}
 	}
 
+	/// Return `Ok` iff `who` has permission `what` on the asset, otherwise emit `PermissionDenied` and fail with
+	/// `error`. The event lets indexers see rejected supply and permission changes.
+	fn ensure_permission(
+		asset_id: &T::AssetId,
+		who: &T::AccountId,
+		what: PermissionType,
+		error: &'static str,
+	) -> Result {
+		if Self::check_permission(asset_id, who, &what) {
+			return Ok(());
+		}
+		Self::deposit_event(RawEvent::PermissionDenied(*asset_id, who.clone(), what));
+		Err(error)
+	}
+
 	/// Return `Ok` iff the account is able to make a withdrawal of the given amount
 	/// for the given reason.
 	///
@@ -733,16 +2515,21 @@ impl<T: Trait> Module<T> {
 		reason: WithdrawReason,
 		new_balance: T::Balance,
 	) -> Result {
-		if asset_id != &Self::staking_asset_id() {
+		Self::ensure_account_not_frozen(asset_id, who)?;
+		ensure!(
+			new_balance >= Self::vesting_balance(asset_id, who),
+			"vesting balance too high to send value"
+		);
+		// The frozen amount is the largest lock, so the locks need only be read when it is not covered
+		if new_balance >= Self::account(asset_id, who).frozen(reason) {
 			return Ok(());
 		}
-
-		let locks = Self::locks(who);
+		let locks = Self::locks(asset_id, who);
 		if locks.is_empty() {
 			return Ok(());
 		}
 		let now = <system::Module<T>>::block_number();
-		if Self::locks(who)
+		if locks
 			.into_iter()
 			.all(|l| now >= l.until || new_balance >= l.amount || !l.reasons.contains(reason))
 		{
@@ -752,26 +2539,421 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
+	/// Get the balance of an asset under an account which is still locked by its vesting schedule.
+	pub fn vesting_balance(asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
+		<Vesting<T>>::get(asset_id, who)
+			.map(|schedule| schedule.locked_at(<system::Module<T>>::block_number()))
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Get the minted balance of an asset under an account which is still cooling down.
+	pub fn cooling_down_balance(asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
+		let now = <system::Module<T>>::block_number();
+		<MintBatches<T>>::get(asset_id, who)
+			.into_iter()
+			.filter(|batch| batch.until > now)
+			.fold(Zero::zero(), |total, batch| total.saturating_add(batch.amount))
+	}
+
+	/// Return `Ok` iff the account would keep at least its cooling down minted balance
+	/// after a transfer leaving it with `new_balance`.
+	pub fn ensure_mint_cooldown_passed(asset_id: &T::AssetId, who: &T::AccountId, new_balance: T::Balance) -> Result {
+		if new_balance >= Self::cooling_down_balance(asset_id, who) {
+			Ok(())
+		} else {
+			Err("minted balance is still in its transfer cooldown")
+		}
+	}
+
+	/// Return `Ok` iff `to` accepts transfers of the asset from `from` under its receive rules.
+	pub fn ensure_can_receive(asset_id: &T::AssetId, from: &T::AccountId, to: &T::AccountId) -> Result {
+		Self::ensure_account_not_frozen(asset_id, to)?;
+		let (denied_assets, denied_senders) = Self::receive_rules(to);
+		if denied_assets.contains(asset_id) || denied_senders.contains(from) {
+			Err("receiver does not accept this transfer")
+		} else {
+			Ok(())
+		}
+	}
+
+	/// The governance voting weight of `who` as of the start of the snapshot period containing `block`:
+	/// its free staking asset balance, which includes locked balance but not reserved balance.
+	/// Returns `None` if `block` is in the future or older than the snapshot history.
+	pub fn voting_weight_at(who: &T::AccountId, block: T::BlockNumber) -> Option<T::Balance> {
+		let period = block.as_() / VOTING_SNAPSHOT_PERIOD;
+		let current_period = <system::Module<T>>::block_number().as_() / VOTING_SNAPSHOT_PERIOD;
+		if period > current_period || period < current_period.saturating_sub(VOTING_SNAPSHOT_HISTORY) {
+			return None;
+		}
+
+		// The balance is unchanged from the start of `period` until the first period it changed in after that
+		let weight = Self::voting_snapshots(who)
+			.into_iter()
+			.find(|(snapshot_period, _)| *snapshot_period >= period)
+			.map(|(_, balance)| balance)
+			.unwrap_or_else(|| Self::free_balance(&Self::staking_asset_id(), who));
+		Some(weight)
+	}
+
+	/// Snapshot the staking asset balance of `who` before it first changes in the current snapshot period.
+	/// Must be called before the balance is written.
+	fn snapshot_voting_weight(asset_id: &T::AssetId, who: &T::AccountId) {
+		if *asset_id != Self::staking_asset_id() {
+			return;
+		}
+		let period = <system::Module<T>>::block_number().as_() / VOTING_SNAPSHOT_PERIOD;
+		let mut snapshots = Self::voting_snapshots(who);
+		if snapshots.last().map_or(false, |(last, _)| *last == period) {
+			return;
+		}
+		let oldest = period.saturating_sub(VOTING_SNAPSHOT_HISTORY);
+		snapshots.retain(|(snapshot_period, _)| *snapshot_period >= oldest);
+		snapshots.push((period, Self::free_balance(asset_id, who)));
+		<VotingSnapshots<T>>::insert(who, snapshots);
+	}
+
+	/// The velocity limit of `who` on an asset as of the current block, with any due pending change applied
+	/// and the period rolled over if it has ended.
+	pub fn velocity_limit(
+		asset_id: &T::AssetId,
+		who: &T::AccountId,
+	) -> Option<VelocityLimit<T::Balance, T::BlockNumber>> {
+		let now = <system::Module<T>>::block_number();
+		let mut velocity_limit = <VelocityLimits<T>>::get(asset_id, who);
+		if let Some((pending, effective_at)) = Self::pending_velocity_limit(asset_id, who) {
+			if now >= effective_at {
+				velocity_limit = pending.map(|(limit, period)| VelocityLimit {
+					limit,
+					period,
+					spent: velocity_limit.as_ref().map(|v| v.spent).unwrap_or_else(Zero::zero),
+					period_start: velocity_limit.as_ref().map(|v| v.period_start).unwrap_or(now),
+				});
+			}
+		}
+		velocity_limit.map(|mut v| {
+			if now >= v.period_start + v.period {
+				// Periods stay aligned with the block the limit was set at
+				v.period_start = now - (now - v.period_start) % v.period;
+				v.spent = Zero::zero();
+			}
+			v
+		})
+	}
+
+	/// A transfer of `amount` out of `who` must be within its velocity limit, if any
+	fn ensure_within_velocity_limit(asset_id: &T::AssetId, who: &T::AccountId, amount: T::Balance) -> Result {
+		match Self::velocity_limit(asset_id, who) {
+			Some(ref v) if v.spent.saturating_add(amount) > v.limit => {
+				Err("transfer exceeds the account's velocity limit")
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Record a transfer of `amount` out of `who` against its velocity limit, if any
+	fn note_velocity_spend(asset_id: &T::AssetId, who: &T::AccountId, amount: T::Balance) {
+		let pending_due = Self::pending_velocity_limit(asset_id, who)
+			.map_or(false, |(_, effective_at)| <system::Module<T>>::block_number() >= effective_at);
+		let velocity_limit = Self::velocity_limit(asset_id, who);
+		if pending_due {
+			<PendingVelocityLimits<T>>::remove(asset_id, who);
+		}
+		match velocity_limit {
+			Some(mut v) => {
+				v.spent = v.spent.saturating_add(amount);
+				<VelocityLimits<T>>::insert(asset_id, who, v);
+			}
+			None => <VelocityLimits<T>>::remove(asset_id, who),
+		}
+	}
+
+	/// A transfer of `amount` may not leave `to` with a non-zero balance below the asset's minimum balance
+	fn ensure_can_create(asset_id: &T::AssetId, to: &T::AccountId, amount: T::Balance) -> Result {
+		if Self::total_balance(asset_id, to).saturating_add(amount) < Self::minimum_balance(asset_id) {
+			Err("destination balance too low to receive value")
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Get the liquidity locks on the balance of an asset under an account, ordered by `until`.
+	pub fn locks(asset_id: &T::AssetId, who: &T::AccountId) -> Vec<BalanceLock<T::Balance, T::BlockNumber>> {
+		if *asset_id == Self::staking_asset_id() && <Locks<T>>::exists(who) {
+			return <Locks<T>>::get(who);
+		}
+		<AssetLocks<T>>::get(asset_id, who)
+	}
+
+	/// Get a page of the balance locks of an asset on `who`, ordered by `until`.
+	/// Each lock is annotated with the name of the module which set it.
+	pub fn locks_paged(
+		asset_id: &T::AssetId,
+		who: &T::AccountId,
+		page: u32,
+		page_size: u32,
+	) -> Vec<(Vec<u8>, BalanceLock<T::Balance, T::BlockNumber>)> {
+		Self::locks(asset_id, who)
+			.into_iter()
+			.skip(page.saturating_mul(page_size) as usize)
+			.take(page_size as usize)
+			.map(|lock| (lock_module_name(&lock.id), lock))
+			.collect()
+	}
+
+	/// Get a page of the accounts holding a non-zero free or reserved balance of an asset, in no particular order.
+	/// Positions are reused as holders leave, so a holder may move between pages from one block to the next.
+	/// Holders are indexed as their balances are written, older holders once listed to `migrate_storage`.
+	pub fn holders_paged(asset_id: &T::AssetId, page: u32, page_size: u32) -> Vec<T::AccountId> {
+		let start = page.saturating_mul(page_size);
+		let end = start.saturating_add(page_size).min(Self::holder_count(asset_id));
+		(start..end).map(|position| <HolderAt<T>>::get((*asset_id, position))).collect()
+	}
+
+	/// Get the future unlocks of every asset on `who`: lock expiries, vesting ends and mint cooldowns,
+	/// ordered by block. Locks without an expiry are left out, lock amounts overlap rather than add up.
+	pub fn unlock_timeline(who: &T::AccountId) -> Vec<Unlock<T::AssetId, T::Balance, T::BlockNumber>> {
+		let now = <system::Module<T>>::block_number();
+		let mut timeline = Vec::new();
+		for asset_id in Self::registered_assets() {
+			for lock in Self::locks(&asset_id, who) {
+				if lock.until > now && lock.until != T::BlockNumber::max_value() {
+					timeline.push(Unlock {
+						asset_id,
+						amount: lock.amount,
+						at: lock.until,
+						reason: UnlockReason::Lock(lock.id),
+					});
+				}
+			}
+			if let Some(schedule) = <Vesting<T>>::get(&asset_id, who) {
+				let remaining = schedule.locked_at(now);
+				if !remaining.is_zero() {
+					let mut blocks_left = remaining / schedule.per_block;
+					if !(remaining % schedule.per_block).is_zero() {
+						blocks_left += One::one();
+					}
+					timeline.push(Unlock {
+						asset_id,
+						amount: remaining,
+						at: now.max(schedule.starting_block) + T::BlockNumber::sa(blocks_left.as_()),
+						reason: UnlockReason::Vesting,
+					});
+				}
+			}
+			for batch in <MintBatches<T>>::get(&asset_id, who) {
+				if batch.until > now {
+					timeline.push(Unlock {
+						asset_id,
+						amount: batch.amount,
+						at: batch.until,
+						reason: UnlockReason::MintCooldown,
+					});
+				}
+			}
+		}
+		timeline.sort_by(|a, b| a.at.cmp(&b.at));
+		timeline
+	}
+
+	/// Limit `data` to `EVENT_DATA_MAX` bytes before it is embedded in an event.
+	/// If it is truncated an `EventTruncated` marker is deposited, the caller should deposit its event straight after.
+	/// Modules putting user supplied names, memos or metadata into events should pass them through here.
+	pub fn bounded_event_data(mut data: Vec<u8>) -> Vec<u8> {
+		if data.len() > EVENT_DATA_MAX {
+			Self::deposit_event(RawEvent::EventTruncated(data.len() as u32));
+			data.truncate(EVENT_DATA_MAX);
+		}
+		data
+	}
+
+	/// Set the free and reserved balance of an asset under an account, adjusting the total issuance to match.
+	/// Only for setting up tests and benchmarks.
+	#[cfg(feature = "test-utils")]
+	pub fn force_set_balance(asset_id: &T::AssetId, who: &T::AccountId, free: T::Balance, reserved: T::Balance) {
+		let old_total = Self::total_balance(asset_id, who);
+		let new_total = free.saturating_add(reserved);
+		Self::set_free_balance(asset_id, who, free);
+		Self::set_reserved_balance(asset_id, who, reserved);
+		if new_total > old_total {
+			<TotalIssuance<T>>::mutate(asset_id, |x| *x = x.saturating_add(new_total - old_total));
+			Self::note_supply_change(asset_id, new_total - old_total, Zero::zero());
+		} else {
+			Self::decrease_total_issuance(asset_id, old_total - new_total);
+		}
+	}
+
 	// PRIVATE MUTABLES
 
+	/// Record a change to the total issuance of an asset in this block's supply deltas.
+	fn note_supply_change(asset_id: &T::AssetId, increase: T::Balance, decrease: T::Balance) {
+		if increase.is_zero() && decrease.is_zero() {
+			return;
+		}
+		<SupplyDeltas<T>>::mutate(|deltas| match deltas.iter_mut().find(|(id, _, _)| id == asset_id) {
+			Some((_, total_increase, total_decrease)) => {
+				*total_increase = total_increase.saturating_add(increase);
+				*total_decrease = total_decrease.saturating_add(decrease);
+			}
+			None => deltas.push((*asset_id, increase, decrease)),
+		});
+	}
+
+	/// Raise a `LargeTransfer` event if `amount` exceeds the asset's large transfer threshold.
+	fn watch_transfer(asset_id: &T::AssetId, from: &T::AccountId, to: &T::AccountId, amount: T::Balance) {
+		if let Some(threshold) = Self::large_transfer_threshold(asset_id) {
+			if amount > threshold {
+				Self::deposit_event(RawEvent::LargeTransfer(*asset_id, from.clone(), to.clone(), amount));
+			}
+		}
+	}
+
+	/// Record a minted balance for `who` if the asset has a mint cooldown.
+	/// Batches whose cooldown has passed are pruned.
+	fn record_mint_batch(asset_id: &T::AssetId, who: &T::AccountId, amount: T::Balance) {
+		let cooldown = Self::mint_cooldown(asset_id);
+		let now = <system::Module<T>>::block_number();
+		let mut batches: Vec<_> = <MintBatches<T>>::get(asset_id, who)
+			.into_iter()
+			.filter(|batch| batch.until > now)
+			.collect();
+
+		if !cooldown.is_zero() && !amount.is_zero() {
+			batches.push(MintBatch {
+				amount,
+				until: now + cooldown,
+			});
+		}
+
+		if batches.is_empty() {
+			<MintBatches<T>>::remove(asset_id, who);
+		} else {
+			<MintBatches<T>>::insert(asset_id, who, batches);
+		}
+	}
+
+	/// Collect the free balance of `who` if it is below the asset's minimum balance and nothing is reserved.
+	/// Balances under an active lock or vesting schedule are left alone, the dust is not the account's to give up.
+	/// The dust goes to the dust collector, or is burned if there is none.
+	fn reap_dust(asset_id: &T::AssetId, who: &T::AccountId) {
+		let dust = Self::free_balance(asset_id, who);
+		if dust.is_zero()
+			|| dust >= Self::minimum_balance(asset_id)
+			|| !Self::reserved_balance(asset_id, who).is_zero()
+			|| !Self::vesting_balance(asset_id, who).is_zero()
+		{
+			return;
+		}
+		let now = <system::Module<T>>::block_number();
+		if Self::locks(asset_id, who).iter().any(|lock| lock.until > now) {
+			return;
+		}
+		Self::snapshot_voting_weight(asset_id, who);
+		Self::mutate_account(asset_id, who, |account| account.free = Zero::zero());
+		Self::note_holder(asset_id, who);
+		match Self::dust_collector() {
+			Some(ref collector) if collector != who => {
+				Self::snapshot_voting_weight(asset_id, collector);
+				Self::mutate_account(asset_id, collector, |account| account.free = account.free.saturating_add(dust));
+				Self::note_holder(asset_id, collector);
+			}
+			_ => Self::decrease_total_issuance(asset_id, dust),
+		}
+		Self::deposit_event(RawEvent::DustReaped(*asset_id, who.clone(), dust));
+	}
+
 	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
 	/// the caller will do this.
 	fn set_reserved_balance(asset_id: &T::AssetId, who: &T::AccountId, balance: T::Balance) {
-		<ReservedBalance<T>>::insert(asset_id, who, balance);
+		Self::mutate_account(asset_id, who, |account| account.reserved = balance);
+		Self::note_holder(asset_id, who);
 	}
 
 	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
 	/// the caller will do this.
 	fn set_free_balance(asset_id: &T::AssetId, who: &T::AccountId, balance: T::Balance) {
-		<FreeBalance<T>>::insert(asset_id, who, balance);
+		Self::snapshot_voting_weight(asset_id, who);
+		Self::mutate_account(asset_id, who, |account| account.free = balance);
+		Self::note_holder(asset_id, who);
+	}
+
+	/// Apply `f` to the balances of an asset under an account, storing them in `AccountStore`.
+	/// Balances still in `FreeBalance` and `ReservedBalance` are moved, an account left empty is removed.
+	fn mutate_account<F: FnOnce(&mut AccountData<T::Balance>)>(asset_id: &T::AssetId, who: &T::AccountId, f: F) {
+		let mut account = match <AccountStore<T>>::get(asset_id, who) {
+			Some(account) => account,
+			None => {
+				let account = Self::legacy_account(asset_id, who);
+				<FreeBalance<T>>::remove(asset_id, who);
+				<ReservedBalance<T>>::remove(asset_id, who);
+				account
+			}
+		};
+		f(&mut account);
+		if account == AccountData::default() {
+			<AccountStore<T>>::remove(asset_id, who);
+		} else {
+			<AccountStore<T>>::insert(asset_id, who, account);
+		}
+	}
+
+	/// The balances of an account from before they were kept in `AccountStore`
+	fn legacy_account(asset_id: &T::AssetId, who: &T::AccountId) -> AccountData<T::Balance> {
+		let mut account = AccountData {
+			free: <FreeBalance<T>>::get(asset_id, who),
+			reserved: <ReservedBalance<T>>::get(asset_id, who),
+			..Default::default()
+		};
+		account.freeze(&Self::locks(asset_id, who));
+		account
+	}
+
+	/// Add `who` to or remove it from the holders of the asset, as its total balance became non-zero or zero.
+	/// A leaving holder's position is taken by the last holder, so positions stay contiguous.
+	fn note_holder(asset_id: &T::AssetId, who: &T::AccountId) {
+		let holds = !Self::total_balance(asset_id, who).is_zero();
+		match <HolderPosition<T>>::get(asset_id, who) {
+			None if holds => {
+				let count = Self::holder_count(asset_id);
+				<HolderAt<T>>::insert((*asset_id, count), who.clone());
+				<HolderPosition<T>>::insert(asset_id, who, count);
+				<HolderCount<T>>::insert(asset_id, count + 1);
+			}
+			Some(position) if !holds => {
+				let last = Self::holder_count(asset_id).saturating_sub(1);
+				if position != last {
+					let moved = <HolderAt<T>>::get((*asset_id, last));
+					<HolderAt<T>>::insert((*asset_id, position), moved.clone());
+					<HolderPosition<T>>::insert(asset_id, &moved, position);
+				}
+				<HolderAt<T>>::remove((*asset_id, last));
+				<HolderPosition<T>>::remove(asset_id, who);
+				<HolderCount<T>>::insert(asset_id, last);
+			}
+			_ => (),
+		}
 	}
 
 	fn set_lock(
+		asset_id: &T::AssetId,
+		id: LockIdentifier,
+		who: &T::AccountId,
+		amount: T::Balance,
+		until: T::BlockNumber,
+		reasons: WithdrawReasons,
+	) {
+		Self::set_lock_with_metadata(asset_id, id, who, amount, until, reasons, None)
+	}
+
+	/// Set a lock on the `asset_id` balance of `who` with some human readable `metadata`
+	/// explaining why the balance is locked.
+	pub fn set_lock_with_metadata(
+		asset_id: &T::AssetId,
 		id: LockIdentifier,
 		who: &T::AccountId,
 		amount: T::Balance,
 		until: T::BlockNumber,
 		reasons: WithdrawReasons,
+		metadata: Option<Vec<u8>>,
 	) {
 		let now = <system::Module<T>>::block_number();
 		let mut new_lock = Some(BalanceLock {
@@ -779,8 +2961,9 @@ impl<T: Trait> Module<T> {
 			amount,
 			until,
 			reasons,
+			metadata,
 		});
-		let mut locks = <Module<T>>::locks(who)
+		let mut locks = <Module<T>>::locks(asset_id, who)
 			.into_iter()
 			.filter_map(|l| {
 				if l.id == id {
@@ -795,10 +2978,11 @@ impl<T: Trait> Module<T> {
 		if let Some(lock) = new_lock {
 			locks.push(lock)
 		}
-		<Locks<T>>::insert(who, locks);
+		Self::put_locks(asset_id, who, locks);
 	}
 
 	fn extend_lock(
+		asset_id: &T::AssetId,
 		id: LockIdentifier,
 		who: &T::AccountId,
 		amount: T::Balance,
@@ -811,8 +2995,9 @@ impl<T: Trait> Module<T> {
 			amount,
 			until,
 			reasons,
+			metadata: None,
 		});
-		let mut locks = <Module<T>>::locks(who)
+		let mut locks = <Module<T>>::locks(asset_id, who)
 			.into_iter()
 			.filter_map(|l| {
 				if l.id == id {
@@ -821,6 +3006,7 @@ impl<T: Trait> Module<T> {
 						amount: l.amount.max(nl.amount),
 						until: l.until.max(nl.until),
 						reasons: l.reasons | nl.reasons,
+						metadata: l.metadata,
 					})
 				} else if l.until > now {
 					Some(l)
@@ -832,21 +3018,48 @@ impl<T: Trait> Module<T> {
 		if let Some(lock) = new_lock {
 			locks.push(lock)
 		}
-		<Locks<T>>::insert(who, locks);
+		Self::put_locks(asset_id, who, locks);
 	}
 
-	fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
+	fn remove_lock(asset_id: &T::AssetId, id: LockIdentifier, who: &T::AccountId) {
 		let now = <system::Module<T>>::block_number();
-		let locks = <Module<T>>::locks(who)
+		let locks = <Module<T>>::locks(asset_id, who)
 			.into_iter()
 			.filter_map(|l| if l.until > now && l.id != id { Some(l) } else { None })
 			.collect::<Vec<_>>();
-		<Locks<T>>::insert(who, locks);
+		Self::put_locks(asset_id, who, locks);
+	}
+
+	/// Store `locks` on the `asset_id` balance of `who` ordered by `until`, ties are ordered by lock id.
+	fn put_locks(asset_id: &T::AssetId, who: &T::AccountId, mut locks: Vec<BalanceLock<T::Balance, T::BlockNumber>>) {
+		if *asset_id == Self::staking_asset_id() {
+			<Locks<T>>::remove(who);
+		}
+		locks.sort_by(|a, b| a.until.cmp(&b.until).then_with(|| a.id.cmp(&b.id)));
+		Self::mutate_account(asset_id, who, |account| account.freeze(&locks));
+		if locks.is_empty() {
+			<AssetLocks<T>>::remove(asset_id, who);
+		} else {
+			<AssetLocks<T>>::insert(asset_id, who, locks);
+		}
 	}
 }
 
+/// The name of the module which set a lock or reservation, by convention ids are module names padded with spaces
+/// e.g. `b"staking "`
+pub fn lock_module_name(id: &LockIdentifier) -> Vec<u8> {
+	let len = id.iter().rposition(|b| *b != b' ' && *b != 0).map_or(0, |i| i + 1);
+	id[..len].to_vec()
+}
+
+/// Provides the asset an `AssetCurrency` operates on.
+///
+/// The one asset backs every role of the currency: spending, locking for stake and reserving.
+/// Imbalances settle issuance against this asset, so a role backed by another asset would move funds
+/// between assets.
 pub trait AssetIdProvider {
 	type AssetId;
+	/// The asset which is spent, locked and reserved
 	fn asset_id() -> Self::AssetId;
 }
 
@@ -996,6 +3209,7 @@ mod imbalances {
 		/// Basic drop handler will just square up the total issuance.
 		fn drop(&mut self) {
 			<super::TotalIssuance<super::ElevatedTrait<T>>>::mutate(&U::asset_id(), |v| *v = v.saturating_add(self.0));
+			<super::Module<super::ElevatedTrait<T>>>::note_supply_change(&U::asset_id(), self.0, Zero::zero());
 		}
 	}
 
@@ -1007,6 +3221,7 @@ mod imbalances {
 		/// Basic drop handler will just square up the total issuance.
 		fn drop(&mut self) {
 			<super::TotalIssuance<super::ElevatedTrait<T>>>::mutate(&U::asset_id(), |v| *v = v.saturating_sub(self.0));
+			<super::Module<super::ElevatedTrait<T>>>::note_supply_change(&U::asset_id(), Zero::zero(), self.0);
 		}
 	}
 }
@@ -1053,6 +3268,7 @@ impl<T: Subtrait> Trait for ElevatedTrait<T> {
 	type Balance = T::Balance;
 	type AssetId = T::AssetId;
 	type Event = ();
+	type OnTransferPayload = ();
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq)]
@@ -1102,13 +3318,20 @@ where
 		who: &T::AccountId,
 		value: Self::Balance,
 		reason: WithdrawReason,
-		_: ExistenceRequirement, // no existential deposit policy for generic asset
+		liveness: ExistenceRequirement,
 	) -> result::Result<Self::NegativeImbalance, &'static str> {
 		let new_balance = Self::free_balance(who)
 			.checked_sub(&value)
 			.ok_or_else(|| "account has too few funds")?;
+		if liveness == ExistenceRequirement::KeepAlive
+			&& !new_balance.is_zero()
+			&& new_balance < <Module<T>>::minimum_balance(&U::asset_id())
+		{
+			return Err("payment would kill account");
+		}
 		Self::ensure_can_withdraw(who, value, reason, new_balance)?;
 		<Module<T>>::set_free_balance(&U::asset_id(), who, new_balance);
+		<Module<T>>::reap_dust(&U::asset_id(), who);
 		Ok(NegativeImbalance::new(value))
 	}
 
@@ -1144,7 +3367,13 @@ where
 			SignedImbalance::Negative(NegativeImbalance::new(original - balance))
 		};
 		<Module<T>>::set_free_balance(&U::asset_id(), who, balance);
-		(imbalance, UpdateBalanceOutcome::Updated)
+		<Module<T>>::reap_dust(&U::asset_id(), who);
+		let outcome = if !balance.is_zero() && <Module<T>>::free_balance(&U::asset_id(), who).is_zero() {
+			UpdateBalanceOutcome::AccountKilled
+		} else {
+			UpdateBalanceOutcome::Updated
+		};
+		(imbalance, outcome)
 	}
 
 	fn can_slash(who: &T::AccountId, value: Self::Balance) -> bool {
@@ -1167,8 +3396,7 @@ where
 	U: AssetIdProvider<AssetId = T::AssetId>,
 {
 	fn can_reserve(who: &T::AccountId, value: Self::Balance) -> bool {
-		// TODO: check with lock
-		<Module<T>>::free_balance(&U::asset_id(), &who) >= value
+		<Module<T>>::can_reserve(&U::asset_id(), who, value)
 	}
 
 	fn reserved_balance(who: &T::AccountId) -> Self::Balance {
@@ -1200,6 +3428,33 @@ where
 	}
 }
 
+/// A currency whose reservations are attributed to the module which made them
+pub trait NamedReservableCurrency<AccountId>: ReservableCurrency<AccountId> {
+	/// Reserve `value` from `who`, attributing it to the reservation `id`
+	fn reserve_named(id: &ReserveIdentifier, who: &AccountId, value: Self::Balance) -> result::Result<(), &'static str>;
+
+	/// Unreserve up to `value` held by the reservation `id` on `who`, returning the amount which could not be
+	fn unreserve_named(id: &ReserveIdentifier, who: &AccountId, value: Self::Balance) -> Self::Balance;
+}
+
+impl<T, U> NamedReservableCurrency<T::AccountId> for AssetCurrency<T, U>
+where
+	T: Trait,
+	U: AssetIdProvider<AssetId = T::AssetId>,
+{
+	fn reserve_named(
+		id: &ReserveIdentifier,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> result::Result<(), &'static str> {
+		<Module<T>>::reserve_named(id, &U::asset_id(), who, value)
+	}
+
+	fn unreserve_named(id: &ReserveIdentifier, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		<Module<T>>::unreserve_named(id, &U::asset_id(), who, value)
+	}
+}
+
 pub struct StakingAssetIdProvider<T>(rstd::marker::PhantomData<T>);
 
 impl<T: Trait> AssetIdProvider for StakingAssetIdProvider<T> {
@@ -1218,10 +3473,11 @@ impl<T: Trait> AssetIdProvider for SpendingAssetIdProvider<T> {
 	}
 }
 
-impl<T> LockableCurrency<T::AccountId> for AssetCurrency<T, StakingAssetIdProvider<T>>
+impl<T, U> LockableCurrency<T::AccountId> for AssetCurrency<T, U>
 where
 	T: Trait,
 	T::Balance: MaybeSerializeDebug,
+	U: AssetIdProvider<AssetId = T::AssetId>,
 {
 	type Moment = T::BlockNumber;
 
@@ -1232,7 +3488,7 @@ where
 		until: T::BlockNumber,
 		reasons: WithdrawReasons,
 	) {
-		<Module<T>>::set_lock(id, who, amount, until, reasons)
+		<Module<T>>::set_lock(&U::asset_id(), id, who, amount, until, reasons)
 	}
 
 	fn extend_lock(
@@ -1242,11 +3498,11 @@ where
 		until: T::BlockNumber,
 		reasons: WithdrawReasons,
 	) {
-		<Module<T>>::extend_lock(id, who, amount, until, reasons)
+		<Module<T>>::extend_lock(&U::asset_id(), id, who, amount, until, reasons)
 	}
 
 	fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
-		<Module<T>>::remove_lock(id, who)
+		<Module<T>>::remove_lock(&U::asset_id(), id, who)
 	}
 }
 
@@ -0,0 +1,77 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Storage migrations of the generic asset module
+//!
+//! `StorageVersion` records the layout a chain's storage is in. A runtime upgrade which changes the layout is
+//! enacted together with a root `migrate_storage` call, which migrates storage behind `StorageLayout::LATEST` one
+//! layout at a time. Nothing is checked from block to block.
+//!
+//! To change the layout, add a `StorageLayout` version, make it `LATEST` and add its migration step to `migrate`.
+//! A step runs within a single block, so it may only touch a bounded number of items. Hashed maps can not be
+//! iterated on-chain, so a step migrates the accounts listed in the call, batches of accounts may follow in more
+//! calls. Every step must be safe to apply to an account again. Accounts left out are still read from the old
+//! layout and moved when next written, the way `Locks` are moved to `AssetLocks`, balances to `AccountStore` and
+//! holders into the holder index.
+
+use crate::{Locks, Module, RawEvent, StorageLayout, StorageVersion, Trait};
+use support::{StorageMap, StorageValue};
+
+impl<T: Trait> Module<T> {
+	/// Migrate storage to `StorageLayout::LATEST` and apply every migration step to `accounts`.
+	/// Returns the number of layouts migrated.
+	pub fn migrate(accounts: &[(T::AssetId, T::AccountId)]) -> u32 {
+		let mut migrations = 0;
+		loop {
+			let next = match Self::storage_version() {
+				StorageLayout::Unversioned => StorageLayout::V1,
+				StorageLayout::V1 => StorageLayout::V2,
+				StorageLayout::V2 => StorageLayout::V3,
+				StorageLayout::V3 => break,
+			};
+			<StorageVersion<T>>::put(next);
+			Self::deposit_event(RawEvent::StorageMigrated(next));
+			migrations += 1;
+		}
+		// Later batches arrive after the layout is up to date, so the steps are applied to every batch
+		Self::migrate_to_v1(accounts);
+		Self::migrate_to_v2(accounts);
+		Self::migrate_to_v3(accounts);
+		migrations
+	}
+
+	/// Unversioned to V1: move staking asset locks from `Locks` to `AssetLocks`
+	fn migrate_to_v1(accounts: &[(T::AssetId, T::AccountId)]) {
+		for (asset_id, who) in accounts {
+			if *asset_id == Self::staking_asset_id() && <Locks<T>>::exists(who) {
+				Self::put_locks(asset_id, who, <Locks<T>>::get(who));
+			}
+		}
+	}
+
+	/// V1 to V2: move balances from `FreeBalance` and `ReservedBalance` to `AccountStore`
+	fn migrate_to_v2(accounts: &[(T::AssetId, T::AccountId)]) {
+		for (asset_id, who) in accounts {
+			Self::mutate_account(asset_id, who, |_| ());
+		}
+	}
+
+	/// V2 to V3: index the holders of balances written before the holder index
+	fn migrate_to_v3(accounts: &[(T::AssetId, T::AccountId)]) {
+		for (asset_id, who) in accounts {
+			Self::note_holder(asset_id, who);
+		}
+	}
+}
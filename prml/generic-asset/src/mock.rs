@@ -25,7 +25,7 @@ use runtime_primitives::{
 };
 use serde::{Deserialize, Serialize};
 use primitives::{Blake2Hasher, H256};
-use support::{impl_outer_event, impl_outer_origin};
+use support::{decl_module, decl_storage, impl_outer_event, impl_outer_origin};
 
 use super::*;
 
@@ -68,8 +68,37 @@ impl Trait for Test {
 	type Balance = u64;
 	type AssetId = u32;
 	type Event = TestEvent;
+	type OnTransferPayload = TransferPayloadMock;
 }
 
+/// The account with a transfer payload handler in tests
+pub const PAYLOAD_HANDLER_ACCOUNT: u64 = 9;
+
+pub trait TransferPayloadMockTrait: Trait {}
+
+decl_module! {
+	pub struct TransferPayloadMockModule<T: TransferPayloadMockTrait> for enum Call where origin: T::Origin {}
+}
+
+decl_storage! {
+	trait Store for TransferPayloadMockModule<T: TransferPayloadMockTrait> as P {
+		pub LastPayload get(last_payload): Option<(T::AssetId, T::AccountId, T::Balance, Vec<u8>)>;
+	}
+}
+
+impl OnTransferPayload<u64, u32, u64> for TransferPayloadMockModule<Test> {
+	fn has_handler(who: &u64) -> bool {
+		*who == PAYLOAD_HANDLER_ACCOUNT
+	}
+	fn on_transfer_payload(asset_id: &u32, from: &u64, _to: &u64, amount: u64, payload: &[u8]) {
+		<LastPayload<Test>>::put((*asset_id, *from, amount, payload.to_vec()));
+	}
+}
+
+impl TransferPayloadMockTrait for Test {}
+
+pub type TransferPayloadMock = TransferPayloadMockModule<Test>;
+
 mod generic_asset {
 	pub use crate::Event;
 }
@@ -125,6 +154,8 @@ impl ExtBuilder {
 			GenesisConfig::<Test> {
 				assets: vec![self.asset_id],
 				endowed_accounts: self.accounts,
+				endowments: vec![],
+				genesis_locks: vec![],
 				initial_balance: self.initial_balance,
 				next_asset_id: self.next_asset_id,
 				create_asset_stake: 10,
@@ -18,9 +18,13 @@
 #![cfg(test)]
 
 use super::*;
-use crate::mock::{new_test_ext, ExtBuilder, GenericAsset, Origin, System, Test, TestEvent};
+use crate::mock::{
+	new_test_ext, ExtBuilder, GenericAsset, Origin, System, Test, TestEvent, TransferPayloadMock, PAYLOAD_HANDLER_ACCOUNT,
+};
+use primitives::H256;
+use runtime_primitives::traits::OnInitialize;
 use runtime_io::with_externalities;
-use support::{assert_noop, assert_ok};
+use support::{assert_err, assert_noop, assert_ok};
 
 #[test]
 fn issuing_asset_units_to_issuer_should_work() {
@@ -31,6 +35,7 @@ fn issuing_asset_units_to_issuer_should_work() {
 			update: Owner::Address(1),
 			mint: Owner::Address(1),
 			burn: Owner::Address(1),
+			freeze: Owner::None,
 		};
 
 		let expected_balance = balance - GenericAsset::create_asset_stake();
@@ -56,6 +61,7 @@ fn issuing_with_next_asset_id_overflow_should_not_work() {
 				update: Owner::Address(1),
 				mint: Owner::Address(1),
 				burn: Owner::Address(1),
+				freeze: Owner::None,
 			};
 			assert_noop!(
 				GenericAsset::create(
@@ -83,6 +89,7 @@ fn querying_total_supply_should_work() {
 				update: Owner::Address(1),
 				mint: Owner::Address(1),
 				burn: Owner::Address(1),
+				freeze: Owner::None,
 			};
 			assert_ok!(GenericAsset::create(
 				Origin::signed(1),
@@ -131,6 +138,7 @@ fn transferring_amount_should_work() {
 				update: Owner::Address(1),
 				mint: Owner::Address(1),
 				burn: Owner::Address(1),
+				freeze: Owner::None,
 			};
 			assert_ok!(GenericAsset::create(
 				Origin::signed(1),
@@ -172,6 +180,7 @@ fn transferring_amount_should_fail_when_transferring_more_than_free_balance() {
 				update: Owner::Address(1),
 				mint: Owner::Address(1),
 				burn: Owner::Address(1),
+				freeze: Owner::None,
 			};
 			assert_ok!(GenericAsset::create(
 				Origin::signed(1),
@@ -199,6 +208,7 @@ fn transferring_less_than_one_unit_should_not_work() {
 				update: Owner::Address(1),
 				mint: Owner::Address(1),
 				burn: Owner::Address(1),
+				freeze: Owner::None,
 			};
 			assert_ok!(GenericAsset::create(
 				Origin::signed(1),
@@ -237,6 +247,7 @@ fn self_transfer_should_fail() {
 				update: Owner::Address(1),
 				mint: Owner::Address(1),
 				burn: Owner::Address(1),
+				freeze: Owner::None,
 			};
 			assert_ok!(GenericAsset::create(
 				Origin::signed(1),
@@ -263,6 +274,7 @@ fn transferring_more_units_than_total_supply_should_not_work() {
 				update: Owner::Address(1),
 				mint: Owner::Address(1),
 				burn: Owner::Address(1),
+				freeze: Owner::None,
 			};
 			assert_ok!(GenericAsset::create(
 				Origin::signed(1),
@@ -319,6 +331,7 @@ fn total_balance_should_be_equal_to_account_balance() {
 		update: Owner::Address(1),
 		mint: Owner::Address(1),
 		burn: Owner::Address(1),
+		freeze: Owner::None,
 	};
 	with_externalities(
 		&mut ExtBuilder::default().free_balance((16000, 1, 100000)).build(),
@@ -616,14 +629,15 @@ fn repatriate_reserved_return_none() {
 // When
 // - After calling `create_reserved` function.
 // Then
-// - Should create a new reserved asset.
+// - Should create a new reserved asset, crediting the beneficiary.
 #[test]
-fn create_reserved_should_create_a_default_account_with_the_balance_given() {
+fn create_reserved_should_credit_the_beneficiary_with_the_balance_given() {
 	with_externalities(&mut ExtBuilder::default().next_asset_id(10).build(), || {
 		let default_permission = PermissionLatest {
 			update: Owner::Address(1),
 			mint: Owner::Address(1),
 			burn: Owner::Address(1),
+			freeze: Owner::None,
 		};
 		let options = AssetOptions {
 			initial_issuance: 500,
@@ -632,16 +646,39 @@ fn create_reserved_should_create_a_default_account_with_the_balance_given() {
 
 		let expected_total_issuance = 500;
 		let created_asset_id = 9;
-		let created_account_id = 0;
+		let beneficiary = 2;
 
-		assert_ok!(GenericAsset::create_reserved(created_asset_id, options));
+		assert_ok!(GenericAsset::create_reserved(created_asset_id, beneficiary, options.clone(), false));
 
 		// Tests for side effects.
 		assert_eq!(<TotalIssuance<Test>>::get(created_asset_id), expected_total_issuance);
-		assert_eq!(
-			<FreeBalance<Test>>::get(&created_asset_id, &created_account_id),
-			expected_total_issuance
+		assert_eq!(GenericAsset::free_balance(&created_asset_id, &beneficiary), expected_total_issuance);
+		assert_eq!(GenericAsset::free_balance(&created_asset_id, &0), 0);
+		let expected_event = TestEvent::generic_asset(RawEvent::ReservedAssetCreated(created_asset_id, beneficiary, options));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+// Given
+// - An asset without any permission holders
+// When
+// - After calling `create_reserved` function.
+// Then
+// - Should only create the asset when empty permissions are allowed.
+#[test]
+fn create_reserved_should_refuse_empty_permissions_unless_allowed() {
+	with_externalities(&mut ExtBuilder::default().next_asset_id(10).build(), || {
+		let options = AssetOptions {
+			initial_issuance: 500,
+			permissions: PermissionLatest::default(),
+		};
+
+		assert_noop!(
+			GenericAsset::create_reserved(9, 2, options.clone(), false),
+			"Reserved asset permissions are empty."
 		);
+		assert_ok!(GenericAsset::create_reserved(9, 2, options, true));
+		assert_eq!(GenericAsset::free_balance(&9, &2), 500);
 	});
 }
 
@@ -652,6 +689,7 @@ fn create_reserved_should_create_a_default_account_with_the_balance_given() {
 // - After calling mint function
 // Then
 // - Should throw a permission error
+// - Should raise a permission denied event
 #[test]
 fn mint_should_throw_permission_error() {
 	with_externalities(&mut ExtBuilder::default().build(), || {
@@ -660,10 +698,13 @@ fn mint_should_throw_permission_error() {
 		let to_account = 2;
 		let amount = 100;
 
-		assert_noop!(
+		assert_err!(
 			GenericAsset::mint(Origin::signed(origin), asset_id, to_account, amount),
 			"The origin does not have permission to mint an asset, Permission error."
 		);
+		assert!(System::events().iter().any(|record| record.event
+			== TestEvent::generic_asset(RawEvent::PermissionDenied(asset_id, origin, PermissionType::Mint))));
+		assert_eq!(GenericAsset::total_issuance(asset_id), 0);
 	});
 }
 
@@ -690,6 +731,7 @@ fn mint_should_increase_asset() {
 				update: Owner::Address(origin),
 				mint: Owner::Address(origin),
 				burn: Owner::Address(origin),
+				freeze: Owner::None,
 			};
 
 			assert_ok!(GenericAsset::create(
@@ -726,7 +768,7 @@ fn burn_should_throw_permission_error() {
 			let to_account = 2;
 			let amount = 10;
 
-			assert_noop!(
+			assert_err!(
 				GenericAsset::burn(Origin::signed(origin), asset_id, to_account, amount),
 				"The origin does not have permission to burn an asset, Permission error."
 			);
@@ -759,6 +801,7 @@ fn burn_should_burn_an_asset() {
 				update: Owner::Address(origin),
 				mint: Owner::Address(origin),
 				burn: Owner::Address(origin),
+				freeze: Owner::None,
 			};
 
 			assert_ok!(GenericAsset::create(
@@ -801,6 +844,7 @@ fn check_permission_should_return_correct_permission() {
 				update: Owner::Address(origin),
 				mint: Owner::Address(origin),
 				burn: Owner::Address(origin),
+				freeze: Owner::None,
 			};
 
 			assert_ok!(GenericAsset::create(
@@ -847,6 +891,7 @@ fn check_permission_should_return_false_for_no_permission() {
 				update: Owner::None,
 				mint: Owner::None,
 				burn: Owner::None,
+				freeze: Owner::None,
 			};
 
 			assert_ok!(GenericAsset::create(
@@ -893,12 +938,14 @@ fn update_permission_should_change_permission() {
 				update: Owner::Address(origin),
 				mint: Owner::None,
 				burn: Owner::None,
+				freeze: Owner::None,
 			};
 
 			let new_permission = PermissionLatest {
 				update: Owner::Address(origin),
 				mint: Owner::Address(origin),
 				burn: Owner::None,
+				freeze: Owner::None,
 			};
 
 			assert_ok!(GenericAsset::create(
@@ -945,12 +992,14 @@ fn update_permission_should_throw_error_when_lack_of_permissions() {
 				update: Owner::None,
 				mint: Owner::None,
 				burn: Owner::None,
+				freeze: Owner::None,
 			};
 
 			let new_permission = PermissionLatest {
 				update: Owner::Address(origin),
 				mint: Owner::Address(origin),
 				burn: Owner::None,
+				freeze: Owner::None,
 			};
 
 			let expected_error_message = "Origin does not have enough permission to update permissions.";
@@ -963,7 +1012,7 @@ fn update_permission_should_throw_error_when_lack_of_permissions() {
 				}
 			));
 
-			assert_noop!(
+			assert_err!(
 				GenericAsset::update_permission(Origin::signed(origin), asset_id, new_permission),
 				expected_error_message
 			);
@@ -993,8 +1042,9 @@ fn create_asset_works_with_given_asset_id_and_from_account() {
 			update: Owner::Address(origin),
 			mint: Owner::Address(origin),
 			burn: Owner::Address(origin),
+			freeze: Owner::None,
 		};
-		let expected_permission = PermissionVersions::V1(default_permission.clone());
+		let expected_permission = PermissionVersions::V2(default_permission.clone());
 		let asset_id = 9;
 		let initial_issuance = 100;
 
@@ -1010,7 +1060,7 @@ fn create_asset_works_with_given_asset_id_and_from_account() {
 		// Test for side effects.
 		assert_eq!(<NextAssetId<Test>>::get(), 10);
 		assert_eq!(<TotalIssuance<Test>>::get(asset_id), initial_issuance);
-		assert_eq!(<FreeBalance<Test>>::get(&asset_id, &origin), initial_issuance);
+		assert_eq!(GenericAsset::free_balance(&asset_id, &origin), initial_issuance);
 		assert_eq!(<Permissions<Test>>::get(&asset_id), expected_permission);
 	});
 }
@@ -1030,6 +1080,7 @@ fn create_asset_with_non_reserved_asset_id_should_not_work() {
 			update: Owner::Address(origin),
 			mint: Owner::Address(origin),
 			burn: Owner::Address(origin),
+			freeze: Owner::None,
 		};
 
 		let asset_id = 11;
@@ -1064,6 +1115,7 @@ fn create_asset_with_a_taken_asset_id_should_not_work() {
 			update: Owner::Address(origin),
 			mint: Owner::Address(origin),
 			burn: Owner::Address(origin),
+			freeze: Owner::None,
 		};
 
 		let asset_id = 9;
@@ -1109,6 +1161,7 @@ fn create_asset_should_create_a_reserved_asset_when_from_account_is_none() {
 			update: Owner::Address(origin),
 			mint: Owner::Address(origin),
 			burn: Owner::Address(origin),
+			freeze: Owner::None,
 		};
 
 		let created_account_id = 0;
@@ -1126,7 +1179,7 @@ fn create_asset_should_create_a_reserved_asset_when_from_account_is_none() {
 
 		// Test for a side effect.
 		assert_eq!(
-			<FreeBalance<Test>>::get(&asset_id, &created_account_id),
+			GenericAsset::free_balance(&asset_id, &created_account_id),
 			initial_issuance
 		);
 	});
@@ -1152,6 +1205,7 @@ fn create_asset_should_create_a_user_asset() {
 			update: Owner::Address(origin),
 			mint: Owner::Address(origin),
 			burn: Owner::Address(origin),
+			freeze: Owner::None,
 		};
 
 		let created_account_id = 0;
@@ -1169,9 +1223,9 @@ fn create_asset_should_create_a_user_asset() {
 		));
 
 		// Test for side effects.
-		assert_eq!(<FreeBalance<Test>>::get(&reserved_asset_id, &created_account_id), 0);
+		assert_eq!(GenericAsset::free_balance(&reserved_asset_id, &created_account_id), 0);
 		assert_eq!(
-			<FreeBalance<Test>>::get(&created_user_asset_id, &created_account_id),
+			GenericAsset::free_balance(&created_user_asset_id, &created_account_id),
 			initial_issuance
 		);
 		assert_eq!(<TotalIssuance<Test>>::get(created_user_asset_id), initial_issuance);
@@ -1185,6 +1239,7 @@ fn create_should_throw_error_when_initial_issuance_lower_than_free_balance() {
 			update: Owner::Address(1),
 			mint: Owner::Address(1),
 			burn: Owner::Address(1),
+			freeze: Owner::None,
 		};
 		assert_noop!(
 			GenericAsset::create(
@@ -1208,6 +1263,7 @@ fn create_should_reserve_stake_asset() {
 				update: Owner::Address(1),
 				mint: Owner::Address(1),
 				burn: Owner::Address(1),
+				freeze: Owner::None,
 			};
 			assert_ok!(GenericAsset::create(
 				Origin::signed(1),
@@ -1236,6 +1292,7 @@ fn update_permission_should_raise_event() {
 		update: Owner::Address(origin),
 		mint: Owner::Address(origin),
 		burn: Owner::Address(origin),
+		freeze: Owner::None,
 	};
 
 	with_externalities(
@@ -1277,6 +1334,7 @@ fn mint_should_raise_event() {
 		update: Owner::Address(origin),
 		mint: Owner::Address(origin),
 		burn: Owner::Address(origin),
+		freeze: Owner::None,
 	};
 	let to = 2;
 	let amount = 100;
@@ -1317,6 +1375,7 @@ fn burn_should_raise_event() {
 		update: Owner::Address(origin),
 		mint: Owner::Address(origin),
 		burn: Owner::Address(origin),
+		freeze: Owner::None,
 	};
 	let amount = 100;
 
@@ -1344,3 +1403,1924 @@ fn burn_should_raise_event() {
 		},
 	);
 }
+
+#[test]
+fn minted_balance_cannot_be_transferred_during_cooldown() {
+	let staking_asset_id = 16000;
+	let asset_id = 1000;
+	let origin = 1;
+	let to = 2;
+	let permissions = PermissionLatest {
+		update: Owner::Address(origin),
+		mint: Owner::Address(origin),
+		burn: Owner::Address(origin),
+		freeze: Owner::None,
+	};
+
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((staking_asset_id, origin, 1000))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(origin),
+				AssetOptions {
+					initial_issuance: 0,
+					permissions: permissions.clone(),
+				}
+			));
+			assert_ok!(GenericAsset::set_mint_cooldown(Origin::signed(origin), asset_id, 10));
+
+			System::set_block_number(1);
+			assert_ok!(GenericAsset::mint(Origin::signed(origin), asset_id, to, 100));
+			System::set_block_number(5);
+			assert_ok!(GenericAsset::mint(Origin::signed(origin), asset_id, to, 50));
+			assert_eq!(GenericAsset::cooling_down_balance(&asset_id, &to), 150);
+
+			assert_noop!(
+				GenericAsset::transfer(Origin::signed(to), asset_id, origin, 1),
+				"minted balance is still in its transfer cooldown"
+			);
+
+			// The first batch expires
+			System::set_block_number(11);
+			assert_eq!(GenericAsset::cooling_down_balance(&asset_id, &to), 50);
+			assert_ok!(GenericAsset::transfer(Origin::signed(to), asset_id, origin, 100));
+			assert_noop!(
+				GenericAsset::transfer(Origin::signed(to), asset_id, origin, 1),
+				"minted balance is still in its transfer cooldown"
+			);
+
+			System::set_block_number(15);
+			assert_ok!(GenericAsset::transfer(Origin::signed(to), asset_id, origin, 50));
+			assert_eq!(GenericAsset::free_balance(&asset_id, &to), 0);
+		},
+	);
+}
+
+#[test]
+fn set_mint_cooldown_should_require_update_permission() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_err!(
+			GenericAsset::set_mint_cooldown(Origin::signed(1), 1000, 10),
+			"Origin does not have enough permission to update the mint cooldown."
+		);
+	});
+}
+
+#[test]
+fn locks_are_ordered_by_until_and_paged() {
+	use support::traits::{LockableCurrency, WithdrawReasons};
+	type Staking = StakingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let who = 1;
+		Staking::set_lock(*b"staking ", &who, 10, 30, WithdrawReasons::all());
+		Staking::set_lock(*b"democrac", &who, 10, 10, WithdrawReasons::all());
+		GenericAsset::set_lock_with_metadata(
+			&16000,
+			*b"vesting ",
+			&who,
+			10,
+			20,
+			WithdrawReasons::all(),
+			Some(b"team allocation".to_vec()),
+		);
+
+		let ids: Vec<LockIdentifier> = GenericAsset::locks(&16000, &who).iter().map(|l| l.id).collect();
+		assert_eq!(ids, vec![*b"democrac", *b"vesting ", *b"staking "]);
+
+		let page = GenericAsset::locks_paged(&16000, &who, 1, 2);
+		assert_eq!(page.len(), 1);
+		assert_eq!(page[0].0, b"staking".to_vec());
+
+		let page = GenericAsset::locks_paged(&16000, &who, 0, 2);
+		assert_eq!(page[1].0, b"vesting".to_vec());
+		assert_eq!(page[1].1.metadata, Some(b"team allocation".to_vec()));
+
+		// Extending a lock keeps its metadata and the ordering
+		Staking::extend_lock(*b"vesting ", &who, 10, 40, WithdrawReasons::all());
+		let locks = GenericAsset::locks(&16000, &who);
+		assert_eq!(locks[2].id, *b"vesting ");
+		assert_eq!(locks[2].metadata, Some(b"team allocation".to_vec()));
+	});
+}
+
+#[test]
+fn mint_batch_and_burn_batch_should_update_balances_and_total_issuance() {
+	let staking_asset_id = 16000;
+	let asset_id = 1000;
+	let origin = 1;
+	let permissions = PermissionLatest {
+		update: Owner::Address(origin),
+		mint: Owner::Address(origin),
+		burn: Owner::Address(origin),
+		freeze: Owner::None,
+	};
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((staking_asset_id, origin, 1000))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(origin),
+				AssetOptions {
+					initial_issuance: 100,
+					permissions: permissions.clone(),
+				}
+			));
+
+			assert_ok!(GenericAsset::mint_batch(
+				Origin::signed(origin),
+				asset_id,
+				vec![(2, 10), (3, 20), (2, 5)]
+			));
+			assert_eq!(GenericAsset::free_balance(&asset_id, &2), 15);
+			assert_eq!(GenericAsset::free_balance(&asset_id, &3), 20);
+			assert_eq!(GenericAsset::total_issuance(asset_id), 135);
+
+			assert_ok!(GenericAsset::burn_batch(
+				Origin::signed(origin),
+				asset_id,
+				vec![(2, 15), (3, 5), (origin, 50)]
+			));
+			assert_eq!(GenericAsset::free_balance(&asset_id, &2), 0);
+			assert_eq!(GenericAsset::free_balance(&asset_id, &3), 15);
+			assert_eq!(GenericAsset::free_balance(&asset_id, &origin), 50);
+			assert_eq!(GenericAsset::total_issuance(asset_id), 65);
+		},
+	);
+}
+
+#[test]
+fn mint_batch_and_burn_batch_should_be_bounded() {
+	let oversized: Vec<(u64, u64)> = (0..=MINT_BATCH_MAX as u64).map(|i| (i, 1)).collect();
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		assert_noop!(
+			GenericAsset::mint_batch(Origin::signed(1), 16000, oversized.clone()),
+			"too many mints in batch"
+		);
+		assert_noop!(
+			GenericAsset::burn_batch(Origin::signed(1), 16000, oversized.clone()),
+			"too many burns in batch"
+		);
+		assert!(
+			GenericAsset::call_weight(&1, &Call::mint_batch(16000, oversized.clone()))
+				> GenericAsset::call_weight(&1, &Call::mint_batch(16000, vec![(1, 1)]))
+		);
+	});
+}
+
+#[test]
+fn burn_batch_should_fail_atomically() {
+	let staking_asset_id = 16000;
+	let asset_id = 1000;
+	let origin = 1;
+	let permissions = PermissionLatest {
+		update: Owner::Address(origin),
+		mint: Owner::Address(origin),
+		burn: Owner::Address(origin),
+		freeze: Owner::None,
+	};
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((staking_asset_id, origin, 1000))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(origin),
+				AssetOptions {
+					initial_issuance: 100,
+					permissions: permissions.clone(),
+				}
+			));
+
+			assert_ok!(GenericAsset::mint(Origin::signed(origin), asset_id, 2, 10));
+
+			// the account is burnt twice, more than its balance in total
+			assert_noop!(
+				GenericAsset::burn_batch(Origin::signed(origin), asset_id, vec![(origin, 50), (2, 8), (2, 8)]),
+				"free_balance got underflow after burning"
+			);
+			assert_err!(
+				GenericAsset::mint_batch(Origin::signed(2), asset_id, vec![(2, 10)]),
+				"The origin does not have permission to mint an asset, Permission error."
+			);
+		},
+	);
+}
+
+#[test]
+fn mint_should_not_credit_frozen_accounts() {
+	let staking_asset_id = 16000;
+	let asset_id = 1000;
+	let origin = 1;
+	let permissions = PermissionLatest {
+		update: Owner::Address(origin),
+		mint: Owner::Address(origin),
+		burn: Owner::Address(origin),
+		freeze: Owner::Address(origin),
+	};
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((staking_asset_id, origin, 1000))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(origin),
+				AssetOptions {
+					initial_issuance: 100,
+					permissions: permissions.clone(),
+				}
+			));
+			assert_ok!(GenericAsset::freeze_account(Origin::signed(origin), asset_id, 2, 10));
+
+			assert_noop!(
+				GenericAsset::mint(Origin::signed(origin), asset_id, 2, 10),
+				"account is frozen"
+			);
+			assert_noop!(
+				GenericAsset::mint_batch(Origin::signed(origin), asset_id, vec![(3, 10), (2, 10)]),
+				"account is frozen"
+			);
+			assert_noop!(GenericAsset::reward(&asset_id, &2, 10), "account is frozen");
+
+			assert_ok!(GenericAsset::unfreeze_account(Origin::signed(origin), asset_id, 2));
+			assert_ok!(GenericAsset::mint_batch(Origin::signed(origin), asset_id, vec![(3, 10), (2, 10)]));
+			assert_eq!(GenericAsset::free_balance(&asset_id, &2), 10);
+		},
+	);
+}
+
+#[test]
+fn reserves_should_be_broken_down_by_reservation() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		let stake = GenericAsset::create_asset_stake();
+		assert_ok!(GenericAsset::create(
+			Origin::signed(1),
+			AssetOptions {
+				initial_issuance: 0,
+				permissions: PermissionLatest {
+					update: Owner::Address(1),
+					mint: Owner::Address(1),
+					burn: Owner::Address(1),
+					freeze: Owner::None,
+				},
+			}
+		));
+		assert_eq!(GenericAsset::reserves(&16000, &1), vec![(b"gasset".to_vec(), stake)]);
+		assert_eq!(GenericAsset::creator_stakes(&1), (stake, vec![(1000, stake)]));
+		assert_eq!(GenericAsset::registered_assets(), vec![16000, 1000]);
+
+		assert_ok!(GenericAsset::reserve(&16000, &1, 5));
+		assert_ok!(GenericAsset::reserve_named(b"escrow  ", &16000, &1, 10));
+		assert_eq!(
+			GenericAsset::reserves(&16000, &1),
+			vec![(b"gasset".to_vec(), stake), (b"escrow".to_vec(), 10), (vec![], 5)]
+		);
+
+		// only the escrow reservation is released
+		assert_eq!(GenericAsset::unreserve_named(b"escrow  ", &16000, &1, 15), 5);
+		assert_eq!(GenericAsset::reserved_balance(&16000, &1), stake + 5);
+		assert_eq!(GenericAsset::reserves(&16000, &1), vec![(b"gasset".to_vec(), stake), (vec![], 5)]);
+	});
+}
+
+#[test]
+fn set_metadata_link_should_require_update_permission_and_raise_event() {
+	let staking_asset_id = 16000;
+	let asset_id = 1000;
+	let origin = 1;
+	let permissions = PermissionLatest {
+		update: Owner::Address(origin),
+		mint: Owner::Address(origin),
+		burn: Owner::Address(origin),
+		freeze: Owner::None,
+	};
+	let link = MetadataLink {
+		uri: b"ipfs://QmToken".to_vec(),
+		hash: H256::repeat_byte(1),
+	};
+
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((staking_asset_id, origin, 1000))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(origin),
+				AssetOptions {
+					initial_issuance: 0,
+					permissions: permissions.clone(),
+				}
+			));
+
+			assert_err!(
+				GenericAsset::set_metadata_link(Origin::signed(2), asset_id, Some(link.clone())),
+				"Origin does not have enough permission to update the metadata link."
+			);
+			assert_noop!(
+				GenericAsset::set_metadata_link(
+					Origin::signed(origin),
+					asset_id,
+					Some(MetadataLink {
+						uri: vec![b'a'; METADATA_URI_MAX + 1],
+						hash: H256::repeat_byte(1),
+					})
+				),
+				"Metadata URI is too long"
+			);
+
+			assert_ok!(GenericAsset::set_metadata_link(Origin::signed(origin), asset_id, Some(link.clone())));
+			assert_eq!(GenericAsset::metadata_link(asset_id), Some(link.clone()));
+
+			assert_ok!(GenericAsset::set_metadata_link(Origin::signed(origin), asset_id, None));
+			assert_eq!(GenericAsset::metadata_link(asset_id), None);
+
+			let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+			assert!(events.contains(&TestEvent::generic_asset(RawEvent::MetadataLinkUpdated(asset_id, Some(link)))));
+			assert!(events.contains(&TestEvent::generic_asset(RawEvent::MetadataLinkUpdated(asset_id, None))));
+		},
+	);
+}
+
+#[test]
+fn transfers_above_the_threshold_should_raise_large_transfer_event() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 1000)).build(), || {
+		assert_ok!(GenericAsset::set_large_transfer_threshold(16000, Some(100)));
+
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 100));
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 101));
+
+		let large_transfers: Vec<_> = System::events()
+			.into_iter()
+			.filter(|record| match record.event {
+				TestEvent::generic_asset(RawEvent::LargeTransfer(..)) => true,
+				_ => false,
+			})
+			.map(|record| record.event)
+			.collect();
+		assert_eq!(large_transfers, vec![TestEvent::generic_asset(RawEvent::LargeTransfer(16000, 1, 2, 101))]);
+
+		// unwatched transfers do not raise the event
+		assert_ok!(GenericAsset::set_large_transfer_threshold(16000, None));
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 500));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 701);
+		assert_eq!(System::events().len(), 4);
+	});
+}
+
+#[test]
+fn transfers_below_the_minimum_transfer_amount_should_fail() {
+	let staking_asset_id = 16000;
+	let asset_id = 1000;
+	let origin = 1;
+	let permissions = PermissionLatest {
+		update: Owner::Address(origin),
+		mint: Owner::Address(origin),
+		burn: Owner::Address(origin),
+		freeze: Owner::None,
+	};
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((staking_asset_id, origin, 1000))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(origin),
+				AssetOptions {
+					initial_issuance: 100,
+					permissions: permissions.clone(),
+				}
+			));
+
+			assert_err!(
+				GenericAsset::set_min_transfer_amount(Origin::signed(2), asset_id, 10),
+				"Origin does not have enough permission to update the minimum transfer amount."
+			);
+			assert_ok!(GenericAsset::set_min_transfer_amount(Origin::signed(origin), asset_id, 10));
+
+			assert_noop!(
+				GenericAsset::transfer(Origin::signed(origin), asset_id, 2, 9),
+				"transfer amount is below the asset's minimum transfer amount"
+			);
+			assert_noop!(
+				GenericAsset::make_transfer(&asset_id, &origin, &2, 9),
+				"transfer amount is below the asset's minimum transfer amount"
+			);
+			assert_ok!(GenericAsset::transfer(Origin::signed(origin), asset_id, 2, 10));
+			assert_eq!(GenericAsset::free_balance(&asset_id, &2), 10);
+
+			// other assets are unaffected
+			assert_ok!(GenericAsset::transfer(Origin::signed(origin), staking_asset_id, 2, 1));
+		},
+	);
+}
+
+#[test]
+fn transfers_refused_by_the_receive_rules_should_fail() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_ok!(GenericAsset::reward(&16001, &1, 100));
+		assert_ok!(GenericAsset::reward(&16000, &3, 100));
+		assert_ok!(GenericAsset::set_receive_rules(Origin::signed(2), vec![16001], vec![3]));
+
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(1), 16001, 2, 10),
+			"receiver does not accept this transfer"
+		);
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(3), 16000, 2, 10),
+			"receiver does not accept this transfer"
+		);
+		assert_noop!(
+			GenericAsset::make_transfer(&16001, &1, &2, 10),
+			"receiver does not accept this transfer"
+		);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 10));
+
+		// clearing the rules accepts all transfers again
+		assert_ok!(GenericAsset::set_receive_rules(Origin::signed(2), vec![], vec![]));
+		assert_ok!(GenericAsset::transfer(Origin::signed(3), 16000, 2, 10));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 20);
+	});
+}
+
+#[test]
+fn settle_should_transfer_all_legs_or_none() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_ok!(GenericAsset::reward(&16001, &1, 100));
+
+		// the second 16001 leg overdraws the account
+		assert_noop!(
+			GenericAsset::settle(
+				Origin::signed(1),
+				vec![(16000, 1, 2, 50), (16001, 1, 2, 60), (16001, 1, 3, 60)]
+			),
+			"balance too low to send amount"
+		);
+		assert_noop!(
+			GenericAsset::settle(Origin::signed(1), vec![(16000, 2, 1, 1)]),
+			"settlement legs must be sent by the origin"
+		);
+
+		assert_ok!(GenericAsset::settle(
+			Origin::signed(1),
+			vec![(16000, 1, 2, 50), (16001, 1, 2, 60), (16001, 1, 3, 40)]
+		));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 50);
+		assert_eq!(GenericAsset::free_balance(&16001, &2), 60);
+		assert_eq!(GenericAsset::free_balance(&16001, &3), 40);
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 0);
+	});
+}
+
+#[test]
+fn settle_should_leave_earlier_legs_untouched_when_a_later_leg_fails() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_ok!(GenericAsset::reward(&16001, &1, 100));
+
+		<Frozen<Test>>::insert(16001, true);
+		assert_noop!(
+			GenericAsset::settle(Origin::signed(1), vec![(16000, 1, 2, 50), (16001, 1, 2, 60)]),
+			"asset is frozen"
+		);
+		<Frozen<Test>>::remove(16001);
+
+		<MinimumBalance<Test>>::insert(16001, 70);
+		assert_noop!(
+			GenericAsset::settle(Origin::signed(1), vec![(16000, 1, 2, 50), (16001, 1, 3, 60)]),
+			"destination balance too low to receive value"
+		);
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 100);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 0);
+
+		// the sender's dust of the second leg is reaped once all legs settle
+		assert_ok!(GenericAsset::settle(Origin::signed(1), vec![(16000, 1, 2, 50), (16001, 1, 3, 80)]));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 50);
+		assert_eq!(GenericAsset::free_balance(&16001, &3), 80);
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 0);
+	});
+}
+
+#[test]
+fn supply_deltas_should_track_issuance_changes_within_a_block() {
+	let staking_asset_id = 16000;
+	let asset_id = 1000;
+	let origin = 1;
+	let permissions = PermissionLatest {
+		update: Owner::Address(origin),
+		mint: Owner::Address(origin),
+		burn: Owner::Address(origin),
+		freeze: Owner::None,
+	};
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((staking_asset_id, origin, 1000))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(origin),
+				AssetOptions {
+					initial_issuance: 100,
+					permissions: permissions,
+				}
+			));
+			assert_ok!(GenericAsset::mint(Origin::signed(origin), asset_id, 2, 50));
+			assert_ok!(GenericAsset::burn(Origin::signed(origin), asset_id, 2, 30));
+			assert_eq!(GenericAsset::supply_deltas(), vec![(asset_id, 150, 30)]);
+
+			GenericAsset::on_initialize(2);
+			assert_eq!(GenericAsset::supply_deltas(), vec![]);
+		},
+	);
+}
+
+#[test]
+fn transfer_with_payload_should_deliver_payload_to_handler() {
+	with_externalities(
+		&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(),
+		|| {
+			assert_ok!(GenericAsset::transfer_with_payload(
+				Origin::signed(1),
+				16000,
+				PAYLOAD_HANDLER_ACCOUNT,
+				40,
+				b"order #42".to_vec()
+			));
+			assert_eq!(GenericAsset::free_balance(&16000, &1), 60);
+			assert_eq!(GenericAsset::free_balance(&16000, &PAYLOAD_HANDLER_ACCOUNT), 40);
+			assert_eq!(
+				TransferPayloadMock::last_payload(),
+				Some((16000, 1, 40, b"order #42".to_vec()))
+			);
+		},
+	);
+}
+
+#[test]
+fn transfer_with_payload_should_fail_without_handler_or_with_long_payload() {
+	with_externalities(
+		&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(),
+		|| {
+			assert_noop!(
+				GenericAsset::transfer_with_payload(Origin::signed(1), 16000, 2, 40, b"order #42".to_vec()),
+				"receiver has no transfer payload handler"
+			);
+			assert_noop!(
+				GenericAsset::transfer_with_payload(
+					Origin::signed(1),
+					16000,
+					PAYLOAD_HANDLER_ACCOUNT,
+					40,
+					vec![0; TRANSFER_PAYLOAD_MAX + 1]
+				),
+				"transfer payload is too long"
+			);
+			assert_eq!(TransferPayloadMock::last_payload(), None);
+		},
+	);
+}
+
+#[test]
+fn vested_transfer_should_release_balance_linearly() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		let schedule = VestingSchedule {
+			locked: 100,
+			per_block: 10,
+			starting_block: 5,
+		};
+		assert_ok!(GenericAsset::vested_transfer(Origin::signed(1), 16000, 2, schedule.clone()));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 100);
+		assert_eq!(GenericAsset::vesting(&16000, &2), Some(schedule));
+
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(2), 16000, 3, 1),
+			"vesting balance too high to send value"
+		);
+
+		System::set_block_number(8);
+		assert_eq!(GenericAsset::vesting_balance(&16000, &2), 70);
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), 16000, 3, 30));
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(2), 16000, 3, 1),
+			"vesting balance too high to send value"
+		);
+
+		System::set_block_number(15);
+		assert_eq!(GenericAsset::vesting_balance(&16000, &2), 0);
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), 16000, 3, 70));
+	});
+}
+
+#[test]
+fn vested_transfer_should_not_replace_an_existing_schedule() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		let schedule = VestingSchedule {
+			locked: 10,
+			per_block: 1,
+			starting_block: 0,
+		};
+		assert_ok!(GenericAsset::vested_transfer(Origin::signed(1), 16000, 2, schedule.clone()));
+		assert_noop!(
+			GenericAsset::vested_transfer(Origin::signed(1), 16000, 2, schedule),
+			"receiver already has a vesting schedule for this asset"
+		);
+	});
+}
+
+#[test]
+fn set_co_signer_should_require_update_permission() {
+	let asset_id = 1000;
+	let origin = 1;
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((16000, origin, 100))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(origin),
+				AssetOptions {
+					initial_issuance: 100,
+					permissions: PermissionLatest {
+						update: Owner::Address(origin),
+						mint: Owner::Address(origin),
+						burn: Owner::Address(origin),
+						freeze: Owner::None,
+					},
+				}
+			));
+			assert_err!(
+				GenericAsset::set_co_signer(Origin::signed(2), asset_id, Some(2)),
+				"Origin does not have enough permission to update the co-signer."
+			);
+
+			assert_ok!(GenericAsset::set_co_signer(Origin::signed(origin), asset_id, Some(2)));
+			assert_eq!(GenericAsset::co_signer(asset_id), Some(2));
+
+			assert_ok!(GenericAsset::set_co_signer(Origin::signed(origin), asset_id, None));
+			assert_eq!(GenericAsset::co_signer(asset_id), None);
+		},
+	);
+}
+
+#[test]
+fn transfer_batch_should_transfer_to_all_receivers() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_ok!(GenericAsset::transfer_batch(
+			Origin::signed(1),
+			16000,
+			vec![(2, 10), (3, 20), (2, 5)]
+		));
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 65);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 15);
+		assert_eq!(GenericAsset::free_balance(&16000, &3), 20);
+	});
+}
+
+#[test]
+fn transfer_batch_should_transfer_nothing_if_any_transfer_fails() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_noop!(
+			GenericAsset::transfer_batch(Origin::signed(1), 16000, vec![(2, 60), (3, 50)]),
+			"balance too low to send amount"
+		);
+		assert_noop!(
+			GenericAsset::transfer_batch(Origin::signed(1), 16000, vec![(2, 10), (3, 0)]),
+			"cannot transfer zero amount"
+		);
+		assert_ok!(GenericAsset::set_receive_rules(Origin::signed(3), vec![16000], vec![]));
+		assert_noop!(
+			GenericAsset::transfer_batch(Origin::signed(1), 16000, vec![(2, 10), (3, 10)]),
+			"receiver does not accept this transfer"
+		);
+	});
+}
+
+#[test]
+fn transfer_batch_should_check_balances_left_by_every_transfer() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 15)).build(), || {
+		<MinimumBalance<Test>>::insert(16000, 10);
+
+		// the middle transfer would leave 3 below the minimum balance
+		assert_noop!(
+			GenericAsset::transfer_batch(Origin::signed(1), 16000, vec![(2, 10), (3, 4), (2, 1)]),
+			"destination balance too low to receive value"
+		);
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 15);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 0);
+
+		// the sender is not reaped between transfers, only once all are made
+		assert_ok!(GenericAsset::transfer_batch(Origin::signed(1), 16000, vec![(2, 10), (2, 4)]));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 14);
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 0);
+		assert_eq!(GenericAsset::total_issuance(&16000), 14);
+	});
+}
+
+#[test]
+fn transfer_from_should_spend_allowance() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::approve(Origin::signed(1), 16000, 2, 50));
+		assert_eq!(GenericAsset::approvals(&(16000, 1), &2), 50);
+		assert_noop!(
+			GenericAsset::transfer_from(Origin::signed(2), 16000, 1, 3, 51),
+			"transfer amount exceeds allowance"
+		);
+		assert_noop!(
+			GenericAsset::transfer_from(Origin::signed(3), 16000, 1, 3, 1),
+			"transfer amount exceeds allowance"
+		);
+
+		assert_ok!(GenericAsset::transfer_from(Origin::signed(2), 16000, 1, 3, 30));
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 70);
+		assert_eq!(GenericAsset::free_balance(&16000, &3), 30);
+		assert_eq!(GenericAsset::approvals(&(16000, 1), &2), 20);
+
+		assert_ok!(GenericAsset::transfer_from(Origin::signed(2), 16000, 1, 2, 20));
+		assert_eq!(GenericAsset::approvals(&(16000, 1), &2), 0);
+
+		let expected_event = TestEvent::generic_asset(RawEvent::Approved(16000, 1, 2, 50));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+#[test]
+fn revoke_approval_should_remove_allowance() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_noop!(
+			GenericAsset::revoke_approval(Origin::signed(1), 16000, 2),
+			"no approval to revoke"
+		);
+		assert_ok!(GenericAsset::approve(Origin::signed(1), 16000, 2, 50));
+		assert_ok!(GenericAsset::revoke_approval(Origin::signed(1), 16000, 2));
+		assert_eq!(GenericAsset::approvals(&(16000, 1), &2), 0);
+		assert_noop!(
+			GenericAsset::transfer_from(Origin::signed(2), 16000, 1, 2, 1),
+			"transfer amount exceeds allowance"
+		);
+	});
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn force_set_balance_should_keep_total_issuance_in_sync() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		GenericAsset::force_set_balance(&16000, &2, 50, 10);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 50);
+		assert_eq!(GenericAsset::reserved_balance(&16000, &2), 10);
+		assert_eq!(GenericAsset::total_issuance(&16000), 160);
+
+		GenericAsset::force_set_balance(&16000, &1, 20, 0);
+		assert_eq!(GenericAsset::total_issuance(&16000), 80);
+	});
+}
+
+#[test]
+fn long_metadata_uri_should_be_truncated_in_event() {
+	let asset_id = 1000;
+	let permissions = PermissionLatest {
+		update: Owner::Address(1),
+		mint: Owner::Address(1),
+		burn: Owner::Address(1),
+		freeze: Owner::None,
+	};
+	let link = MetadataLink {
+		uri: vec![b'a'; EVENT_DATA_MAX + 10],
+		hash: H256::repeat_byte(1),
+	};
+
+	with_externalities(
+		&mut ExtBuilder::default()
+			.next_asset_id(asset_id)
+			.free_balance((16000, 1, 1000))
+			.build(),
+		|| {
+			assert_ok!(GenericAsset::create(
+				Origin::signed(1),
+				AssetOptions {
+					initial_issuance: 0,
+					permissions,
+				}
+			));
+			assert_ok!(GenericAsset::set_metadata_link(Origin::signed(1), asset_id, Some(link.clone())));
+			assert_eq!(GenericAsset::metadata_link(asset_id), Some(link.clone()));
+
+			let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+			let truncated = MetadataLink {
+				uri: vec![b'a'; EVENT_DATA_MAX],
+				hash: H256::repeat_byte(1),
+			};
+			let n = events.len();
+			assert_eq!(
+				events[n - 2],
+				TestEvent::generic_asset(RawEvent::EventTruncated(EVENT_DATA_MAX as u32 + 10))
+			);
+			assert_eq!(
+				events[n - 1],
+				TestEvent::generic_asset(RawEvent::MetadataLinkUpdated(asset_id, Some(truncated)))
+			);
+		},
+	);
+}
+
+#[test]
+fn transfer_should_reap_dust_below_minimum_balance() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		<MinimumBalance<Test>>::insert(16000, 10);
+
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(1), 16000, 2, 5),
+			"destination balance too low to receive value"
+		);
+
+		// burned without a dust collector
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 95));
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 0);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 95);
+		assert_eq!(GenericAsset::total_issuance(&16000), 95);
+		let expected_event = TestEvent::generic_asset(RawEvent::DustReaped(16000, 1, 5));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// sent to the dust collector
+		assert_ok!(GenericAsset::set_dust_collector(Some(8)));
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), 16000, 3, 90));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 0);
+		assert_eq!(GenericAsset::free_balance(&16000, &8), 5);
+		assert_eq!(GenericAsset::total_issuance(&16000), 95);
+	});
+}
+
+#[test]
+fn transfer_should_not_reap_locked_or_vesting_dust() {
+	use support::traits::{LockableCurrency, WithdrawReasons};
+	type Spending = SpendingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16001, 1, 200)).build(), || {
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16001, 2, 100));
+		<MinimumBalance<Test>>::insert(16001, 70);
+		Spending::set_lock(*b"escrow  ", &1, 60, 10, WithdrawReasons::all());
+
+		// 65 is below the minimum balance but still locked
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16001, 2, 35));
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 65);
+		assert_eq!(GenericAsset::total_issuance(&16001), 200);
+
+		// 65 is below the minimum balance but still vesting
+		let schedule = VestingSchedule {
+			locked: 60,
+			per_block: 1,
+			starting_block: 0,
+		};
+		<Vesting<Test>>::insert(16001, 2, schedule);
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), 16001, 3, 70));
+		assert_eq!(GenericAsset::free_balance(&16001, &2), 65);
+		assert_eq!(GenericAsset::total_issuance(&16001), 200);
+
+		// reaped once the lock expires
+		System::set_block_number(10);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16001, 3, 1));
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 0);
+	});
+}
+
+#[test]
+fn set_minimum_balance_should_require_update_permission() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_err!(
+			GenericAsset::set_minimum_balance(Origin::signed(1), 16000, 10),
+			"Origin does not have enough permission to update the minimum balance."
+		);
+	});
+}
+
+#[test]
+fn velocity_limit_should_cap_transfers_per_period() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_noop!(
+			GenericAsset::set_velocity_limit(Origin::signed(1), 16000, Some((30, 0))),
+			"velocity limit period must be greater than zero"
+		);
+		assert_ok!(GenericAsset::set_velocity_limit(Origin::signed(1), 16000, Some((30, 10))));
+
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 20));
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(1), 16000, 2, 11),
+			"transfer exceeds the account's velocity limit"
+		);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 10));
+
+		// a new period starts
+		System::set_block_number(11);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 30));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 60);
+	});
+}
+
+#[test]
+fn velocity_limit_should_tighten_immediately_and_raise_after_a_delay() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::set_velocity_limit(Origin::signed(1), 16000, Some((30, 10))));
+
+		assert_ok!(GenericAsset::set_velocity_limit(Origin::signed(1), 16000, Some((10, 10))));
+		assert_eq!(GenericAsset::velocity_limit(&16000, &1).map(|v| v.limit), Some(10));
+
+		assert_ok!(GenericAsset::set_velocity_limit(Origin::signed(1), 16000, None));
+		let effective_at = 1 + VELOCITY_LIMIT_INCREASE_DELAY;
+		let expected_event =
+			TestEvent::generic_asset(RawEvent::VelocityLimitIncreaseScheduled(16000, 1, None, effective_at));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(1), 16000, 2, 11),
+			"transfer exceeds the account's velocity limit"
+		);
+
+		System::set_block_number(effective_at);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 50));
+		assert_eq!(GenericAsset::velocity_limit(&16000, &1), None);
+		assert_eq!(GenericAsset::pending_velocity_limit(&16000, &1), None);
+	});
+}
+
+#[test]
+fn voting_weight_should_use_the_balance_at_the_start_of_the_snapshot_period() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		let period = VOTING_SNAPSHOT_PERIOD;
+		System::set_block_number(period + 1);
+		assert_eq!(GenericAsset::voting_weight_at(&1, period + 1), Some(100));
+
+		// borrowing mid-period does not raise the weight of the period
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 40));
+		assert_eq!(GenericAsset::voting_weight_at(&1, period + 1), Some(100));
+		assert_eq!(GenericAsset::voting_weight_at(&2, period + 1), Some(0));
+
+		System::set_block_number(3 * period);
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), 16000, 1, 10));
+		assert_eq!(GenericAsset::voting_weight_at(&1, period), Some(100));
+		assert_eq!(GenericAsset::voting_weight_at(&1, 2 * period), Some(60));
+		assert_eq!(GenericAsset::voting_weight_at(&1, 3 * period), Some(60));
+		assert_eq!(GenericAsset::voting_weight_at(&2, 2 * period), Some(40));
+
+		// reserved balance carries no weight
+		assert_ok!(GenericAsset::reserve(&16000, &1, 20));
+		System::set_block_number(4 * period);
+		assert_eq!(GenericAsset::voting_weight_at(&1, 4 * period), Some(50));
+
+		assert_eq!(GenericAsset::voting_weight_at(&1, 5 * period), None);
+		System::set_block_number((VOTING_SNAPSHOT_HISTORY + 2) * period);
+		assert_eq!(GenericAsset::voting_weight_at(&1, period), None);
+	});
+}
+
+#[test]
+fn locks_should_apply_to_any_asset() {
+	use support::traits::{LockableCurrency, WithdrawReasons};
+	type Spending = SpendingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16001, 1, 100)).build(), || {
+		Spending::set_lock(*b"escrow  ", &1, 60, 10, WithdrawReasons::all());
+		assert_eq!(GenericAsset::locks(&16000, &1), vec![]);
+
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(1), 16001, 2, 41),
+			"account liquidity restrictions prevent withdrawal"
+		);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16001, 2, 40));
+
+		Spending::remove_lock(*b"escrow  ", &1);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16001, 2, 60));
+	});
+}
+
+#[test]
+fn legacy_staking_locks_should_be_read_until_updated() {
+	use support::traits::{LockableCurrency, WithdrawReasons};
+	type Staking = StakingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		let legacy = BalanceLock {
+			id: *b"staking ",
+			amount: 50,
+			until: 10,
+			reasons: WithdrawReasons::all(),
+			metadata: None,
+		};
+		// Legacy locks predate `AccountStore`, so the balance is in the legacy layout too
+		<AccountStore<Test>>::remove(&16000, &1);
+		<FreeBalance<Test>>::insert(&16000, &1, 100);
+		<Locks<Test>>::insert(1, vec![legacy.clone()]);
+		assert_eq!(GenericAsset::locks(&16000, &1), vec![legacy]);
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(1), 16000, 2, 51),
+			"account liquidity restrictions prevent withdrawal"
+		);
+
+		Staking::set_lock(*b"democrac", &1, 20, 10, WithdrawReasons::all());
+		assert!(!<Locks<Test>>::exists(1));
+		let ids: Vec<LockIdentifier> = GenericAsset::locks(&16000, &1).iter().map(|l| l.id).collect();
+		assert_eq!(ids, vec![*b"democrac", *b"staking "]);
+	});
+}
+
+#[test]
+fn unlock_timeline_should_list_future_unlocks_by_block() {
+	use support::traits::{LockableCurrency, WithdrawReasons};
+	type Staking = StakingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		let schedule = VestingSchedule {
+			locked: 45,
+			per_block: 10,
+			starting_block: 5,
+		};
+		assert_ok!(GenericAsset::vested_transfer(Origin::signed(1), 16000, 2, schedule));
+		Staking::set_lock(*b"democrac", &2, 20, 20, WithdrawReasons::all());
+		Staking::set_lock(*b"staking ", &2, 30, u64::max_value(), WithdrawReasons::all());
+
+		assert_eq!(
+			GenericAsset::unlock_timeline(&2),
+			vec![
+				Unlock {
+					asset_id: 16000,
+					amount: 45,
+					at: 10,
+					reason: UnlockReason::Vesting,
+				},
+				Unlock {
+					asset_id: 16000,
+					amount: 20,
+					at: 20,
+					reason: UnlockReason::Lock(*b"democrac"),
+				},
+			]
+		);
+
+		// Part way through vesting only the remaining balance is listed
+		System::set_block_number(8);
+		assert_eq!(GenericAsset::unlock_timeline(&2)[0].amount, 15);
+		assert_eq!(GenericAsset::unlock_timeline(&2)[0].at, 10);
+	});
+}
+
+#[test]
+fn asset_currency_reserve_should_round_trip_on_its_own_asset() {
+	use support::traits::ReservableCurrency;
+	type Staking = StakingAssetCurrency<Test>;
+	type Spending = SpendingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		GenericAsset::set_free_balance(&16001, &1, 100);
+
+		assert_ok!(Staking::reserve(&1, 30));
+		assert_ok!(Spending::reserve(&1, 40));
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 70);
+		assert_eq!(GenericAsset::reserved_balance(&16000, &1), 30);
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 60);
+		assert_eq!(GenericAsset::reserved_balance(&16001, &1), 40);
+
+		assert_eq!(Staking::unreserve(&1, 30), 0);
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 100);
+		assert_eq!(Spending::reserved_balance(&1), 40);
+
+		assert_eq!(Spending::slash_reserved(&1, 10).1, 0);
+		assert_eq!(Spending::unreserve(&1, 50), 20);
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 90);
+		assert_eq!(GenericAsset::reserved_balance(&16001, &1), 0);
+		assert_eq!(GenericAsset::total_balance(&16000, &1), 100);
+	});
+}
+
+#[test]
+fn reserve_should_respect_locks() {
+	use support::traits::{LockableCurrency, ReservableCurrency, WithdrawReasons};
+	type Staking = StakingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		Staking::set_lock(*b"democrac", &1, 60, 10, WithdrawReasons::all());
+
+		assert!(Staking::can_reserve(&1, 40));
+		assert!(!Staking::can_reserve(&1, 41));
+		assert_noop!(Staking::reserve(&1, 41), "account liquidity restrictions prevent withdrawal");
+		assert_ok!(Staking::reserve(&1, 40));
+	});
+}
+
+#[test]
+fn create_for_should_reserve_stake_from_sponsor() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		let permissions = PermissionLatest {
+			update: Owner::Address(2),
+			mint: Owner::Address(2),
+			burn: Owner::Address(2),
+			freeze: Owner::None,
+		};
+		assert_ok!(GenericAsset::create_for(
+			Origin::signed(1),
+			2,
+			AssetOptions {
+				initial_issuance: 500,
+				permissions,
+			}
+		));
+
+		assert_eq!(GenericAsset::reserved_balance(&16000, &1), 10);
+		assert_eq!(GenericAsset::reserved_balance(&16000, &2), 0);
+		assert_eq!(GenericAsset::creator_stakes(&1), (10, vec![(1000, 10)]));
+		assert_eq!(GenericAsset::creator_stakes(&2), (0, vec![]));
+		assert_eq!(GenericAsset::free_balance(&1000, &2), 500);
+		assert_eq!(GenericAsset::free_balance(&1000, &1), 0);
+		assert!(GenericAsset::check_permission(&1000, &2, &PermissionType::Mint));
+		assert!(!GenericAsset::check_permission(&1000, &1, &PermissionType::Mint));
+		assert!(System::events().iter().any(|record| record.event
+			== TestEvent::generic_asset(RawEvent::CreationSponsored(1000, 1, 2))));
+	});
+}
+
+#[test]
+fn create_for_should_respect_creator_receive_rules() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		let options = AssetOptions {
+			initial_issuance: 500,
+			permissions: PermissionLatest {
+				update: Owner::Address(2),
+				mint: Owner::Address(2),
+				burn: Owner::Address(2),
+				freeze: Owner::None,
+			},
+		};
+		assert_ok!(GenericAsset::set_receive_rules(Origin::signed(2), vec![], vec![1]));
+		assert_noop!(
+			GenericAsset::create_for(Origin::signed(1), 2, options.clone()),
+			"receiver does not accept this transfer"
+		);
+
+		assert_ok!(GenericAsset::set_receive_rules(Origin::signed(2), vec![], vec![]));
+		assert_ok!(GenericAsset::create_for(Origin::signed(1), 2, options));
+		assert_eq!(GenericAsset::free_balance(&1000, &2), 500);
+	});
+}
+
+#[test]
+fn create_for_should_fail_when_sponsor_cannot_pay_stake() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 2, 100)).build(), || {
+		let options = AssetOptions {
+			initial_issuance: 500,
+			permissions: PermissionLatest {
+				update: Owner::Address(2),
+				mint: Owner::Address(2),
+				burn: Owner::Address(2),
+				freeze: Owner::None,
+			},
+		};
+		assert_noop!(GenericAsset::create_for(Origin::signed(1), 2, options), "not enough free funds");
+		assert_eq!(GenericAsset::next_asset_id(), 1000);
+	});
+}
+
+#[test]
+fn call_weight_should_scale_with_locks_and_entries() {
+	use crate::weights::{MINT, PER_LOCK, TRANSFER};
+	use support::traits::{LockableCurrency, WithdrawReasons};
+	type Staking = StakingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		let transfer = Call::<Test>::transfer(16000, 2, 10);
+		assert_eq!(GenericAsset::call_weight(&1, &transfer), TRANSFER);
+
+		Staking::set_lock(*b"democrac", &1, 10, 10, WithdrawReasons::all());
+		Staking::set_lock(*b"staking ", &1, 10, 10, WithdrawReasons::all());
+		assert_eq!(GenericAsset::call_weight(&1, &transfer), TRANSFER + 2 * PER_LOCK);
+		assert_eq!(GenericAsset::call_weight(&2, &transfer), TRANSFER);
+
+		let batch = Call::<Test>::transfer_batch(16000, vec![(2, 1), (3, 1), (4, 1)]);
+		assert_eq!(GenericAsset::call_weight(&1, &batch), 3 * TRANSFER + 2 * PER_LOCK);
+
+		let mints = Call::<Test>::mint_batch(16000, vec![(2, 1), (3, 1)]);
+		assert_eq!(GenericAsset::call_weight(&1, &mints), 2 * MINT);
+		assert!(GenericAsset::call_weight(&1, &mints) > GenericAsset::call_weight(&1, &Call::<Test>::mint(16000, 2, 1)));
+	});
+}
+
+#[test]
+fn renounce_permission_should_be_irreversible() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		let permissions = PermissionLatest {
+			update: Owner::Address(1),
+			mint: Owner::Address(1),
+			burn: Owner::Address(1),
+			freeze: Owner::None,
+		};
+		assert_ok!(GenericAsset::create(
+			Origin::signed(1),
+			AssetOptions {
+				initial_issuance: 100,
+				permissions: permissions.clone(),
+			}
+		));
+
+		assert_noop!(
+			GenericAsset::renounce_permission(Origin::signed(1), 1000, PermissionType::Mint, false),
+			"Renouncing a permission is irreversible and must be confirmed."
+		);
+		assert_err!(
+			GenericAsset::renounce_permission(Origin::signed(2), 1000, PermissionType::Mint, true),
+			"Origin does not have enough permission to renounce the permission."
+		);
+
+		assert_ok!(GenericAsset::renounce_permission(Origin::signed(1), 1000, PermissionType::Mint, true));
+		assert!(System::events().iter().any(|record| record.event
+			== TestEvent::generic_asset(RawEvent::PermissionRenounced(1000, PermissionType::Mint))));
+		assert!(GenericAsset::is_fixed_supply(&1000));
+		assert!(!GenericAsset::is_immutable(&1000));
+		assert!(!GenericAsset::check_permission(&1000, &1, &PermissionType::Mint));
+		assert_err!(
+			GenericAsset::mint(Origin::signed(1), 1000, 1, 10),
+			"The origin does not have permission to mint an asset, Permission error."
+		);
+
+		// The update permission can change other permissions but can not restore a renounced one
+		assert_noop!(
+			GenericAsset::update_permission(Origin::signed(1), 1000, permissions),
+			"A renounced permission can not be restored."
+		);
+		assert_ok!(GenericAsset::update_permission(
+			Origin::signed(1),
+			1000,
+			PermissionLatest {
+				update: Owner::Address(1),
+				mint: Owner::None,
+				burn: Owner::Address(2),
+				freeze: Owner::None,
+			}
+		));
+
+		assert_ok!(GenericAsset::renounce_permission(Origin::signed(2), 1000, PermissionType::Burn, true));
+		assert_ok!(GenericAsset::renounce_permission(Origin::signed(1), 1000, PermissionType::Update, true));
+		assert!(GenericAsset::is_immutable(&1000));
+		assert_eq!(
+			GenericAsset::renounced_permissions(1000),
+			vec![PermissionType::Mint, PermissionType::Burn, PermissionType::Update]
+		);
+	});
+}
+
+#[test]
+fn make_module_transfer_should_skip_user_transfer_rules_and_raise_module_transfer() {
+	use support::traits::{LockableCurrency, WithdrawReasons};
+	type Staking = StakingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::set_velocity_limit(Origin::signed(1), 16000, Some((10, 10))));
+		assert_ok!(GenericAsset::make_module_transfer(b"bounties", &16000, &1, &2, 30));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 30);
+
+		let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+		assert!(events.contains(&TestEvent::generic_asset(RawEvent::ModuleTransfer(*b"bounties", 16000, 1, 2, 30))));
+		assert!(!events.contains(&TestEvent::generic_asset(RawEvent::Transferred(16000, 1, 2, 30))));
+
+		// locks still apply
+		Staking::set_lock(*b"staking ", &1, 60, 10, WithdrawReasons::all());
+		assert_noop!(
+			GenericAsset::make_module_transfer(b"bounties", &16000, &1, &2, 11),
+			"account liquidity restrictions prevent withdrawal"
+		);
+		assert_noop!(
+			GenericAsset::make_module_transfer(b"bounties", &16000, &1, &2, 0),
+			"cannot transfer zero amount"
+		);
+	});
+}
+
+#[test]
+fn freeze_asset_should_halt_transfers_mint_burn_and_reserve_until_thawed() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_ok!(GenericAsset::create(
+			Origin::signed(1),
+			AssetOptions {
+				initial_issuance: 100,
+				permissions: PermissionLatest {
+					update: Owner::Address(1),
+					mint: Owner::Address(1),
+					burn: Owner::Address(1),
+					freeze: Owner::None,
+				},
+			}
+		));
+		// Permissions stored before `freeze` existed decode without a freeze owner
+		<Permissions<Test>>::insert(
+			1000,
+			PermissionVersions::V1(PermissionsV1 {
+				update: Owner::Address(1),
+				mint: Owner::Address(1),
+				burn: Owner::Address(1),
+			}),
+		);
+		assert!(!GenericAsset::check_permission(&1000, &1, &PermissionType::Freeze));
+		assert_err!(
+			GenericAsset::freeze_asset(Origin::signed(1), 1000),
+			"The origin does not have permission to freeze an asset, Permission error."
+		);
+
+		assert_ok!(GenericAsset::update_permission(
+			Origin::signed(1),
+			1000,
+			PermissionLatest {
+				update: Owner::Address(1),
+				mint: Owner::Address(1),
+				burn: Owner::Address(1),
+				freeze: Owner::Address(2),
+			}
+		));
+		assert_eq!(
+			GenericAsset::get_permission(1000),
+			PermissionVersions::V2(PermissionLatest {
+				update: Owner::Address(1),
+				mint: Owner::Address(1),
+				burn: Owner::Address(1),
+				freeze: Owner::Address(2),
+			})
+		);
+
+		assert_ok!(GenericAsset::freeze_asset(Origin::signed(2), 1000));
+		assert!(GenericAsset::is_frozen(1000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::generic_asset(RawEvent::AssetFrozen(1000))));
+		assert_noop!(
+			GenericAsset::freeze_asset(Origin::signed(2), 1000),
+			"asset is already frozen"
+		);
+		assert_noop!(GenericAsset::transfer(Origin::signed(1), 1000, 2, 10), "asset is frozen");
+		assert_noop!(GenericAsset::mint(Origin::signed(1), 1000, 1, 10), "asset is frozen");
+		assert_noop!(GenericAsset::burn(Origin::signed(1), 1000, 1, 10), "asset is frozen");
+		assert_noop!(GenericAsset::reserve(&1000, &1, 10), "asset is frozen");
+		assert!(!GenericAsset::can_reserve(&1000, &1, 10));
+
+		assert_err!(
+			GenericAsset::thaw_asset(Origin::signed(1), 1000),
+			"The origin does not have permission to thaw an asset, Permission error."
+		);
+		assert_ok!(GenericAsset::thaw_asset(Origin::signed(2), 1000));
+		assert!(!GenericAsset::is_frozen(1000));
+		assert_noop!(GenericAsset::thaw_asset(Origin::signed(2), 1000), "asset is not frozen");
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 1000, 2, 10));
+		assert_eq!(GenericAsset::free_balance(&1000, &2), 10);
+	});
+}
+
+#[test]
+fn freeze_account_should_block_sending_and_receiving_only_for_that_account() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::create(
+			Origin::signed(1),
+			AssetOptions {
+				initial_issuance: 100,
+				permissions: PermissionLatest {
+					update: Owner::Address(1),
+					mint: Owner::Address(1),
+					burn: Owner::Address(1),
+					freeze: Owner::Address(1),
+				},
+			}
+		));
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 1000, 2, 50));
+
+		assert_err!(
+			GenericAsset::freeze_account(Origin::signed(2), 1000, 3, 10),
+			"The origin does not have permission to freeze an account, Permission error."
+		);
+		assert_ok!(GenericAsset::freeze_account(Origin::signed(1), 1000, 2, 10));
+		assert!(GenericAsset::is_account_frozen(&1000, &2));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::generic_asset(RawEvent::AccountFrozen(1000, 2, 11))));
+		assert_noop!(
+			GenericAsset::freeze_account(Origin::signed(1), 1000, 2, 10),
+			"account is already frozen"
+		);
+
+		assert_noop!(GenericAsset::transfer(Origin::signed(2), 1000, 3, 10), "account is frozen");
+		assert_noop!(GenericAsset::transfer(Origin::signed(1), 1000, 2, 10), "account is frozen");
+		assert_noop!(GenericAsset::make_transfer(&1000, &1, &2, 10), "account is frozen");
+		assert_noop!(GenericAsset::reserve(&1000, &2, 10), "account is frozen");
+		// Other accounts and other assets are unaffected
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 1000, 3, 10));
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 10));
+
+		assert_ok!(GenericAsset::unfreeze_account(Origin::signed(1), 1000, 2));
+		assert!(!GenericAsset::is_account_frozen(&1000, &2));
+		assert_noop!(
+			GenericAsset::unfreeze_account(Origin::signed(1), 1000, 2),
+			"account is not frozen"
+		);
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), 1000, 3, 10));
+		assert_eq!(GenericAsset::free_balance(&1000, &3), 20);
+	});
+}
+
+#[test]
+fn root_can_set_module_parameters() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_ok!(GenericAsset::set_create_asset_stake(5));
+		assert_eq!(GenericAsset::create_asset_stake(), 5);
+
+		assert_noop!(GenericAsset::set_staking_asset_id(1000), "asset has not been issued");
+		assert_noop!(GenericAsset::set_spending_asset_id(1000), "asset has not been issued");
+		assert_ok!(GenericAsset::create(
+			Origin::signed(1),
+			AssetOptions {
+				initial_issuance: 100,
+				permissions: PermissionLatest::default(),
+			}
+		));
+		assert_ok!(GenericAsset::set_staking_asset_id(1000));
+		assert_eq!(GenericAsset::staking_asset_id(), 1000);
+		assert_ok!(GenericAsset::set_spending_asset_id(1000));
+		assert_eq!(GenericAsset::spending_asset_id(), 1000);
+	});
+}
+
+#[test]
+fn scheduled_transfer_should_reserve_then_execute_at_the_target_block() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_noop!(
+			GenericAsset::schedule_transfer(Origin::signed(1), 16000, 2, 40, 1),
+			"scheduled transfer must be in the future"
+		);
+		assert_noop!(
+			GenericAsset::schedule_transfer(Origin::signed(1), 16000, 2, 0, 5),
+			"cannot transfer zero amount"
+		);
+		assert_ok!(GenericAsset::schedule_transfer(Origin::signed(1), 16000, 2, 40, 5));
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 60);
+		assert_eq!(GenericAsset::reserves(&16000, &1), vec![(b"schedule".to_vec(), 40)]);
+		assert_eq!(GenericAsset::scheduled_transfers_due(5), vec![0]);
+
+		GenericAsset::on_initialize(4);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 0);
+		GenericAsset::on_initialize(5);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 40);
+		assert_eq!(GenericAsset::total_balance(&16000, &1), 60);
+		assert_eq!(GenericAsset::scheduled_transfer(0), None);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::generic_asset(RawEvent::ScheduledTransferExecuted(0))));
+	});
+}
+
+#[test]
+fn cancel_scheduled_transfer_should_release_the_reserved_amount() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::schedule_transfer(Origin::signed(1), 16000, 2, 40, 5));
+		assert_noop!(
+			GenericAsset::cancel_scheduled_transfer(Origin::signed(2), 0),
+			"only the sender can cancel a scheduled transfer"
+		);
+		assert_ok!(GenericAsset::cancel_scheduled_transfer(Origin::signed(1), 0));
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 100);
+		assert_eq!(GenericAsset::reserved_balance(&16000, &1), 0);
+		assert_eq!(GenericAsset::scheduled_transfers_due(5), Vec::<ScheduledTransferId>::new());
+		assert_noop!(
+			GenericAsset::cancel_scheduled_transfer(Origin::signed(1), 0),
+			"scheduled transfer does not exist"
+		);
+
+		GenericAsset::on_initialize(5);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 0);
+	});
+}
+
+#[test]
+fn account_freeze_should_expire_after_its_period_unless_extended() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::create(
+			Origin::signed(1),
+			AssetOptions {
+				initial_issuance: 100,
+				permissions: PermissionLatest {
+					update: Owner::Address(1),
+					mint: Owner::Address(1),
+					burn: Owner::Address(1),
+					freeze: Owner::Address(1),
+				},
+			}
+		));
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 1000, 2, 50));
+
+		assert_ok!(GenericAsset::set_max_freeze_period(20));
+		assert_noop!(
+			GenericAsset::freeze_account(Origin::signed(1), 1000, 2, 21),
+			"freeze period exceeds the maximum"
+		);
+		assert_noop!(
+			GenericAsset::freeze_account(Origin::signed(1), 1000, 2, 0),
+			"freeze period must be greater than zero"
+		);
+		assert_ok!(GenericAsset::freeze_account(Origin::signed(1), 1000, 2, 20));
+		assert_eq!(GenericAsset::account_frozen_until(&1000, &2), Some(21));
+
+		// root extends the freeze past the freeze owner's maximum
+		assert_noop!(
+			GenericAsset::extend_account_freeze(1000, 2, 21),
+			"an account freeze can only be extended"
+		);
+		assert_ok!(GenericAsset::extend_account_freeze(1000, 2, 31));
+		assert_eq!(GenericAsset::account_freezes_expiring(21), vec![]);
+		GenericAsset::on_initialize(21);
+		assert!(GenericAsset::is_account_frozen(&1000, &2));
+		assert_noop!(GenericAsset::transfer(Origin::signed(2), 1000, 3, 10), "account is frozen");
+
+		GenericAsset::on_initialize(31);
+		assert!(!GenericAsset::is_account_frozen(&1000, &2));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::generic_asset(RawEvent::AccountFreezeExpired(1000, 2))));
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), 1000, 3, 10));
+		assert_noop!(GenericAsset::extend_account_freeze(1000, 2, 41), "account is not frozen");
+	});
+}
+
+#[test]
+fn holders_are_tracked_as_balances_become_non_zero_and_zero() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_eq!(GenericAsset::holder_count(16000), 1);
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 10));
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 3, 10));
+		assert_eq!(GenericAsset::holder_count(16000), 3);
+		assert_eq!(GenericAsset::holders_paged(&16000, 0, 10), vec![1, 2, 3]);
+		assert_eq!(GenericAsset::holders_paged(&16000, 1, 2), vec![3]);
+		assert_eq!(GenericAsset::holders_paged(&16000, 2, 2), vec![]);
+
+		// the last holder takes the place of a leaving holder
+		assert_ok!(GenericAsset::transfer(Origin::signed(2), 16000, 1, 10));
+		assert_eq!(GenericAsset::holders_paged(&16000, 0, 10), vec![1, 3]);
+
+		// a reserved balance is held too
+		assert_ok!(GenericAsset::reserve(&16000, &3, 10));
+		assert_eq!(GenericAsset::holders_paged(&16000, 0, 10), vec![1, 3]);
+		GenericAsset::unreserve(&16000, &3, 10);
+		assert_ok!(GenericAsset::transfer(Origin::signed(3), 16000, 1, 10));
+		assert_eq!(GenericAsset::holder_count(16000), 1);
+		assert_eq!(GenericAsset::holders_paged(&16000, 0, 10), vec![1]);
+	});
+}
+
+#[test]
+fn holders_from_before_the_holder_index_are_backfilled_by_the_migration() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		<FreeBalance<Test>>::insert(&16000, &2, 50);
+		assert_eq!(GenericAsset::total_balance(&16000, &2), 50);
+		assert_eq!(GenericAsset::holders_paged(&16000, 0, 10), vec![1]);
+
+		assert_ok!(GenericAsset::migrate_storage(vec![(16000, 2), (16000, 1), (16000, 3)]));
+		assert_eq!(GenericAsset::holder_count(16000), 2);
+		assert_eq!(GenericAsset::holders_paged(&16000, 0, 10), vec![1, 2]);
+
+		// listing a holder again does not index it twice
+		assert_ok!(GenericAsset::migrate_storage(vec![(16000, 2)]));
+		assert_eq!(GenericAsset::holders_paged(&16000, 0, 10), vec![1, 2]);
+	});
+}
+
+#[test]
+fn asset_creations_are_capped_per_block() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100000)).build(), || {
+		let options = AssetOptions {
+			initial_issuance: 100,
+			permissions: PermissionLatest {
+				update: Owner::Address(1),
+				mint: Owner::Address(1),
+				burn: Owner::Address(1),
+				freeze: Owner::None,
+			},
+		};
+		assert_ok!(GenericAsset::set_max_asset_creations_per_block(1));
+		assert_ok!(GenericAsset::create(Origin::signed(1), options.clone()));
+		assert_noop!(
+			GenericAsset::create(Origin::signed(1), options.clone()),
+			"too many assets have been created in this block"
+		);
+		assert_noop!(GenericAsset::allocate_asset_id(&1), "too many assets have been created in this block");
+
+		GenericAsset::on_initialize(2);
+		assert_eq!(GenericAsset::asset_creations(), 0);
+		assert_ok!(GenericAsset::create(Origin::signed(1), options));
+		assert_eq!(GenericAsset::asset_creations(), 1);
+	});
+}
+
+#[test]
+fn locked_transfer_is_claimed_with_the_preimage_before_the_timelock() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		let hashlock = H256(runtime_io::blake2_256(b"secret"));
+		assert_noop!(
+			GenericAsset::transfer_locked(Origin::signed(1), 16000, 2, 40, hashlock, HashAlgorithm::Blake2_256, 1),
+			"timelock must be in the future"
+		);
+		assert_ok!(GenericAsset::transfer_locked(
+			Origin::signed(1),
+			16000,
+			2,
+			40,
+			hashlock,
+			HashAlgorithm::Blake2_256,
+			5
+		));
+		assert_eq!(GenericAsset::reserves(&16000, &1), vec![(b"htlc".to_vec(), 40)]);
+		assert_noop!(
+			GenericAsset::transfer_locked(Origin::signed(1), 16000, 3, 10, hashlock, HashAlgorithm::Blake2_256, 5),
+			"hashlock is already in use"
+		);
+
+		assert_noop!(
+			GenericAsset::claim_locked_transfer(Origin::signed(3), 1, b"guess".to_vec()),
+			"locked transfer does not exist"
+		);
+		assert_noop!(
+			GenericAsset::refund_locked_transfer(Origin::signed(1), 1, hashlock),
+			"locked transfer's timelock has not passed"
+		);
+		assert_ok!(GenericAsset::claim_locked_transfer(Origin::signed(3), 1, b"secret".to_vec()));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 40);
+		assert_eq!(GenericAsset::total_balance(&16000, &1), 60);
+		assert_eq!(GenericAsset::locked_transfer(&1, &hashlock), None);
+		assert!(System::events().iter().any(|record| record.event
+			== TestEvent::generic_asset(RawEvent::LockedTransferClaimed(1, hashlock, b"secret".to_vec()))));
+	});
+}
+
+#[test]
+fn locked_transfer_hashlock_can_not_be_taken_by_another_sender() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::reward(&16000, &3, 10));
+		let hashlock = H256(runtime_io::blake2_256(b"secret"));
+
+		// the hashlock is public from the swap's first leg, a dust transfer under it does not block the second leg
+		assert_ok!(GenericAsset::transfer_locked(
+			Origin::signed(3),
+			16000,
+			2,
+			1,
+			hashlock,
+			HashAlgorithm::Blake2_256,
+			5
+		));
+		assert_ok!(GenericAsset::transfer_locked(
+			Origin::signed(1),
+			16000,
+			2,
+			40,
+			hashlock,
+			HashAlgorithm::Blake2_256,
+			5
+		));
+
+		assert_ok!(GenericAsset::claim_locked_transfer(Origin::signed(2), 1, b"secret".to_vec()));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 40);
+		assert_eq!(GenericAsset::locked_transfer(&1, &hashlock), None);
+		assert!(GenericAsset::locked_transfer(&3, &hashlock).is_some());
+	});
+}
+
+#[test]
+fn locked_transfer_is_refunded_from_its_timelock() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		let hashlock = H256(runtime_io::blake2_256(b"secret"));
+		assert_ok!(GenericAsset::transfer_locked(
+			Origin::signed(1),
+			16000,
+			2,
+			40,
+			hashlock,
+			HashAlgorithm::Blake2_256,
+			5
+		));
+
+		System::set_block_number(5);
+		assert_noop!(
+			GenericAsset::claim_locked_transfer(Origin::signed(2), 1, b"secret".to_vec()),
+			"locked transfer's timelock has passed"
+		);
+		assert_ok!(GenericAsset::refund_locked_transfer(Origin::signed(1), 1, hashlock));
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 100);
+		assert_eq!(GenericAsset::reserved_balance(&16000, &1), 0);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 0);
+		assert_eq!(GenericAsset::locked_transfer(&1, &hashlock), None);
+	});
+}
+
+#[test]
+fn locked_transfer_is_checked_like_a_transfer() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		let hashlock = H256(runtime_io::blake2_256(b"secret"));
+		let transfer_locked = |amount| {
+			GenericAsset::transfer_locked(Origin::signed(1), 16000, 2, amount, hashlock, HashAlgorithm::Blake2_256, 5)
+		};
+
+		<MinTransferAmount<Test>>::insert(16000, 10);
+		assert_noop!(transfer_locked(5), "transfer amount is below the asset's minimum transfer amount");
+		assert_ok!(transfer_locked(40));
+
+		// the receiver refuses the asset after the transfer was locked
+		assert_ok!(GenericAsset::set_receive_rules(Origin::signed(2), vec![16000], vec![]));
+		assert_noop!(
+			GenericAsset::claim_locked_transfer(Origin::signed(3), 1, b"secret".to_vec()),
+			"receiver does not accept this transfer"
+		);
+		assert_ok!(GenericAsset::set_receive_rules(Origin::signed(2), vec![], vec![]));
+		assert_ok!(GenericAsset::claim_locked_transfer(Origin::signed(3), 1, b"secret".to_vec()));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 40);
+	});
+}
+
+#[test]
+fn locked_transfer_hashlock_may_be_shared_with_bitcoin_and_ethereum() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		// SHA-256 of "abc"
+		let sha256_hashlock = H256([
+			0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23, 0xb0, 0x03,
+			0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+		]);
+		assert_eq!(GenericAsset::hashlock_of(HashAlgorithm::Sha256, b"abc"), sha256_hashlock);
+		let keccak256_hashlock = H256(runtime_io::keccak_256(b"secret"));
+		assert_ok!(GenericAsset::transfer_locked(
+			Origin::signed(1),
+			16000,
+			2,
+			40,
+			sha256_hashlock,
+			HashAlgorithm::Sha256,
+			5
+		));
+		assert_ok!(GenericAsset::transfer_locked(
+			Origin::signed(1),
+			16000,
+			3,
+			10,
+			keccak256_hashlock,
+			HashAlgorithm::Keccak256,
+			5
+		));
+
+		assert_ok!(GenericAsset::claim_locked_transfer(Origin::signed(2), 1, b"abc".to_vec()));
+		assert_ok!(GenericAsset::claim_locked_transfer(Origin::signed(3), 1, b"secret".to_vec()));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 40);
+		assert_eq!(GenericAsset::free_balance(&16000, &3), 10);
+		assert_eq!(GenericAsset::reserved_balance(&16000, &1), 0);
+	});
+}
+
+#[test]
+fn locked_transfer_is_only_claimed_with_its_own_hash_algorithm() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		System::set_block_number(1);
+		let hashlock = GenericAsset::hashlock_of(HashAlgorithm::Blake2_256, b"secret");
+		assert_ok!(GenericAsset::transfer_locked(
+			Origin::signed(1),
+			16000,
+			2,
+			40,
+			hashlock,
+			HashAlgorithm::Sha256,
+			5
+		));
+		assert_noop!(
+			GenericAsset::claim_locked_transfer(Origin::signed(2), 1, b"secret".to_vec()),
+			"locked transfer does not exist"
+		);
+	});
+}
+
+#[test]
+fn creation_stake_share_is_paid_to_the_treasury() {
+	with_externalities(
+		&mut ExtBuilder::default().free_balance((16000, 1, 100000)).build(),
+		|| {
+			let stake = GenericAsset::create_asset_stake();
+			GenericAsset::set_creation_stake_treasury(Some((9, Permill::from_percent(20))));
+			assert_ok!(GenericAsset::create(
+				Origin::signed(1),
+				AssetOptions {
+					initial_issuance: 100,
+					permissions: PermissionLatest {
+						update: Owner::Address(1),
+						mint: Owner::Address(1),
+						burn: Owner::Address(1),
+						freeze: Owner::None,
+					},
+				}
+			));
+
+			let paid = Permill::from_percent(20) * stake;
+			assert_eq!(GenericAsset::free_balance(&16000, &9), paid);
+			assert_eq!(GenericAsset::reserved_balance(&16000, &1), stake - paid);
+			assert_eq!(GenericAsset::creator_stakes(&1), (stake - paid, vec![(1000, stake - paid)]));
+		},
+	);
+}
+
+#[test]
+fn unversioned_storage_is_migrated_to_the_latest_layout_once() {
+	use support::traits::WithdrawReasons;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		assert_eq!(GenericAsset::storage_version(), StorageLayout::LATEST);
+		assert_eq!(GenericAsset::migrate(&[]), 0);
+
+		<StorageVersion<Test>>::kill();
+		<AccountStore<Test>>::remove(&16000, &1);
+		<FreeBalance<Test>>::insert(&16000, &1, 100);
+		let legacy = BalanceLock {
+			id: *b"staking ",
+			amount: 50,
+			until: 10,
+			reasons: WithdrawReasons::all(),
+			metadata: None,
+		};
+		<Locks<Test>>::insert(1, vec![legacy.clone()]);
+
+		// Blocks no longer check the layout, the upgrade is enacted with the root call
+		GenericAsset::on_initialize(1);
+		assert_eq!(GenericAsset::storage_version(), StorageLayout::Unversioned);
+
+		assert_ok!(GenericAsset::migrate_storage(vec![(16000, 1)]));
+		assert_eq!(GenericAsset::storage_version(), StorageLayout::LATEST);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::generic_asset(RawEvent::StorageMigrated(StorageLayout::V1))));
+		assert!(!<Locks<Test>>::exists(1));
+		assert_eq!(GenericAsset::locks(&16000, &1), vec![legacy]);
+		assert!(!<FreeBalance<Test>>::exists(&16000, &1));
+		assert_eq!(<AccountStore<Test>>::get(&16000, &1).map(|account| account.free), Some(100));
+
+		assert_eq!(GenericAsset::migrate(&[(16000, 1)]), 0);
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 100);
+		assert_noop!(
+			GenericAsset::migrate_storage(vec![(16000, 1); MIGRATE_ACCOUNTS_MAX + 1]),
+			"too many accounts to migrate"
+		);
+	});
+}
+
+#[test]
+fn legacy_balances_should_move_to_account_store_when_written() {
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		<AccountStore<Test>>::remove(&16000, &1);
+		<FreeBalance<Test>>::insert(&16000, &1, 70);
+		<ReservedBalance<Test>>::insert(&16000, &1, 30);
+		assert_eq!(GenericAsset::total_balance(&16000, &1), 100);
+
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 20));
+		assert!(!<FreeBalance<Test>>::exists(&16000, &1));
+		assert!(!<ReservedBalance<Test>>::exists(&16000, &1));
+		assert_eq!(
+			<AccountStore<Test>>::get(&16000, &1),
+			Some(AccountData { free: 50, reserved: 30, ..Default::default() })
+		);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 20);
+	});
+}
+
+#[test]
+fn account_frozen_amounts_should_track_the_largest_lock() {
+	use support::traits::{LockableCurrency, WithdrawReasons};
+	type Staking = StakingAssetCurrency<Test>;
+
+	with_externalities(&mut ExtBuilder::default().free_balance((16000, 1, 100)).build(), || {
+		Staking::set_lock(*b"staking ", &1, 60, 10, WithdrawReasons::all());
+		Staking::set_lock(*b"democrac", &1, 40, 20, WithdrawReason::Transfer.into());
+		let account = GenericAsset::account(&16000, &1);
+		assert_eq!((account.misc_frozen, account.fee_frozen), (60, 60));
+
+		Staking::remove_lock(*b"staking ", &1);
+		let account = GenericAsset::account(&16000, &1);
+		assert_eq!((account.misc_frozen, account.fee_frozen), (40, 0));
+		assert_ok!(GenericAsset::transfer(Origin::signed(1), 16000, 2, 60));
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(1), 16000, 2, 1),
+			"account liquidity restrictions prevent withdrawal"
+		);
+	});
+}
@@ -0,0 +1,117 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Weights of the generic asset calls
+//!
+//! A call's weight is its worst case count of storage reads and writes, scaled by the number of locks,
+//! recipients or entries it processes. The runtime charges `Fee::PerWeight` for each unit of weight,
+//! so a heavy call can not be priced like a light one.
+//! The benchmarks in `benchmarks.rs` time each call against its weight.
+
+use crate::{Call, Module, Trait};
+
+/// The execution cost of a call in weight units
+pub type Weight = u64;
+
+/// The weight of reading a storage item
+pub const READ: Weight = 1;
+
+/// The weight of writing a storage item
+pub const WRITE: Weight = 3;
+
+/// The weight of checking one balance lock on a withdrawal
+pub const PER_LOCK: Weight = 1;
+
+/// The weight of moving balance from one account to another, excluding the sender's locks
+pub const TRANSFER: Weight = 14 * READ + 6 * WRITE;
+
+/// The weight of minting or burning into one account, including checking whether it is frozen
+pub const MINT: Weight = 5 * READ + 4 * WRITE;
+
+/// The weight of creating an asset, including reserving the creation stake
+pub const CREATE: Weight = 7 * READ + 11 * WRITE;
+
+/// The weight of reserving the amount of a scheduled transfer, paying ahead for executing it when due
+pub const SCHEDULE_TRANSFER: Weight = 6 * READ + 6 * WRITE + TRANSFER;
+
+/// The weight of freezing or unfreezing one account, including scheduling its thaw
+pub const ACCOUNT_FREEZE: Weight = 4 * READ + 2 * WRITE;
+
+/// The weight of moving one account's locks and balances to the latest storage layout and indexing it as a holder
+pub const MIGRATE_ACCOUNT: Weight = 11 * READ + 8 * WRITE;
+
+/// The weight of a call which reads and writes a single item, e.g. a setting
+pub const SETTING: Weight = 2 * READ + WRITE;
+
+impl<T: Trait> Module<T> {
+	/// The weight of `call` made by `who`.
+	/// Transfers are weighed by the number of locks on the sender, batches by their number of entries.
+	/// Every call is matched by name, so a new call does not compile until it is given a weight.
+	pub fn call_weight(who: &T::AccountId, call: &Call<T>) -> Weight {
+		let entries = |len: usize| len as Weight;
+		match call {
+			Call::create(..) | Call::create_for(..) | Call::create_reserved(..) => CREATE,
+			Call::transfer(asset_id, ..) | Call::transfer_with_payload(asset_id, ..) => {
+				TRANSFER + Self::lock_weight(asset_id, who)
+			}
+			Call::transfer_from(asset_id, owner, ..) => TRANSFER + READ + WRITE + Self::lock_weight(asset_id, owner),
+			Call::vested_transfer(asset_id, ..) => TRANSFER + READ + WRITE + Self::lock_weight(asset_id, who),
+			Call::transfer_batch(asset_id, transfers) => {
+				TRANSFER * entries(transfers.len()) + Self::lock_weight(asset_id, who)
+			}
+			Call::settle(legs) => legs
+				.iter()
+				.map(|(asset_id, from, ..)| TRANSFER + Self::lock_weight(asset_id, from))
+				.sum(),
+			Call::schedule_transfer(asset_id, ..) => SCHEDULE_TRANSFER + Self::lock_weight(asset_id, who),
+			Call::cancel_scheduled_transfer(..) => 3 * READ + 4 * WRITE,
+			Call::transfer_locked(asset_id, ..) => TRANSFER + 2 * READ + 4 * WRITE + Self::lock_weight(asset_id, who),
+			// the preimage is hashed with each algorithm and the receiver checked again
+			Call::claim_locked_transfer(..) => 9 * READ + 5 * WRITE,
+			Call::refund_locked_transfer(..) => 3 * READ + 4 * WRITE,
+			Call::mint(..) | Call::burn(..) => MINT,
+			Call::mint_batch(_, mints) => MINT * entries(mints.len()),
+			Call::burn_batch(_, burns) => MINT * entries(burns.len()),
+			Call::freeze_asset(..) | Call::thaw_asset(..) => SETTING,
+			Call::freeze_account(..) | Call::unfreeze_account(..) => ACCOUNT_FREEZE,
+			Call::extend_account_freeze(..) => ACCOUNT_FREEZE + WRITE,
+			Call::renounce_permission(..) => SETTING + READ + WRITE,
+			Call::migrate_storage(accounts) => SETTING + MIGRATE_ACCOUNT * entries(accounts.len()),
+			Call::approve(..)
+			| Call::revoke_approval(..)
+			| Call::update_permission(..)
+			| Call::set_mint_cooldown(..)
+			| Call::set_min_transfer_amount(..)
+			| Call::set_minimum_balance(..)
+			| Call::set_velocity_limit(..)
+			| Call::set_dust_collector(..)
+			| Call::set_co_signer(..)
+			| Call::set_receive_rules(..)
+			| Call::set_metadata_link(..)
+			| Call::set_large_transfer_threshold(..)
+			| Call::set_max_freeze_period(..)
+			| Call::set_create_asset_stake(..)
+			| Call::set_staking_asset_id(..)
+			| Call::set_spending_asset_id(..)
+			| Call::set_max_asset_creations_per_block(..) => SETTING,
+			Call::__PhantomItem(_, never) => match *never {},
+		}
+	}
+
+	/// The weight of checking the locks on the `asset_id` balance of `who`
+	fn lock_weight(asset_id: &T::AssetId, who: &T::AccountId) -> Weight {
+		PER_LOCK * Self::locks(asset_id, who).len() as Weight
+	}
+}
@@ -0,0 +1,179 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! An adapter exposing the generic asset module through the `MultiCurrency` trait family, so modules
+//! written against a multi-currency interface can be used without per-module glue code.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use generic_asset::{Module as GenericAsset, Trait as GenericAssetTrait};
+use parity_codec::Codec;
+use rstd::{fmt::Debug, marker::PhantomData, result};
+use runtime_primitives::traits::{CheckedSub, MaybeSerializeDebug, SimpleArithmetic, Zero};
+use support::{dispatch::Result, traits::WithdrawReason};
+
+mod mock;
+mod tests;
+
+/// Abstraction over a fungible multi-currency system.
+pub trait MultiCurrency<AccountId> {
+	/// The currency identifier.
+	type CurrencyId: Codec + Copy + Eq + Debug;
+	/// The balance of an account.
+	type Balance: SimpleArithmetic + Codec + Copy + MaybeSerializeDebug + Default;
+
+	/// The minimum balance any single account may have.
+	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance;
+	/// The total amount of issuance of `currency_id`.
+	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance;
+	/// The combined free and reserved balance of `who`.
+	fn total_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+	/// The free balance of `who`.
+	fn free_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+	/// Return `Ok` iff `amount` can be withdrawn from the free balance of `who`.
+	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Result;
+	/// Transfer `amount` from `from` to `to`.
+	fn transfer(currency_id: Self::CurrencyId, from: &AccountId, to: &AccountId, amount: Self::Balance) -> Result;
+	/// Add `amount` to the free balance of `who`, increasing the total issuance.
+	fn deposit(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Result;
+	/// Remove `amount` from the free balance of `who`, reducing the total issuance.
+	fn withdraw(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Result;
+	/// Whether `who` has at least `amount` to slash.
+	fn can_slash(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> bool;
+	/// Deduct up to `amount` from the free then reserved balance of `who`, reducing the total issuance.
+	/// Returns the amount which could not be slashed.
+	fn slash(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+}
+
+/// A `MultiCurrency` which supports reserving balances.
+pub trait MultiReservableCurrency<AccountId>: MultiCurrency<AccountId> {
+	/// Whether `who` has at least `amount` of free balance to reserve.
+	fn can_reserve(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> bool;
+	/// Deduct up to `amount` from the reserved balance of `who`, reducing the total issuance.
+	/// Returns the amount which could not be slashed.
+	fn slash_reserved(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+	/// The reserved balance of `who`.
+	fn reserved_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+	/// Move `amount` from the free to the reserved balance of `who`.
+	fn reserve(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Result;
+	/// Move up to `amount` from the reserved to the free balance of `who`.
+	/// Returns the amount which could not be unreserved.
+	fn unreserve(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+	/// Move up to `amount` from the reserved balance of `slashed` to the free balance of `beneficiary`.
+	/// Returns the amount which could not be moved.
+	fn repatriate_reserved(
+		currency_id: Self::CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		amount: Self::Balance,
+	) -> result::Result<Self::Balance, &'static str>;
+}
+
+/// The generic asset module as a `MultiCurrency`, currencies are asset ids.
+pub struct GenericAssetAdapter<T>(PhantomData<T>);
+
+impl<T: GenericAssetTrait> MultiCurrency<T::AccountId> for GenericAssetAdapter<T> {
+	type CurrencyId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn minimum_balance(_currency_id: Self::CurrencyId) -> Self::Balance {
+		// no existential deposit policy for generic asset
+		Zero::zero()
+	}
+
+	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
+		<GenericAsset<T>>::total_issuance(currency_id)
+	}
+
+	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		<GenericAsset<T>>::total_balance(&currency_id, who)
+	}
+
+	fn free_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		<GenericAsset<T>>::free_balance(&currency_id, who)
+	}
+
+	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Result {
+		let new_balance = Self::free_balance(currency_id, who)
+			.checked_sub(&amount)
+			.ok_or_else(|| "account has too few funds")?;
+		<GenericAsset<T>>::ensure_can_withdraw(&currency_id, who, amount, WithdrawReason::Transfer, new_balance)
+	}
+
+	fn transfer(
+		currency_id: Self::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result {
+		<GenericAsset<T>>::make_transfer(&currency_id, from, to, amount)
+	}
+
+	fn deposit(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Result {
+		<GenericAsset<T>>::reward(&currency_id, who, amount)
+	}
+
+	fn withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Result {
+		Self::ensure_can_withdraw(currency_id, who, amount)?;
+		// the free balance covers `amount`, so nothing is slashed from the reserved balance
+		let _ = <GenericAsset<T>>::slash(&currency_id, who, amount);
+		<GenericAsset<T>>::decrease_total_issuance(&currency_id, amount);
+		Ok(())
+	}
+
+	fn can_slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> bool {
+		Self::total_balance(currency_id, who) >= amount
+	}
+
+	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		let remaining = <GenericAsset<T>>::slash(&currency_id, who, amount).unwrap_or_else(Zero::zero);
+		<GenericAsset<T>>::decrease_total_issuance(&currency_id, amount - remaining);
+		remaining
+	}
+}
+
+impl<T: GenericAssetTrait> MultiReservableCurrency<T::AccountId> for GenericAssetAdapter<T> {
+	fn can_reserve(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> bool {
+		Self::free_balance(currency_id, who) >= amount
+	}
+
+	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		let remaining = <GenericAsset<T>>::slash_reserved(&currency_id, who, amount).unwrap_or_else(Zero::zero);
+		<GenericAsset<T>>::decrease_total_issuance(&currency_id, amount - remaining);
+		remaining
+	}
+
+	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		<GenericAsset<T>>::reserved_balance(&currency_id, who)
+	}
+
+	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Result {
+		<GenericAsset<T>>::reserve(&currency_id, who, amount)
+	}
+
+	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		<GenericAsset<T>>::unreserve(&currency_id, who, amount)
+	}
+
+	fn repatriate_reserved(
+		currency_id: Self::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: Self::Balance,
+	) -> result::Result<Self::Balance, &'static str> {
+		<GenericAsset<T>>::repatriate_reserved(&currency_id, slashed, beneficiary, amount)
+	}
+}
@@ -0,0 +1,91 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Test utilities
+#![cfg(test)]
+
+use parity_codec::{Decode, Encode};
+use primitives::{Blake2Hasher, H256};
+use runtime_primitives::{
+	testing::{Digest, DigestItem, Header},
+	traits::{BlakeTwo256, IdentityLookup, Lazy, Verify},
+	BuildStorage,
+};
+use serde::{Deserialize, Serialize};
+use support::impl_outer_origin;
+
+use crate::GenericAssetAdapter;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Debug)]
+pub struct Signature;
+
+impl Verify for Signature {
+	type Signer = u64;
+	fn verify<L: Lazy<[u8]>>(&self, _msg: L, _signer: &Self::Signer) -> bool {
+		true
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+impl system::Trait for Test {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Digest = Digest;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<u64>;
+	type Header = Header;
+	type Event = ();
+	type Log = DigestItem;
+	type Signature = Signature;
+}
+
+impl generic_asset::Trait for Test {
+	type Balance = u64;
+	type AssetId = u32;
+	type Event = ();
+	type OnTransferPayload = ();
+}
+
+pub type Currencies = GenericAssetAdapter<Test>;
+
+pub const ASSET_ID: u32 = 16_000;
+
+/// Genesis with accounts 1 and 2 holding 100 of `ASSET_ID` each
+pub fn new_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
+	let (mut t, mut c) = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let _ = generic_asset::GenesisConfig::<Test> {
+		staking_asset_id: ASSET_ID,
+		spending_asset_id: 16_001,
+		assets: vec![ASSET_ID],
+		endowed_accounts: vec![1, 2],
+		endowments: vec![],
+		genesis_locks: vec![],
+		create_asset_stake: 10,
+		initial_balance: 100,
+		next_asset_id: 17_000,
+	}
+	.assimilate_storage(&mut t, &mut c);
+
+	t.into()
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Tests for the module.
+
+#![cfg(test)]
+
+use crate::mock::{new_test_ext, Currencies, ASSET_ID};
+use crate::{MultiCurrency, MultiReservableCurrency};
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok};
+
+#[test]
+fn transfer_deposit_and_withdraw_should_update_balances_and_issuance() {
+	with_externalities(&mut new_test_ext(), || {
+		assert_eq!(Currencies::total_issuance(ASSET_ID), 200);
+
+		assert_ok!(Currencies::transfer(ASSET_ID, &1, &2, 30));
+		assert_eq!(Currencies::free_balance(ASSET_ID, &1), 70);
+		assert_eq!(Currencies::free_balance(ASSET_ID, &2), 130);
+
+		assert_ok!(Currencies::deposit(ASSET_ID, &3, 50));
+		assert_eq!(Currencies::free_balance(ASSET_ID, &3), 50);
+		assert_eq!(Currencies::total_issuance(ASSET_ID), 250);
+
+		assert_ok!(Currencies::withdraw(ASSET_ID, &3, 20));
+		assert_eq!(Currencies::free_balance(ASSET_ID, &3), 30);
+		assert_eq!(Currencies::total_issuance(ASSET_ID), 230);
+
+		assert_noop!(Currencies::withdraw(ASSET_ID, &3, 31), "account has too few funds");
+	});
+}
+
+#[test]
+fn slash_should_take_free_then_reserved_balance() {
+	with_externalities(&mut new_test_ext(), || {
+		assert_ok!(Currencies::reserve(ASSET_ID, &1, 40));
+		assert_eq!(Currencies::free_balance(ASSET_ID, &1), 60);
+		assert_eq!(Currencies::reserved_balance(ASSET_ID, &1), 40);
+
+		assert!(Currencies::can_slash(ASSET_ID, &1, 100));
+		assert_eq!(Currencies::slash(ASSET_ID, &1, 80), 0);
+		assert_eq!(Currencies::total_balance(ASSET_ID, &1), 20);
+		assert_eq!(Currencies::slash_reserved(ASSET_ID, &1, 30), 10);
+		assert_eq!(Currencies::total_balance(ASSET_ID, &1), 0);
+		assert_eq!(Currencies::total_issuance(ASSET_ID), 100);
+	});
+}
+
+#[test]
+fn unreserve_and_repatriate_should_return_the_remainder() {
+	with_externalities(&mut new_test_ext(), || {
+		assert_ok!(Currencies::reserve(ASSET_ID, &1, 40));
+		assert_eq!(Currencies::unreserve(ASSET_ID, &1, 10), 0);
+		assert_eq!(Currencies::repatriate_reserved(ASSET_ID, &1, &2, 50), Ok(20));
+		assert_eq!(Currencies::reserved_balance(ASSET_ID, &1), 0);
+		assert_eq!(Currencies::free_balance(ASSET_ID, &2), 130);
+		assert_eq!(Currencies::total_issuance(ASSET_ID), 200);
+	});
+}
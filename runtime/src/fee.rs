@@ -16,14 +16,31 @@
 //!
 //! Runtime extrinsic fee logic
 //!
-use crate::{AccountId, Call, CheckedExtrinsic, Fee, Fees, Runtime};
-use fees::{AssetOf, BuyFeeAsset, CheckCallFee, Trait as FeesTrait};
-use runtime_primitives::traits::{Applyable, As, Zero};
+use crate::{
+	AccountId, Aura, Balance, Call, CennzxSpot, CheckedExtrinsic, Doughnuts, Fee, FeeBudget, Fees, GenericAsset, Hash,
+	Index, Indices, Runtime, Session, System, Timestamp, UncheckedExtrinsic,
+};
+use cennznet_primitives::{runtime_api::ExtrinsicTemplate, FeeExchange};
+use parity_codec::Encode;
+use rstd::result;
+use fees::{AssetOf, CheckCallFee};
+use generic_asset::ModuleIdentifier;
+use runtime_primitives::generic::Era;
+use runtime_primitives::traits::{Applyable, As, StaticLookup, Zero};
 use support::{
 	additional_traits::{ChargeExtrinsicFee, ChargeFee},
 	dispatch::Result,
 };
 
+/// The module identifier of tip payments to block authors
+const TIP_MODULE_ID: ModuleIdentifier = *b"fees    ";
+
+/// The number of blocks an extrinsic template is valid for
+pub const TEMPLATE_ERA_PERIOD: u64 = 64;
+
+/// An extrinsic template's fee exchange max. payment is its quoted payment plus 1 / `TEMPLATE_PAYMENT_MARGIN`
+pub const TEMPLATE_PAYMENT_MARGIN: Balance = 20;
+
 /// A type that does fee calculation and payment for extrinsics
 pub struct ExtrinsicFeeCharger;
 
@@ -31,24 +48,175 @@ impl ChargeExtrinsicFee<AccountId, CheckedExtrinsic> for ExtrinsicFeeCharger {
 	/// Calculate and charge a fee to `transactor` for the given `extrinsic`
 	/// The fee is calculated as: 'base fee +e (byte fee * encoded length)'
 	fn charge_extrinsic_fee(transactor: &AccountId, encoded_len: usize, extrinsic: &CheckedExtrinsic) -> Result {
-		let bytes_fee = Fees::fee_registry(Fee::fees(fees::Fee::Bytes))
-			.checked_mul(As::sa(encoded_len))
-			.ok_or_else(|| "extrinsic fee overflow (bytes)")?;
+		// Reject calls missing a required co-signature or not permitted by their doughnut before anything is charged
+		crate::permission::ensure_co_signed(extrinsic)?;
+		crate::permission::ensure_doughnut_usable(extrinsic)?;
+		crate::permission::ensure_doughnut_permits(extrinsic)?;
 
-		let call_fee = Runtime::check_call_fee(extrinsic.call());
+		let total_fee = extrinsic_fee(Some(transactor), encoded_len, extrinsic.call())?;
+		let tip = extrinsic.tip.unwrap_or_else(Zero::zero);
+		let total_payment = total_fee.checked_add(tip).ok_or_else(|| "extrinsic fee overflow (tip)")?;
 
-		let total_fee = Fees::fee_registry(Fee::fees(fees::Fee::Base))
-			.checked_add(bytes_fee)
-			.ok_or_else(|| "extrinsic fee overflow (base + bytes)")?
-			.checked_add(call_fee)
-			.ok_or_else(|| "extrinsic fee overflow (base + bytes + call)")?;
+		// Delegated extrinsics are paid for by the doughnut issuer within the delegate's budget
+		if let Some(ref delegate) = &extrinsic.delegate {
+			FeeBudget::debit(transactor, delegate, total_payment)?;
+		}
 
 		if let Some(ref op) = &extrinsic.fee_exchange {
-			<Runtime as FeesTrait>::BuyFeeAsset::buy_fee_asset(transactor, total_fee, op)?;
+			Fees::buy_fee_asset(transactor, total_payment, op)?;
 		}
 
-		Fees::charge_fee(transactor, total_fee)
+		Fees::charge_fee(transactor, total_fee)?;
+		pay_tip(transactor, tip)
+	}
+}
+
+/// Pay `tip` from `transactor` to the author of the current block.
+/// Without a known author the tip is charged as part of the fee.
+fn pay_tip(transactor: &AccountId, tip: Balance) -> Result {
+	if tip.is_zero() {
+		return Ok(());
+	}
+	match block_author() {
+		Some(author) => GenericAsset::make_module_transfer(
+			&TIP_MODULE_ID,
+			&GenericAsset::spending_asset_id(),
+			transactor,
+			&author,
+			tip,
+		),
+		None => Fees::charge_fee(transactor, tip),
+	}
+}
+
+/// The validator authoring the current block: the one whose turn the current Aura slot is.
+/// Aura authorities are kept in the order of the session validators.
+pub fn block_author() -> Option<AccountId> {
+	let validators = Session::validators();
+	let slot_duration = Aura::slot_duration();
+	if validators.is_empty() || slot_duration == 0 {
+		return None;
 	}
+	let slot = Timestamp::now() / slot_duration;
+	validators.get((slot % validators.len() as u64) as usize).cloned()
+}
+
+/// Calculate the fee of an extrinsic from `transactor` making `call` with `encoded_len` bytes.
+/// The fee is calculated as: 'base fee + (byte fee * encoded length) + call fee + (weight fee * call weight)'
+/// Without a `transactor` the call is weighed as if it were made by an account without locks.
+pub fn extrinsic_fee(
+	transactor: Option<&AccountId>,
+	encoded_len: usize,
+	call: &Call,
+) -> result::Result<Balance, &'static str> {
+	let bytes_fee = Fees::fee_registry(Fee::fees(fees::Fee::Bytes))
+		.checked_mul(As::sa(encoded_len))
+		.ok_or_else(|| "extrinsic fee overflow (bytes)")?;
+
+	let call_fee = Runtime::check_call_fee(call);
+
+	let weight = match call {
+		Call::GenericAsset(call) => GenericAsset::call_weight(transactor.unwrap_or(&Default::default()), call),
+		Call::Doughnuts(call) => Doughnuts::call_weight(call),
+		_ => 0,
+	};
+	let weight_fee = weight_fee(weight).ok_or_else(|| "extrinsic fee overflow (weight)")?;
+
+	Fees::fee_registry(Fee::fees(fees::Fee::Base))
+		.checked_add(bytes_fee)
+		.ok_or_else(|| "extrinsic fee overflow (base + bytes)")?
+		.checked_add(call_fee)
+		.ok_or_else(|| "extrinsic fee overflow (base + bytes + call)")?
+		.checked_add(weight_fee)
+		.ok_or_else(|| "extrinsic fee overflow (base + bytes + call + weight)")
+}
+
+/// The fee of `weight` units of call weight, `None` on overflow
+fn weight_fee(weight: generic_asset::Weight) -> Option<Balance> {
+	Fees::fee_registry(Fee::generic_asset(generic_asset::Fee::PerWeight)).checked_mul(As::sa(weight))
+}
+
+/// Refunds the fee of call weight left unused, e.g. by a purge which finds fewer doughnuts than its limit
+pub struct WeightRefunder;
+
+impl doughnut::RefundWeight<AccountId> for WeightRefunder {
+	fn refund_weight(who: &AccountId, weight: doughnut::Weight) {
+		if let Some(fee) = weight_fee(weight).filter(|fee| !fee.is_zero()) {
+			let _ = Fees::refund_fee(who, fee);
+		}
+	}
+}
+
+/// Query the fee `extrinsic` would be charged, and the amount of its fee exchange asset which would be sold
+/// to pay it and any tip at current exchange prices (zero if it has no fee exchange).
+/// With fallbacks, the payment is quoted in the first asset which can pay within its max. payment.
+pub fn query_fee_exchange_info(extrinsic: &UncheckedExtrinsic) -> result::Result<(Balance, Balance), &'static str> {
+	let transactor = match extrinsic.signature {
+		Some((ref address, ..)) => Some(Indices::lookup(address.clone())?),
+		None => None,
+	};
+	let fee = extrinsic_fee(transactor.as_ref(), extrinsic.encode().len(), &extrinsic.function)?;
+	let tip = extrinsic.tip.map_or_else(Zero::zero, |tip| tip.0);
+	let total_payment = fee.checked_add(tip).ok_or_else(|| "extrinsic fee overflow (tip)")?;
+	let op = match extrinsic.fee_exchange {
+		Some(ref op) => op,
+		None => return Ok((fee, Zero::zero())),
+	};
+
+	let mut first_error = None;
+	for (asset_id, max_payment) in op.candidates() {
+		let quote = CennzxSpot::get_asset_swap_output_price(
+			&asset_id,
+			&GenericAsset::spending_asset_id(),
+			total_payment,
+			CennzxSpot::fee_rate(),
+		);
+		match quote {
+			Ok(payment) if payment <= max_payment => return Ok((fee, payment)),
+			Ok(_) => first_error.get_or_insert("fee exchange payment would exceed the max. payment"),
+			Err(err) => first_error.get_or_insert(err),
+		};
+	}
+	Err(first_error.unwrap_or("fee exchange payment would exceed the max. payment"))
+}
+
+/// Build an unsigned extrinsic of `function` from `sender`, paying its fee in `asset_id`, with its index, era
+/// and a fee exchange quoted at current exchange prices, so it only needs signing.
+pub fn extrinsic_template(
+	sender: AccountId,
+	function: Call,
+	asset_id: u32,
+) -> result::Result<ExtrinsicTemplate<UncheckedExtrinsic, Index, Hash>, &'static str> {
+	let index = System::account_nonce(&sender);
+	// The era begins at the latest block whose hash is known to the runtime
+	let checkpoint = System::block_number().saturating_sub(1);
+	let era = Era::mortal(TEMPLATE_ERA_PERIOD, checkpoint);
+	let era_block_hash = System::block_hash(era.birth(checkpoint));
+
+	let mut extrinsic = UncheckedExtrinsic::new_unsigned(function);
+	if asset_id != GenericAsset::spending_asset_id() {
+		// Quote with a placeholder signature, a full account address and the largest max. payment,
+		// so the quoted length, and so the fee, is no less than that of the signed extrinsic
+		let mut quoted = UncheckedExtrinsic::new_signed(
+			index,
+			extrinsic.function.clone(),
+			Indices::unlookup(sender),
+			Default::default(),
+			era,
+			None,
+		);
+		quoted.fee_exchange = Some(FeeExchange::new(asset_id, Balance::max_value()));
+		let (_, payment) = query_fee_exchange_info(&quoted)?;
+		let margin = (payment / TEMPLATE_PAYMENT_MARGIN).max(1);
+		extrinsic.fee_exchange = Some(FeeExchange::new(asset_id, payment.saturating_add(margin)));
+	}
+
+	Ok(ExtrinsicTemplate {
+		extrinsic,
+		index,
+		era,
+		era_block_hash,
+	})
 }
 
 /// Check the call fee for the given runtime call
@@ -62,6 +230,10 @@ impl CheckCallFee<AssetOf<Self>, Call> for Runtime {
 				generic_asset::Call::<Self>::transfer(_, _, _) => {
 					return Fees::fee_registry(Fee::generic_asset(generic_asset::Fee::Transfer))
 				}
+				// A batch is charged as a single transfer
+				generic_asset::Call::<Self>::transfer_batch(_, _) => {
+					return Fees::fee_registry(Fee::generic_asset(generic_asset::Fee::Transfer))
+				}
 				_ => Zero::zero(),
 			},
 			_ => Zero::zero(),
@@ -0,0 +1,102 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//!
+//! Priority lanes for system-critical extrinsics
+//!
+//! Operational extrinsics, i.e. inherents, misbehaviour reports, the fee peg oracle's price feed, the sudo key's
+//! enactments and councillors' motions and votes, are picked from the transaction pool before any normal extrinsic
+//! and may fill the whole block. Only senders trusted to keep the lane clear make operational extrinsics, anyone
+//! else's governance calls are normal. Normal extrinsics may only fill `NORMAL_DISPATCH_PERCENT` of the block, so a
+//! flood of fee-paying transfers can not crowd out operational ones.
+//!
+//! The share is only enforced by this runtime's block authors, when they build blocks. Importing a block does not
+//! check it, so a block from an author ignoring the share is still valid.
+//!
+use crate::{AccountId, Call, Council, FeePeg, Indices, Sudo, System, UncheckedExtrinsic};
+use parity_codec::Encode;
+use rstd::result;
+use runtime_primitives::traits::StaticLookup;
+use runtime_primitives::transaction_validity::TransactionPriority;
+use runtime_primitives::ApplyError;
+
+/// The max. total length of the extrinsics in a block, as enforced by the executive
+pub const MAX_TRANSACTIONS_SIZE: u32 = 4 * 1024 * 1024;
+
+/// The percentage of a block's extrinsics length normal extrinsics may fill, the rest is reserved for operational ones
+pub const NORMAL_DISPATCH_PERCENT: u32 = 75;
+
+/// The least priority of an operational transaction, normal transactions' priorities are kept below it
+pub const OPERATIONAL_PRIORITY: TransactionPriority = 1 << 63;
+
+/// The lane an extrinsic is dispatched in
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DispatchClass {
+	/// Fee-paying user extrinsics
+	Normal,
+	/// System-critical extrinsics which must not be crowded out
+	Operational,
+}
+
+/// The account which signed `extrinsic`, `None` if it is unsigned or its address is unknown
+pub fn signer(extrinsic: &UncheckedExtrinsic) -> Option<AccountId> {
+	extrinsic
+		.signature
+		.as_ref()
+		.and_then(|(address, ..)| Indices::lookup(address.clone()).ok())
+}
+
+/// The lane `call` made by `signer` is dispatched in
+pub fn dispatch_class(signer: Option<&AccountId>, call: &Call) -> DispatchClass {
+	let operational = match (signer, call) {
+		// inherents
+		(None, Call::Timestamp(_)) | (None, Call::Consensus(_)) => true,
+		(_, Call::Consensus(consensus::Call::report_misbehavior(..)))
+		| (_, Call::Grandpa(grandpa::Call::report_misbehavior(..))) => true,
+		(Some(who), Call::FeePeg(fee_peg::Call::report_price(..))) => FeePeg::oracle().as_ref() == Some(who),
+		(Some(who), Call::Sudo(_)) => Sudo::key() == *who,
+		(Some(who), Call::CouncilVoting(_)) | (Some(who), Call::CouncilMotions(_)) => Council::is_a_councillor(who),
+		_ => false,
+	};
+	if operational {
+		DispatchClass::Operational
+	} else {
+		DispatchClass::Normal
+	}
+}
+
+/// The transaction pool priority of a transaction in lane `class` with executive `priority` and `tip`.
+/// A tip raises the priority of the transaction by its amount, but never lifts a normal transaction to an
+/// operational one's priority.
+pub fn lane_priority(class: DispatchClass, priority: TransactionPriority, tip: u128) -> TransactionPriority {
+	let priority = priority.saturating_add(tip.min(u128::from(u64::max_value())) as u64);
+	match class {
+		DispatchClass::Normal => priority.min(OPERATIONAL_PRIORITY - 1),
+		DispatchClass::Operational => OPERATIONAL_PRIORITY.saturating_add(priority),
+	}
+}
+
+/// Return `Err(ApplyError::FullBlock)` if `extrinsic` is normal and would take the block's extrinsics past the
+/// normal share, so the block author moves on to sealing the block
+pub fn ensure_lane_capacity(extrinsic: &UncheckedExtrinsic) -> result::Result<(), ApplyError> {
+	if dispatch_class(signer(extrinsic).as_ref(), &extrinsic.function) == DispatchClass::Operational {
+		return Ok(());
+	}
+	let length = System::all_extrinsics_len().saturating_add(extrinsic.encode().len() as u32);
+	if length > MAX_TRANSACTIONS_SIZE / 100 * NORMAL_DISPATCH_PERCENT {
+		return Err(ApplyError::FullBlock);
+	}
+	Ok(())
+}
@@ -20,8 +20,8 @@
 #![recursion_limit = "512"]
 
 use cennznet_primitives::{
-	AccountId, AccountIndex, AuthorityId, AuthoritySignature, Balance, BlockNumber, CennznetExtrinsic, Hash, Index,
-	Signature,
+	runtime_api, AccountId, AccountIndex, AuthorityId, AuthoritySignature, Balance, BlockNumber, CennznetExtrinsic,
+	Doughnut, Hash, Index, Signature,
 };
 #[cfg(feature = "std")]
 use council::seats as council_seats;
@@ -46,9 +46,13 @@ use support::traits::Currency;
 use version::NativeVersion;
 use version::RuntimeVersion;
 
-use generic_asset::{SpendingAssetCurrency, StakingAssetCurrency};
+use attestation::{AttestationTopic, AttestationValue};
+use generic_asset::{BalanceLock, SpendingAssetCurrency, StakingAssetCurrency, Unlock, UnlockReason};
 
 pub use consensus::Call as ConsensusCall;
+pub use lanes::{dispatch_class, lane_priority, DispatchClass, OPERATIONAL_PRIORITY};
+pub use limits::{creation_tag, is_creation};
+pub use permission::can_call;
 #[cfg(any(feature = "std", test))]
 pub use runtime_primitives::BuildStorage;
 pub use runtime_primitives::{Perbill, Permill};
@@ -66,14 +70,17 @@ pub use sylo::response as sylo_response;
 pub use sylo::vault as sylo_vault;
 
 mod fee;
+mod lanes;
+mod limits;
+mod permission;
 
 /// Runtime version.
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("cennznet"),
 	impl_name: create_runtime_str!("centrality-cennznet"),
 	authoring_version: 1,
-	spec_version: 24,
-	impl_version: 24,
+	spec_version: 25,
+	impl_version: 25,
 	apis: RUNTIME_API_VERSIONS,
 };
 
@@ -213,17 +220,47 @@ impl generic_asset::Trait for Runtime {
 	type Balance = Balance;
 	type AssetId = u32;
 	type Event = Event;
+	type OnTransferPayload = ();
 }
 
 impl fees::Trait for Runtime {
 	type Event = Event;
 	type Currency = SpendingAssetCurrency<Self>;
 	type BuyFeeAsset = CennzxSpot;
-	type OnFeeCharged = Rewards;
+	type OnFeeCharged = (Rewards, Treasury);
 	type Fee = Fee;
 }
 
-impl rewards::Trait for Runtime {}
+impl rewards::Trait for Runtime {
+	type AsBalance = Balance;
+}
+
+impl integrity::Trait for Runtime {
+	type Event = Event;
+}
+
+impl fee_peg::Trait for Runtime {
+	type Event = Event;
+	type AsBalance = Balance;
+}
+
+impl doughnut::Trait for Runtime {
+	type Event = Event;
+	type Signature = Signature;
+	type Currency = SpendingAssetCurrency<Self>;
+	type RefundWeight = fee::WeightRefunder;
+}
+
+impl multisig::Trait for Runtime {
+	type Event = Event;
+	type Proposal = Call;
+	type MultisigAddressGenerator = multisig::MultisigAddressGenerator<Self>;
+}
+
+impl fee_budget::Trait for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+}
 
 impl cennzx_spot::Trait for Runtime {
 	type Call = Call;
@@ -232,6 +269,61 @@ impl cennzx_spot::Trait for Runtime {
 	type AsBalance = Balance;
 }
 
+impl bounties::Trait for Runtime {
+	type Event = Event;
+}
+
+impl standing_orders::Trait for Runtime {
+	type Event = Event;
+}
+
+/// Governance may change the generic asset parameters, the transfer fee and the treasury's shares, and extend
+/// account freezes
+pub struct GovernableParameters;
+
+impl governance::ProposalFilter<Call> for GovernableParameters {
+	fn allows(proposal: &Call) -> bool {
+		match proposal {
+			Call::GenericAsset(generic_asset::Call::set_create_asset_stake(..))
+			| Call::GenericAsset(generic_asset::Call::set_staking_asset_id(..))
+			| Call::GenericAsset(generic_asset::Call::set_spending_asset_id(..))
+			| Call::GenericAsset(generic_asset::Call::set_max_freeze_period(..))
+			| Call::GenericAsset(generic_asset::Call::set_max_asset_creations_per_block(..))
+			| Call::GenericAsset(generic_asset::Call::extend_account_freeze(..))
+			| Call::Fees(fees::Call::set_fee(Fee::generic_asset(generic_asset::Fee::Transfer), _))
+			| Call::Treasury(treasury::Call::set_fee_share(..))
+			| Call::Treasury(treasury::Call::set_creation_stake_share(..)) => true,
+			_ => false,
+		}
+	}
+}
+
+impl governance::Trait for Runtime {
+	type Event = Event;
+	type Proposal = Call;
+	type ProposalFilter = GovernableParameters;
+}
+
+impl subscriptions::Trait for Runtime {
+	type Event = Event;
+}
+
+impl escrow::Trait for Runtime {
+	type Event = Event;
+}
+
+impl nft::Trait for Runtime {
+	type Event = Event;
+}
+
+impl atomic_swap::Trait for Runtime {
+	type Event = Event;
+}
+
+impl treasury::Trait for Runtime {
+	type Event = Event;
+}
+
 impl attestation::Trait for Runtime {
 	type Event = Event;
 }
@@ -243,9 +335,15 @@ impl sylo::e2ee::Trait for Runtime {
 impl sylo::device::Trait for Runtime {
 	type Event = Event;
 }
-impl sylo::response::Trait for Runtime {}
-impl sylo::inbox::Trait for Runtime {}
-impl sylo::vault::Trait for Runtime {}
+impl sylo::response::Trait for Runtime {
+	type Event = Event;
+}
+impl sylo::inbox::Trait for Runtime {
+	type Currency = SpendingAssetCurrency<Self>;
+}
+impl sylo::vault::Trait for Runtime {
+	type Currency = SpendingAssetCurrency<Self>;
+}
 
 construct_runtime!(
 	pub enum Runtime with Log(InternalLog: DigestItem<Hash, AuthorityId, AuthoritySignature>) where
@@ -276,9 +374,22 @@ construct_runtime!(
 		SyloGroups: sylo_groups::{Module, Call, Storage},
 		SyloE2EE: sylo_e2ee::{Module, Call, Event<T>, Storage},
 		SyloDevice: sylo_device::{Module, Call, Event<T>, Storage},
-		SyloInbox: sylo_inbox::{Module, Call, Storage},
-		SyloResponse: sylo_response::{Module, Call, Storage},
-		SyloVault: sylo_vault::{Module, Call, Storage},
+		SyloInbox: sylo_inbox::{Module, Call, Storage, Config<T>},
+		SyloResponse: sylo_response::{Module, Call, Event<T>, Storage, Config<T>},
+		SyloVault: sylo_vault::{Module, Call, Storage, Config<T>},
+		FeeBudget: fee_budget::{Module, Call, Storage, Config<T>, Event<T>},
+		Integrity: integrity::{Module, Call, Storage, Config<T>, Event},
+		FeePeg: fee_peg::{Module, Call, Storage, Config<T>, Event<T>},
+		Doughnuts: doughnut::{Module, Call, Storage, Config<T>, Event<T>},
+		Multisig: multisig::{Module, Call, Storage, Event<T>},
+		Bounties: bounties::{Module, Call, Storage, Config<T>, Event<T>},
+		StandingOrders: standing_orders::{Module, Call, Storage, Config<T>, Event<T>},
+		Governance: governance::{Module, Call, Storage, Config<T>, Event<T>},
+		Subscriptions: subscriptions::{Module, Call, Storage, Event<T>},
+		Escrow: escrow::{Module, Call, Storage, Event<T>},
+		Nft: nft::{Module, Call, Storage, Event<T>},
+		AtomicSwap: atomic_swap::{Module, Call, Storage, Event<T>},
+		Treasury: treasury::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -328,6 +439,7 @@ impl_runtime_apis! {
 
 	impl block_builder_api::BlockBuilder<Block> for Runtime {
 		fn apply_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> ApplyResult {
+			lanes::ensure_lane_capacity(&extrinsic)?;
 			Executive::apply_extrinsic(extrinsic)
 		}
 
@@ -350,7 +462,15 @@ impl_runtime_apis! {
 
 	impl client_api::TaggedTransactionQueue<Block> for Runtime {
 		fn validate_transaction(tx: <Block as BlockT>::Extrinsic) -> TransactionValidity {
-			Executive::validate_transaction(tx)
+			let class = lanes::dispatch_class(lanes::signer(&tx).as_ref(), &tx.function);
+			let tip = tx.tip.map_or(0, |tip| tip.0);
+			let creation_tag = limits::creation_tag(&tx);
+			let mut validity = Executive::validate_transaction(tx);
+			if let TransactionValidity::Valid { ref mut priority, ref mut provides, .. } = validity {
+				*priority = lanes::lane_priority(class, *priority, tip);
+				provides.extend(creation_tag);
+			}
+			validity
 		}
 	}
 
@@ -405,4 +525,143 @@ impl_runtime_apis! {
 			Consensus::authorities()
 		}
 	}
+
+	impl runtime_api::GenericAssetApi<
+		Block,
+		AccountId,
+		BalanceLock<Balance, BlockNumber>,
+		u32,
+		Balance,
+		Unlock<u32, Balance, BlockNumber>,
+	> for Runtime {
+		fn locks(
+			who: AccountId,
+			asset_id: u32,
+			page: u32,
+			page_size: u32,
+		) -> Vec<(Vec<u8>, BalanceLock<Balance, BlockNumber>)> {
+			GenericAsset::locks_paged(&asset_id, &who, page, page_size)
+		}
+
+		fn reserves(who: AccountId, asset_id: u32) -> Vec<(Vec<u8>, Balance)> {
+			GenericAsset::reserves(&asset_id, &who)
+		}
+
+		fn creator_stakes(who: AccountId) -> (Balance, Vec<(u32, Balance)>) {
+			GenericAsset::creator_stakes(&who)
+		}
+
+		fn asset_balance(asset_id: u32, who: AccountId) -> Balance {
+			GenericAsset::free_balance(&asset_id, &who)
+		}
+
+		fn total_issuance(asset_id: u32) -> Balance {
+			GenericAsset::total_issuance(asset_id)
+		}
+
+		fn registered_assets() -> Vec<u32> {
+			GenericAsset::registered_assets()
+		}
+
+		fn voting_weight_at(who: AccountId, block: u64) -> Option<Balance> {
+			GenericAsset::voting_weight_at(&who, block)
+		}
+
+		fn unlock_timeline(who: AccountId) -> Vec<Unlock<u32, Balance, BlockNumber>> {
+			let mut timeline = GenericAsset::unlock_timeline(&who);
+			// Unbonding chunks unlock at an era, assume the current and future eras run their full length
+			let ledger = Staking::bonded(&who).and_then(|controller| Staking::ledger(&controller));
+			if let Some(ledger) = ledger {
+				let now = System::block_number();
+				let era_length = Staking::sessions_per_era() * Session::length();
+				let current_era = Staking::current_era();
+				for chunk in ledger.unlocking {
+					timeline.push(Unlock {
+						asset_id: GenericAsset::staking_asset_id(),
+						amount: chunk.value,
+						at: now + chunk.era.saturating_sub(current_era) * era_length,
+						reason: UnlockReason::Unbonding,
+					});
+				}
+				timeline.sort_by(|a, b| a.at.cmp(&b.at));
+			}
+			timeline
+		}
+	}
+
+	impl runtime_api::FeeApi<Block, Balance> for Runtime {
+		fn fee_percentiles(percentiles: Vec<Permill>) -> Vec<Balance> {
+			Fees::fee_percentiles(&percentiles)
+		}
+
+		fn query_fee_exchange_info(extrinsic: <Block as BlockT>::Extrinsic) -> Result<(Balance, Balance), Vec<u8>> {
+			fee::query_fee_exchange_info(&extrinsic).map_err(|reason| reason.as_bytes().to_vec())
+		}
+	}
+
+	impl runtime_api::ExtrinsicApi<Block, AccountId, Call, u32, Index, Hash> for Runtime {
+		fn extrinsic_template(
+			sender: AccountId,
+			call: Call,
+			asset_id: u32,
+		) -> Result<runtime_api::ExtrinsicTemplate<<Block as BlockT>::Extrinsic, Index, Hash>, Vec<u8>> {
+			fee::extrinsic_template(sender, call, asset_id).map_err(|reason| reason.as_bytes().to_vec())
+		}
+	}
+
+	impl runtime_api::PermissionApi<Block, AccountId, Doughnut<AccountId, Signature>> for Runtime {
+		fn can_call(
+			account: AccountId,
+			call: Vec<u8>,
+			doughnut: Option<Doughnut<AccountId, Signature>>,
+		) -> runtime_api::CallVerdict {
+			permission::can_call(account, call, doughnut)
+		}
+
+		fn can_read(
+			account: AccountId,
+			scope: Vec<u8>,
+			doughnut: Doughnut<AccountId, Signature>,
+		) -> runtime_api::CallVerdict {
+			permission::can_read(account, scope, doughnut)
+		}
+	}
+
+	impl runtime_api::AttestationApi<Block, AccountId, AttestationTopic, AttestationValue> for Runtime {
+		fn claims(holder: AccountId) -> Vec<(AccountId, AttestationTopic, AttestationValue)> {
+			Attestation::claims(&holder)
+		}
+
+		fn claim(holder: AccountId, issuer: AccountId, topic: AttestationTopic) -> Option<AttestationValue> {
+			Attestation::claim(&holder, &issuer, topic)
+		}
+	}
+
+	impl runtime_api::ComplianceApi<
+		Block,
+		AccountId,
+		u32,
+		AttestationTopic,
+		runtime_api::AssetHolder<AccountId, Balance, BlockNumber, AttestationValue>,
+	> for Runtime {
+		fn asset_holders(
+			asset_id: u32,
+			issuer: AccountId,
+			topic: AttestationTopic,
+			page: u32,
+			page_size: u32,
+		) -> (u32, Vec<runtime_api::AssetHolder<AccountId, Balance, BlockNumber, AttestationValue>>) {
+			let holders = GenericAsset::holders_paged(&asset_id, page, page_size)
+				.into_iter()
+				.map(|who| runtime_api::AssetHolder {
+					free_balance: GenericAsset::free_balance(&asset_id, &who),
+					reserved_balance: GenericAsset::reserved_balance(&asset_id, &who),
+					frozen_until: GenericAsset::account_frozen_until(&asset_id, &who),
+					attestation: Attestation::claim(&who, &issuer, topic),
+					who,
+				})
+				.collect();
+			(GenericAsset::holder_count(asset_id), holders)
+		}
+	}
 }
@@ -0,0 +1,57 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//!
+//! Per-block limits on state growth
+//!
+//! Creating a generic asset or a non-fungible collection adds storage which lives forever, so only
+//! `generic_asset::MaxAssetCreationsPerBlock` of them are included in a block. The limit is kept in the transaction
+//! pool: each signed creation takes one of `MaxAssetCreationsPerBlock` creation slots, picked by its signer, and
+//! provides the slot's pool tag. Only one transaction can provide a tag, so at most the limit of creations is ready
+//! at a time and a block author never picks more of them into a block. A creation whose slot is taken is refused,
+//! or replaces the creation there if it has a higher priority, and can be resubmitted once the slot is free again.
+//! A signer always picks the same slot, so a single account can not take every slot of consecutive blocks.
+//!
+//! Blocks are never ended early for creations. Any creation beyond the limit which still reaches a block, e.g. from
+//! another author's pool, fails on-chain.
+//!
+use crate::{lanes, Call, GenericAsset, UncheckedExtrinsic};
+use parity_codec::{Decode, Encode};
+use runtime_primitives::traits::{BlakeTwo256, Hash};
+use runtime_primitives::transaction_validity::TransactionTag;
+
+/// The prefix of the pool tag provided by the creation in a creation slot
+const CREATION_TAG_PREFIX: &[u8] = b"cennznet:creation";
+
+/// Whether `call` grows state under the per-block creation limit
+pub fn is_creation(call: &Call) -> bool {
+	match call {
+		Call::GenericAsset(generic_asset::Call::create(..))
+		| Call::GenericAsset(generic_asset::Call::create_for(..))
+		| Call::Nft(nft::Call::create_collection(..)) => true,
+		_ => false,
+	}
+}
+
+/// The pool tag of the creation slot taken by `extrinsic` if it is a signed creation
+pub fn creation_tag(extrinsic: &UncheckedExtrinsic) -> Option<TransactionTag> {
+	if !is_creation(&extrinsic.function) {
+		return None;
+	}
+	let signer = lanes::signer(extrinsic)?;
+	let slots = GenericAsset::max_asset_creations_per_block().max(1);
+	let slot = u32::decode(&mut BlakeTwo256::hash_of(&signer).as_ref()).unwrap_or_default() % slots;
+	Some((CREATION_TAG_PREFIX, slot).encode())
+}
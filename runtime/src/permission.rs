@@ -0,0 +1,244 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//!
+//! Runtime call permission queries
+//!
+use crate::{
+	fee, AccountId, Call, CheckedExtrinsic, Doughnuts, GenericAsset, Indices, Origin, Signature, Timestamp,
+	UncheckedExtrinsic,
+};
+use cennznet_primitives::{runtime_api::CallVerdict, Certificate, CheckedCennznetExtrinsic, Doughnut};
+use parity_codec::{Decode, Encode};
+use runtime_primitives::generic::Era;
+use runtime_primitives::traits::StaticLookup;
+use support::{dispatch::Result, Dispatchable};
+
+/// Check whether `account` may make the encoded `call`, optionally acting as the holder of `doughnut`.
+/// The call goes through the checks of a submitted extrinsic: the doughnut, its domain permission and transfer
+/// constraints, the asset co-signer, the fee, and then the call itself is dispatched from the signer, so module
+/// permissions and asset or account freezes apply. Runtime API calls are not committed, so nothing is changed.
+pub fn can_call(account: AccountId, call: Vec<u8>, doughnut: Option<Doughnut<AccountId, Signature>>) -> CallVerdict {
+	let call: Call = match Decode::decode(&mut &call[..]) {
+		Some(call) => call,
+		None => return CallVerdict::InvalidCall,
+	};
+
+	// Delegated calls are made on behalf of the doughnut issuer, at the root of the chain if it was re-delegated
+	let (signer, delegate, certificate) = match doughnut {
+		Some(ref d) => {
+			if d.certificate.holder != account {
+				return CallVerdict::NotDoughnutHolder;
+			}
+			if let Err(reason) = d.validate(Timestamp::now()).and_then(|_| Doughnuts::ensure_usable(&d.certificate)) {
+				return CallVerdict::InvalidDoughnut(reason.as_bytes().to_vec());
+			}
+			(d.root_issuer(), Some(account.clone()), Some(d.certificate.clone()))
+		}
+		None => (account.clone(), None, None),
+	};
+
+	// Quote the fee on the length of the signed extrinsic, with a placeholder signature
+	let encoded_len = UncheckedExtrinsic::new_signed(
+		Default::default(),
+		call.clone(),
+		Indices::unlookup(account),
+		Default::default(),
+		Era::immortal(),
+		doughnut,
+	)
+	.encode()
+	.len();
+	let extrinsic: CheckedExtrinsic = CheckedCennznetExtrinsic {
+		signed: Some((signer.clone(), Default::default())),
+		function: call,
+		fee_exchange: None,
+		delegate,
+		certificate,
+		tip: None,
+	};
+
+	if ensure_co_signed(&extrinsic).and_then(|_| ensure_doughnut_permits(&extrinsic)).is_err() {
+		return CallVerdict::NoPermission;
+	}
+	let spending_asset_id = GenericAsset::spending_asset_id();
+	match fee::extrinsic_fee(Some(&signer), encoded_len, &extrinsic.function) {
+		Ok(fee) if GenericAsset::free_balance(&spending_asset_id, &signer) >= fee => (),
+		_ => return CallVerdict::InsufficientFeeBalance,
+	}
+	if let Err(reason) = extrinsic.function.dispatch(Origin::signed(signer)) {
+		return CallVerdict::CallFailed(reason.as_bytes().to_vec());
+	}
+
+	CallVerdict::Allowed
+}
+
+/// Check whether `account` may read `scope` on behalf of the issuer of the read-only `doughnut`.
+/// Read-only doughnuts permit no calls, privileged read endpoints use this to recognise them as a login.
+pub fn can_read(account: AccountId, scope: Vec<u8>, doughnut: Doughnut<AccountId, Signature>) -> CallVerdict {
+	if doughnut.certificate.holder != account {
+		return CallVerdict::NotDoughnutHolder;
+	}
+	if let Err(reason) = doughnut
+		.validate(Timestamp::now())
+		.and_then(|_| Doughnuts::ensure_usable(&doughnut.certificate))
+	{
+		return CallVerdict::InvalidDoughnut(reason.as_bytes().to_vec());
+	}
+	match doughnut.certificate.read_scopes() {
+		Some(ref scopes) if scopes.contains(&scope) => CallVerdict::Allowed,
+		_ => CallVerdict::NoPermission,
+	}
+}
+
+/// Return `Ok` iff the doughnut `extrinsic` was delegated under, if any, is usable on-chain.
+/// The doughnut must be valid at the current on-chain time and, if it requires activation, have been activated
+/// by its holder. A re-delegated doughnut's period is within its parents', so checking it alone suffices.
+pub fn ensure_doughnut_usable(extrinsic: &CheckedExtrinsic) -> Result {
+	match extrinsic.certificate {
+		Some(ref certificate) => {
+			certificate.validate_period(Timestamp::now())?;
+			Doughnuts::ensure_usable(certificate)
+		}
+		None => Ok(()),
+	}
+}
+
+/// Verifies that a doughnut's permission `payload` in one domain permits `call`
+type DomainVerifier = fn(certificate: &Certificate<AccountId>, payload: &[u8], call: &Call) -> Result;
+
+/// The doughnut permission domains verified on-chain, by domain name.
+/// The call of a delegated extrinsic must be permitted by the doughnut's permission in the domain of its module.
+const DOMAINS: &[(&str, DomainVerifier)] = &[("cennznet", verify_cennznet_domain), ("sylo", verify_sylo_domain)];
+
+/// The permission domain of `call`
+fn call_domain(call: &Call) -> &'static str {
+	match call {
+		Call::SyloGroups(_)
+		| Call::SyloE2EE(_)
+		| Call::SyloDevice(_)
+		| Call::SyloInbox(_)
+		| Call::SyloResponse(_)
+		| Call::SyloVault(_) => "sylo",
+		_ => "cennznet",
+	}
+}
+
+/// Return `Ok` iff the doughnut `extrinsic` was delegated under, if any, permits its call
+/// in the call's permission domain.
+pub fn ensure_doughnut_permits(extrinsic: &CheckedExtrinsic) -> Result {
+	let certificate = match extrinsic.certificate {
+		Some(ref certificate) => certificate,
+		None => return Ok(()),
+	};
+
+	if certificate.read_scopes().is_some() {
+		return Err("read-only doughnut does not permit calls");
+	}
+
+	let domain = call_domain(&extrinsic.function);
+	let key = domain.encode();
+	let payload = certificate
+		.permissions
+		.iter()
+		.find(|(k, _)| *k == key)
+		.map(|(_, payload)| payload)
+		.ok_or("doughnut does not permit calls in this domain")?;
+	let verify = DOMAINS
+		.iter()
+		.find(|(name, _)| *name == domain)
+		.map(|(_, verify)| verify)
+		.ok_or("doughnut domain has no verifier")?;
+
+	verify(certificate, payload, &extrinsic.function)
+}
+
+/// Return `Ok` iff a doughnut's permission `payload` in a domain permits `call`.
+/// An empty payload permits every call in the domain. Otherwise the payload is an encoded `Vec<(u8, Vec<u8>)>` of
+/// module indices, each with the indices of its permitted calls or none to permit all of them. The indices are the
+/// first two bytes of the encoded call.
+fn ensure_call_permitted(payload: &[u8], call: &Call) -> Result {
+	if payload.is_empty() {
+		return Ok(());
+	}
+	let permitted: Vec<(u8, Vec<u8>)> =
+		Decode::decode(&mut &payload[..]).ok_or("invalid doughnut domain permission")?;
+	let encoded = call.encode();
+	let (module, function) = (encoded[0], encoded[1]);
+	if permitted
+		.iter()
+		.any(|(m, calls)| *m == module && (calls.is_empty() || calls.contains(&function)))
+	{
+		Ok(())
+	} else {
+		Err("doughnut does not permit this call")
+	}
+}
+
+/// The calls permitted by the payload are permitted, unless the doughnut has transfer constraints.
+/// A doughnut with transfer constraints may only make asset transfers within them.
+fn verify_cennznet_domain(certificate: &Certificate<AccountId>, payload: &[u8], call: &Call) -> Result {
+	ensure_call_permitted(payload, call)?;
+	let constraints = match certificate.transfer_constraints()? {
+		Some(constraints) => constraints,
+		None => return Ok(()),
+	};
+
+	match call {
+		Call::GenericAsset(generic_asset::Call::transfer(asset_id, to, amount))
+		| Call::GenericAsset(generic_asset::Call::transfer_with_payload(asset_id, to, amount, _))
+		| Call::GenericAsset(generic_asset::Call::schedule_transfer(asset_id, to, amount, _)) => {
+			constraints.check(*asset_id, to, *amount)
+		}
+		Call::GenericAsset(generic_asset::Call::transfer_batch(asset_id, transfers)) => transfers
+			.iter()
+			.map(|(to, amount)| constraints.check(*asset_id, to, *amount))
+			.collect(),
+		_ => Err("doughnut does not permit this call"),
+	}
+}
+
+/// The sylo calls permitted by the payload are permitted
+fn verify_sylo_domain(_certificate: &Certificate<AccountId>, payload: &[u8], call: &Call) -> Result {
+	ensure_call_permitted(payload, call)
+}
+
+/// Return `Ok` iff `extrinsic` is co-signed, as required when it makes a sensitive call on an asset with a co-signer.
+/// Sensitive calls change the asset's supply, permissions or freezes.
+/// The account holding the asset permission issues a doughnut to the co-signer, who signs and submits the extrinsic.
+pub fn ensure_co_signed(extrinsic: &CheckedExtrinsic) -> Result {
+	let asset_id = match &extrinsic.function {
+		Call::GenericAsset(generic_asset::Call::mint(asset_id, ..))
+		| Call::GenericAsset(generic_asset::Call::burn(asset_id, ..))
+		| Call::GenericAsset(generic_asset::Call::mint_batch(asset_id, _))
+		| Call::GenericAsset(generic_asset::Call::burn_batch(asset_id, _))
+		| Call::GenericAsset(generic_asset::Call::set_mint_cooldown(asset_id, _))
+		| Call::GenericAsset(generic_asset::Call::update_permission(asset_id, _))
+		| Call::GenericAsset(generic_asset::Call::renounce_permission(asset_id, ..))
+		| Call::GenericAsset(generic_asset::Call::set_co_signer(asset_id, _))
+		| Call::GenericAsset(generic_asset::Call::freeze_asset(asset_id))
+		| Call::GenericAsset(generic_asset::Call::thaw_asset(asset_id))
+		| Call::GenericAsset(generic_asset::Call::freeze_account(asset_id, ..))
+		| Call::GenericAsset(generic_asset::Call::unfreeze_account(asset_id, _)) => asset_id,
+		_ => return Ok(()),
+	};
+
+	match GenericAsset::co_signer(asset_id) {
+		Some(co_signer) if extrinsic.delegate.as_ref() != Some(&co_signer) => {
+			Err("asset operation must be co-signed by the asset's co-signer")
+		}
+		_ => Ok(()),
+	}
+}
@@ -0,0 +1,130 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//!
+//! Call index stability tests
+//!
+//! A call is encoded as its module's index in the runtime `Call` enum, then the call's index within its module.
+//! Reordering modules in `construct_runtime!` or calls in a `decl_module!` silently changes what an already
+//! signed extrinsic or stored doughnut template decodes to, so released indices are pinned here.
+//! New modules must be appended to `construct_runtime!` and new calls to the end of their `decl_module!`.
+//!
+use cennznet_runtime::Call;
+use parity_codec::{Decode, Encode};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+/// The index of each module in the runtime `Call` enum, in `construct_runtime!` order.
+/// Indices may be added but never changed.
+const MODULE_INDICES: &[(&str, u8)] = &[
+	("System", 0),
+	("Timestamp", 1),
+	("GenericAsset", 2),
+	("Consensus", 3),
+	("Indices", 4),
+	("Session", 5),
+	("Staking", 6),
+	("Democracy", 7),
+	("Council", 8),
+	("CouncilVoting", 9),
+	("CouncilMotions", 10),
+	("Grandpa", 11),
+	("Contract", 12),
+	("Sudo", 13),
+	("Fees", 14),
+	("Rewards", 15),
+	("Attestation", 16),
+	("CennzxSpot", 17),
+	("SyloGroups", 18),
+	("SyloE2EE", 19),
+	("SyloDevice", 20),
+	("SyloInbox", 21),
+	("SyloResponse", 22),
+	("SyloVault", 23),
+	("FeeBudget", 24),
+	("Integrity", 25),
+	("FeePeg", 26),
+	("Doughnuts", 27),
+	("Multisig", 28),
+	("Bounties", 29),
+	("StandingOrders", 30),
+	("Governance", 31),
+	("Subscriptions", 32),
+	("Escrow", 33),
+	("Nft", 34),
+	("AtomicSwap", 35),
+	("Treasury", 36),
+];
+
+/// A call as encoded by a released runtime
+#[derive(Deserialize)]
+struct EncodedCall {
+	module: String,
+	call: String,
+	/// The hex encoded call
+	encoded: String,
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+	assert_eq!(hex.len() % 2, 0, "odd length hex: {}", hex);
+	(0..hex.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("invalid hex"))
+		.collect()
+}
+
+fn module_index(module: &str) -> u8 {
+	MODULE_INDICES
+		.iter()
+		.find(|(name, _)| *name == module)
+		.map(|(_, index)| *index)
+		.unwrap_or_else(|| panic!("module {} is missing from the index registry", module))
+}
+
+#[test]
+fn module_indices_are_unique_and_contiguous() {
+	for (expected, (name, index)) in MODULE_INDICES.iter().enumerate() {
+		assert_eq!(*index as usize, expected, "module {} is out of order", name);
+	}
+}
+
+#[test]
+fn released_calls_decode_to_the_same_module_and_call() {
+	let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/call_corpus.ron");
+	let corpus: Vec<EncodedCall> = ron::de::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+	for entry in corpus {
+		let encoded = from_hex(&entry.encoded);
+		assert_eq!(
+			encoded[0],
+			module_index(&entry.module),
+			"{}::{} was encoded with a different module index",
+			entry.module,
+			entry.call
+		);
+
+		let call = Call::decode(&mut &encoded[..])
+			.unwrap_or_else(|| panic!("{}::{} no longer decodes", entry.module, entry.call));
+		let decoded = format!("{:?}", call);
+		assert!(
+			decoded.starts_with(&format!("{}({}(", entry.module, entry.call)),
+			"{}::{} now decodes as {}",
+			entry.module,
+			entry.call,
+			decoded
+		);
+		assert_eq!(call.encode(), encoded, "{}::{} no longer round trips", entry.module, entry.call);
+	}
+}
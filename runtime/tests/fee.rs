@@ -16,12 +16,17 @@
 //!
 //! Fee integration tests
 //!
-use cennznet_primitives::CheckedCennznetExtrinsic;
-use cennznet_runtime::{Call, ExtrinsicFeePayment, Fee, Runtime};
+use cennznet_primitives::{
+	runtime_api::CallVerdict, Certificate, CheckedCennznetExtrinsic, TransferConstraints, ACTIVATION_WINDOW_KEY,
+	TRANSFER_CONSTRAINTS_KEY,
+};
+use cennznet_runtime::{can_call, Call, ExtrinsicFeePayment, Fee, Origin, Runtime};
 use runtime_io::with_externalities;
+use parity_codec::Encode;
+use runtime_primitives::traits::{BlakeTwo256, Hash};
 use runtime_primitives::BuildStorage;
 use primitives::{sr25519::Public, Blake2Hasher};
-use support::{additional_traits::ChargeExtrinsicFee, assert_err, assert_ok};
+use support::{additional_traits::ChargeExtrinsicFee, assert_err, assert_ok, StorageMap, StorageValue};
 
 // A default address for ChargeExtrinsicFee `transactor`
 const DEFAULT_TRANSACTOR: Public = Public([0u8; 32]);
@@ -29,11 +34,13 @@ const DEFAULT_TRANSACTOR: Public = Public([0u8; 32]);
 type MockCheckedExtrinsic = CheckedCennznetExtrinsic<primitives::sr25519::Public, u64, Call, u128>;
 type System = system::Module<Runtime>;
 type Fees = fees::Module<Runtime>;
+type FeeBudget = fee_budget::Module<Runtime>;
 
 // Nice aliases
 const BASE_FEE: Fee = Fee::fees(fees::Fee::Base);
 const BYTE_FEE: Fee = Fee::fees(fees::Fee::Bytes);
 const CREATE_ACCOUNT_FEE: Fee = Fee::generic_asset(generic_asset::Fee::Transfer);
+const WEIGHT_FEE: Fee = Fee::generic_asset(generic_asset::Fee::PerWeight);
 
 #[test]
 fn charge_extrinsic_fee_works() {
@@ -44,6 +51,9 @@ fn charge_extrinsic_fee_works() {
 				signed: None,
 				function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)), // An arbitrarily chosen Runtime call
 				fee_exchange: None,
+				delegate: None,
+				certificate: None,
+				tip: None,
 			};
 
 			System::set_extrinsic_index(0);
@@ -70,6 +80,25 @@ fn charge_extrinsic_fee_works() {
 	);
 }
 
+#[test]
+fn charge_extrinsic_fee_charges_tip_without_block_author() {
+	with_externalities(&mut ExtBuilder::default().set_fee(BASE_FEE, 3).build(), || {
+		let xt = MockCheckedExtrinsic {
+			signed: None,
+			function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)),
+			fee_exchange: None,
+			delegate: None,
+			certificate: None,
+			tip: Some(50),
+		};
+
+		// There are no session validators to credit the tip to
+		System::set_extrinsic_index(0);
+		assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 0, &xt));
+		assert_eq!(Fees::current_transaction_fee(0), Fees::fee_registry(BASE_FEE) + 50);
+	});
+}
+
 #[test]
 fn charge_extrinsic_fee_for_generic_asset_transfer() {
 	with_externalities(
@@ -83,6 +112,9 @@ fn charge_extrinsic_fee_for_generic_asset_transfer() {
 				signed: None,
 				function: Call::GenericAsset(generic_asset::Call::<Runtime>::transfer(0, DEFAULT_TRANSACTOR, 10)),
 				fee_exchange: None,
+				delegate: None,
+				certificate: None,
+				tip: None,
 			};
 
 			System::set_extrinsic_index(0);
@@ -97,6 +129,37 @@ fn charge_extrinsic_fee_for_generic_asset_transfer() {
 	);
 }
 
+#[test]
+fn charge_extrinsic_fee_for_generic_asset_call_weight() {
+	with_externalities(
+		&mut ExtBuilder::default()
+			.set_fee(BASE_FEE, 3)
+			.set_fee(CREATE_ACCOUNT_FEE, 20)
+			.set_fee(WEIGHT_FEE, 2)
+			.build(),
+		|| {
+			let transfers = vec![(DEFAULT_TRANSACTOR, 10), (Public([1u8; 32]), 10)];
+			let xt = MockCheckedExtrinsic {
+				signed: None,
+				function: Call::GenericAsset(generic_asset::Call::<Runtime>::transfer_batch(0, transfers)),
+				fee_exchange: None,
+				delegate: None,
+				certificate: None,
+				tip: None,
+			};
+
+			System::set_extrinsic_index(0);
+			assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt));
+			assert_eq!(
+				Fees::current_transaction_fee(0),
+				Fees::fee_registry(BASE_FEE)
+					+ Fees::fee_registry(CREATE_ACCOUNT_FEE)
+					+ Fees::fee_registry(WEIGHT_FEE) * u128::from(2 * generic_asset::weights::TRANSFER)
+			);
+		},
+	);
+}
+
 #[test]
 fn charge_extrinsic_fee_for_generic_asset_transfer_overflow() {
 	with_externalities(
@@ -110,6 +173,9 @@ fn charge_extrinsic_fee_for_generic_asset_transfer_overflow() {
 				signed: None,
 				function: Call::GenericAsset(generic_asset::Call::<Runtime>::transfer(0, DEFAULT_TRANSACTOR, 10)),
 				fee_exchange: None,
+				delegate: None,
+				certificate: None,
+				tip: None,
 			};
 
 			System::set_extrinsic_index(0);
@@ -127,6 +193,9 @@ fn charge_extrinsic_fee_fails_with_bytes_fee_overflow() {
 		signed: None,
 		function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)),
 		fee_exchange: None,
+		delegate: None,
+		certificate: None,
+		tip: None,
 	};
 
 	// bytes fee overflows.
@@ -151,6 +220,9 @@ fn charge_extrinsic_fee_fails_with_total_fee_overflow() {
 		signed: None,
 		function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)),
 		fee_exchange: None,
+		delegate: None,
+		certificate: None,
+		tip: None,
 	};
 
 	// bytes fee doesn't overflow, but total fee (bytes_fee + BASE_FEE) does
@@ -169,11 +241,357 @@ fn charge_extrinsic_fee_fails_with_total_fee_overflow() {
 	);
 }
 
+#[test]
+fn charge_extrinsic_fee_debits_delegate_budget() {
+	with_externalities(
+		&mut ExtBuilder::default().set_fee(BASE_FEE, 3).set_fee(BYTE_FEE, 5).build(),
+		|| {
+			let delegate = Public([1u8; 32]);
+			let xt = MockCheckedExtrinsic {
+				signed: None,
+				function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)),
+				fee_exchange: None,
+				delegate: Some(delegate.clone()),
+				certificate: None,
+				tip: None,
+			};
+
+			// Each extrinsic costs 3 + 5 * 7 = 38
+			assert_ok!(FeeBudget::set_budget(Origin::signed(DEFAULT_TRANSACTOR), delegate.clone(), 40));
+
+			System::set_extrinsic_index(0);
+			assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt));
+			assert_eq!(FeeBudget::budget(&DEFAULT_TRANSACTOR, &delegate).unwrap().spent, 38);
+
+			System::set_extrinsic_index(1);
+			assert_err!(
+				ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt),
+				"delegate fee budget exhausted"
+			);
+		},
+	);
+}
+
+#[test]
+fn charge_extrinsic_fee_requires_co_signer_for_sensitive_asset_calls() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let co_signer = Public([2u8; 32]);
+		generic_asset::CoSigner::<Runtime>::insert(16_001, co_signer.clone());
+		let mint = |delegate| MockCheckedExtrinsic {
+			signed: None,
+			function: Call::GenericAsset(generic_asset::Call::<Runtime>::mint(16_001, DEFAULT_TRANSACTOR, 1)),
+			fee_exchange: None,
+			delegate,
+			certificate: None,
+			tip: None,
+		};
+
+		System::set_extrinsic_index(0);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &mint(None)),
+			"asset operation must be co-signed by the asset's co-signer"
+		);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &mint(Some(Public([1u8; 32])))),
+			"asset operation must be co-signed by the asset's co-signer"
+		);
+		assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(
+			&DEFAULT_TRANSACTOR,
+			7,
+			&mint(Some(co_signer))
+		));
+	});
+}
+
+#[test]
+fn charge_extrinsic_fee_requires_co_signer_for_batch_mints() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let co_signer = Public([2u8; 32]);
+		generic_asset::CoSigner::<Runtime>::insert(16_001, co_signer.clone());
+		let mint_batch = |delegate| MockCheckedExtrinsic {
+			signed: None,
+			function: Call::GenericAsset(generic_asset::Call::<Runtime>::mint_batch(
+				16_001,
+				vec![(DEFAULT_TRANSACTOR, 1)],
+			)),
+			fee_exchange: None,
+			delegate,
+			certificate: None,
+			tip: None,
+		};
+
+		System::set_extrinsic_index(0);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &mint_batch(None)),
+			"asset operation must be co-signed by the asset's co-signer"
+		);
+		assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(
+			&DEFAULT_TRANSACTOR,
+			7,
+			&mint_batch(Some(co_signer))
+		));
+	});
+}
+
+#[test]
+fn can_call_checks_calls_like_submitted_extrinsics() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let transfer = Call::GenericAsset(generic_asset::Call::<Runtime>::transfer(16_001, Public([1u8; 32]), 1));
+		let mint = Call::GenericAsset(generic_asset::Call::<Runtime>::mint(16_001, DEFAULT_TRANSACTOR, 1));
+
+		assert_eq!(can_call(DEFAULT_TRANSACTOR, vec![255], None), CallVerdict::InvalidCall);
+		assert_eq!(can_call(DEFAULT_TRANSACTOR, transfer.encode(), None), CallVerdict::Allowed);
+		assert_eq!(
+			can_call(DEFAULT_TRANSACTOR, mint.encode(), None),
+			CallVerdict::CallFailed(b"The origin does not have permission to mint an asset, Permission error.".to_vec())
+		);
+
+		generic_asset::Frozen::<Runtime>::insert(16_001, true);
+		assert_eq!(
+			can_call(DEFAULT_TRANSACTOR, transfer.encode(), None),
+			CallVerdict::CallFailed(b"asset is frozen".to_vec())
+		);
+	});
+}
+
+#[test]
+fn charge_extrinsic_fee_requires_doughnut_activation() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let certificate = Certificate {
+			expires: 1000,
+			version: 0,
+			holder: Public([1u8; 32]),
+			not_before: 0,
+			permissions: vec![
+				("cennznet".encode(), vec![]),
+				(ACTIVATION_WINDOW_KEY.encode(), 100u64.encode()),
+			],
+			issuer: DEFAULT_TRANSACTOR,
+		};
+		let xt = MockCheckedExtrinsic {
+			signed: None,
+			function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)),
+			fee_exchange: None,
+			delegate: Some(Public([1u8; 32])),
+			certificate: Some(certificate.clone()),
+			tip: None,
+		};
+
+		System::set_extrinsic_index(0);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt),
+			"doughnut has not been activated"
+		);
+
+		doughnut::Activated::<Runtime>::insert(BlakeTwo256::hash_of(&certificate), true);
+		assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt));
+	});
+}
+
+#[test]
+fn charge_extrinsic_fee_enforces_doughnut_validity_period() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let xt = MockCheckedExtrinsic {
+			signed: None,
+			function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)),
+			fee_exchange: None,
+			delegate: Some(Public([1u8; 32])),
+			certificate: Some(Certificate {
+				expires: 1000,
+				version: 0,
+				holder: Public([1u8; 32]),
+				not_before: 100,
+				permissions: vec![("cennznet".encode(), vec![])],
+				issuer: DEFAULT_TRANSACTOR,
+			}),
+			tip: None,
+		};
+		let charge_at = |now: u64| {
+			<timestamp::Now<Runtime>>::put(now);
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt)
+		};
+
+		System::set_extrinsic_index(0);
+		assert_err!(charge_at(99), "doughnut is not valid yet");
+		assert_ok!(charge_at(100));
+		assert_ok!(charge_at(999));
+		assert_err!(charge_at(1000), "doughnut has expired");
+	});
+}
+
+#[test]
+fn charge_extrinsic_fee_enforces_doughnut_transfer_constraints() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let holder = Public([1u8; 32]);
+		let merchant = Public([2u8; 32]);
+		let constraints = TransferConstraints {
+			max_amount: Some(100),
+			asset_ids: vec![16_001],
+			destinations: vec![merchant.clone()],
+		};
+		let certificate = Certificate {
+			expires: 1000,
+			version: 0,
+			holder: holder.clone(),
+			not_before: 0,
+			permissions: vec![
+				("cennznet".encode(), vec![]),
+				(TRANSFER_CONSTRAINTS_KEY.encode(), constraints.encode()),
+			],
+			issuer: DEFAULT_TRANSACTOR,
+		};
+		let xt = |function| MockCheckedExtrinsic {
+			signed: None,
+			function,
+			fee_exchange: None,
+			delegate: Some(holder.clone()),
+			certificate: Some(certificate.clone()),
+			tip: None,
+		};
+		let transfer = |asset_id, to, amount| Call::GenericAsset(generic_asset::Call::transfer(asset_id, to, amount));
+
+		let too_much = xt(transfer(16_001, merchant.clone(), 101));
+		let other_asset = xt(transfer(16_000, merchant.clone(), 1));
+		let other_destination = xt(transfer(16_001, holder.clone(), 1));
+
+		System::set_extrinsic_index(0);
+		assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(
+			&DEFAULT_TRANSACTOR,
+			7,
+			&xt(transfer(16_001, merchant.clone(), 100))
+		));
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &too_much),
+			"doughnut does not permit a transfer of this amount"
+		);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &other_asset),
+			"doughnut does not permit transfers of this asset"
+		);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &other_destination),
+			"doughnut does not permit transfers to this account"
+		);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(
+				&DEFAULT_TRANSACTOR,
+				7,
+				&xt(Call::Timestamp(timestamp::Call::<Runtime>::set(0)))
+			),
+			"doughnut does not permit this call"
+		);
+	});
+}
+
+#[test]
+fn charge_extrinsic_fee_requires_doughnut_permission_in_call_domain() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let certificate = |domain: &str| Certificate {
+			expires: 1000,
+			version: 0,
+			holder: Public([1u8; 32]),
+			not_before: 0,
+			permissions: vec![(domain.encode(), vec![])],
+			issuer: DEFAULT_TRANSACTOR,
+		};
+		let xt = |domain| MockCheckedExtrinsic {
+			signed: None,
+			function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)),
+			fee_exchange: None,
+			delegate: Some(Public([1u8; 32])),
+			certificate: Some(certificate(domain)),
+			tip: None,
+		};
+
+		System::set_extrinsic_index(0);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt("sylo")),
+			"doughnut does not permit calls in this domain"
+		);
+		assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt("cennznet")));
+	});
+}
+
+#[test]
+fn charge_extrinsic_fee_requires_doughnut_domain_payload_to_permit_the_call() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let transfer = Call::GenericAsset(generic_asset::Call::<Runtime>::transfer(16_001, Public([1u8; 32]), 1));
+		let mint = Call::GenericAsset(generic_asset::Call::<Runtime>::mint(16_001, Public([1u8; 32]), 1));
+		let set_time = Call::Timestamp(timestamp::Call::<Runtime>::set(0));
+		let (module, function) = (transfer.encode()[0], transfer.encode()[1]);
+		let xt = |payload: Vec<u8>, function| MockCheckedExtrinsic {
+			signed: None,
+			function,
+			fee_exchange: None,
+			delegate: Some(Public([1u8; 32])),
+			certificate: Some(Certificate {
+				expires: 1000,
+				version: 0,
+				holder: Public([1u8; 32]),
+				not_before: 0,
+				permissions: vec![("cennznet".encode(), payload)],
+				issuer: DEFAULT_TRANSACTOR,
+			}),
+			tip: None,
+		};
+		let only_transfer = vec![(module, vec![function])].encode();
+		let any_generic_asset_call = vec![(module, Vec::<u8>::new())].encode();
+
+		System::set_extrinsic_index(0);
+		assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(
+			&DEFAULT_TRANSACTOR,
+			7,
+			&xt(only_transfer.clone(), transfer.clone())
+		));
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt(only_transfer, mint.clone())),
+			"doughnut does not permit this call"
+		);
+		assert_ok!(ExtrinsicFeePayment::charge_extrinsic_fee(
+			&DEFAULT_TRANSACTOR,
+			7,
+			&xt(any_generic_asset_call.clone(), mint)
+		));
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt(any_generic_asset_call, set_time)),
+			"doughnut does not permit this call"
+		);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt(vec![7], transfer)),
+			"invalid doughnut domain permission"
+		);
+	});
+}
+
+#[test]
+fn charge_extrinsic_fee_rejects_read_only_doughnuts() {
+	with_externalities(&mut ExtBuilder::default().build(), || {
+		let mut certificate = Certificate::read_only(DEFAULT_TRANSACTOR, Public([1u8; 32]), 0, 1000, vec![]);
+		// even alongside a call domain, a read-only doughnut permits no calls
+		certificate.permissions.push(("cennznet".encode(), vec![]));
+		let xt = MockCheckedExtrinsic {
+			signed: None,
+			function: Call::Timestamp(timestamp::Call::<Runtime>::set(0)),
+			fee_exchange: None,
+			delegate: Some(Public([1u8; 32])),
+			certificate: Some(certificate),
+			tip: None,
+		};
+
+		System::set_extrinsic_index(0);
+		assert_err!(
+			ExtrinsicFeePayment::charge_extrinsic_fee(&DEFAULT_TRANSACTOR, 7, &xt),
+			"read-only doughnut does not permit calls"
+		);
+	});
+}
+
 // Lifted from `prml-fees`, importing doesn't work...
 pub struct ExtBuilder {
 	transaction_base_fee: u128,
 	transaction_byte_fee: u128,
 	create_account_fee: u128,
+	weight_fee: u128,
 }
 
 impl Default for ExtBuilder {
@@ -182,6 +600,7 @@ impl Default for ExtBuilder {
 			transaction_base_fee: 0,
 			transaction_byte_fee: 0,
 			create_account_fee: 0,
+			weight_fee: 0,
 		}
 	}
 }
@@ -192,6 +611,7 @@ impl ExtBuilder {
 			Fee::fees(fees::Fee::Base) => self.transaction_base_fee = amount,
 			Fee::fees(fees::Fee::Bytes) => self.transaction_byte_fee = amount,
 			Fee::generic_asset(generic_asset::Fee::Transfer) => self.create_account_fee = amount,
+			Fee::generic_asset(generic_asset::Fee::PerWeight) => self.weight_fee = amount,
 		};
 
 		self
@@ -203,6 +623,8 @@ impl ExtBuilder {
 			spending_asset_id: 16_001,
 			assets: vec![16_001],
 			endowed_accounts: vec![DEFAULT_TRANSACTOR],
+			endowments: vec![],
+			genesis_locks: vec![],
 			create_asset_stake: 10,
 			initial_balance: u128::max_value(),
 			next_asset_id: 10_000,
@@ -214,6 +636,7 @@ impl ExtBuilder {
 				(BASE_FEE, self.transaction_base_fee),
 				(BYTE_FEE, self.transaction_byte_fee),
 				(CREATE_ACCOUNT_FEE, self.create_account_fee),
+				(WEIGHT_FEE, self.weight_fee),
 			],
 		}
 		.assimilate_storage(&mut t, &mut c);
@@ -0,0 +1,73 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//!
+//! Priority lane tests
+//!
+use cennznet_runtime::{
+	dispatch_class, lane_priority, Call, DispatchClass, Runtime, TimestampCall, OPERATIONAL_PRIORITY,
+};
+use primitives::{sr25519::Public, Blake2Hasher};
+use runtime_io::with_externalities;
+use support::StorageValue;
+
+#[test]
+fn system_critical_calls_are_operational() {
+	with_externalities(&mut runtime_io::TestExternalities::<Blake2Hasher>::default(), || {
+		let oracle = Public([1u8; 32]);
+		let anyone = Public([2u8; 32]);
+		fee_peg::Oracle::<Runtime>::put(oracle.clone());
+		let price_report = Call::FeePeg(fee_peg::Call::<Runtime>::report_price(100));
+		let offence_report = Call::Grandpa(grandpa::Call::<Runtime>::report_misbehavior(vec![]));
+		let set_time = Call::Timestamp(TimestampCall::set(0));
+
+		assert_eq!(dispatch_class(None, &set_time), DispatchClass::Operational);
+		assert_eq!(dispatch_class(Some(&anyone), &offence_report), DispatchClass::Operational);
+		assert_eq!(dispatch_class(Some(&oracle), &price_report), DispatchClass::Operational);
+		// the sudo key defaults to the zero account
+		let sudo = Call::Sudo(sudo::Call::<Runtime>::set_key(anyone.clone().into()));
+		assert_eq!(dispatch_class(Some(&Public([0u8; 32])), &sudo), DispatchClass::Operational);
+	});
+}
+
+#[test]
+fn calls_anyone_can_make_are_normal() {
+	with_externalities(&mut runtime_io::TestExternalities::<Blake2Hasher>::default(), || {
+		let anyone = Public([2u8; 32]);
+		fee_peg::Oracle::<Runtime>::put(Public([1u8; 32]));
+		let calls = vec![
+			Call::FeePeg(fee_peg::Call::<Runtime>::report_price(100)),
+			Call::FeePeg(fee_peg::Call::<Runtime>::set_enabled(false)),
+			Call::Governance(governance::Call::<Runtime>::vote(0, true)),
+			Call::CouncilMotions(council::motions::Call::<Runtime>::vote(Default::default(), 0, true)),
+			Call::Sudo(sudo::Call::<Runtime>::set_key(anyone.clone().into())),
+			Call::Timestamp(TimestampCall::set(0)),
+			Call::GenericAsset(generic_asset::Call::<Runtime>::transfer(16_000, Public([1u8; 32]), 1)),
+		];
+
+		for call in calls {
+			assert_eq!(dispatch_class(Some(&anyone), &call), DispatchClass::Normal);
+		}
+	});
+}
+
+#[test]
+fn operational_transactions_outrank_tipped_normal_transactions() {
+	let tipped = lane_priority(DispatchClass::Normal, 10, u128::max_value());
+	assert_eq!(tipped, OPERATIONAL_PRIORITY - 1);
+	assert!(lane_priority(DispatchClass::Operational, 0, 0) > tipped);
+	assert_eq!(lane_priority(DispatchClass::Normal, 10, 5), 15);
+	assert_eq!(lane_priority(DispatchClass::Operational, 10, 5), OPERATIONAL_PRIORITY + 15);
+}
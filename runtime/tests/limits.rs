@@ -0,0 +1,84 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//!
+//! Per-block state growth limit tests
+//!
+use cennznet_runtime::{creation_tag, is_creation, Call, Indices, Runtime, UncheckedExtrinsic};
+use generic_asset::{AssetOptions, Owner, PermissionLatest};
+use primitives::{sr25519::Public, Blake2Hasher};
+use runtime_io::with_externalities;
+use runtime_primitives::generic::Era;
+use runtime_primitives::traits::StaticLookup;
+use std::collections::BTreeSet;
+use support::StorageValue;
+
+#[test]
+fn asset_and_collection_creations_are_limited() {
+	let alice = Public([1u8; 32]);
+	let permissions = PermissionLatest {
+		update: Owner::Address(alice.clone()),
+		mint: Owner::Address(alice.clone()),
+		burn: Owner::Address(alice.clone()),
+		freeze: Owner::None,
+	};
+	let options = AssetOptions {
+		initial_issuance: 100,
+		permissions: permissions.clone(),
+	};
+	let create = Call::GenericAsset(generic_asset::Call::<Runtime>::create(options.clone()));
+	let create_for = Call::GenericAsset(generic_asset::Call::<Runtime>::create_for(alice.clone(), options));
+	let create_collection = Call::Nft(nft::Call::<Runtime>::create_collection(generic_asset::PermissionsV1 {
+		update: permissions.update,
+		mint: permissions.mint,
+		burn: permissions.burn,
+	}));
+	let transfer = Call::GenericAsset(generic_asset::Call::<Runtime>::transfer(16_000, alice, 1));
+
+	assert!(is_creation(&create));
+	assert!(is_creation(&create_for));
+	assert!(is_creation(&create_collection));
+	assert!(!is_creation(&transfer));
+}
+
+#[test]
+fn creations_take_one_of_the_block_limit_of_pool_slots() {
+	with_externalities(&mut runtime_io::TestExternalities::<Blake2Hasher>::default(), || {
+		generic_asset::MaxAssetCreationsPerBlock::<Runtime>::put(3);
+		let options = AssetOptions {
+			initial_issuance: 100,
+			permissions: PermissionLatest {
+				update: Owner::Address(Public([1u8; 32])),
+				mint: Owner::None,
+				burn: Owner::None,
+				freeze: Owner::None,
+			},
+		};
+		let create = Call::GenericAsset(generic_asset::Call::<Runtime>::create(options.clone()));
+		let create_for = Call::GenericAsset(generic_asset::Call::<Runtime>::create_for(Public([1u8; 32]), options));
+		let transfer = Call::GenericAsset(generic_asset::Call::<Runtime>::transfer(16_000, Public([1u8; 32]), 1));
+		let signed = |signer: u8, call: &Call| {
+			let address = Indices::unlookup(Public([signer; 32]));
+			UncheckedExtrinsic::new_signed(0, call.clone(), address, Default::default(), Era::immortal(), None)
+		};
+
+		let slots: BTreeSet<_> = (0..50).map(|signer| creation_tag(&signed(signer, &create)).unwrap()).collect();
+		assert_eq!(slots.len(), 3);
+		// a signer's creations compete for the same slot
+		assert_eq!(creation_tag(&signed(1, &create)), creation_tag(&signed(1, &create_for)));
+		assert_eq!(creation_tag(&signed(1, &transfer)), None);
+		assert_eq!(creation_tag(&UncheckedExtrinsic::new_unsigned(create)), None);
+	});
+}
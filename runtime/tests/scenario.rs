@@ -0,0 +1,238 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//!
+//! Scenario integration tests
+//!
+//! Runs each scenario described in `tests/scenarios/*.ron` against the full runtime.
+//! A scenario sets up accounts and exchange pools, then applies a sequence of extrinsics, charging fees
+//! as the executive would, and checks the resulting balances.
+//!
+use cennznet_primitives::{Certificate, CheckedCennznetExtrinsic, FeeExchange};
+use cennznet_runtime::{Call, ExtrinsicFeePayment, Fee, FeeRate, Origin, Runtime};
+use parity_codec::Encode;
+use primitives::{sr25519::Public, Blake2Hasher};
+use runtime_io::{blake2_256, with_externalities};
+use runtime_primitives::BuildStorage;
+use serde::Deserialize;
+use std::{fs, path::Path};
+use support::{additional_traits::ChargeExtrinsicFee, Dispatchable};
+
+type MockCheckedExtrinsic = CheckedCennznetExtrinsic<Public, u64, Call, u128>;
+type System = system::Module<Runtime>;
+type GenericAsset = generic_asset::Module<Runtime>;
+type CennzxSpot = cennzx_spot::Module<Runtime>;
+
+const STAKING_ASSET: u32 = 16_000;
+const SPENDING_ASSET: u32 = 16_001;
+
+#[derive(Deserialize)]
+struct Scenario {
+	/// What the scenario certifies
+	name: String,
+	#[serde(default)]
+	fees: Fees,
+	/// Accounts by name, with their initial (asset_id, balance)s
+	accounts: Vec<(String, Vec<(u32, u128)>)>,
+	#[serde(default)]
+	pools: Vec<Pool>,
+	steps: Vec<Step>,
+}
+
+#[derive(Deserialize, Default)]
+struct Fees {
+	base: u128,
+	byte: u128,
+	transfer: u128,
+}
+
+/// An exchange pool between the core asset and `asset`, seeded by `provider`
+#[derive(Deserialize)]
+struct Pool {
+	provider: String,
+	asset: u32,
+	asset_amount: u128,
+	core_amount: u128,
+}
+
+/// An extrinsic signed by `signer`, optionally as the holder of a doughnut issued by `doughnut_issuer`
+#[derive(Deserialize)]
+struct Step {
+	signer: String,
+	#[serde(default)]
+	doughnut_issuer: Option<String>,
+	/// Pay the fee by selling (asset_id, max_payment) on the exchange
+	#[serde(default)]
+	fee_exchange: Option<(u32, u128)>,
+	call: StepCall,
+	/// The error the extrinsic should fail with, if any
+	#[serde(default)]
+	error: Option<String>,
+	/// The (account, asset_id, free_balance)s expected after the step
+	#[serde(default)]
+	expect: Vec<(String, u32, u128)>,
+}
+
+#[derive(Deserialize)]
+enum StepCall {
+	Transfer { asset: u32, to: String, amount: u128 },
+	AddLiquidity { asset: u32, asset_amount: u128, core_amount: u128 },
+	SwapInput { sold: u32, bought: u32, amount: u128, min_receive: u128 },
+}
+
+fn account(name: &str) -> Public {
+	Public(blake2_256(name.as_bytes()))
+}
+
+impl StepCall {
+	fn into_call(self) -> Call {
+		match self {
+			StepCall::Transfer { asset, to, amount } => {
+				Call::GenericAsset(generic_asset::Call::transfer(asset, account(&to), amount))
+			}
+			StepCall::AddLiquidity {
+				asset,
+				asset_amount,
+				core_amount,
+			} => Call::CennzxSpot(cennzx_spot::Call::add_liquidity(asset, 0, asset_amount, core_amount)),
+			StepCall::SwapInput {
+				sold,
+				bought,
+				amount,
+				min_receive,
+			} => Call::CennzxSpot(cennzx_spot::Call::asset_swap_input(None, sold, bought, amount, min_receive)),
+		}
+	}
+}
+
+impl Scenario {
+	fn build(&self) -> runtime_io::TestExternalities<Blake2Hasher> {
+		let (mut t, mut c) = system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		let _ = generic_asset::GenesisConfig::<Runtime> {
+			staking_asset_id: STAKING_ASSET,
+			spending_asset_id: SPENDING_ASSET,
+			assets: vec![STAKING_ASSET, SPENDING_ASSET],
+			endowed_accounts: vec![],
+			endowments: self
+				.accounts
+				.iter()
+				.flat_map(|(name, balances)| {
+					balances
+						.iter()
+						.map(move |(asset_id, balance)| (*asset_id, account(name), *balance))
+				})
+				.collect(),
+			genesis_locks: vec![],
+			create_asset_stake: 10,
+			initial_balance: 0,
+			next_asset_id: 17_000,
+		}
+		.assimilate_storage(&mut t, &mut c);
+		let _ = fees::GenesisConfig::<Runtime> {
+			_genesis_phantom_data: rstd::marker::PhantomData {},
+			fee_registry: vec![
+				(Fee::fees(fees::Fee::Base), self.fees.base),
+				(Fee::fees(fees::Fee::Bytes), self.fees.byte),
+				(Fee::generic_asset(generic_asset::Fee::Transfer), self.fees.transfer),
+			],
+		}
+		.assimilate_storage(&mut t, &mut c);
+		let _ = cennzx_spot::GenesisConfig::<Runtime> {
+			core_asset_id: SPENDING_ASSET,
+			fee_rate: FeeRate::from_milli(0),
+			fee_exchange_reserve: FeeRate::from_milli(0),
+			liquidity_lock_period: 0,
+			liquidity_lock_bonus: FeeRate::from_milli(0),
+			auto_sell_deposit: 0,
+		}
+		.assimilate_storage(&mut t, &mut c);
+
+		t.into()
+	}
+
+	fn run(self) {
+		let name = self.name.clone();
+		with_externalities(&mut self.build(), || {
+			for pool in self.pools {
+				CennzxSpot::add_liquidity(
+					Origin::signed(account(&pool.provider)),
+					pool.asset,
+					0,
+					pool.asset_amount,
+					pool.core_amount,
+				)
+				.unwrap_or_else(|e| panic!("{}: pool setup failed: {}", name, e));
+			}
+
+			for (i, step) in self.steps.into_iter().enumerate() {
+				let signer = account(&step.signer);
+				let (transactor, delegate, certificate) = match step.doughnut_issuer {
+					Some(ref issuer) => {
+						let certificate = Certificate {
+							expires: u64::max_value(),
+							version: 0,
+							holder: signer.clone(),
+							not_before: 0,
+							permissions: vec![("cennznet".encode(), vec![])],
+							issuer: account(issuer),
+						};
+						(account(issuer), Some(signer), Some(certificate))
+					}
+					None => (signer, None, None),
+				};
+				let call = step.call.into_call();
+				let encoded_len = call.encode().len();
+				let xt = MockCheckedExtrinsic {
+					signed: Some((transactor.clone(), i as u64)),
+					function: call.clone(),
+					fee_exchange: step.fee_exchange.map(|(asset_id, max)| FeeExchange::new(asset_id, max)),
+					delegate,
+					certificate,
+					tip: None,
+				};
+
+				System::set_extrinsic_index(i as u32);
+				let result = ExtrinsicFeePayment::charge_extrinsic_fee(&transactor, encoded_len, &xt)
+					.and_then(|_| call.dispatch(Origin::signed(transactor)));
+				assert_eq!(result.err(), step.error.as_ref().map(|e| e.as_str()), "{}: step {}", name, i);
+
+				for (who, asset_id, balance) in step.expect {
+					assert_eq!(
+						GenericAsset::free_balance(&asset_id, &account(&who)),
+						balance,
+						"{}: step {}, balance of {} in asset {}",
+						name,
+						i,
+						who,
+						asset_id
+					);
+				}
+			}
+		});
+	}
+}
+
+#[test]
+fn scenarios() {
+	let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+	let mut paths: Vec<_> = fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+	paths.sort();
+	assert!(!paths.is_empty(), "no scenarios found");
+	for path in paths {
+		let scenario: Scenario = ron::de::from_str(&fs::read_to_string(&path).unwrap())
+			.unwrap_or_else(|e| panic!("{}: invalid scenario: {}", path.display(), e));
+		scenario.run();
+	}
+}
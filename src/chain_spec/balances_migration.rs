@@ -0,0 +1,106 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conversion of a Substrate `balances` genesis section into generic-asset endowments and locks.
+//!
+//! Lets chains migrating from the `balances` module reuse their existing genesis tooling, e.g.
+//! ```ignore
+//! let balances = BalancesGenesis::from_json(&balances_section)?;
+//! let (endowments, genesis_locks) = balances.into_generic_asset(&[16000, 16001]);
+//! ```
+
+use cennznet_primitives::{AccountId, Balance, BlockNumber};
+use serde::Deserialize;
+
+/// The asset id type used by generic-asset in the runtime.
+pub type AssetId = u32;
+
+/// The parts of a `balances` genesis section which carry over to generic-asset.
+#[derive(Debug, Default, Deserialize)]
+pub struct BalancesGenesis {
+	/// Endowed accounts (account_id, balance).
+	pub balances: Vec<(AccountId, Balance)>,
+	/// Vesting schedules (account_id, begin, length).
+	#[serde(default)]
+	pub vesting: Vec<(AccountId, BlockNumber, BlockNumber)>,
+}
+
+impl BalancesGenesis {
+	/// Parse a JSON `balances` genesis section, unknown fields (fees, existential deposit etc.) are ignored.
+	pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+		serde_json::from_str(json)
+	}
+
+	/// Convert into the generic-asset `endowments` and `genesis_locks` genesis fields.
+	///
+	/// Every balance is endowed in each of `asset_ids` (e.g. CENNZ and CPAY).
+	/// A vesting schedule becomes a lock over the whole balance until the schedule ends.
+	pub fn into_generic_asset(
+		self,
+		asset_ids: &[AssetId],
+	) -> (Vec<(AssetId, AccountId, Balance)>, Vec<(AccountId, Balance, BlockNumber)>) {
+		let endowments = asset_ids
+			.iter()
+			.flat_map(|asset_id| {
+				self.balances
+					.iter()
+					.map(move |(who, balance)| (*asset_id, who.clone(), *balance))
+			})
+			.collect();
+		let genesis_locks = self
+			.vesting
+			.iter()
+			.filter_map(|(who, begin, length)| {
+				self.balances
+					.iter()
+					.find(|(account, _)| account == who)
+					.map(|(_, balance)| (who.clone(), *balance, begin.saturating_add(*length)))
+			})
+			.collect();
+		(endowments, genesis_locks)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::get_account_id_from_seed;
+
+	#[test]
+	fn balances_genesis_converts_to_endowments_and_locks() {
+		let alice = get_account_id_from_seed("Alice");
+		let bob = get_account_id_from_seed("Bob");
+		let json = format!(
+			r#"{{"balances": [["{}", 1000], ["{}", 500]], "vesting": [["{}", 10, 90]], "existentialDeposit": 1}}"#,
+			alice, bob, bob
+		);
+
+		let (endowments, genesis_locks) = BalancesGenesis::from_json(&json)
+			.expect("valid balances genesis")
+			.into_generic_asset(&[16000, 16001]);
+
+		assert_eq!(
+			endowments,
+			vec![
+				(16000, alice.clone(), 1000),
+				(16000, bob.clone(), 500),
+				(16001, alice, 1000),
+				(16001, bob.clone(), 500),
+			]
+		);
+		assert_eq!(genesis_locks, vec![(bob, 500, 100)]);
+	}
+}
@@ -13,12 +13,14 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
-use super::{get_account_id_from_seed, get_authority_keys_from_seed, ChainSpec, GenesisConfig};
-use cennznet_primitives::AccountId;
+use super::{get_account_id_from_seed, get_authority_keys_from_seed, properties, ChainSpec, GenesisConfig};
+use cennznet_primitives::{ss58::Network, AccountId};
 use cennznet_runtime::{
-	CennzxSpotConfig, ConsensusConfig, ContractConfig, CouncilSeatsConfig, CouncilVotingConfig, DemocracyConfig, Fee,
-	FeeRate, FeesConfig, GenericAssetConfig, GrandpaConfig, IndicesConfig, Perbill, Permill, RewardsConfig, Schedule,
-	SessionConfig, StakerStatus, StakingConfig, SudoConfig, TimestampConfig,
+	BountiesConfig, CennzxSpotConfig, ConsensusConfig, ContractConfig, CouncilSeatsConfig, CouncilVotingConfig,
+	DemocracyConfig, DoughnutsConfig, Fee, FeeBudgetConfig, FeePegConfig, FeeRate, FeesConfig, GenericAssetConfig,
+	GovernanceConfig, GrandpaConfig, IndicesConfig, IntegrityConfig, Perbill, Permill, RewardsConfig, Schedule,
+	SessionConfig, StakerStatus, StakingConfig, StandingOrdersConfig, SudoConfig, SyloInboxConfig, SyloResponseConfig,
+	SyloVaultConfig, TimestampConfig, Treasury,
 };
 use primitives::ed25519::Public as AuthorityId;
 
@@ -156,6 +158,8 @@ pub fn genesis(initial_authorities: Vec<(AccountId, AccountId, AuthorityId)>, ro
 			],
 			initial_balance: 10u128.pow(18 + 9), // 1 billion token with 18 decimals
 			endowed_accounts: endowed_accounts.clone().into_iter().map(Into::into).collect(),
+			endowments: vec![],
+			genesis_locks: vec![],
 			next_asset_id: 17000,
 			create_asset_stake: 1000,
 			staking_asset_id: 16000,
@@ -172,11 +176,49 @@ pub fn genesis(initial_authorities: Vec<(AccountId, AccountId, AuthorityId)>, ro
 		cennzx_spot: Some(CennzxSpotConfig {
 			fee_rate: FeeRate::from_milli(3),
 			core_asset_id: 16001,
+			fee_exchange_reserve: FeeRate::from_percent(10),
+			liquidity_lock_period: 60,
+			liquidity_lock_bonus: FeeRate::from_percent(5),
+			auto_sell_deposit: 10,
 		}),
 		rewards: Some(RewardsConfig {
 			block_reward: 1000,
 			fee_reward_multiplier: Permill::from_percent(100),
 		}),
+		sylo_inbox: Some(SyloInboxConfig {
+			deposit: 1,
+			deposit_period: 1_000,
+		}),
+		sylo_response: Some(SyloResponseConfig { request_timeout: 100 }),
+		sylo_vault: Some(SyloVaultConfig { deposit: 1 }),
+		fee_budget: Some(FeeBudgetConfig { budget_period: 1_000 }),
+		integrity: Some(IntegrityConfig {
+			check_period: 50,
+			required_fees: vec![Fee::fees(fees::Fee::Base), Fee::fees(fees::Fee::Bytes)],
+		}),
+		fee_peg: Some(FeePegConfig {
+			enabled: false,
+			oracle: None,
+			target_fees: vec![],
+			max_step: Permill::from_percent(5),
+			adjustment_period: 50,
+		}),
+		doughnuts: Some(DoughnutsConfig {
+			activation_deposit: 10,
+			purge_reward: 1,
+		}),
+		bounties: Some(BountiesConfig {
+			treasury: Some(Treasury::account_id()),
+		}),
+		governance: Some(GovernanceConfig {
+			voting_period: 18,
+			quorum: 10,
+			proposal_deposit: 10,
+		}),
+		standing_orders: Some(StandingOrdersConfig {
+			order_deposit: 10,
+			min_order_interval: 10,
+		}),
 	}
 }
 
@@ -197,6 +239,6 @@ pub fn config() -> Result<ChainSpec, String> {
 		None,
 		Some("dev"), // protocol id, unique for each chain
 		None,
-		None,
+		Some(properties(Network::Testnet)),
 	))
 }
@@ -15,11 +15,13 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //! CENNZNET chain configurations.
 
+use cennznet_primitives::ss58::Network;
 use cennznet_primitives::AccountId;
 pub use cennznet_runtime::GenesisConfig;
 use primitives::{ed25519, ed25519::Public as AuthorityId, sr25519, Pair};
-use substrate_service;
+use substrate_service::{self, Properties};
 
+pub mod balances_migration;
 pub mod dev;
 pub mod testnet;
 
@@ -28,6 +30,27 @@ pub const TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 /// Specialised `ChainSpec`.
 pub type ChainSpec = substrate_service::ChainSpec<GenesisConfig>;
 
+/// The chain spec properties of a chain on `network`, wallets format its addresses with `ss58Format`
+pub fn properties(network: Network) -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("ss58Format".into(), network.ss58_prefix().into());
+	properties
+}
+
+/// The network of a chain, from the `ss58Format` property of its chain spec
+pub fn network(chain_spec: &ChainSpec) -> Result<Network, String> {
+	let prefix = chain_spec
+		.properties()
+		.get("ss58Format")
+		.and_then(|prefix| prefix.as_u64())
+		.ok_or_else(|| format!("Chain spec of {} has no ss58Format property", chain_spec.name()))?;
+	if prefix > u64::from(u8::max_value()) {
+		return Err(format!("Chain spec of {} has an invalid ss58Format {}", chain_spec.name(), prefix));
+	}
+	Network::from_ss58_prefix(prefix as u8)
+		.ok_or_else(|| format!("Chain spec of {} is not for a CENNZnet network", chain_spec.name()))
+}
+
 pub struct NetworkKeys {
 	pub endowed_accounts: Vec<AccountId>,
 	pub initial_authorities: Vec<(AccountId, AccountId, AuthorityId)>,
@@ -14,12 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 use super::{
-	get_account_id_from_seed, get_authority_keys_from_seed, ChainSpec, GenesisConfig, NetworkKeys, TELEMETRY_URL,
+	get_account_id_from_seed, get_authority_keys_from_seed, properties, ChainSpec, GenesisConfig, NetworkKeys,
+	TELEMETRY_URL,
 };
+use cennznet_primitives::ss58::Network;
 use cennznet_runtime::{
-	CennzxSpotConfig, ConsensusConfig, ContractConfig, CouncilSeatsConfig, CouncilVotingConfig, DemocracyConfig, Fee,
-	FeeRate, FeesConfig, GenericAssetConfig, GrandpaConfig, IndicesConfig, Perbill, Permill, RewardsConfig, Schedule,
-	SessionConfig, StakerStatus, StakingConfig, SudoConfig, TimestampConfig,
+	BountiesConfig, CennzxSpotConfig, ConsensusConfig, ContractConfig, CouncilSeatsConfig, CouncilVotingConfig,
+	DemocracyConfig, DoughnutsConfig, Fee, FeeBudgetConfig, FeePegConfig, FeeRate, FeesConfig, GenericAssetConfig,
+	GovernanceConfig, GrandpaConfig, IndicesConfig, IntegrityConfig, Perbill, Permill, RewardsConfig, Schedule,
+	SessionConfig, StakerStatus, StakingConfig, StandingOrdersConfig, SudoConfig, SyloInboxConfig, SyloResponseConfig,
+	SyloVaultConfig, TimestampConfig, Treasury,
 };
 use hex_literal::{hex, hex_impl};
 use primitives::crypto::UncheckedInto;
@@ -168,6 +172,8 @@ fn genesis(keys: NetworkKeys) -> GenesisConfig {
 			],
 			initial_balance: 10u128.pow(6) * DOLLARS, // 1 million token
 			endowed_accounts: endowed_accounts.clone().into_iter().map(Into::into).collect(),
+			endowments: vec![],
+			genesis_locks: vec![],
 			next_asset_id: 17000,
 			create_asset_stake: 1000,
 			staking_asset_id: 16000,
@@ -184,11 +190,49 @@ fn genesis(keys: NetworkKeys) -> GenesisConfig {
 		cennzx_spot: Some(CennzxSpotConfig {
 			fee_rate: FeeRate::from_milli(3),
 			core_asset_id: 16001,
+			fee_exchange_reserve: FeeRate::from_percent(10),
+			liquidity_lock_period: 7 * DAYS,
+			liquidity_lock_bonus: FeeRate::from_percent(5),
+			auto_sell_deposit: 100 * MICRO_DOLLARS,
 		}),
 		rewards: Some(RewardsConfig {
 			block_reward: 10 * MICRO_DOLLARS,
 			fee_reward_multiplier: Permill::from_percent(70),
 		}),
+		sylo_inbox: Some(SyloInboxConfig {
+			deposit: 100 * MICRO_DOLLARS,
+			deposit_period: 7 * DAYS,
+		}),
+		sylo_response: Some(SyloResponseConfig { request_timeout: HOURS }),
+		sylo_vault: Some(SyloVaultConfig { deposit: 100 * MICRO_DOLLARS }),
+		fee_budget: Some(FeeBudgetConfig { budget_period: 30 * DAYS }),
+		integrity: Some(IntegrityConfig {
+			check_period: 10 * MINUTES,
+			required_fees: vec![Fee::fees(fees::Fee::Base), Fee::fees(fees::Fee::Bytes)],
+		}),
+		fee_peg: Some(FeePegConfig {
+			enabled: false,
+			oracle: None,
+			target_fees: vec![],
+			max_step: Permill::from_percent(5),
+			adjustment_period: 10 * MINUTES,
+		}),
+		doughnuts: Some(DoughnutsConfig {
+			activation_deposit: 100 * MICRO_DOLLARS,
+			purge_reward: 10 * MICRO_DOLLARS,
+		}),
+		bounties: Some(BountiesConfig {
+			treasury: Some(Treasury::account_id()),
+		}),
+		governance: Some(GovernanceConfig {
+			voting_period: 7 * DAYS,
+			quorum: 1_000_000 * DOLLARS,
+			proposal_deposit: 1_000 * DOLLARS,
+		}),
+		standing_orders: Some(StandingOrdersConfig {
+			order_deposit: 100 * MICRO_DOLLARS,
+			min_order_interval: HOURS,
+		}),
 	}
 }
 
@@ -220,7 +264,7 @@ pub fn kauri_latest_config() -> Result<ChainSpec, String> {
 		Some(TelemetryEndpoints::new(vec![(TELEMETRY_URL.into(), 0)])),
 		Some("kauri"), // protocol id, unique for each chain
 		None,
-		None,
+		Some(properties(Network::Testnet)),
 	))
 }
 
@@ -233,7 +277,7 @@ pub fn kauri_dev_config() -> Result<ChainSpec, String> {
 		None,
 		Some("kauri"), // protocol id, unique for each chain
 		None,
-		None,
+		Some(properties(Network::Testnet)),
 	))
 }
 
@@ -305,7 +349,7 @@ pub fn rimu_latest_config() -> Result<ChainSpec, String> {
 		Some(TelemetryEndpoints::new(vec![(TELEMETRY_URL.into(), 0)])),
 		Some("rimu20"), // protocol id, unique for each chain
 		None,
-		None,
+		Some(properties(Network::Testnet)),
 	))
 }
 
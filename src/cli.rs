@@ -15,7 +15,10 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 use crate::chain_spec;
 use crate::service;
+use cennznet_primitives::ss58;
+use primitives::hexdisplay::HexDisplay;
 use std::ops::Deref;
+use structopt::StructOpt;
 use substrate_cli as cli;
 pub use substrate_cli::{error, IntoExit, NoCustom, VersionInfo};
 use substrate_service::{Roles as ServiceRoles, ServiceFactory};
@@ -41,6 +44,24 @@ pub enum ChainSpec {
 	CennznetRimuLatest,
 }
 
+/// Extra options of the run command.
+#[derive(Clone, Debug, Default, StructOpt)]
+pub struct CustomArgs {
+	/// Replay the stored blocks from this block number against the native runtime and exit, instead of running the node
+	#[structopt(long = "replay-from", value_name = "BLOCK")]
+	pub replay_from: Option<u64>,
+
+	/// The last block to replay, defaults to the best block
+	#[structopt(long = "replay-to", value_name = "BLOCK")]
+	pub replay_to: Option<u64>,
+
+	/// Check an address is for this chain's network, print its account id and exit, instead of running the node
+	#[structopt(long = "inspect-address", value_name = "ADDRESS")]
+	pub inspect_address: Option<String>,
+}
+
+cli::impl_augment_clap!(CustomArgs);
+
 /// Get a chain config from a spec setting.
 impl ChainSpec {
 	pub(crate) fn load(self) -> Result<chain_spec::ChainSpec, String> {
@@ -81,13 +102,19 @@ where
 	T: Into<std::ffi::OsString> + Clone,
 	E: IntoExit,
 {
-	cli::parse_and_execute::<service::Factory, NoCustom, NoCustom, _, _, _, _, _>(
+	cli::parse_and_execute::<service::Factory, NoCustom, CustomArgs, _, _, _, _, _>(
 		load_spec,
 		&version,
 		"cennznet-node",
 		args,
 		exit,
-		|exit, _cli_args, _custom_args, mut config| {
+		|exit, _cli_args, custom_args, mut config| {
+			if let Some(from) = custom_args.replay_from {
+				return crate::replay::replay_blocks(config, from, custom_args.replay_to);
+			}
+			if let Some(address) = custom_args.inspect_address {
+				return inspect_address(&config.chain_spec, &address);
+			}
 			config.rpc_cors = None; // TODO: remove this when we figured out how react native plays with CORS
 			info!("{}", version.name);
 			info!("  version {}", config.full_version());
@@ -116,6 +143,15 @@ where
 	.map(|_| ())
 }
 
+/// Print the account id of `address`, which must be an address of the chain's network.
+fn inspect_address(chain_spec: &chain_spec::ChainSpec, address: &str) -> Result<(), String> {
+	let network = chain_spec::network(chain_spec)?;
+	let account = ss58::decode_address(address, network).map_err(|e| format!("{}: {}", address, e))?;
+	println!("Network: {}", network);
+	println!("Account id: 0x{}", HexDisplay::from(&account.0));
+	Ok(())
+}
+
 fn run_until_exit<T, C, E>(mut runtime: Runtime, service: T, e: E) -> error::Result<()>
 where
 	T: Deref<Target = substrate_service::Service<C>>,
@@ -41,6 +41,7 @@ extern crate log;
 
 mod chain_spec;
 mod cli;
+mod replay;
 mod service;
 
 use cli::VersionInfo;
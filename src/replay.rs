@@ -0,0 +1,164 @@
+// Copyright (C) 2019 Centrality Investments Limited
+// This file is part of CENNZnet.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Replay historical blocks against the native runtime of this build.
+//!
+//! Used to validate changes to the extrinsic check/apply pipeline before release. Each block is
+//! re-executed on top of its parent state and the generic-asset supply of the staking and spending
+//! assets is checked against the sum of account balances. Each extrinsic is also decoded in the
+//! extrinsic format of the runtime version the block was authored with.
+
+use crate::service::Factory;
+use cennznet_primitives::{Balance, Block, BlockNumber, ExtrinsicFormat};
+use cennznet_runtime::{Runtime, UncheckedExtrinsic};
+use client::runtime_api::{Core, ProvideRuntimeApi};
+use parity_codec::{Decode, Encode};
+use primitives::storage::StorageKey;
+use primitives::{blake2_256, twox_128};
+use sr_primitives::generic::{BlockId, DigestItem};
+use sr_primitives::traits::{Block as BlockT, Digest, Header};
+use srml_support::storage::hashed::generator::{StorageMap, StorageValue};
+use srml_support::storage::unhashed::generator::StorageDoubleMap;
+use substrate_service::{new_client, FactoryFullConfiguration, FullClient};
+
+/// The generic-asset asset id type of the runtime
+type AssetId = u32;
+
+/// Replay blocks `from` to `to` (or the best block), logging every divergence found.
+pub fn replay_blocks(
+	config: FactoryFullConfiguration<Factory>,
+	from: BlockNumber,
+	to: Option<BlockNumber>,
+) -> Result<(), String> {
+	let client = new_client::<Factory>(&config).map_err(|e| format!("{:?}", e))?;
+	let to = match to {
+		Some(to) => to,
+		None => client.info().map_err(|e| format!("{:?}", e))?.chain.best_number,
+	};
+
+	let mut divergences = 0;
+	// the genesis block has no parent to execute on
+	for number in from.max(1)..=to {
+		let block = client
+			.block(&BlockId::Number(number))
+			.map_err(|e| format!("{:?}", e))?
+			.ok_or_else(|| format!("Block #{} not found", number))?
+			.block;
+		let (mut header, extrinsics) = block.deconstruct();
+		// The seal is added by the author after execution
+		if let Some(DigestItem::Seal(..)) = header.digest().logs().last() {
+			header.digest_mut().pop();
+		}
+		let parent = BlockId::Hash(*header.parent_hash());
+
+		let spec_version = client.runtime_version_at(&parent).map_err(|e| format!("{:?}", e))?.spec_version;
+		let format = ExtrinsicFormat::for_spec_version(spec_version);
+		for (index, extrinsic) in extrinsics.iter().enumerate() {
+			if UncheckedExtrinsic::decode_format(format, &mut &extrinsic.encode()[..]).is_none() {
+				divergences += 1;
+				warn!("Block #{}: extrinsic {} does not decode in format {:?}", number, index, format);
+			}
+		}
+
+		if let Err(e) = client.runtime_api().execute_block(&parent, Block::new(header, extrinsics)) {
+			divergences += 1;
+			warn!("Block #{}: re-execution failed: {:?}", number, e);
+			continue;
+		}
+
+		let at = BlockId::Number(number);
+		for asset_id in vec![
+			storage_value::<AssetId>(&client, &at, generic_asset::StakingAssetId::<Runtime>::key())?,
+			storage_value::<AssetId>(&client, &at, generic_asset::SpendingAssetId::<Runtime>::key())?,
+		] {
+			let (total_issuance, total_balance) = asset_supply(&client, &at, asset_id)?;
+			if total_issuance != total_balance {
+				divergences += 1;
+				warn!(
+					"Block #{}: asset {} total issuance {} does not match account balances {}",
+					number, asset_id, total_issuance, total_balance
+				);
+			}
+		}
+	}
+
+	info!("Replayed blocks #{} to #{} with {} divergences", from, to, divergences);
+	if divergences > 0 {
+		return Err(format!("{} divergences found", divergences));
+	}
+	Ok(())
+}
+
+/// Return the total issuance of `asset_id` and the sum of all free and reserved balances of it at `at`.
+fn asset_supply(
+	client: &FullClient<Factory>,
+	at: &BlockId<Block>,
+	asset_id: AssetId,
+) -> Result<(Balance, Balance), String> {
+	let total_issuance = storage::<Balance>(
+		client,
+		at,
+		&blake2_256(&generic_asset::TotalIssuance::<Runtime>::key_for(&asset_id)),
+	)?
+	.unwrap_or_default();
+
+	let mut total_balance: Balance = 0;
+	let accounts = client
+		.storage_keys(at, &StorageKey(generic_asset::AccountStore::<Runtime>::prefix_for(&asset_id)))
+		.map_err(|e| format!("{:?}", e))?;
+	for key in accounts {
+		if let Some(account) = storage::<generic_asset::AccountData<Balance>>(client, at, &key.0)? {
+			total_balance = total_balance.saturating_add(account.free).saturating_add(account.reserved);
+		}
+	}
+	// Balances not yet moved to `AccountStore`
+	for prefix in vec![
+		generic_asset::FreeBalance::<Runtime>::prefix_for(&asset_id),
+		generic_asset::ReservedBalance::<Runtime>::prefix_for(&asset_id),
+	] {
+		let keys = client
+			.storage_keys(at, &StorageKey(prefix))
+			.map_err(|e| format!("{:?}", e))?;
+		for key in keys {
+			let balance = storage::<Balance>(client, at, &key.0)?.unwrap_or_default();
+			total_balance = total_balance.saturating_add(balance);
+		}
+	}
+
+	Ok((total_issuance, total_balance))
+}
+
+/// Read a storage value stored under the twox hashed `key`
+fn storage_value<T: Decode + Default>(
+	client: &FullClient<Factory>,
+	at: &BlockId<Block>,
+	key: &[u8],
+) -> Result<T, String> {
+	Ok(storage(client, at, &twox_128(key))?.unwrap_or_default())
+}
+
+/// Read and decode the raw storage entry at `key`
+fn storage<T: Decode>(
+	client: &FullClient<Factory>,
+	at: &BlockId<Block>,
+	key: &[u8],
+) -> Result<Option<T>, String> {
+	client
+		.storage(at, &StorageKey(key.to_vec()))
+		.map_err(|e| format!("{:?}", e))?
+		.map(|data| Decode::decode(&mut &data.0[..]).ok_or_else(|| format!("Undecodable storage at {:?}", key)))
+		.transpose()
+}